@@ -0,0 +1,81 @@
+use crate::{Allocator, Context};
+
+use super::EntryDecoder;
+
+/// Trait governing how a type is decoded when it's flattened into the map of
+/// its parent.
+///
+/// Entries which the parent doesn't recognize are offered to its flattened
+/// fields in declaration order through [`decode_flatten_entry`]. Once the map
+/// has been fully consumed the value is constructed with [`finish_flatten`].
+///
+/// The `K` parameter is the type of field name used by the parent, like `str`
+/// for types which are named by string and `usize` for types which are named
+/// by index. A flattened type must therefore use the same kind of names as its
+/// parent, and since the `Binary` mode names fields by index by default, types
+/// which are flattened in it typically want to use `#[musli(name_all =
+/// "name")]`.
+///
+/// This is used automatically through the `#[musli(flatten)]` field attribute
+/// of the [`Decode` derive], which implements this trait for structs marked
+/// with `#[musli(flattenable)]`. It is also implemented for maps such as
+/// [`HashMap<K, V>`], which accept every entry offered to them and can
+/// therefore be used as a catch-all for unknown fields.
+///
+/// [`decode_flatten_entry`]: DecodeFlatten::decode_flatten_entry
+/// [`finish_flatten`]: DecodeFlatten::finish_flatten
+/// [`Decode` derive]: https://docs.rs/musli/latest/musli/_help/derives/
+/// [`HashMap<K, V>`]: std::collections::HashMap
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use musli::Decode;
+///
+/// #[derive(Decode)]
+/// #[musli(name_all = "name", flattenable)]
+/// struct Metadata {
+///     id: u32,
+/// }
+///
+/// #[derive(Decode)]
+/// #[musli(name_all = "name")]
+/// struct Message {
+///     body: String,
+///     #[musli(flatten)]
+///     metadata: Metadata,
+///     #[musli(flatten)]
+///     unknown: HashMap<String, u32>,
+/// }
+/// ```
+pub trait DecodeFlatten<'de, M, A, K>: Sized
+where
+    A: Allocator,
+    K: ?Sized,
+{
+    /// The partially decoded value.
+    type Partial;
+
+    /// Construct an empty partially decoded value.
+    fn partial() -> Self::Partial;
+
+    /// Decode the value of the entry with the given name if it belongs to this
+    /// type, in which case `None` is returned.
+    ///
+    /// If the entry is not recognized, the entry decoder is returned
+    /// unconsumed so that it can be offered elsewhere.
+    fn decode_flatten_entry<D>(
+        partial: &mut Self::Partial,
+        name: &K,
+        decoder: D,
+    ) -> Result<Option<D>, D::Error>
+    where
+        D: EntryDecoder<'de, Mode = M, Allocator = A>;
+
+    /// Construct the value once all entries have been offered to it.
+    fn finish_flatten<C>(cx: C, partial: Self::Partial) -> Result<Self, C::Error>
+    where
+        C: Context<Allocator = A>;
+}
@@ -1683,6 +1683,66 @@ pub trait Decoder<'de>: Sized {
             ExpectingWrapper::new(&self).format()
         )))
     }
+
+    /// Decode the unparsed source of the next value.
+    ///
+    /// Text formats which support this, such as JSON, skip over the next value
+    /// and provide the exact slice of input it occupies to the visitor. No
+    /// other formats support this by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fmt;
+    ///
+    /// use musli::{Allocator, Context, Decode, Decoder};
+    /// use musli::de::UnsizedVisitor;
+    ///
+    /// struct Raw<'de>(&'de str);
+    ///
+    /// impl<'de, M, A> Decode<'de, M, A> for Raw<'de>
+    /// where
+    ///     A: Allocator,
+    /// {
+    ///     #[inline]
+    ///     fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    ///     where
+    ///         D: Decoder<'de>,
+    ///     {
+    ///         struct Visitor;
+    ///
+    ///         #[musli::trait_defaults]
+    ///         impl<'de, C> UnsizedVisitor<'de, C, str> for Visitor
+    ///         where
+    ///             C: Context,
+    ///         {
+    ///             type Ok = &'de str;
+    ///
+    ///             #[inline]
+    ///             fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///                 write!(f, "a borrowed raw value")
+    ///             }
+    ///
+    ///             #[inline]
+    ///             fn visit_borrowed(self, _: C, raw: &'de str) -> Result<Self::Ok, C::Error> {
+    ///                 Ok(raw)
+    ///             }
+    ///         }
+    ///
+    ///         Ok(Self(decoder.decode_raw(Visitor)?))
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn decode_raw<V>(self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: UnsizedVisitor<'de, Self::Cx, str, Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        Err(self.cx().message(expecting::unsupported_type(
+            &expecting::Raw,
+            ExpectingWrapper::new(&self),
+        )))
+    }
 }
 
 #[repr(transparent)]
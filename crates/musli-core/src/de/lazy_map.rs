@@ -0,0 +1,213 @@
+#![allow(clippy::type_complexity)]
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{Allocator, Context};
+
+use super::{Decode, Decoder, EntryDecoder, MapDecoder};
+
+/// A map which is decoded lazily.
+///
+/// This is the map counterpart to [`LazySeq`]. Decoding a `LazyMap` only
+/// records the number of entries and the range of bytes which hold them,
+/// without collecting the entries into a container. Each entry is stored as a
+/// key followed by its value in the encoding `E`, which should be the same
+/// encoding as the outer value was decoded with. They can later be decoded one
+/// at a time through [`LazyMap::iter`].
+///
+/// The same restrictions as for [`LazySeq`] apply, so this requires a format
+/// which decodes from a borrowed slice of bytes and which can skip over values
+/// without decoding them.
+///
+/// [`LazySeq`]: super::LazySeq
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use musli::{Decode, Encode};
+/// use musli::de::LazyMap;
+/// use musli::wire::Encoding;
+/// # use musli::wire::Error;
+///
+/// const ENCODING: Encoding = Encoding::new();
+///
+/// #[derive(Encode)]
+/// struct Index {
+///     name: String,
+///     entries: BTreeMap<String, u32>,
+/// }
+///
+/// #[derive(Decode)]
+/// struct LazyIndex<'de> {
+///     name: String,
+///     entries: LazyMap<'de, &'de str, u32, Encoding>,
+/// }
+///
+/// let data = ENCODING.to_vec(&Index {
+///     name: "index".to_string(),
+///     entries: (0..100).map(|n| (format!("entry {n}"), n)).collect(),
+/// })?;
+///
+/// let index: LazyIndex<'_> = ENCODING.from_slice(&data)?;
+/// assert_eq!(index.name, "index");
+/// assert_eq!(index.entries.len(), 100);
+///
+/// for entry in index.entries {
+///     let (key, value) = entry?;
+///
+///     if key == "entry 42" {
+///         assert_eq!(value, 42);
+///     }
+/// }
+/// # Ok::<_, Error>(())
+/// ```
+pub struct LazyMap<'de, K, V, E> {
+    len: usize,
+    bytes: &'de [u8],
+    _marker: PhantomData<fn() -> (K, V, E)>,
+}
+
+/// An encoding which the entries of a [`LazyMap`] can be decoded through.
+///
+/// This is implemented by the encodings of formats which support lazy maps.
+pub trait LazyMapEncoding<'de, K, V> {
+    /// The error raised when decoding an entry.
+    type Error;
+
+    /// The iterator decoding entries.
+    type Iter: Iterator<Item = Result<(K, V), Self::Error>>;
+
+    /// Construct an iterator decoding entries with keys of type `K` and values
+    /// of type `V` which are stored back to back in the given bytes.
+    fn decode_lazy_map(bytes: &'de [u8]) -> Self::Iter;
+}
+
+impl<'de, K, V, E> LazyMap<'de, K, V, E> {
+    /// Get the number of entries in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Test if the map is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the encoded bytes of the entries in the map.
+    ///
+    /// Each entry is stored as a key followed by its value in the encoding
+    /// `E`.
+    #[inline]
+    pub fn as_bytes(&self) -> &'de [u8] {
+        self.bytes
+    }
+
+    /// Construct an iterator decoding the entries of the map using the
+    /// encoding `E`.
+    #[inline]
+    pub fn iter(&self) -> E::Iter
+    where
+        E: LazyMapEncoding<'de, K, V>,
+    {
+        E::decode_lazy_map(self.bytes)
+    }
+}
+
+impl<'de, K, V, E> IntoIterator for LazyMap<'de, K, V, E>
+where
+    E: LazyMapEncoding<'de, K, V>,
+{
+    type Item = Result<(K, V), E::Error>;
+    type IntoIter = E::Iter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        E::decode_lazy_map(self.bytes)
+    }
+}
+
+impl<'de, K, V, E> IntoIterator for &LazyMap<'de, K, V, E>
+where
+    E: LazyMapEncoding<'de, K, V>,
+{
+    type Item = Result<(K, V), E::Error>;
+    type IntoIter = E::Iter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        E::decode_lazy_map(self.bytes)
+    }
+}
+
+impl<K, V, E> Clone for LazyMap<'_, K, V, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V, E> Copy for LazyMap<'_, K, V, E> {}
+
+impl<K, V, E> fmt::Debug for LazyMap<'_, K, V, E> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyMap")
+            .field("len", &self.len)
+            .field("bytes", &self.bytes.len())
+            .finish()
+    }
+}
+
+impl<'de, M, A, K, V, E> Decode<'de, M, A> for LazyMap<'de, K, V, E>
+where
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        let cx = decoder.cx();
+
+        decoder.decode_map(|map| {
+            let Some(start) = map.try_borrow_remaining() else {
+                return Err(
+                    cx.message("Lazy maps can only be decoded from a borrowed slice of bytes")
+                );
+            };
+
+            let mut len = 0usize;
+
+            while let Some(mut entry) = map.decode_entry()? {
+                if !entry.decode_key()?.try_skip()?.is_skipped() {
+                    return Err(cx.message(
+                        "Lazy maps can only be decoded by formats which can skip values",
+                    ));
+                }
+
+                entry.decode_value()?.skip()?;
+                len = len.wrapping_add(1);
+            }
+
+            let Some(bytes) = map
+                .try_borrow_remaining()
+                .and_then(|end| start.get(..start.len().checked_sub(end.len())?))
+            else {
+                return Err(cx.message("Lost track of the borrowed slice of bytes"));
+            };
+
+            Ok(LazyMap {
+                len,
+                bytes,
+                _marker: PhantomData,
+            })
+        })
+    }
+}
@@ -0,0 +1,214 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{Allocator, Context};
+
+use super::{Decode, Decoder, SequenceDecoder};
+
+/// A sequence which is decoded lazily.
+///
+/// Decoding a `LazySeq` only records the number of elements and the range of
+/// bytes which hold them, without collecting the elements into a container.
+/// The elements are stored back to back in the encoding `E`, which should be
+/// the same encoding as the outer value was decoded with. They can later be
+/// decoded one at a time through [`LazySeq::iter`].
+///
+/// This requires a format which decodes from a borrowed slice of bytes and
+/// which can skip over values without decoding them, such as `musli::wire` or
+/// `musli::descriptive`. Formats which don't describe their values, like
+/// `musli::storage`, can't find where an element ends without decoding it, so
+/// decoding a `LazySeq` through them results in an error.
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::de::LazySeq;
+/// use musli::wire::Encoding;
+/// # use musli::wire::Error;
+///
+/// const ENCODING: Encoding = Encoding::new();
+///
+/// #[derive(Encode)]
+/// struct Samples {
+///     name: String,
+///     values: Vec<u32>,
+/// }
+///
+/// #[derive(Decode)]
+/// struct LazySamples<'de> {
+///     name: String,
+///     values: LazySeq<'de, u32, Encoding>,
+/// }
+///
+/// let data = ENCODING.to_vec(&Samples {
+///     name: "samples".to_string(),
+///     values: (0..1000).collect(),
+/// })?;
+///
+/// let samples: LazySamples<'_> = ENCODING.from_slice(&data)?;
+/// assert_eq!(samples.name, "samples");
+/// assert_eq!(samples.values.len(), 1000);
+///
+/// let mut total = 0;
+///
+/// for value in samples.values {
+///     let value = value?;
+///
+///     if value % 100 == 0 {
+///         total += value;
+///     }
+/// }
+///
+/// assert_eq!(total, 4500);
+/// # Ok::<_, Error>(())
+/// ```
+pub struct LazySeq<'de, T, E> {
+    len: usize,
+    bytes: &'de [u8],
+    _marker: PhantomData<fn() -> (T, E)>,
+}
+
+/// An encoding which the elements of a [`LazySeq`] can be decoded through.
+///
+/// This is implemented by the encodings of formats which support lazy
+/// sequences.
+pub trait LazySeqEncoding<'de, T> {
+    /// The error raised when decoding an element.
+    type Error;
+
+    /// The iterator decoding elements.
+    type Iter: Iterator<Item = Result<T, Self::Error>>;
+
+    /// Construct an iterator decoding elements of type `T` which are stored
+    /// back to back in the given bytes.
+    fn decode_lazy(bytes: &'de [u8]) -> Self::Iter;
+}
+
+impl<'de, T, E> LazySeq<'de, T, E> {
+    /// Get the number of elements in the sequence.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Test if the sequence is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the encoded bytes of the elements in the sequence.
+    ///
+    /// These are stored back to back in the encoding `E`.
+    #[inline]
+    pub fn as_bytes(&self) -> &'de [u8] {
+        self.bytes
+    }
+
+    /// Construct an iterator decoding the elements of the sequence using the
+    /// encoding `E`.
+    #[inline]
+    pub fn iter(&self) -> E::Iter
+    where
+        E: LazySeqEncoding<'de, T>,
+    {
+        E::decode_lazy(self.bytes)
+    }
+}
+
+impl<'de, T, E> IntoIterator for LazySeq<'de, T, E>
+where
+    E: LazySeqEncoding<'de, T>,
+{
+    type Item = Result<T, E::Error>;
+    type IntoIter = E::Iter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        E::decode_lazy(self.bytes)
+    }
+}
+
+impl<'de, T, E> IntoIterator for &LazySeq<'de, T, E>
+where
+    E: LazySeqEncoding<'de, T>,
+{
+    type Item = Result<T, E::Error>;
+    type IntoIter = E::Iter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        E::decode_lazy(self.bytes)
+    }
+}
+
+impl<T, E> Clone for LazySeq<'_, T, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E> Copy for LazySeq<'_, T, E> {}
+
+impl<T, E> fmt::Debug for LazySeq<'_, T, E> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazySeq")
+            .field("len", &self.len)
+            .field("bytes", &self.bytes.len())
+            .finish()
+    }
+}
+
+impl<'de, M, A, T, E> Decode<'de, M, A> for LazySeq<'de, T, E>
+where
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        let cx = decoder.cx();
+
+        decoder.decode_sequence(|seq| {
+            let Some(start) = seq.try_borrow_remaining() else {
+                return Err(
+                    cx.message("Lazy sequences can only be decoded from a borrowed slice of bytes")
+                );
+            };
+
+            let mut len = 0;
+
+            while let Some(value) = seq.try_decode_next()? {
+                cx.enter_sequence_index(len);
+
+                if !value.try_skip()?.is_skipped() {
+                    return Err(cx.message(
+                        "Lazy sequences can only be decoded by formats which can skip values",
+                    ));
+                }
+
+                cx.leave_sequence_index();
+                len = len.wrapping_add(1);
+            }
+
+            let Some(bytes) = seq
+                .try_borrow_remaining()
+                .and_then(|end| start.get(..start.len().checked_sub(end.len())?))
+            else {
+                return Err(cx.message("Lost track of the borrowed slice of bytes"));
+            };
+
+            Ok(LazySeq {
+                len,
+                bytes,
+                _marker: PhantomData,
+            })
+        })
+    }
+}
@@ -42,6 +42,19 @@ pub trait MapDecoder<'de> {
         SizeHint::any()
     }
 
+    /// Try to borrow the remaining input which the map is being decoded from.
+    ///
+    /// This is only supported by formats which decode from a borrowed slice
+    /// of bytes, and is used by [`LazyMap`] to capture the raw bytes of
+    /// entries without collecting them. The default implementation returns
+    /// `None`.
+    ///
+    /// [`LazyMap`]: crate::de::LazyMap
+    #[inline]
+    fn try_borrow_remaining(&self) -> Option<&'de [u8]> {
+        None
+    }
+
     /// Decode the next key. This returns `Ok(None)` where there are no more
     /// elements to decode.
     #[must_use = "Decoders must be consumed"]
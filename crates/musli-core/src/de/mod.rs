@@ -49,6 +49,7 @@ pub use self::__traits::*;
 mod as_decoder;
 mod decode;
 mod decode_bytes;
+mod decode_flatten;
 mod decode_owned;
 mod decode_packed;
 mod decode_slice_builder;
@@ -58,6 +59,8 @@ mod decode_unsized_bytes;
 mod decoder;
 mod entries_decoder;
 mod entry_decoder;
+mod lazy_map;
+mod lazy_seq;
 mod map_decoder;
 mod sequence_decoder;
 mod size_hint;
@@ -71,6 +74,7 @@ pub mod __traits {
     pub use super::as_decoder::AsDecoder;
     pub use super::decode::Decode;
     pub use super::decode_bytes::DecodeBytes;
+    pub use super::decode_flatten::DecodeFlatten;
     pub use super::decode_owned::DecodeOwned;
     pub use super::decode_packed::DecodePacked;
     pub use super::decode_slice_builder::DecodeSliceBuilder;
@@ -80,6 +84,8 @@ pub mod __traits {
     pub use super::decoder::{Decoder, TryFastDecode};
     pub use super::entries_decoder::EntriesDecoder;
     pub use super::entry_decoder::EntryDecoder;
+    pub use super::lazy_map::{LazyMap, LazyMapEncoding};
+    pub use super::lazy_seq::{LazySeq, LazySeqEncoding};
     pub use super::map_decoder::MapDecoder;
     pub use super::sequence_decoder::SequenceDecoder;
     pub use super::size_hint::SizeHint;
@@ -32,6 +32,20 @@ pub trait SequenceDecoder<'de> {
         SizeHint::any()
     }
 
+    /// Try to borrow the remaining input which the sequence is being decoded
+    /// from.
+    ///
+    /// This is only supported by formats which decode from a borrowed slice
+    /// of bytes, and is used by [`LazySeq`] to capture the raw bytes of
+    /// elements without collecting them. The default implementation returns
+    /// `None`.
+    ///
+    /// [`LazySeq`]: crate::de::LazySeq
+    #[inline]
+    fn try_borrow_remaining(&self) -> Option<&'de [u8]> {
+        None
+    }
+
     /// Return decoder to decode the next element.
     ///
     /// This will error or provide garbled data in case the next element is not
@@ -222,6 +222,40 @@ where
         )))
     }
 
+    /// Indicates that the visited type is a number in its textual form.
+    ///
+    /// This is used by formats which can represent numbers with arbitrary
+    /// precision, so that the visitor can retain it without loss of precision.
+    /// By default the number is parsed into the smallest primitive that can
+    /// represent it, which is then visited instead.
+    #[inline]
+    fn visit_number(self, cx: C, number: &str) -> Result<Self::Ok, Self::Error> {
+        if let Ok(value) = number.parse::<u128>() {
+            return match value {
+                value if value <= u8::MAX as u128 => self.visit_u8(cx, value as u8),
+                value if value <= u16::MAX as u128 => self.visit_u16(cx, value as u16),
+                value if value <= u32::MAX as u128 => self.visit_u32(cx, value as u32),
+                value if value <= u64::MAX as u128 => self.visit_u64(cx, value as u64),
+                value => self.visit_u128(cx, value),
+            };
+        }
+
+        if let Ok(value) = number.parse::<i128>() {
+            return match value {
+                value if value >= i8::MIN as i128 => self.visit_i8(cx, value as i8),
+                value if value >= i16::MIN as i128 => self.visit_i16(cx, value as i16),
+                value if value >= i32::MIN as i128 => self.visit_i32(cx, value as i32),
+                value if value >= i64::MIN as i128 => self.visit_i64(cx, value as i64),
+                value => self.visit_i128(cx, value),
+            };
+        }
+
+        match number.parse::<f64>() {
+            Ok(value) => self.visit_f64(cx, value),
+            Err(..) => Err(cx.message(format_args!("Invalid number `{number}`"))),
+        }
+    }
+
     /// Indicates that the visited type is an optional value that is absent.
     #[inline]
     fn visit_none(self, cx: C) -> Result<Self::Ok, Self::Error> {
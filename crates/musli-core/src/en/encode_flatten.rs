@@ -0,0 +1,77 @@
+use crate::en::MapEncoder;
+
+/// Trait governing how a type is encoded when it's flattened into the map of
+/// its parent.
+///
+/// This is used automatically through the `#[musli(flatten)]` field attribute
+/// of the [`Encode` derive], which implements this trait for structs marked
+/// with `#[musli(flattenable)]`. It is also implemented for maps such as
+/// [`HashMap<K, V>`], which allows a map to be used as a catch-all for unknown
+/// fields.
+///
+/// [`Encode` derive]: https://docs.rs/musli/latest/musli/_help/derives/
+/// [`HashMap<K, V>`]: std::collections::HashMap
+///
+/// # Examples
+///
+/// ```
+/// use musli::Encode;
+///
+/// #[derive(Encode)]
+/// #[musli(flattenable)]
+/// struct Metadata {
+///     id: u32,
+/// }
+///
+/// #[derive(Encode)]
+/// struct Message {
+///     body: String,
+///     #[musli(flatten)]
+///     metadata: Metadata,
+/// }
+/// ```
+pub trait EncodeFlatten<M> {
+    /// Encode the entries of the value into the map of the parent.
+    fn encode_flatten<E>(&self, encoder: &mut E) -> Result<(), E::Error>
+    where
+        E: MapEncoder<Mode = M>;
+
+    /// The number of entries the value adds to the map of the parent.
+    fn flatten_len(&self) -> usize;
+}
+
+impl<T, M> EncodeFlatten<M> for &T
+where
+    T: ?Sized + EncodeFlatten<M>,
+{
+    #[inline]
+    fn encode_flatten<E>(&self, encoder: &mut E) -> Result<(), E::Error>
+    where
+        E: MapEncoder<Mode = M>,
+    {
+        (**self).encode_flatten(encoder)
+    }
+
+    #[inline]
+    fn flatten_len(&self) -> usize {
+        (**self).flatten_len()
+    }
+}
+
+impl<T, M> EncodeFlatten<M> for &mut T
+where
+    T: ?Sized + EncodeFlatten<M>,
+{
+    #[inline]
+    fn encode_flatten<E>(&self, encoder: &mut E) -> Result<(), E::Error>
+    where
+        E: MapEncoder<Mode = M>,
+    {
+        (**self).encode_flatten(encoder)
+    }
+
+    #[inline]
+    fn flatten_len(&self) -> usize {
+        (**self).flatten_len()
+    }
+}
@@ -885,6 +885,61 @@ pub trait Encoder: Sized {
         )))
     }
 
+    /// Encode a number in its textual form.
+    ///
+    /// Formats which can represent numbers with arbitrary precision, such as
+    /// JSON, encode the number as-is. By default the number is parsed into the
+    /// first of `u64`, `i64`, `u128`, `i128` or `f64` which can represent it,
+    /// which is then encoded instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Encode, Encoder};
+    ///
+    /// struct Decimal(&'static str);
+    ///
+    /// impl<M> Encode<M> for Decimal {
+    ///     type Encode = Self;
+    ///
+    ///     #[inline]
+    ///     fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    ///     where
+    ///         E: Encoder,
+    ///     {
+    ///         encoder.encode_number(self.0)
+    ///     }
+    ///
+    ///     #[inline]
+    ///     fn as_encode(&self) -> &Self::Encode {
+    ///         self
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_number(self, number: &str) -> Result<(), Self::Error> {
+        if let Ok(value) = number.parse::<u64>() {
+            return self.encode_u64(value);
+        }
+
+        if let Ok(value) = number.parse::<i64>() {
+            return self.encode_i64(value);
+        }
+
+        if let Ok(value) = number.parse::<u128>() {
+            return self.encode_u128(value);
+        }
+
+        if let Ok(value) = number.parse::<i128>() {
+            return self.encode_i128(value);
+        }
+
+        match number.parse::<f64>() {
+            Ok(value) => self.encode_f64(value),
+            Err(..) => Err(self.cx().message(format_args!("Invalid number `{number}`"))),
+        }
+    }
+
     /// Encode fixed-length array.
     ///
     /// # Examples
@@ -1436,6 +1491,94 @@ pub trait Encoder: Sized {
         utils::default_encode_slices(self, len, slices)
     }
 
+    /// Encode a sequence from an iterator which might not know its length.
+    ///
+    /// If the iterator reports an exact [`Iterator::size_hint`] its elements
+    /// are encoded as they are produced, otherwise they are first buffered
+    /// using the allocator associated with the context. Formats which do not
+    /// need the length up front, such as JSON, specialize this to always encode
+    /// elements as they are produced.
+    ///
+    /// This defaults to using [`Encoder::encode_sequence`] and if specialized
+    /// must implement the same format as would calling that method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Encode, Encoder};
+    /// # struct MyType { data: Vec<u32> }
+    ///
+    /// impl<M> Encode<M> for MyType {
+    ///     type Encode = Self;
+    ///
+    ///     #[inline]
+    ///     fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    ///     where
+    ///         E: Encoder,
+    ///     {
+    ///         encoder.encode_sequence_iter(self.data.iter().filter(|n| *n % 2 == 0))
+    ///     }
+    ///
+    ///     #[inline]
+    ///     fn as_encode(&self) -> &Self::Encode {
+    ///         self
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_sequence_iter<I>(self, iter: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item: Encode<Self::Mode>>,
+    {
+        utils::default_encode_sequence_iter(self, iter)
+    }
+
+    /// Encode a map from an iterator of key-value pairs which might not know
+    /// its length.
+    ///
+    /// If the iterator reports an exact [`Iterator::size_hint`] its entries are
+    /// encoded as they are produced, otherwise they are first buffered using
+    /// the allocator associated with the context. Formats which do not need the
+    /// length up front, such as JSON, specialize this to always encode entries
+    /// as they are produced.
+    ///
+    /// This defaults to using [`Encoder::encode_map`] and if specialized must
+    /// implement the same format as would calling that method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use musli::{Encode, Encoder};
+    /// # struct MyType { data: HashMap<String, u32> }
+    ///
+    /// impl<M> Encode<M> for MyType {
+    ///     type Encode = Self;
+    ///
+    ///     #[inline]
+    ///     fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    ///     where
+    ///         E: Encoder,
+    ///     {
+    ///         encoder.encode_map_iter(self.data.iter().filter(|(_, n)| **n > 0))
+    ///     }
+    ///
+    ///     #[inline]
+    ///     fn as_encode(&self) -> &Self::Encode {
+    ///         self
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_map_iter<I, K, V>(self, iter: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Encode<Self::Mode>,
+        V: Encode<Self::Mode>,
+    {
+        utils::default_encode_map_iter(self, iter)
+    }
+
     /// Encode a sequence with a known length `len`.
     ///
     /// A sequence encodes one element following another and must in some way
@@ -2065,6 +2208,87 @@ pub trait Encoder: Sized {
             ExpectingWrapper::new(&self),
         )))
     }
+
+    /// Encode a value annotated with a numerical semantic tag.
+    ///
+    /// Formats which natively support tagging values, such as CBOR, encode
+    /// this as a tag. By default this is encoded as a variant where the tag is
+    /// encoded as a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Encode, Encoder};
+    ///
+    /// struct Timestamp(u64);
+    ///
+    /// impl<M> Encode<M> for Timestamp {
+    ///     type Encode = Self;
+    ///
+    ///     #[inline]
+    ///     fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    ///     where
+    ///         E: Encoder<Mode = M>,
+    ///     {
+    ///         encoder.encode_tagged(1, &self.0)
+    ///     }
+    ///
+    ///     #[inline]
+    ///     fn as_encode(&self) -> &Self::Encode {
+    ///         self
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_tagged<T>(self, tag: u64, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Encode<Self::Mode>,
+    {
+        self.encode_variant_fn(|variant| {
+            variant.encode_tag()?.encode_u64(tag)?;
+            variant.encode_data()?.encode(value)?;
+            Ok(())
+        })
+    }
+
+    /// Encode already serialized source verbatim.
+    ///
+    /// Text formats which support this, such as JSON, write the source
+    /// directly to the output. It is up to the caller to ensure that it is
+    /// valid for the format being encoded. No other formats support this by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Encode, Encoder};
+    ///
+    /// struct Raw<'a>(&'a str);
+    ///
+    /// impl<M> Encode<M> for Raw<'_> {
+    ///     type Encode = Self;
+    ///
+    ///     #[inline]
+    ///     fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    ///     where
+    ///         E: Encoder<Mode = M>,
+    ///     {
+    ///         encoder.encode_raw(self.0)
+    ///     }
+    ///
+    ///     #[inline]
+    ///     fn as_encode(&self) -> &Self::Encode {
+    ///         self
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_raw(self, raw: &str) -> Result<(), Self::Error> {
+        Err(self.cx().message(expecting::unsupported_type(
+            &expecting::Raw,
+            ExpectingWrapper::new(&self),
+        )))
+    }
 }
 
 #[repr(transparent)]
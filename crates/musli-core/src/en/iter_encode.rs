@@ -0,0 +1,65 @@
+use super::{Encode, Encoder};
+
+/// Encode the elements produced by an iterator as a sequence.
+///
+/// Since [`Encode`] only has access to a shared reference, the iterator is
+/// cloned each time the value is encoded. Iterators over borrowed collections
+/// and most iterator adapters are cheap to clone.
+///
+/// This uses [`Encoder::encode_sequence_iter`], so the iterator does not need
+/// to know its length ahead of time.
+///
+/// # Examples
+///
+/// ```
+/// use musli::en::IterEncode;
+/// # use musli::json::Error;
+///
+/// let values = [1u32, 2, 3, 4, 5, 6];
+/// let even = IterEncode::new(values.iter().filter(|n| *n % 2 == 0));
+///
+/// let json = musli::json::to_string(&even)?;
+/// assert_eq!(json, "[2,4,6]");
+///
+/// let json = musli::json::to_string(&(&even, "done"))?;
+/// assert_eq!(json, r#"[[2,4,6],"done"]"#);
+/// # Ok::<_, Error>(())
+/// ```
+#[derive(Clone, Copy)]
+pub struct IterEncode<I> {
+    iter: I,
+}
+
+impl<I> IterEncode<I> {
+    /// Construct a new wrapper encoding the elements of the given iterator.
+    #[inline]
+    pub const fn new(iter: I) -> Self {
+        Self { iter }
+    }
+
+    /// Coerce back into the wrapped iterator.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<M, I> Encode<M> for IterEncode<I>
+where
+    I: Clone + IntoIterator<Item: Encode<M>>,
+{
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_sequence_iter(self.iter.clone())
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
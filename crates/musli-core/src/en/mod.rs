@@ -59,11 +59,13 @@ pub use self::__traits::*;
 
 mod encode;
 mod encode_bytes;
+mod encode_flatten;
 mod encode_packed;
 mod encode_trace;
 mod encoder;
 mod entries_encoder;
 mod entry_encoder;
+mod iter_encode;
 mod map_encoder;
 mod sequence_encoder;
 mod variant_encoder;
@@ -72,11 +74,13 @@ mod variant_encoder;
 pub mod __traits {
     pub use super::encode::Encode;
     pub use super::encode_bytes::EncodeBytes;
+    pub use super::encode_flatten::EncodeFlatten;
     pub use super::encode_packed::EncodePacked;
     pub use super::encode_trace::EncodeTrace;
     pub use super::encoder::{Encoder, TryFastEncode};
     pub use super::entries_encoder::EntriesEncoder;
     pub use super::entry_encoder::EntryEncoder;
+    pub use super::iter_encode::IterEncode;
     pub use super::map_encoder::MapEncoder;
     pub use super::sequence_encoder::SequenceEncoder;
     pub use super::variant_encoder::VariantEncoder;
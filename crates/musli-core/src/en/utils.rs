@@ -1,6 +1,9 @@
+use core::fmt;
+
 use crate::Context;
+use crate::alloc::Vec;
 
-use super::{Encode, Encoder, SequenceEncoder};
+use super::{Encode, Encoder, MapEncoder, SequenceEncoder};
 
 /// The default implementation of [`Encoder::encode_slice`].
 #[inline]
@@ -51,6 +54,129 @@ where
     seq.finish_sequence()
 }
 
+/// The default implementation of [`Encoder::encode_sequence_iter`].
+///
+/// If the iterator reports an exact length the elements are streamed directly,
+/// otherwise they are buffered using the allocator of the context so that the
+/// length is known before the sequence is encoded.
+#[inline]
+pub fn default_encode_sequence_iter<E, I>(encoder: E, iter: I) -> Result<(), E::Error>
+where
+    E: Encoder,
+    I: IntoIterator<Item: Encode<E::Mode>>,
+{
+    let cx = encoder.cx();
+    let iter = iter.into_iter();
+
+    let (lower, upper) = iter.size_hint();
+
+    if upper == Some(lower) {
+        let mut seq = encoder.encode_sequence(lower)?;
+        let mut index = 0usize;
+
+        for item in iter {
+            if index == lower {
+                return Err(cx.message(LengthMismatch { expected: lower }));
+            }
+
+            cx.enter_sequence_index(index);
+            seq.push(item)?;
+            cx.leave_sequence_index();
+            index = index.wrapping_add(1);
+        }
+
+        if index != lower {
+            return Err(cx.message(LengthMismatch { expected: lower }));
+        }
+
+        return seq.finish_sequence();
+    }
+
+    let mut buf = Vec::new_in(cx.alloc());
+
+    for item in iter {
+        buf.push(item).map_err(cx.map())?;
+    }
+
+    let mut seq = encoder.encode_sequence(buf.len())?;
+
+    for (index, item) in buf.iter().enumerate() {
+        cx.enter_sequence_index(index);
+        seq.push(item)?;
+        cx.leave_sequence_index();
+    }
+
+    seq.finish_sequence()
+}
+
+/// The default implementation of [`Encoder::encode_map_iter`].
+///
+/// If the iterator reports an exact length the entries are streamed directly,
+/// otherwise they are buffered using the allocator of the context so that the
+/// length is known before the map is encoded.
+#[inline]
+pub fn default_encode_map_iter<E, I, K, V>(encoder: E, iter: I) -> Result<(), E::Error>
+where
+    E: Encoder,
+    I: IntoIterator<Item = (K, V)>,
+    K: Encode<E::Mode>,
+    V: Encode<E::Mode>,
+{
+    let cx = encoder.cx();
+    let iter = iter.into_iter();
+
+    let (lower, upper) = iter.size_hint();
+
+    if upper == Some(lower) {
+        let mut map = encoder.encode_map(lower)?;
+        let mut index = 0usize;
+
+        for (key, value) in iter {
+            if index == lower {
+                return Err(cx.message(LengthMismatch { expected: lower }));
+            }
+
+            map.insert_entry(key, value)?;
+            index = index.wrapping_add(1);
+        }
+
+        if index != lower {
+            return Err(cx.message(LengthMismatch { expected: lower }));
+        }
+
+        return map.finish_map();
+    }
+
+    let mut buf = Vec::new_in(cx.alloc());
+
+    for entry in iter {
+        buf.push(entry).map_err(cx.map())?;
+    }
+
+    let mut map = encoder.encode_map(buf.len())?;
+
+    for (key, value) in buf.iter() {
+        map.insert_entry(key, value)?;
+    }
+
+    map.finish_map()
+}
+
+struct LengthMismatch {
+    expected: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let LengthMismatch { expected } = self;
+        write!(
+            f,
+            "Iterator did not yield the {expected} elements it reported"
+        )
+    }
+}
+
 /// The default implementation of [`SequenceEncoder::encode_slice`].
 #[inline]
 pub fn default_sequence_encode_slice<E, T>(
@@ -146,4 +146,5 @@ expect! {
     pub(crate) SequenceVariant("sequence variant");
     pub(crate) Variant("variant");
     pub(crate) AnyValue("a value");
+    pub(crate) Raw("raw value");
 }
@@ -4,6 +4,12 @@ use crate::de::SizeHint;
 mod sealed {
     pub trait Sealed {}
     impl<T, M> Sealed for crate::__priv::EncodeMapHint<'_, T, M> where T: ?Sized {}
+    impl<T, R, M> Sealed for crate::__priv::EncodeRemoteMapHint<'_, T, R, M>
+    where
+        T: ?Sized,
+        R: ?Sized,
+    {
+    }
     impl Sealed for usize {}
     impl Sealed for Option<usize> {}
 }
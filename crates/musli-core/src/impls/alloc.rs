@@ -21,12 +21,12 @@ use std::path::{Path, PathBuf};
 
 use crate::alloc::ToOwned;
 use crate::de::{
-    Decode, DecodeBytes, DecodeTrace, Decoder, EntryDecoder, MapDecoder, SequenceDecoder,
-    UnsizedVisitor,
+    Decode, DecodeBytes, DecodeFlatten, DecodeTrace, Decoder, EntryDecoder, MapDecoder,
+    SequenceDecoder, UnsizedVisitor,
 };
 use crate::en::{
-    Encode, EncodeBytes, EncodePacked, EncodeTrace, Encoder, EntryEncoder, MapEncoder,
-    SequenceEncoder,
+    Encode, EncodeBytes, EncodeFlatten, EncodePacked, EncodeTrace, Encoder, EntryEncoder,
+    MapEncoder, SequenceEncoder,
 };
 use crate::internal::size_hint;
 use crate::{Allocator, Context};
@@ -489,7 +489,8 @@ macro_rules! map {
         $cx:ident,
         $ty:ident<K $(: $key_bound0:ident $(+ $key_bound:ident)*)?, V $(, $extra:ident: $extra_bound0:ident $(+ $extra_bound:ident)*)*>,
         $access:ident,
-        $with_capacity:expr
+        $with_capacity:expr,
+        $new:expr
     ) => {
         $(#[$($meta)*])*
         impl<'de, M, K, V $(, $extra)*> Encode<M> for $ty<K, V $(, $extra)*>
@@ -556,6 +557,31 @@ macro_rules! map {
             }
         }
 
+        $(#[$($meta)*])*
+        impl<M, K, V $(, $extra)*> EncodeFlatten<M> for $ty<K, V $(, $extra)*>
+        where
+            K: Encode<M>,
+            V: Encode<M>,
+            $($extra: $extra_bound0 $(+ $extra_bound)*),*
+        {
+            #[inline]
+            fn encode_flatten<E>(&self, encoder: &mut E) -> Result<(), E::Error>
+            where
+                E: MapEncoder<Mode = M>,
+            {
+                for (k, v) in self {
+                    encoder.insert_entry(k, v)?;
+                }
+
+                Ok(())
+            }
+
+            #[inline]
+            fn flatten_len(&self) -> usize {
+                self.len()
+            }
+        }
+
         $(#[$($meta)*])*
         impl<'de, K, V, A, M $(, $extra)*> Decode<'de, M, A> for $ty<K, V $(, $extra)*>
         where
@@ -583,6 +609,45 @@ macro_rules! map {
             }
         }
 
+        $(#[$($meta)*])*
+        impl<'de, K, V, A, M, Q $(, $extra)*> DecodeFlatten<'de, M, A, Q> for $ty<K, V $(, $extra)*>
+        where
+            A: Allocator,
+            K: $($key_bound0 $(+ $key_bound)*)*,
+            V: Decode<'de, M, A>,
+            Q: ?Sized + rust_alloc::borrow::ToOwned<Owned = K>,
+            $($extra: $extra_bound0 $(+ $extra_bound)*),*
+        {
+            type Partial = Self;
+
+            #[inline]
+            fn partial() -> Self::Partial {
+                $new
+            }
+
+            #[inline]
+            fn decode_flatten_entry<D>(
+                partial: &mut Self::Partial,
+                name: &Q,
+                decoder: D,
+            ) -> Result<Option<D>, D::Error>
+            where
+                D: EntryDecoder<'de, Mode = M, Allocator = A>,
+            {
+                let value = decoder.decode_value()?.decode()?;
+                partial.insert(rust_alloc::borrow::ToOwned::to_owned(name), value);
+                Ok(None)
+            }
+
+            #[inline]
+            fn finish_flatten<C>(_: C, partial: Self::Partial) -> Result<Self, C::Error>
+            where
+                C: Context<Allocator = A>,
+            {
+                Ok(partial)
+            }
+        }
+
         $(#[$($meta)*])*
         impl<'de, K, V, A, M $(, $extra)*> DecodeTrace<'de, M, A> for $ty<K, V $(, $extra)*>
         where
@@ -616,7 +681,7 @@ macro_rules! map {
     }
 }
 
-map!(_cx, BTreeMap<K: Ord, V>, map, BTreeMap::new());
+map!(_cx, BTreeMap<K: Ord, V>, map, BTreeMap::new(), BTreeMap::new());
 
 map!(
     #[cfg(feature = "std")]
@@ -624,7 +689,8 @@ map!(
     _cx,
     HashMap<K: Eq + Hash, V, S: BuildHasher + Default>,
     map,
-    HashMap::with_capacity_and_hasher(size_hint::cautious::<(K, V)>(map.size_hint()), S::default())
+    HashMap::with_capacity_and_hasher(size_hint::cautious::<(K, V)>(map.size_hint()), S::default()),
+    HashMap::with_hasher(S::default())
 );
 
 impl<M> Encode<M> for CString {
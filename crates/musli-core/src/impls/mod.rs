@@ -5,16 +5,22 @@ mod alloc;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
 mod net;
 mod range;
+mod time;
 mod tuples;
 
 #[cfg(feature = "std")]
 use core::any::TypeId;
+use core::cell::{Cell, RefCell};
+use core::cmp::{Ordering, Reverse};
 use core::ffi::CStr;
 use core::num::{
     NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize, NonZeroU8,
     NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize, Saturating, Wrapping,
 };
+use core::ops::Bound;
 use core::{fmt, marker};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, RwLock};
 
 use crate::de::{
     Decode, DecodeBytes, DecodePacked, DecodeUnsized, DecodeUnsizedBytes, Decoder, SequenceDecoder,
@@ -923,6 +929,326 @@ where
     }
 }
 
+impl<T, M> Encode<M> for Reverse<T>
+where
+    T: Encode<M>,
+{
+    const IS_BITWISE_ENCODE: bool = T::IS_BITWISE_ENCODE;
+
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        self.0.encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, T, A> Decode<'de, M, A> for Reverse<T>
+where
+    T: Decode<'de, M, A>,
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = T::IS_BITWISE_DECODE;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        Ok(Reverse(decoder.decode()?))
+    }
+}
+
+impl<T, M> Encode<M> for Cell<T>
+where
+    T: Copy + Encode<M>,
+{
+    type Encode = Self;
+
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        self.get().encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, T, A> Decode<'de, M, A> for Cell<T>
+where
+    T: Decode<'de, M, A>,
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        Ok(Cell::new(decoder.decode()?))
+    }
+}
+
+impl<T, M> Encode<M> for RefCell<T>
+where
+    T: ?Sized + Encode<M>,
+{
+    type Encode = Self;
+
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        let cx = encoder.cx();
+
+        let Ok(value) = self.try_borrow() else {
+            return Err(cx.message("RefCell is already mutably borrowed"));
+        };
+
+        value.encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, T, A> Decode<'de, M, A> for RefCell<T>
+where
+    T: Decode<'de, M, A>,
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        Ok(RefCell::new(decoder.decode()?))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, M> Encode<M> for Mutex<T>
+where
+    T: ?Sized + Encode<M>,
+{
+    type Encode = Self;
+
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        let cx = encoder.cx();
+
+        let Ok(value) = self.lock() else {
+            return Err(cx.message("Mutex is poisoned"));
+        };
+
+        value.encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, M, T, A> Decode<'de, M, A> for Mutex<T>
+where
+    T: Decode<'de, M, A>,
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        Ok(Mutex::new(decoder.decode()?))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, M> Encode<M> for RwLock<T>
+where
+    T: ?Sized + Encode<M>,
+{
+    type Encode = Self;
+
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        let cx = encoder.cx();
+
+        let Ok(value) = self.read() else {
+            return Err(cx.message("RwLock is poisoned"));
+        };
+
+        value.encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, M, T, A> Decode<'de, M, A> for RwLock<T>
+where
+    T: Decode<'de, M, A>,
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        Ok(RwLock::new(decoder.decode()?))
+    }
+}
+
+#[derive(Encode, Decode)]
+#[musli(crate)]
+enum BoundTag {
+    Included,
+    Excluded,
+    Unbounded,
+}
+
+impl<T, M> Encode<M> for Bound<T>
+where
+    T: Encode<M>,
+    BoundTag: Encode<M>,
+{
+    type Encode = Self;
+
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        let variant = encoder.encode_variant()?;
+
+        match self {
+            Bound::Included(value) => variant.insert_variant(&BoundTag::Included, value),
+            Bound::Excluded(value) => variant.insert_variant(&BoundTag::Excluded, value),
+            Bound::Unbounded => variant.insert_variant(&BoundTag::Unbounded, ()),
+        }
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A, T> Decode<'de, M, A> for Bound<T>
+where
+    A: Allocator,
+    T: Decode<'de, M, A>,
+    BoundTag: Decode<'de, M, A>,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        decoder.decode_variant(|variant| {
+            let tag = variant.decode_tag()?.decode()?;
+
+            Ok(match tag {
+                BoundTag::Included => Bound::Included(variant.decode_value()?.decode()?),
+                BoundTag::Excluded => Bound::Excluded(variant.decode_value()?.decode()?),
+                BoundTag::Unbounded => {
+                    variant.decode_value()?.decode::<()>()?;
+                    Bound::Unbounded
+                }
+            })
+        })
+    }
+}
+
+impl<M> Encode<M> for Ordering {
+    type Encode = Self;
+
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_i8(*self as i8)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A> Decode<'de, M, A> for Ordering
+where
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        let cx = decoder.cx();
+
+        match decoder.decode_i8()? {
+            -1 => Ok(Ordering::Less),
+            0 => Ok(Ordering::Equal),
+            1 => Ok(Ordering::Greater),
+            value => Err(cx.message(format_args!(
+                "Unsupported ordering `{value}`, expected -1, 0, or 1"
+            ))),
+        }
+    }
+}
+
 impl<M> Encode<M> for CStr {
     type Encode = Self;
 
@@ -0,0 +1,593 @@
+use core::fmt;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::de::{Decode, Decoder, SequenceDecoder};
+use crate::en::{Encode, Encoder, SequenceEncoder};
+use crate::mode::{Binary, Text};
+use crate::{Allocator, Context};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+impl Encode<Binary> for Duration {
+    type Encode = Self;
+
+    // Not packed since it doesn't have a strongly defined memory layout.
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = Binary>,
+    {
+        encoder.encode_pack_fn(|pack| {
+            pack.push(self.as_secs())?;
+            pack.push(self.subsec_nanos())?;
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl Encode<Text> for Duration {
+    type Encode = Self;
+
+    // Not packed since it doesn't have a strongly defined memory layout.
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = Text>,
+    {
+        encoder.collect_string(&Seconds {
+            secs: self.as_secs(),
+            nanos: self.subsec_nanos(),
+        })
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, A> Decode<'de, Binary, A> for Duration
+where
+    A: Allocator,
+{
+    // Not packed since it doesn't have a strongly defined memory layout.
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = Binary>,
+    {
+        let cx = decoder.cx();
+
+        decoder.decode_pack(|p| {
+            let secs = p.next::<u64>()?;
+            let nanos = p.next::<u32>()?;
+            duration(secs, nanos).map_err(|e| cx.message(e))
+        })
+    }
+}
+
+impl<'de, A> Decode<'de, Text, A> for Duration
+where
+    A: Allocator,
+{
+    // Not packed since it doesn't have a strongly defined memory layout.
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = Text>,
+    {
+        let cx = decoder.cx();
+        decoder.decode_unsized(|string: &str| parse_duration(string).map_err(|e| cx.message(e)))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Encode<Binary> for SystemTime {
+    type Encode = Self;
+
+    // Not packed since it doesn't have a strongly defined memory layout.
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = Binary>,
+    {
+        let cx = encoder.cx();
+        let (secs, nanos) = from_system_time(self).map_err(|e| cx.message(e))?;
+
+        encoder.encode_pack_fn(|pack| {
+            pack.push(secs)?;
+            pack.push(nanos)?;
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl Encode<Text> for SystemTime {
+    type Encode = Self;
+
+    // Not packed since it doesn't have a strongly defined memory layout.
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = Text>,
+    {
+        let cx = encoder.cx();
+        let (secs, nanos) = from_system_time(self).map_err(|e| cx.message(e))?;
+        let timestamp = Timestamp::new(secs, nanos).map_err(|e| cx.message(e))?;
+        encoder.collect_string(&timestamp)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, A> Decode<'de, Binary, A> for SystemTime
+where
+    A: Allocator,
+{
+    // Not packed since it doesn't have a strongly defined memory layout.
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = Binary>,
+    {
+        let cx = decoder.cx();
+
+        decoder.decode_pack(|p| {
+            let secs = p.next::<i64>()?;
+            let nanos = p.next::<u32>()?;
+            to_system_time(secs, nanos).map_err(|e| cx.message(e))
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, A> Decode<'de, Text, A> for SystemTime
+where
+    A: Allocator,
+{
+    // Not packed since it doesn't have a strongly defined memory layout.
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = Text>,
+    {
+        let cx = decoder.cx();
+
+        decoder.decode_unsized(|string: &str| {
+            let (secs, nanos) = parse_timestamp(string).map_err(|e| cx.message(e))?;
+            to_system_time(secs, nanos).map_err(|e| cx.message(e))
+        })
+    }
+}
+
+/// Construct a duration, validating the number of nanoseconds.
+#[inline]
+fn duration(secs: u64, nanos: u32) -> Result<Duration, TimeError> {
+    if nanos >= NANOS_PER_SEC {
+        return Err(TimeError::NanosOutOfRange(nanos));
+    }
+
+    Ok(Duration::new(secs, nanos))
+}
+
+/// Convert a system time into seconds and non-negative nanoseconds relative to
+/// the unix epoch.
+#[cfg(feature = "std")]
+fn from_system_time(time: &SystemTime) -> Result<(i64, u32), TimeError> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => {
+            let secs = i64::try_from(d.as_secs()).map_err(|_| TimeError::Overflow)?;
+            Ok((secs, d.subsec_nanos()))
+        }
+        Err(e) => {
+            let d = e.duration();
+            let secs = i64::try_from(d.as_secs()).map_err(|_| TimeError::Overflow)?;
+
+            if d.subsec_nanos() == 0 {
+                Ok((-secs, 0))
+            } else {
+                let secs = (-secs).checked_sub(1).ok_or(TimeError::Overflow)?;
+                Ok((secs, NANOS_PER_SEC - d.subsec_nanos()))
+            }
+        }
+    }
+}
+
+/// Convert seconds and non-negative nanoseconds relative to the unix epoch into
+/// a system time.
+#[cfg(feature = "std")]
+fn to_system_time(secs: i64, nanos: u32) -> Result<SystemTime, TimeError> {
+    let nanos = duration(0, nanos)?;
+
+    let time = if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(secs.unsigned_abs()))
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))
+    };
+
+    time.and_then(|time| time.checked_add(nanos))
+        .ok_or(TimeError::Overflow)
+}
+
+/// Parse a duration, either as a number of seconds like `1.5` or as an
+/// ISO-8601 duration like `PT1.5S`.
+fn parse_duration(string: &str) -> Result<Duration, TimeError> {
+    let Some(rest) = string.strip_prefix('P') else {
+        let (secs, nanos) = parse_seconds(string).ok_or(TimeError::InvalidDuration)?;
+        return duration(secs, nanos?);
+    };
+
+    let mut secs = 0u64;
+    let mut nanos = 0u32;
+    let mut time = false;
+    let mut rest = rest;
+
+    if rest.is_empty() {
+        return Err(TimeError::InvalidDuration);
+    }
+
+    while !rest.is_empty() {
+        if !time {
+            if let Some(tail) = rest.strip_prefix('T') {
+                if tail.is_empty() {
+                    return Err(TimeError::InvalidDuration);
+                }
+
+                time = true;
+                rest = tail;
+                continue;
+            }
+        }
+
+        let end = rest
+            .find(|c: char| c.is_ascii_alphabetic())
+            .ok_or(TimeError::InvalidDuration)?;
+
+        let (number, unit) = rest.split_at(end);
+        rest = &unit[1..];
+
+        let factor = match (time, &unit[..1]) {
+            (false, "W") => 604_800,
+            (false, "D") => 86_400,
+            (true, "H") => 3_600,
+            (true, "M") => 60,
+            (true, "S") => {
+                let (s, n) = parse_seconds(number).ok_or(TimeError::InvalidDuration)?;
+                secs = secs.checked_add(s).ok_or(TimeError::Overflow)?;
+                nanos = n?;
+
+                if !rest.is_empty() {
+                    return Err(TimeError::InvalidDuration);
+                }
+
+                break;
+            }
+            _ => return Err(TimeError::InvalidDuration),
+        };
+
+        let value = parse_digits(number).ok_or(TimeError::InvalidDuration)?;
+
+        secs = value
+            .checked_mul(factor)
+            .and_then(|value| secs.checked_add(value))
+            .ok_or(TimeError::Overflow)?;
+    }
+
+    duration(secs, nanos)
+}
+
+/// Parse a non-empty string of ascii digits.
+fn parse_digits(string: &str) -> Option<u64> {
+    if string.is_empty() || !string.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    string.parse().ok()
+}
+
+/// Parse seconds with an optional fractional part, returning the whole
+/// seconds and the fractional nanoseconds.
+fn parse_seconds(string: &str) -> Option<(u64, Result<u32, TimeError>)> {
+    let (secs, fraction) = match string.split_once('.') {
+        Some((secs, fraction)) => (secs, Some(fraction)),
+        None => (string, None),
+    };
+
+    let secs = parse_digits(secs)?;
+
+    let nanos = match fraction {
+        Some(fraction) => parse_fraction(fraction)?,
+        None => Ok(0),
+    };
+
+    Some((secs, nanos))
+}
+
+/// Parse the digits following a decimal point into nanoseconds.
+fn parse_fraction(fraction: &str) -> Option<Result<u32, TimeError>> {
+    let digits = u32::try_from(fraction.len()).ok()?;
+    let nanos = parse_digits(fraction)?;
+
+    if digits > 9 {
+        return Some(Err(TimeError::FractionTooPrecise));
+    }
+
+    let nanos = u32::try_from(nanos).ok()? * 10u32.pow(9 - digits);
+    Some(Ok(nanos))
+}
+
+/// Parse an RFC 3339 timestamp such as `1970-01-01T00:00:00Z` into seconds
+/// and nanoseconds relative to the unix epoch.
+#[cfg(feature = "std")]
+fn parse_timestamp(string: &str) -> Result<(i64, u32), TimeError> {
+    fn number(string: &str, range: core::ops::Range<usize>) -> Result<i64, TimeError> {
+        let digits = string.get(range).ok_or(TimeError::InvalidTimestamp)?;
+        let value = parse_digits(digits).ok_or(TimeError::InvalidTimestamp)?;
+        i64::try_from(value).map_err(|_| TimeError::InvalidTimestamp)
+    }
+
+    fn expect(string: &str, at: usize, expected: &[u8]) -> Result<(), TimeError> {
+        match string.as_bytes().get(at) {
+            Some(b) if expected.contains(b) => Ok(()),
+            _ => Err(TimeError::InvalidTimestamp),
+        }
+    }
+
+    let year = number(string, 0..4)?;
+    expect(string, 4, b"-")?;
+    let month = number(string, 5..7)?;
+    expect(string, 7, b"-")?;
+    let day = number(string, 8..10)?;
+    expect(string, 10, b"Tt ")?;
+    let hour = number(string, 11..13)?;
+    expect(string, 13, b":")?;
+    let minute = number(string, 14..16)?;
+    expect(string, 16, b":")?;
+    let second = number(string, 17..19)?;
+
+    let mut rest = &string[19..];
+    let mut nanos = 0;
+
+    if let Some(tail) = rest.strip_prefix('.') {
+        let end = tail
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        nanos = parse_fraction(&tail[..end]).ok_or(TimeError::InvalidTimestamp)??;
+        rest = &tail[end..];
+    }
+
+    let offset = match rest {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes().first() {
+                Some(b'+') => 1,
+                Some(b'-') => -1,
+                _ => return Err(TimeError::InvalidTimestamp),
+            };
+
+            if rest.len() != 6 {
+                return Err(TimeError::InvalidTimestamp);
+            }
+
+            let hours = number(rest, 1..3)?;
+            expect(rest, 3, b":")?;
+            let minutes = number(rest, 4..6)?;
+
+            if hours > 23 || minutes > 59 {
+                return Err(TimeError::InvalidTimestamp);
+            }
+
+            sign * (hours * 3_600 + minutes * 60)
+        }
+    };
+
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(TimeError::InvalidTimestamp);
+    }
+
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86_400 + hour * 3_600 + minute * 60 + second - offset;
+    Ok((secs, nanos))
+}
+
+#[cfg(feature = "std")]
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since the unix epoch for the given proleptic Gregorian date.
+#[cfg(feature = "std")]
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Proleptic Gregorian date for the given number of days since the unix epoch.
+#[cfg(feature = "std")]
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Formats seconds with a fractional part, omitting trailing zeros.
+struct Seconds {
+    secs: u64,
+    nanos: u32,
+}
+
+impl fmt::Display for Seconds {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.secs)?;
+        fmt_fraction(f, self.nanos)
+    }
+}
+
+/// Formats an RFC 3339 timestamp in UTC.
+#[cfg(feature = "std")]
+struct Timestamp {
+    year: i64,
+    month: i64,
+    day: i64,
+    secs: i64,
+    nanos: u32,
+}
+
+#[cfg(feature = "std")]
+impl Timestamp {
+    fn new(secs: i64, nanos: u32) -> Result<Self, TimeError> {
+        let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+
+        if !(0..=9999).contains(&year) {
+            return Err(TimeError::TimestampOutOfRange);
+        }
+
+        Ok(Self {
+            year,
+            month,
+            day,
+            secs: secs.rem_euclid(86_400),
+            nanos,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for Timestamp {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            year,
+            month,
+            day,
+            secs,
+            nanos,
+        } = *self;
+
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+            secs / 3_600,
+            secs / 60 % 60,
+            secs % 60
+        )?;
+
+        fmt_fraction(f, nanos)?;
+        f.write_str("Z")
+    }
+}
+
+/// Write nanoseconds as a decimal fraction, omitting trailing zeros.
+fn fmt_fraction(f: &mut fmt::Formatter<'_>, nanos: u32) -> fmt::Result {
+    if nanos == 0 {
+        return Ok(());
+    }
+
+    let mut nanos = nanos;
+    let mut width = 9;
+
+    while nanos % 10 == 0 {
+        nanos /= 10;
+        width -= 1;
+    }
+
+    write!(f, ".{nanos:0width$}")
+}
+
+enum TimeError {
+    NanosOutOfRange(u32),
+    FractionTooPrecise,
+    Overflow,
+    InvalidDuration,
+    #[cfg(feature = "std")]
+    InvalidTimestamp,
+    #[cfg(feature = "std")]
+    TimestampOutOfRange,
+}
+
+impl fmt::Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeError::NanosOutOfRange(nanos) => write!(
+                f,
+                "Nanoseconds `{nanos}` out of range, expected less than {NANOS_PER_SEC}"
+            ),
+            TimeError::FractionTooPrecise => {
+                write!(f, "Fractional seconds exceed nanosecond precision")
+            }
+            TimeError::Overflow => write!(f, "Time out of range"),
+            TimeError::InvalidDuration => write!(
+                f,
+                "Invalid duration, expected seconds like `1.5` or ISO-8601 like `PT1.5S`"
+            ),
+            #[cfg(feature = "std")]
+            TimeError::InvalidTimestamp => write!(
+                f,
+                "Invalid timestamp, expected RFC 3339 like `1970-01-01T00:00:00Z`"
+            ),
+            #[cfg(feature = "std")]
+            TimeError::TimestampOutOfRange => {
+                write!(
+                    f,
+                    "Timestamp out of range for RFC 3339, expected years 0000 to 9999"
+                )
+            }
+        }
+    }
+}
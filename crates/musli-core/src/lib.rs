@@ -186,19 +186,22 @@ pub mod __priv {
     use core::marker::PhantomData;
 
     pub use crate::alloc::Allocator;
-    use crate::alloc::String;
+    use crate::alloc::{String, Vec};
     pub use crate::context::Context;
     pub use crate::de::{
-        AsDecoder, Decode, DecodeBytes, DecodePacked, DecodeTrace, Decoder, EntryDecoder,
-        MapDecoder, SequenceDecoder, TryFastDecode, VariantDecoder,
+        AsDecoder, Decode, DecodeBytes, DecodeFlatten, DecodePacked, DecodeTrace, Decoder,
+        EntryDecoder, MapDecoder, SequenceDecoder, TryFastDecode, VariantDecoder,
     };
     pub use crate::en::{
-        Encode, EncodeBytes, EncodePacked, EncodeTrace, Encoder, EntryEncoder, MapEncoder,
-        SequenceEncoder, TryFastEncode, VariantEncoder,
+        Encode, EncodeBytes, EncodeFlatten, EncodePacked, EncodeTrace, Encoder, EntryEncoder,
+        MapEncoder, SequenceEncoder, TryFastEncode, VariantEncoder,
     };
     pub use crate::hint::MapHint;
     pub use crate::never::Never;
 
+    pub use ::core::clone::Clone;
+    pub use ::core::convert::{From, Into, TryFrom};
+    pub use ::core::error::Error;
     pub use ::core::fmt;
     pub use ::core::mem::{needs_drop, offset_of, size_of};
     pub use ::core::option::Option;
@@ -247,6 +250,20 @@ pub mod __priv {
         }
     }
 
+    /// Collect and allocate a byte buffer from a slice of bytes.
+    #[inline]
+    pub fn collect_bytes<C>(cx: C, value: &[u8]) -> Result<Vec<u8, C::Allocator>, C::Error>
+    where
+        C: Context,
+    {
+        let mut bytes = Vec::new_in(cx.alloc());
+
+        match bytes.extend_from_slice(value) {
+            Ok(()) => Ok(bytes),
+            Err(error) => Err(cx.message(error)),
+        }
+    }
+
     /// Construct a map hint from an `Encode` implementation.
     #[inline]
     pub fn map_hint<M>(encode: &(impl Encode<M> + ?Sized)) -> impl MapHint + '_
@@ -277,6 +294,69 @@ pub mod __priv {
         }
     }
 
+    /// Encode a remote type on behalf of the local definition `R` which mirrors
+    /// it through `#[musli(remote = <type>)]`.
+    pub trait EncodeRemote<R, M>
+    where
+        R: ?Sized,
+    {
+        /// Encode the remote value.
+        fn encode_remote<E>(&self, encoder: E) -> Result<(), E::Error>
+        where
+            E: Encoder<Mode = M>;
+
+        /// The size hint of the remote value.
+        fn size_hint_remote(&self) -> Option<usize>;
+    }
+
+    /// Decode a remote type on behalf of the local definition `R` which mirrors
+    /// it through `#[musli(remote = <type>)]`.
+    pub trait DecodeRemote<'de, R, M, A>: Sized
+    where
+        R: ?Sized,
+        A: Allocator,
+    {
+        /// Decode the remote value.
+        fn decode_remote<D>(decoder: D) -> Result<Self, D::Error>
+        where
+            D: Decoder<'de, Mode = M, Allocator = A>;
+    }
+
+    /// Construct a map hint from an `EncodeRemote` implementation.
+    #[inline]
+    pub fn remote_map_hint<'a, R, M>(
+        encode: &'a (impl EncodeRemote<R, M> + ?Sized),
+    ) -> impl MapHint + 'a
+    where
+        R: 'a + ?Sized,
+        M: 'static,
+    {
+        EncodeRemoteMapHint::<_, R, M> {
+            encode,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) struct EncodeRemoteMapHint<'a, T, R, M>
+    where
+        T: ?Sized,
+        R: ?Sized,
+    {
+        encode: &'a T,
+        _marker: PhantomData<(&'a R, M)>,
+    }
+
+    impl<T, R, M> MapHint for EncodeRemoteMapHint<'_, T, R, M>
+    where
+        T: ?Sized + EncodeRemote<R, M>,
+        R: ?Sized,
+    {
+        #[inline]
+        fn get(self) -> Option<usize> {
+            self.encode.size_hint_remote()
+        }
+    }
+
     /// Helper methods to report errors.
     pub mod m {
         use core::fmt;
@@ -410,6 +490,33 @@ pub mod __priv {
             ))
         }
 
+        /// Encountered a field which is not part of the type.
+        #[inline]
+        pub fn unknown_field<C>(cx: C, type_name: &'static str, field: impl fmt::Debug) -> C::Error
+        where
+            C: Context,
+        {
+            cx.message(format_args!(
+                "Type {type_name} received unknown field {field:?}"
+            ))
+        }
+
+        /// Encountered a field which is not part of the variant.
+        #[inline]
+        pub fn unknown_variant_field<C>(
+            cx: C,
+            type_name: &'static str,
+            variant: impl fmt::Debug,
+            field: impl fmt::Debug,
+        ) -> C::Error
+        where
+            C: Context,
+        {
+            cx.message(format_args!(
+                "Type {type_name} received unknown field {field:?} for variant {variant:?}"
+            ))
+        }
+
         /// Untagged enum could not be decoded.
         #[inline]
         pub fn untagged_mismatch<C>(cx: C, type_name: &'static str) -> C::Error
@@ -418,5 +525,16 @@ pub mod __priv {
         {
             cx.message(format_args!("No variant of {type_name} could be decoded"))
         }
+
+        /// Encountered a version which has no migration to the current type.
+        #[inline]
+        pub fn unsupported_version<C>(cx: C, type_name: &'static str, version: u32) -> C::Error
+        where
+            C: Context,
+        {
+            cx.message(format_args!(
+                "Type {type_name} does not support version {version}"
+            ))
+        }
     }
 }
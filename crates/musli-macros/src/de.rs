@@ -3,11 +3,11 @@ use quote::{ToTokens, quote, quote_spanned};
 use syn::Token;
 use syn::punctuated::Punctuated;
 
-use crate::expander::{Name, NameMethod, StructKind, UnsizedMethod};
+use crate::expander::{Expander, Name, NameMethod, StructKind, UnsizedMethod};
 use crate::internals::apply;
 use crate::internals::attr::{EnumTagging, Packing};
 use crate::internals::build::{self, Body, Build, BuildData, Enum, Field, Variant};
-use crate::internals::{Result, Tokens};
+use crate::internals::{ATTR, Result, Tokens};
 
 struct Ctxt<'a> {
     ctx_var: &'a Ident,
@@ -16,6 +16,9 @@ struct Ctxt<'a> {
     d_param: &'a Ident,
     trace: bool,
     trace_body: bool,
+    /// The field holding the tag of an internally tagged enum, which is part
+    /// of the map of the variant being decoded.
+    internal_tag: Option<&'a syn::Expr>,
 }
 
 pub(crate) fn expand_decode_entry(b: &Build<'_>) -> Result<TokenStream> {
@@ -34,6 +37,7 @@ pub(crate) fn expand_decode_entry(b: &Build<'_>) -> Result<TokenStream> {
         d_param: &d_param,
         trace: true,
         trace_body: true,
+        internal_tag: None,
     };
 
     let Tokens {
@@ -47,18 +51,43 @@ pub(crate) fn expand_decode_entry(b: &Build<'_>) -> Result<TokenStream> {
     } = b.tokens;
 
     let packed;
+    let mut flatten = None;
 
-    let body = match &b.data {
-        BuildData::Struct(st) => {
+    let body = match (&b.data, b.from, b.try_from) {
+        (_, Some((_, from)), _) => {
+            packed = syn::parse_quote!(false);
+            decode_from(&cx, b, from, false)
+        }
+        (_, None, Some((_, try_from))) => {
+            packed = syn::parse_quote!(false);
+            decode_from(&cx, b, try_from, true)
+        }
+        (BuildData::Struct(st), None, None) => {
             packed = crate::internals::packed(b, st);
+
+            // Ineligible types are rejected when validating
+            // `#[musli(flattenable)]`.
+            if b.flattenable.is_some() {
+                flatten = Some(decode_flatten(&cx, b, st)?);
+            }
+
             decode_struct(&cx, b, st)?
         }
-        BuildData::Enum(en) => {
+        (BuildData::Enum(en), None, None) => {
             packed = syn::parse_quote!(false);
             decode_enum(&cx, b, en)?
         }
     };
 
+    // A versioned type is never bitwise, since it's prefixed with its version.
+    let (packed, body) = match b.version {
+        Some((_, version)) => (
+            syn::parse_quote!(false),
+            decode_versioned(&cx, b, version, body),
+        ),
+        None => (packed, body),
+    };
+
     if b.cx.has_errors() {
         return Err(());
     }
@@ -108,8 +137,44 @@ pub(crate) fn expand_decode_entry(b: &Build<'_>) -> Result<TokenStream> {
 
     let existing_bounds = build::existing_bounds(b.decode_bounds);
 
+    if let Some((_, from)) = b.from {
+        let Tokens { from_t, .. } = b.tokens;
+
+        let where_clause = generics.make_where_clause();
+
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(Self: #from_t<#from>));
+
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#from: #decode_t<#lt, #mode_ident, #allocator_ident>));
+    } else if let Some((_, try_from)) = b.try_from {
+        let Tokens {
+            error_t,
+            try_from_t,
+            ..
+        } = b.tokens;
+
+        let where_clause = generics.make_where_clause();
+
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(Self: #try_from_t<#try_from>));
+
+        where_clause.predicates.push(syn::parse_quote! {
+            <Self as #try_from_t<#try_from>>::Error: 'static + Send + Sync + #error_t
+        });
+
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#try_from: #decode_t<#lt, #mode_ident, #allocator_ident>));
+    }
+
+    let proxied = b.from.is_some() || b.try_from.is_some();
+
     for t in b.input.generics.type_params() {
-        if existing_bounds.contains(&t.ident) {
+        if proxied || existing_bounds.contains(&t.ident) {
             continue;
         }
 
@@ -130,15 +195,117 @@ pub(crate) fn expand_decode_entry(b: &Build<'_>) -> Result<TokenStream> {
             }));
     }
 
-    let (impl_generics, _, where_clause) = generics.split_for_impl();
-    let (_, type_generics, _) = b.input.generics.split_for_impl();
-
     let mut attributes = Vec::<syn::Attribute>::new();
 
     if cfg!(not(feature = "verbose")) {
         attributes.push(syn::parse_quote!(#[allow(clippy::just_underscores_and_digits)]));
     }
 
+    if let Some((_, remote)) = b.remote {
+        let Tokens {
+            decode_remote_t,
+            from_t,
+            ..
+        } = b.tokens;
+
+        let (_, type_generics, _) = b.input.generics.split_for_impl();
+
+        // A remote type with private fields is decoded through its local
+        // definition, which is then converted into the remote type.
+        let has_getter = match &b.data {
+            BuildData::Struct(st) => st.all_fields.iter().any(|f| f.getter.is_some()),
+            BuildData::Enum(..) => false,
+        };
+
+        let body = if has_getter {
+            let value_var = b.cx.ident("value");
+
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(Self: #from_t<#type_ident #type_generics>));
+
+            quote! {
+                let #value_var: #type_ident #type_generics = { #body }?;
+                #result::Ok(#from_t::from(#value_var))
+            }
+        } else {
+            body
+        };
+
+        let body = match b.validate {
+            Some((_, validate)) => {
+                let decode = validated(&ctx_var, b, validate, quote!(Self), quote!({ #body }?));
+                quote!(#result::Ok(#decode))
+            }
+            None => body,
+        };
+
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        return Ok(quote! {
+            const _: () = {
+                #[automatically_derived]
+                #(#attributes)*
+                impl #impl_generics #decode_remote_t<#lt, #type_ident #type_generics, #mode_ident, #allocator_ident> for #remote
+                #where_clause
+                {
+                    #[inline]
+                    fn decode_remote<#d_param>(#decoder_var: #d_param) -> #result<Self, <#d_param as #decoder_t<#lt>>::Error>
+                    where
+                        #d_param: #decoder_t<#lt, Mode = #mode_ident, Allocator = #allocator_ident>,
+                    {
+                        let #ctx_var = #decoder_t::cx(&#decoder_var);
+                        #body
+                    }
+                }
+            };
+        });
+    }
+
+    let body = match b.validate {
+        Some((_, validate)) => {
+            let decode = quote! {
+                match #decoder_t::try_fast_decode(#decoder_var)? {
+                    #try_fast_decode::Ok(value) => value,
+                    #try_fast_decode::Unsupported(#decoder_var) => { #body }?,
+                    _ => return #result::Err(#context_t::message(#ctx_var, "Fast decoding failed")),
+                }
+            };
+
+            let decode = validated(&ctx_var, b, validate, quote!(Self), decode);
+            quote!(#result::Ok(#decode))
+        }
+        None => quote! {
+            let #decoder_var = match #decoder_t::try_fast_decode(#decoder_var)? {
+                #try_fast_decode::Ok(value) => return #result::Ok(value),
+                #try_fast_decode::Unsupported(#decoder_var) => #decoder_var,
+                _ => return #result::Err(#context_t::message(#ctx_var, "Fast decoding failed")),
+            };
+
+            #body
+        },
+    };
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, type_generics, _) = b.input.generics.split_for_impl();
+
+    let flatten = flatten.map(|(name_type, items)| {
+        let Tokens {
+            decode_flatten_t, ..
+        } = b.tokens;
+
+        quote! {
+            #[automatically_derived]
+            #(#attributes)*
+            impl #impl_generics #decode_flatten_t<#lt, #mode_ident, #allocator_ident, #name_type> for #type_ident #type_generics
+            #where_clause
+            {
+                #items
+            }
+        }
+    });
+
     Ok(quote! {
         const _: () = {
             #[automatically_derived]
@@ -154,20 +321,197 @@ pub(crate) fn expand_decode_entry(b: &Build<'_>) -> Result<TokenStream> {
                     #d_param: #decoder_t<#lt, Mode = #mode_ident, Allocator = #allocator_ident>,
                 {
                     let #ctx_var = #decoder_t::cx(&#decoder_var);
-
-                    let #decoder_var = match #decoder_t::try_fast_decode(#decoder_var)? {
-                        #try_fast_decode::Ok(value) => return #result::Ok(value),
-                        #try_fast_decode::Unsupported(#decoder_var) => #decoder_var,
-                        _ => return #result::Err(#context_t::message(#ctx_var, "Fast decoding failed")),
-                    };
-
                     #body
                 }
             }
+
+            #flatten
         };
     })
 }
 
+/// Expand the `decode` function of a local definition of a remote type, which
+/// makes it usable through `#[musli(with = <path>)]`.
+pub(crate) fn expand_remote_decode(
+    e: &Expander<'_>,
+    tokens: &Tokens<'_>,
+    remote: &syn::Path,
+) -> TokenStream {
+    let Tokens {
+        decode_remote_t,
+        decoder_t,
+        result,
+        ..
+    } = tokens;
+
+    let type_ident = &e.input.ident;
+    let decoder_var = e.cx.ident("decoder");
+    let lt = e.cx.lifetime("de");
+    let d_param = e.cx.type_with_span("D", Span::call_site());
+    let (impl_generics, type_generics, where_clause) = e.input.generics.split_for_impl();
+    let pretend_used = e.pretend_used(tokens);
+
+    quote! {
+        const _: () = {
+            #[automatically_derived]
+            impl #impl_generics #type_ident #type_generics
+            #where_clause
+            {
+                /// Decode the remote type this is a definition of.
+                #[inline]
+                pub fn decode<#lt, #d_param>(#decoder_var: #d_param) -> #result<#remote, <#d_param as #decoder_t<#lt>>::Error>
+                where
+                    #d_param: #decoder_t<#lt>,
+                    #remote: #decode_remote_t<#lt, Self, <#d_param as #decoder_t<#lt>>::Mode, <#d_param as #decoder_t<#lt>>::Allocator>,
+                {
+                    #pretend_used
+                    #decode_remote_t::<#lt, Self, <#d_param as #decoder_t<#lt>>::Mode, <#d_param as #decoder_t<#lt>>::Allocator>::decode_remote(#decoder_var)
+                }
+            }
+        };
+    }
+}
+
+/// Pass a decoded value through the given `#[musli(validate = <path>)]`
+/// function, reporting a failure at the position where decoding started.
+fn validated(
+    ctx_var: &Ident,
+    b: &Build<'_>,
+    validate: &syn::Path,
+    ty: TokenStream,
+    decode: TokenStream,
+) -> TokenStream {
+    let Tokens {
+        context_t, result, ..
+    } = b.tokens;
+
+    let mark_var = b.cx.ident("mark");
+    let value_var = b.cx.ident("value");
+    let error_var = b.cx.ident("error");
+
+    quote! {{
+        let #mark_var = #context_t::mark(#ctx_var);
+        let #value_var: #ty = #decode;
+
+        if let #result::Err(#error_var) = #validate(&#value_var) {
+            return #result::Err(#context_t::message_at(#ctx_var, &#mark_var, #error_var));
+        }
+
+        #value_var
+    }}
+}
+
+/// Decode a type from a pack prefixed with its version through `#[musli(version
+/// = <int>)]`, migrating older versions with `#[musli(migrate_from(<int> =
+/// <path>))]`.
+fn decode_versioned(
+    cx: &Ctxt<'_>,
+    b: &Build<'_>,
+    version: &syn::LitInt,
+    body: TokenStream,
+) -> TokenStream {
+    let Ctxt {
+        ctx_var,
+        decoder_var,
+        ..
+    } = *cx;
+
+    let Tokens {
+        decoder_t,
+        messages,
+        pack_decoder_t,
+        result,
+        ..
+    } = b.tokens;
+
+    let pack_var = b.cx.ident("pack");
+    let version_var = b.cx.ident("version");
+    let value_var = b.cx.ident("value");
+    let decode_t_decode = &b.decode_t_decode;
+    let type_name = syn::LitStr::new(&b.input.ident.to_string(), b.input.ident.span());
+
+    let migrations = b.migrations.iter().map(|(_, migration)| {
+        let version = &migration.version;
+        let path = &migration.path;
+
+        quote! {
+            #version => {
+                let #decoder_var = #pack_decoder_t::decode_next(#pack_var)?;
+                let #value_var = #decode_t_decode(#decoder_var)?;
+                #result::Ok(#path(#value_var))
+            }
+        }
+    });
+
+    quote! {
+        #decoder_t::decode_pack(#decoder_var, |#pack_var| {
+            let #version_var: u32 = #pack_decoder_t::next(#pack_var)?;
+
+            match #version_var {
+                #version => {
+                    let #decoder_var = #pack_decoder_t::decode_next(#pack_var)?;
+                    #body
+                }
+                #(#migrations,)*
+                _ => #result::Err(#messages::unsupported_version(#ctx_var, #type_name, #version_var)),
+            }
+        })
+    }
+}
+
+/// Decode the value of a field, validating it if it has a `#[musli(validate =
+/// <path>)]` attribute.
+fn decode_field(ctx_var: &Ident, b: &Build<'_>, f: &Field<'_>, decoder_var: &Ident) -> TokenStream {
+    let (_, decode_path) = &f.decode_path;
+    let decode = quote!(#decode_path(#decoder_var)?);
+
+    match f.validate {
+        Some((_, validate)) => {
+            let ty = f.ty;
+            validated(ctx_var, b, validate, quote!(#ty), decode)
+        }
+        None => decode,
+    }
+}
+
+/// Decode a type by converting it from another type through `#[musli(from =
+/// <type>)]` or `#[musli(try_from = <type>)]`.
+fn decode_from(cx: &Ctxt<'_>, b: &Build<'_>, from: &syn::Type, fallible: bool) -> TokenStream {
+    let Ctxt {
+        ctx_var,
+        decoder_var,
+        ..
+    } = *cx;
+
+    let Tokens {
+        context_t,
+        from_t,
+        result,
+        try_from_t,
+        ..
+    } = b.tokens;
+
+    let decode_t_decode = &b.decode_t_decode;
+    let value_var = b.cx.ident("value");
+    let error_var = b.cx.ident("error");
+
+    let convert = if fallible {
+        quote! {
+            match #try_from_t::try_from(#value_var) {
+                #result::Ok(#value_var) => #result::Ok(#value_var),
+                #result::Err(#error_var) => #result::Err(#context_t::custom(#ctx_var, #error_var)),
+            }
+        }
+    } else {
+        quote!(#result::Ok(#from_t::from(#value_var)))
+    };
+
+    quote! {
+        let #value_var: #from = #decode_t_decode(#decoder_var)?;
+        #convert
+    }
+}
+
 fn decode_struct(cx: &Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<TokenStream> {
     let Tokens { result, .. } = b.tokens;
 
@@ -229,7 +573,7 @@ fn decode_empty_enum(cx: &Ctxt<'_>, b: &Build<'_>, en: &Enum<'_>) -> Result<Toke
 
     for v in &en.variants {
         let path = &v.st.path;
-        let pat = sized_arm(b, v.pattern, &v.name);
+        let pat = sized_arm(b, v.pattern, &v.name, v.aliases);
         arms.push(quote!(#pat => #result::Ok(#path {})));
     }
 
@@ -506,10 +850,16 @@ fn decode_internal(
 
     let tag_value = tag.value;
 
+    let variant_cx = Ctxt {
+        internal_tag: Some(tag_value),
+        ..*cx
+    };
+
     let arms = output_arms.iter().flat_map(|(v, pat, tag_value)| {
         let name = v.st.name.value;
 
-        let decode = decode_variant(cx, b, v, &buffer_decoder_var, &variant_tag_var).ok()?;
+        let decode =
+            decode_variant(&variant_cx, b, v, &buffer_decoder_var, &variant_tag_var).ok()?;
 
         let enter = cx.trace.then(|| {
             let formatted_tag = en.name.name_format(&tag_static);
@@ -550,7 +900,7 @@ fn decode_internal(
             outcome_enum = None;
 
             let tag_type = &tag.ty;
-            let tag_arm = sized_arm(b, None, tag_value);
+            let tag_arm = sized_arm(b, None, tag_value, &[]);
 
             decode_match = quote! {
                 let #value_var: #tag_type = #decode_t_decode(#field_name_var)?;
@@ -575,7 +925,7 @@ fn decode_internal(
             let visit_type = &tag.ty;
             let method = method.as_method_name();
             let format_value_var = tag.name_format(&value_var);
-            let tag_arm = sized_arm(b, None, tag_value);
+            let tag_arm = sized_arm(b, None, tag_value, &[]);
 
             let decode_outcome = quote! {
                 #decoder_t::#method(#field_name_var, |#value_var: &#visit_type| {
@@ -747,8 +1097,8 @@ fn decode_adjacent(
     match tag.method {
         NameMethod::Sized => {
             let value_type = &tag.ty;
-            let tag_arm = sized_arm(b, None, tag_value);
-            let content_arm = sized_arm(b, None, content_value);
+            let tag_arm = sized_arm(b, None, tag_value, &[]);
+            let content_arm = sized_arm(b, None, content_value, &[]);
 
             outcome_enum = None;
 
@@ -785,8 +1135,8 @@ fn decode_adjacent(
             let visit_type = &tag.ty;
             let format_value_var = tag.name_format(&value_var);
             let method = method.as_method_name();
-            let tag_arm = sized_arm(b, None, tag_value);
-            let content_arm = sized_arm(b, None, content_value);
+            let tag_arm = sized_arm(b, None, tag_value, &[]);
+            let content_arm = sized_arm(b, None, content_value, &[]);
 
             outcome_enum = Some(quote! {
                 enum #outcome_type<#buf_type> { Tag, Content, Skip(#buf_type) }
@@ -927,7 +1277,7 @@ fn decode_variant_name<'a>(
     match en.name.method {
         NameMethod::Sized => {
             for v in &en.variants {
-                let arm = sized_arm(b, v.pattern, &v.name);
+                let arm = sized_arm(b, v.pattern, &v.name, v.aliases);
                 output_arms.push((v, OutputArm::Sized(arm), &v.name));
             }
 
@@ -943,14 +1293,21 @@ fn decode_variant_name<'a>(
             let output_type = b.cx.type_with_span("VariantTag", b.input.ident.span());
 
             for v in &en.variants {
-                let (pat, variant) =
-                    unsized_arm(b, v.span, v.index, &v.name, v.pattern, &output_type);
+                let (pat, variant) = unsized_arm(
+                    b,
+                    v.span,
+                    v.index,
+                    &v.name,
+                    v.pattern,
+                    v.aliases,
+                    &output_type,
+                );
 
                 output_arms.push((v, OutputArm::Unsized(pat), &v.name));
                 variants.push(variant);
             }
 
-            decode_name = decode_unsized(b, &variants, method, &en.name, variant_decoder_var);
+            decode_name = decode_unsized(b, &variants, method, &en.name, variant_decoder_var, None);
 
             let fmt_debug = variants.iter().map(|o| {
                 let variant = &o.variant;
@@ -1079,6 +1436,8 @@ fn decode_tagged(
     } = *cx;
 
     let Tokens {
+        collect_bytes,
+        collect_string,
         context_t,
         decoder_t,
         default_function,
@@ -1102,12 +1461,12 @@ fn decode_tagged(
 
     let mut assigns = Vec::new();
     let mut fields_with = Vec::new();
+    let mut offers = Vec::new();
 
     for f in &st.all_fields {
         let Field {
             ref name,
             ref var,
-            decode_path: (_, ref decode_path),
             ref member,
             default_attr,
             ..
@@ -1115,6 +1474,18 @@ fn decode_tagged(
 
         let expr = match f.init_default(b) {
             Some(init) => init,
+            None if f.flatten.is_some() => {
+                let flatten_t = flatten_t(b, st, f);
+
+                offers.push(quote! {
+                    let #struct_decoder_var = match #flatten_t::decode_flatten_entry(&mut #var, &#name_var, #struct_decoder_var)? {
+                        #option::Some(#struct_decoder_var) => #struct_decoder_var,
+                        #option::None => continue,
+                    };
+                });
+
+                quote!(#flatten_t::finish_flatten(#ctx_var, #var)?)
+            }
             None => {
                 let formatted_tag = st.name.name_format(&static_name_var);
 
@@ -1141,8 +1512,10 @@ fn decode_tagged(
                     }
                 });
 
+                let decode = decode_field(ctx_var, b, f, &struct_decoder_var);
+
                 let decode = quote! {
-                    #var = #option::Some(#decode_path(#struct_decoder_var)?);
+                    #var = #option::Some(#decode);
                 };
 
                 fields_with.push((f, decode, (enter, leave)));
@@ -1180,28 +1553,119 @@ fn decode_tagged(
         },
     };
 
-    let skip_field = quote! {
+    // The tag of an internally tagged enum is not an unknown field.
+    let internal_tag_arm = cx
+        .internal_tag
+        .filter(|_| st.deny_unknown_fields)
+        .map(|tag| {
+            let arm = sized_arm(b, None, tag, &[]);
+
+            quote! {
+                #arm => {
+                    if #skip_field(#struct_decoder_var)? {
+                        return #result::Err(#unsupported);
+                    }
+                }
+            }
+        });
+
+    let unknown = |name: &syn::Expr| match variant_tag {
+        Some(variant_tag) => quote! {
+            #messages::unknown_variant_field(#ctx_var, #type_name, &#variant_tag, #name)
+        },
+        None => quote! {
+            #messages::unknown_field(#ctx_var, #type_name, #name)
+        },
+    };
+
+    let skip_unknown = quote! {
+        #(#offers)*
+
         if #skip_field(#struct_decoder_var)? {
             return #result::Err(#unsupported);
         }
     };
 
+    let skip_field = if st.deny_unknown_fields {
+        let unknown = unknown(&syn::parse_quote!(&#name_var));
+
+        quote! {
+            #(#offers)*
+            return #result::Err(#unknown);
+        }
+    } else {
+        skip_unknown.clone()
+    };
+
     let body;
     let name_type: syn::Type;
 
     match st.name.method {
+        // Names of unknown fields are passed on to flattened fields, so they
+        // need to be kept around as an owned value.
+        NameMethod::Unsized(method) if !offers.is_empty() => {
+            let mut arms = Vec::with_capacity(fields_with.len());
+
+            for (
+                &Field {
+                    pattern,
+                    aliases,
+                    ref name,
+                    ..
+                },
+                decode,
+                (enter, leave),
+            ) in fields_with
+            {
+                let arm = sized_arm(b, pattern, name, aliases);
+
+                arms.push(quote! {
+                    #arm => {
+                        static #static_name_var: #static_name_type = #name;
+                        #enter
+                        let #struct_decoder_var = #entry_decoder_t::decode_value(#struct_decoder_var)?;
+                        #decode
+                        #leave
+                    }
+                });
+            }
+
+            arms.extend(internal_tag_arm);
+            body = quote!(match &*#name_var { #(#arms,)* _ => { #skip_field } });
+
+            let value_var = b.cx.ident("value");
+            let visit_type = &st.name.ty;
+
+            let collect = match method {
+                UnsizedMethod::Default => collect_string,
+                UnsizedMethod::Bytes => collect_bytes,
+            };
+
+            let method = method.as_method_name();
+
+            decode_tag = quote! {
+                #decoder_t::#method(#struct_decoder_var, |#value_var: &#visit_type| {
+                    #collect(#ctx_var, #value_var)
+                })
+            };
+
+            name_type = syn::parse_quote!(_);
+        }
         NameMethod::Sized => {
             let mut arms = Vec::with_capacity(fields_with.len());
 
             for (
                 &Field {
-                    pattern, ref name, ..
+                    pattern,
+                    aliases,
+                    ref name,
+                    ..
                 },
                 decode,
                 (enter, leave),
             ) in fields_with
             {
-                let arm = sized_arm(b, pattern, name);
+                let arm = sized_arm(b, pattern, name, aliases);
 
                 arms.push(quote! {
                     #arm => {
@@ -1214,6 +1678,7 @@ fn decode_tagged(
                 });
             }
 
+            arms.extend(internal_tag_arm);
             body = quote!(match #name_var { #(#arms,)* _ => { #skip_field } });
 
             let decode_t_decode = &b.decode_t_decode;
@@ -1234,11 +1699,12 @@ fn decode_tagged(
                     index,
                     ref name,
                     pattern,
+                    aliases,
                     ..
                 } = *f;
 
                 let (name_pat, name_variant) =
-                    unsized_arm(b, span, index, name, pattern, &output_type);
+                    unsized_arm(b, span, index, name, pattern, aliases, &output_type);
 
                 outputs.push(name_variant);
                 name_arms.push((name, name_pat, decode, trace));
@@ -1260,13 +1726,34 @@ fn decode_tagged(
                     });
 
                 body = quote! {
-                    match #name_var { #(#arms,)* #name_var => { #skip_field } }
+                    match #name_var { #(#arms,)* #name_var => { #skip_unknown } }
                 }
             } else {
-                body = skip_field;
+                body = skip_unknown;
             }
 
-            decode_tag = decode_unsized(b, &outputs, method, &st.name, &struct_decoder_var);
+            // Unknown names are reported while the visited name is still
+            // borrowed, which leaves only the tag of an internally tagged enum
+            // to be skipped.
+            let fallback = st.deny_unknown_fields.then(|| {
+                let value_var = b.cx.ident("value");
+                let unknown = unknown(&st.name.name_format(&value_var));
+
+                let tag = cx.internal_tag.map(|tag| {
+                    let arm = sized_arm(b, None, tag, &[]);
+                    let pat = &arm.pat;
+                    let cond = &arm.cond;
+                    quote!(#pat #cond => #option::None,)
+                });
+
+                quote! {
+                    #tag
+                    _ => return #result::Err(#unknown),
+                }
+            });
+
+            decode_tag =
+                decode_unsized(b, &outputs, method, &st.name, &struct_decoder_var, fallback);
 
             let fmt_debug = outputs.iter().map(|o| {
                 let variant = &o.variant;
@@ -1296,9 +1783,16 @@ fn decode_tagged(
     let path = &st.path;
     let fields_len = st.unskipped_fields().count();
 
-    let decls = st
-        .unskipped_fields()
-        .map(|Field { var, ty, .. }| quote!(let mut #var: #option<#ty> = #option::None;));
+    let decls = st.unskipped_fields().map(|f| {
+        let Field { var, ty, .. } = f;
+
+        if f.flatten.is_some() {
+            let flatten_t = flatten_t(b, st, f);
+            quote!(let mut #var = #flatten_t::partial();)
+        } else {
+            quote!(let mut #var: #option<#ty> = #option::None;)
+        }
+    });
 
     let enter = (cx.trace && cx.trace_body).then(|| {
         quote! {
@@ -1336,26 +1830,285 @@ fn decode_tagged(
     }})
 }
 
+/// The `DecodeFlatten` implementation of a flattened field.
+fn flatten_t(b: &Build<'_>, st: &Body<'_>, f: &Field<'_>) -> TokenStream {
+    let Tokens {
+        decode_flatten_t, ..
+    } = b.tokens;
+
+    let ty = f.ty;
+    let lt = &b.p.lt;
+    let mode = &b.mode.mode_path;
+    let allocator_ident = &b.p.allocator_ident;
+    let name_type = &st.name.ty;
+
+    quote!(<#ty as #decode_flatten_t<#lt, #mode, #allocator_ident, #name_type>>)
+}
+
+/// Decode the fields of a struct which has been flattened into a parent.
+///
+/// This produces the name type used by the struct and the items of its
+/// `DecodeFlatten` implementation.
+fn decode_flatten(cx: &Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<(syn::Type, TokenStream)> {
+    let Ctxt {
+        ctx_var,
+        decoder_var,
+        name_var,
+        d_param,
+        ..
+    } = *cx;
+
+    let Tokens {
+        context_t,
+        default_function,
+        entry_decoder_t,
+        messages,
+        option,
+        result,
+        ..
+    } = b.tokens;
+
+    let lt = &b.p.lt;
+    let mode = &b.mode.mode_path;
+    let allocator_ident = &b.p.allocator_ident;
+
+    let partial_var = b.cx.ident("partial");
+    let static_name_var = b.cx.ident("FIELD_NAME");
+    let c_param = b.cx.type_with_span("C", Span::call_site());
+    let static_name_type = st.name.ty();
+    let name_type = &st.name.ty;
+    let type_name = st.name.value;
+
+    let mut types = Vec::new();
+    let mut inits = Vec::new();
+    let mut vars = Vec::new();
+    let mut arms = Vec::new();
+    let mut offers = Vec::new();
+    let mut assigns = Vec::new();
+    let mut finish_uses_cx = false;
+    let mut entry_uses_cx = false;
+
+    for f in &st.all_fields {
+        let Field {
+            ref name,
+            ref var,
+            ref member,
+            default_attr,
+            pattern,
+            aliases,
+            ty,
+            ..
+        } = *f;
+
+        if let Some(init) = f.init_default(b) {
+            assigns.push(quote!(#member: #init));
+            continue;
+        }
+
+        vars.push(var);
+
+        if f.flatten.is_some() {
+            let flatten_t = flatten_t(b, st, f);
+
+            types.push(quote!(#flatten_t::Partial));
+            inits.push(quote!(#flatten_t::partial()));
+
+            offers.push(quote! {
+                let #decoder_var = match #flatten_t::decode_flatten_entry(#var, #name_var, #decoder_var)? {
+                    #option::Some(#decoder_var) => #decoder_var,
+                    #option::None => return #result::Ok(#option::None),
+                };
+            });
+
+            assigns.push(quote!(#member: #flatten_t::finish_flatten(#ctx_var, #var)?));
+            finish_uses_cx = true;
+            continue;
+        }
+
+        types.push(quote!(#option<#ty>));
+        inits.push(quote!(#option::None));
+
+        let formatted_tag = st.name.name_format(&static_name_var);
+
+        let (enter, leave) = cx
+            .trace
+            .then(|| {
+                let (name, enter) = match member {
+                    syn::Member::Named(name) => (
+                        syn::Lit::Str(syn::LitStr::new(&name.to_string(), name.span())),
+                        Ident::new("enter_named_field", Span::call_site()),
+                    ),
+                    syn::Member::Unnamed(index) => (
+                        syn::Lit::Int(syn::LitInt::from(Literal::u32_suffixed(index.index))),
+                        Ident::new("enter_unnamed_field", Span::call_site()),
+                    ),
+                };
+
+                (
+                    quote!(#context_t::#enter(#ctx_var, #name, #formatted_tag);),
+                    quote!(#context_t::leave_field(#ctx_var);),
+                )
+            })
+            .unzip();
+
+        let arm = sized_arm(b, pattern, name, aliases);
+        let decode = decode_field(ctx_var, b, f, decoder_var);
+        entry_uses_cx |= f.validate.is_some();
+
+        arms.push(quote! {
+            #arm => {
+                static #static_name_var: #static_name_type = #name;
+                #enter
+                let #decoder_var = #entry_decoder_t::decode_value(#decoder_var)?;
+                *#var = #option::Some(#decode);
+                #leave
+            }
+        });
+
+        let fallback = match default_attr {
+            Some((span, None)) => quote_spanned!(span => #default_function()),
+            Some((_, Some(path))) => quote!(#path()),
+            None => {
+                finish_uses_cx = true;
+
+                quote! {{
+                    static #static_name_var: #static_name_type = #name;
+                    return #result::Err(#messages::expected_tag(#ctx_var, #type_name, #formatted_tag))
+                }}
+            }
+        };
+
+        assigns.push(quote! {
+            #member: match #var {
+                #option::Some(#var) => #var,
+                #option::None => #fallback,
+            }
+        });
+    }
+
+    let cx_decl = ((cx.trace || entry_uses_cx) && !arms.is_empty())
+        .then(|| quote!(let #ctx_var = #entry_decoder_t::cx(&#decoder_var);));
+
+    let name_param = if arms.is_empty() && offers.is_empty() {
+        quote!(_)
+    } else {
+        quote!(#name_var)
+    };
+
+    let decode_entry = if arms.is_empty() {
+        quote! {
+            #(#offers)*
+            #result::Ok(#option::Some(#decoder_var))
+        }
+    } else {
+        let name_expr = match st.name.method {
+            NameMethod::Sized => quote!(*#name_var),
+            NameMethod::Unsized(..) => quote!(#name_var),
+        };
+
+        quote! {
+            match #name_expr {
+                #(#arms,)*
+                _ => {
+                    #(#offers)*
+                    return #result::Ok(#option::Some(#decoder_var));
+                }
+            }
+
+            #result::Ok(#option::None)
+        }
+    };
+
+    let path = &st.path;
+    let finish = quote!(#path { #(#assigns,)* });
+
+    let finish = match b.validate {
+        Some((_, validate)) => {
+            finish_uses_cx = true;
+            validated(ctx_var, b, validate, quote!(Self), finish)
+        }
+        None => finish,
+    };
+
+    let finish = quote!(#result::Ok(#finish));
+
+    let cx_param = if finish_uses_cx {
+        quote!(#ctx_var)
+    } else {
+        quote!(_)
+    };
+
+    // Avoid binding an empty partial, since it's the unit type.
+    let (partial_init, partial_param, bind_mut, bind) = if vars.is_empty() {
+        (quote!(), quote!(_), quote!(), quote!())
+    } else {
+        (
+            quote!((#(#inits,)*)),
+            quote!(#partial_var),
+            quote!(let (#(ref mut #vars,)*) = *#partial_var;),
+            quote!(let (#(#vars,)*) = #partial_var;),
+        )
+    };
+
+    let items = quote! {
+        type Partial = (#(#types,)*);
+
+        #[inline]
+        fn partial() -> Self::Partial {
+            #partial_init
+        }
+
+        #[inline]
+        fn decode_flatten_entry<#d_param>(
+            #partial_param: &mut Self::Partial,
+            #name_param: &#name_type,
+            #decoder_var: #d_param,
+        ) -> #result<#option<#d_param>, <#d_param as #entry_decoder_t<#lt>>::Error>
+        where
+            #d_param: #entry_decoder_t<#lt, Mode = #mode, Allocator = #allocator_ident>,
+        {
+            #cx_decl
+            #bind_mut
+            #decode_entry
+        }
+
+        #[inline]
+        fn finish_flatten<#c_param>(
+            #cx_param: #c_param,
+            #partial_param: Self::Partial,
+        ) -> #result<Self, <#c_param as #context_t>::Error>
+        where
+            #c_param: #context_t<Allocator = #allocator_ident>,
+        {
+            #bind
+            #finish
+        }
+    };
+
+    Ok((name_type.clone(), items))
+}
+
+/// Decode a name by borrowing it as an unsized value and matching it against
+/// the names of `outputs`.
+///
+/// Names which don't match any output are mapped to `None`, unless `fallback`
+/// provides the remaining arms of the match over the visited value.
 fn decode_unsized(
     b: &Build<'_>,
     outputs: &[NameVariant<'_>],
     method: UnsizedMethod,
     name: &Name<'_, syn::LitStr>,
     decoder_var: &Ident,
+    fallback: Option<TokenStream>,
 ) -> TokenStream {
     let arms = outputs.iter().map(|o| {
         let Tokens { option, .. } = b.tokens;
 
         let path = &o.path;
-        let arm = sized_arm(b, o.pattern, o.name);
+        let arm = sized_arm(b, o.pattern, o.name, o.aliases);
 
         let pat = &arm.pat;
-        let binding = &arm.binding;
-
-        let cond = arm.cond.map(|_| {
-            let name = o.name;
-            quote!(if *#binding == #name)
-        });
+        let cond = &arm.cond;
 
         quote!(#pat #cond => #option::Some(#path))
     });
@@ -1371,12 +2124,13 @@ fn decode_unsized(
     } = b.tokens;
 
     let value_var = b.cx.ident("value");
+    let fallback = fallback.unwrap_or_else(|| quote!(_ => #option::None));
 
     quote! {
         #decoder_t::#method(#decoder_var, |#value_var: &#visit_type| {
             #result::Ok(match #value_var {
                 #(#arms,)*
-                _ => #option::None,
+                #fallback
             })
         })
     }
@@ -1409,21 +2163,15 @@ fn decode_transparent(cx: &Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<Tok
         }
     });
 
-    let fields = st.all_fields.iter().map(
-        |f @ Field {
-             decode_path: (_, decode_path),
-             member,
-             ..
-         }| {
-            let init = if let Some(init) = f.init_default(b) {
-                init
-            } else {
-                quote!(#decode_path(#decoder_var)?)
-            };
+    let fields = st.all_fields.iter().map(|f @ Field { member, .. }| {
+        let init = if let Some(init) = f.init_default(b) {
+            init
+        } else {
+            decode_field(ctx_var, b, f, decoder_var)
+        };
 
-            quote!(#member: #init)
-        },
-    );
+        quote!(#member: #init)
+    });
 
     Ok(quote! {{
         #enter
@@ -1459,13 +2207,9 @@ fn decode_packed(cx: &Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<TokenStr
 
     let mut assign = Vec::new();
 
-    for f @ Field {
-        decode_path: (_, decode_path),
-        member,
-        ..
-    } in st.unskipped_fields()
-    {
+    for f @ Field { member, .. } in st.unskipped_fields() {
         let field_decoder = &field_decoder;
+        let decode = decode_field(ctx_var, b, f, field_decoder);
 
         if let Some(init) = f.init_default(b) {
             let value: Box<dyn Fn(&syn::Ident, &mut TokenStream)> =
@@ -1475,7 +2219,7 @@ fn decode_packed(cx: &Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<TokenStr
                             let #field_decoder = #pack_decoder_t::decode_next(#ident)?;
 
                             match #decoder_t::decode_option(#field_decoder)? {
-                                #option::Some(#field_decoder) => #decode_path(#field_decoder)?,
+                                #option::Some(#field_decoder) => #decode,
                                 #option::None => #init,
                             }
                         }
@@ -1489,7 +2233,7 @@ fn decode_packed(cx: &Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<TokenStr
                     tokens.extend(quote! {
                         #member: {
                             let #field_decoder = #pack_decoder_t::decode_next(#ident)?;
-                            #decode_path(#field_decoder)?
+                            #decode
                         }
                     })
                 },
@@ -1538,6 +2282,8 @@ pub(crate) struct NameVariant<'a> {
     name: &'a syn::Expr,
     /// The pattern being matched.
     pattern: Option<&'a syn::Pat>,
+    /// Additional names being matched.
+    aliases: &'a [(Span, syn::Expr)],
 }
 
 fn unsized_arm<'a>(
@@ -1546,6 +2292,7 @@ fn unsized_arm<'a>(
     index: usize,
     name: &'a syn::Expr,
     pattern: Option<&'a syn::Pat>,
+    aliases: &'a [(Span, syn::Expr)],
     output: &Ident,
 ) -> (syn::Pat, NameVariant<'a>) {
     let option = &b.tokens.option;
@@ -1560,38 +2307,32 @@ fn unsized_arm<'a>(
         variant,
         name,
         pattern,
+        aliases,
     };
 
     (syn::parse_quote!(#option::Some(#path)), output)
 }
 
 struct Condition<'a> {
-    if_: Token![if],
-    star: Token![*],
     ident: syn::Ident,
-    equals: Token![==],
-    expr: &'a syn::Expr,
+    exprs: Vec<&'a syn::Expr>,
 }
 
 impl ToTokens for Condition<'_> {
     #[inline]
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.if_.to_tokens(tokens);
-        self.star.to_tokens(tokens);
-        self.ident.to_tokens(tokens);
-        self.equals.to_tokens(tokens);
-        self.expr.to_tokens(tokens);
-    }
-}
+        <Token![if]>::default().to_tokens(tokens);
+
+        for (n, expr) in self.exprs.iter().enumerate() {
+            if n > 0 {
+                <Token![||]>::default().to_tokens(tokens);
+            }
 
-#[inline]
-fn condition(ident: syn::Ident, expr: &syn::Expr) -> Condition<'_> {
-    Condition {
-        if_: <Token![if]>::default(),
-        star: <Token![*]>::default(),
-        ident,
-        equals: <Token![==]>::default(),
-        expr,
+            <Token![*]>::default().to_tokens(tokens);
+            self.ident.to_tokens(tokens);
+            <Token![==]>::default().to_tokens(tokens);
+            expr.to_tokens(tokens);
+        }
     }
 }
 
@@ -1599,32 +2340,63 @@ fn sized_arm<'a>(
     b: &Build<'_>,
     pat: Option<&'a syn::Pat>,
     name: &'a syn::Expr,
+    aliases: &'a [(Span, syn::Expr)],
 ) -> SizedOutputArm<'a> {
     let binding = b.cx.ident("binding");
 
     if let Some(pat) = pat {
+        let mut cases = vec![pat.clone()];
+
+        for (span, alias) in aliases {
+            match expr_to_pat(alias) {
+                Some(pat) => cases.push(pat),
+                None => b.cx.error_span(
+                    *span,
+                    format_args!("#[{ATTR}(alias)] must be a literal when used with a pattern"),
+                ),
+            }
+        }
+
         return SizedOutputArm {
-            pat: pat.clone(),
+            pat: or_pat(cases),
             cond: None,
-            binding,
         };
     }
 
-    if let Some(pat) = expr_to_pat(name) {
+    let exprs = [name]
+        .into_iter()
+        .chain(aliases.iter().map(|(_, alias)| alias))
+        .collect::<Vec<_>>();
+
+    if let Some(cases) = exprs.iter().map(|e| expr_to_pat(e)).collect::<Option<_>>() {
         return SizedOutputArm {
-            pat,
+            pat: or_pat(cases),
             cond: None,
-            binding,
         };
     }
 
     SizedOutputArm {
         pat: syn::parse_quote!(ref #binding),
-        cond: Some(condition(binding.clone(), name)),
-        binding,
+        cond: Some(Condition {
+            ident: binding,
+            exprs,
+        }),
     }
 }
 
+/// Construct a pattern matching any of the given cases.
+fn or_pat(mut cases: Vec<syn::Pat>) -> syn::Pat {
+    if cases.len() == 1 {
+        return cases.remove(0);
+    }
+
+    syn::Pat::Or(syn::PatOr {
+        attrs: Vec::new(),
+        leading_vert: None,
+        cases: cases.into_iter().collect(),
+    })
+}
+
 fn expr_to_pat(expr: &syn::Expr) -> Option<syn::Pat> {
     match expr {
         syn::Expr::Lit(lit) => {
@@ -1648,6 +2420,11 @@ fn expr_to_pat(expr: &syn::Expr) -> Option<syn::Pat> {
                 elems,
             }))
         }
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(..),
+            expr: inner,
+            ..
+        }) if matches!(&**inner, syn::Expr::Lit(..)) => Some(syn::parse_quote!(#expr)),
         _ => None,
     }
 }
@@ -1670,7 +2447,6 @@ impl ToTokens for OutputArm<'_> {
 struct SizedOutputArm<'a> {
     pat: syn::Pat,
     cond: Option<Condition<'a>>,
-    binding: syn::Ident,
 }
 
 impl ToTokens for SizedOutputArm<'_> {
@@ -3,7 +3,7 @@ use quote::{ToTokens, quote};
 use syn::Token;
 use syn::punctuated::Punctuated;
 
-use crate::expander::Name;
+use crate::expander::{Expander, Name};
 use crate::internals::attr::{EnumTagging, Packing};
 use crate::internals::build::{self, Body, Build, BuildData, Enum, Field, Variant};
 use crate::internals::{Result, Tokens};
@@ -51,18 +51,40 @@ pub(crate) fn expand_encode_entry(b: &Build<'_>) -> Result<TokenStream> {
     } = b.tokens;
 
     let packed;
+    let mut flatten = None;
 
-    let (body, size_hint) = match &b.data {
-        BuildData::Struct(st) => {
+    let (body, size_hint) = match (&b.data, b.into) {
+        (_, Some((_, into))) => {
+            packed = syn::parse_quote!(false);
+            encode_into(cx, b, into)
+        }
+        (BuildData::Struct(st), None) => {
             packed = crate::internals::packed(b, st);
+
+            // Ineligible types are rejected when validating
+            // `#[musli(flattenable)]`.
+            if b.flattenable.is_some() {
+                flatten = Some(encode_flatten(cx, b, st)?);
+            }
+
             encode_map(cx, b, st)?
         }
-        BuildData::Enum(en) => {
+        (BuildData::Enum(en), None) => {
             packed = syn::parse_quote!(false);
             encode_enum(cx, b, en)?
         }
     };
 
+    // A versioned type is never bitwise, since it's prefixed with its version.
+    let (packed, body, size_hint) = match b.version {
+        Some((_, version)) => (
+            syn::parse_quote!(false),
+            encode_versioned(cx, b, version, body),
+            quote!(#option::None),
+        ),
+        None => (packed, body, size_hint),
+    };
+
     if b.cx.has_errors() {
         return Err(());
     }
@@ -80,8 +102,24 @@ pub(crate) fn expand_encode_entry(b: &Build<'_>) -> Result<TokenStream> {
 
     let existing_bounds = build::existing_bounds(b.bounds);
 
+    if let Some((_, into)) = b.into {
+        let Tokens {
+            clone_t, into_t, ..
+        } = b.tokens;
+
+        let where_clause = impl_generics.make_where_clause();
+
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(Self: #clone_t + #into_t<#into>));
+
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#into: #encode_t<#mode_ident>));
+    }
+
     for t in b.input.generics.type_params() {
-        if existing_bounds.contains(&t.ident) {
+        if b.into.is_some() || existing_bounds.contains(&t.ident) {
             continue;
         }
 
@@ -111,6 +149,65 @@ pub(crate) fn expand_encode_entry(b: &Build<'_>) -> Result<TokenStream> {
         attributes.push(syn::parse_quote!(#[allow(clippy::just_underscores_and_digits)]));
     }
 
+    if let Some((_, remote)) = b.remote {
+        let Tokens {
+            encode_remote_t, ..
+        } = b.tokens;
+
+        return Ok(quote! {
+            const _: () = {
+                #[automatically_derived]
+                #(#attributes)*
+                impl #impl_generics #encode_remote_t<#type_ident #type_generics, #mode_ident> for #remote
+                #where_clause
+                {
+                    #[inline]
+                    fn encode_remote<#e_param>(&self, #encoder_var: #e_param) -> #result<(), <#e_param as #encoder_t>::Error>
+                    where
+                        #e_param: #encoder_t<Mode = #mode_ident>,
+                    {
+                        let #ctx_var = #encoder_t::cx(&#encoder_var);
+                        #body
+                    }
+
+                    #[inline]
+                    fn size_hint_remote(&self) -> #option<usize> {
+                        #size_hint
+                    }
+                }
+            };
+        });
+    }
+
+    let flatten = flatten.map(|(encode_flatten, flatten_len)| {
+        let Tokens {
+            encode_flatten_t,
+            map_encoder_t,
+            ..
+        } = b.tokens;
+
+        quote! {
+            #[automatically_derived]
+            #(#attributes)*
+            impl #impl_generics #encode_flatten_t<#mode_ident> for #type_ident #type_generics
+            #where_clause
+            {
+                #[inline]
+                fn encode_flatten<#e_param>(&self, #encoder_var: &mut #e_param) -> #result<(), <#e_param as #map_encoder_t>::Error>
+                where
+                    #e_param: #map_encoder_t<Mode = #mode_ident>,
+                {
+                    #encode_flatten
+                }
+
+                #[inline]
+                fn flatten_len(&self) -> usize {
+                    #flatten_len
+                }
+            }
+        }
+    });
+
     Ok(quote! {
         const _: () = {
             #[automatically_derived]
@@ -148,10 +245,152 @@ pub(crate) fn expand_encode_entry(b: &Build<'_>) -> Result<TokenStream> {
                     self
                 }
             }
+
+            #flatten
         };
     })
 }
 
+/// Expand the `encode` function of a local definition of a remote type, which
+/// makes it usable through `#[musli(with = <path>)]`.
+pub(crate) fn expand_remote_encode(
+    e: &Expander<'_>,
+    tokens: &Tokens<'_>,
+    remote: &syn::Path,
+) -> TokenStream {
+    let Tokens {
+        encode_remote_t,
+        encoder_t,
+        result,
+        ..
+    } = tokens;
+
+    let type_ident = &e.input.ident;
+    let encoder_var = e.cx.ident("encoder");
+    let value_var = e.cx.ident("value");
+    let e_param = e.cx.type_with_span("E", Span::call_site());
+    let (impl_generics, type_generics, where_clause) = e.input.generics.split_for_impl();
+    let pretend_used = e.pretend_used(tokens);
+
+    quote! {
+        const _: () = {
+            #[automatically_derived]
+            impl #impl_generics #type_ident #type_generics
+            #where_clause
+            {
+                /// Encode the remote type this is a definition of.
+                #[inline]
+                pub fn encode<#e_param>(#value_var: &#remote, #encoder_var: #e_param) -> #result<(), <#e_param as #encoder_t>::Error>
+                where
+                    #e_param: #encoder_t,
+                    #remote: #encode_remote_t<Self, <#e_param as #encoder_t>::Mode>,
+                {
+                    #pretend_used
+                    #encode_remote_t::<Self, <#e_param as #encoder_t>::Mode>::encode_remote(#value_var, #encoder_var)
+                }
+            }
+        };
+    }
+}
+
+/// Encode a type by converting it into another type through
+/// `#[musli(into = <type>)]`.
+fn encode_into(cx: Ctxt<'_>, b: &Build<'_>, into: &syn::Type) -> (TokenStream, TokenStream) {
+    let Ctxt { encoder_var, .. } = cx;
+
+    let Tokens {
+        clone_t,
+        into_t,
+        option,
+        ..
+    } = b.tokens;
+
+    let encode_t_encode = &b.encode_t_encode;
+    let value_var = b.cx.ident("value");
+
+    let body = quote! {
+        let #value_var: #into = #into_t::into(#clone_t::clone(self));
+        #encode_t_encode(&#value_var, #encoder_var)
+    };
+
+    (body, quote!(#option::None))
+}
+
+/// Wrap the encoding of a type in a pack prefixed with its version through
+/// `#[musli(version = <int>)]`.
+fn encode_versioned(
+    cx: Ctxt<'_>,
+    b: &Build<'_>,
+    version: &syn::LitInt,
+    body: TokenStream,
+) -> TokenStream {
+    let Ctxt { encoder_var, .. } = cx;
+
+    let Tokens {
+        encoder_t,
+        sequence_encoder_t,
+        ..
+    } = b.tokens;
+
+    let pack_var = b.cx.ident("pack");
+    let version = syn::LitInt::new(&format!("{}u32", version.base10_digits()), version.span());
+
+    quote! {
+        #encoder_t::encode_pack_fn(#encoder_var, |#pack_var| {
+            #sequence_encoder_t::push(#pack_var, #version)?;
+            let #encoder_var = #sequence_encoder_t::encode_next(#pack_var)?;
+            #body
+        })
+    }
+}
+
+/// Encode the fields of a struct into the map of a parent which flattens it.
+fn encode_flatten(
+    cx: Ctxt<'_>,
+    b: &Build<'_>,
+    st: &Body<'_>,
+) -> Result<(TokenStream, TokenStream)> {
+    let Ctxt {
+        ctx_var,
+        encoder_var,
+        ..
+    } = cx;
+
+    let Tokens {
+        map_encoder_t,
+        result,
+        ..
+    } = b.tokens;
+
+    let decls = st.field_tests();
+    let encoders = make_encoders(cx, b, st)?;
+
+    let cx_decl = st
+        .unskipped_fields()
+        .next()
+        .is_some()
+        .then(|| quote!(let #ctx_var = #map_encoder_t::cx(&*#encoder_var);));
+
+    let encode = quote! {
+        #cx_decl
+        #(#decls)*
+        #(#encoders)*
+        #result::Ok(())
+    };
+
+    let decls = st.field_tests();
+    let len = length_test(b, st.unskipped_fields());
+    let len_var = b.cx.ident("len");
+
+    let flatten_len = quote! {
+        #(#decls)*
+        let #len_var: usize = #len;
+        #len_var
+    };
+
+    Ok((encode, flatten_len))
+}
+
 /// Encode a struct.
 fn encode_map(cx: Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<(TokenStream, TokenStream)> {
     let Ctxt { ctx_var, .. } = cx;
@@ -220,7 +459,7 @@ fn encode_default(cx: Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<TokenStr
             let decls = st.field_tests();
             let encoders = make_encoders(cx, b, st)?;
 
-            let len = length_test(st.unskipped_fields());
+            let len = length_test(b, st.unskipped_fields());
             let (build_hint, hint) = len.build_hint(b);
 
             encode = quote! {{
@@ -263,7 +502,7 @@ fn default_size_hint(b: &Build<'_>, st: &Body<'_>) -> Result<TokenStream> {
             size_hint = quote!(#option::None);
         }
         (_, Packing::Tagged) => {
-            let len = length_test(st.unskipped_fields());
+            let len = length_test(b, st.unskipped_fields());
             let decls = st.field_tests();
 
             size_hint = quote! {{
@@ -288,6 +527,7 @@ fn make_encoders(cx: Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<Vec<Token
 
     let Tokens {
         context_t,
+        encode_flatten_t,
         entry_encoder_t,
         map_encoder_t,
         result,
@@ -296,6 +536,7 @@ fn make_encoders(cx: Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<Vec<Token
     } = b.tokens;
 
     let encode_t_encode = &b.encode_t_encode;
+    let mode = &b.mode.mode_path;
 
     let sequence_decoder_var = b.cx.ident("sequence_decoder");
     let pair_encoder_var = b.cx.ident("pair_encoder");
@@ -313,6 +554,7 @@ fn make_encoders(cx: Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<Vec<Token
             encode_path: (_, encode_path),
             access,
             skip_encoding_if,
+            flatten,
             var,
             ..
         } = f;
@@ -360,6 +602,13 @@ fn make_encoders(cx: Ctxt<'_>, b: &Build<'_>, st: &Body<'_>) -> Result<Vec<Token
                     #leave
                 }};
             }
+            (_, Packing::Tagged) if flatten.is_some() => {
+                encode = quote! {{
+                    #enter
+                    #encode_flatten_t::<#mode>::encode_flatten(#access, #encoder_var)?;
+                    #leave
+                }};
+            }
             (_, Packing::Tagged) => {
                 encode = quote! {{
                     #enter
@@ -649,7 +898,7 @@ fn encode_internal_variant(
                 return Err(());
             }
             (_, Packing::Tagged) => {
-                len = length_test(v.st.unskipped_fields());
+                len = length_test(b, v.st.unskipped_fields());
                 len.expressions.push(quote!(1));
 
                 let decls = v.st.field_tests();
@@ -794,8 +1043,23 @@ impl LengthTest {
             LengthTestKind::Dynamic => {
                 let mode = &b.mode.mode_path;
                 let hint = b.cx.ident("hint");
-                let item = quote! {
-                    let #hint = #map_hint::<#mode>(self);
+
+                let item = match b.remote {
+                    Some(..) => {
+                        let Tokens {
+                            remote_map_hint, ..
+                        } = b.tokens;
+
+                        let type_ident = &b.input.ident;
+                        let (_, type_generics, _) = b.input.generics.split_for_impl();
+
+                        quote! {
+                            let #hint = #remote_map_hint::<#type_ident #type_generics, #mode>(self);
+                        }
+                    }
+                    None => quote! {
+                        let #hint = #map_hint::<#mode>(self);
+                    },
                 };
 
                 (item, hint)
@@ -811,7 +1075,13 @@ enum LengthTestKind {
     Dynamic,
 }
 
-fn length_test<'a>(fields: impl IntoIterator<Item = &'a Field<'a>>) -> LengthTest {
+fn length_test<'a>(b: &Build<'_>, fields: impl IntoIterator<Item = &'a Field<'a>>) -> LengthTest {
+    let Tokens {
+        encode_flatten_t, ..
+    } = b.tokens;
+
+    let mode = &b.mode.mode_path;
+
     let mut kind = LengthTestKind::Static;
 
     let mut expressions = Punctuated::<_, Token![+]>::new();
@@ -819,13 +1089,23 @@ fn length_test<'a>(fields: impl IntoIterator<Item = &'a Field<'a>>) -> LengthTes
 
     for Field {
         skip_encoding_if,
+        flatten,
+        access,
         var,
         ..
     } in fields
     {
+        let len = match flatten {
+            Some(..) => quote!(#encode_flatten_t::<#mode>::flatten_len(#access)),
+            None => quote!(1),
+        };
+
         if skip_encoding_if.is_some() {
             kind = LengthTestKind::Dynamic;
-            expressions.push(quote!(if #var { 1 } else { 0 }))
+            expressions.push(quote!(if #var { #len } else { 0 }))
+        } else if flatten.is_some() {
+            kind = LengthTestKind::Dynamic;
+            expressions.push(len);
         } else {
             count += 1;
         }
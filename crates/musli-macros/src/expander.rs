@@ -2,6 +2,7 @@ use std::borrow::Cow;
 use std::collections::BTreeMap;
 
 use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
 
@@ -113,6 +114,9 @@ pub(crate) struct VariantData<'a> {
     pub(crate) index: usize,
     pub(crate) attr: attr::VariantAttr,
     pub(crate) ident: &'a syn::Ident,
+    /// The discriminant of the variant, if it could be determined from
+    /// literals.
+    pub(crate) discriminant: Option<syn::Expr>,
     pub(crate) fields: Vec<FieldData<'a>>,
     pub(crate) kind: StructKind,
 }
@@ -126,9 +130,22 @@ pub(crate) enum StructKind {
 
 pub(crate) struct EnumData<'a> {
     pub(crate) name: syn::LitStr,
+    /// The representation specified through `#[repr(..)]`.
+    pub(crate) repr: Repr,
     pub(crate) variants: Vec<VariantData<'a>>,
 }
 
+/// The representation of an enum, as specified through `#[repr(..)]`.
+pub(crate) enum Repr {
+    /// No representation was specified.
+    Default,
+    /// The enum is represented by the given integer type.
+    Integer(syn::Ident),
+    /// The enum has a representation which doesn't specify an integer type,
+    /// like `#[repr(C)]`.
+    Other(Span),
+}
+
 pub(crate) enum Data<'a> {
     Struct(StructData<'a>),
     Enum(EnumData<'a>),
@@ -174,6 +191,8 @@ impl<'a> Expander<'a> {
                 },
             }),
             syn::Data::Enum(en) => {
+                let mut next = Some(0i128);
+
                 let variants = en
                     .variants
                     .iter()
@@ -184,6 +203,15 @@ impl<'a> Expander<'a> {
                         name: syn::LitStr::new(&variant.ident.to_string(), variant.ident.span()),
                         attr: attr::variant_attrs(&cx, &variant.attrs),
                         ident: &variant.ident,
+                        discriminant: {
+                            let value = match &variant.discriminant {
+                                Some((_, expr)) => literal_discriminant(expr),
+                                None => next,
+                            };
+
+                            next = value.and_then(|value| value.checked_add(1));
+                            value.map(|value| discriminant_expr(value, variant.span()))
+                        },
                         fields: fields(&cx, &variant.fields),
                         kind: match &variant.fields {
                             syn::Fields::Unit => StructKind::Empty,
@@ -194,6 +222,7 @@ impl<'a> Expander<'a> {
 
                 Data::Enum(EnumData {
                     name: syn::LitStr::new(&input.ident.to_string(), input.ident.span()),
+                    repr: repr_type(&input.attrs),
                     variants: variants.collect(),
                 })
             }
@@ -350,6 +379,53 @@ impl<'a> Expander<'a> {
         }
     }
 
+    /// Pretend that the fields and variants of a local definition of a remote
+    /// type are used, since the definition is otherwise never constructed or
+    /// read.
+    pub(crate) fn pretend_used(&self, tokens: &Tokens<'_>) -> TokenStream {
+        fn arm(option: &impl ToTokens, path: TokenStream, fields: &[FieldData<'_>]) -> TokenStream {
+            let mut members = Vec::with_capacity(fields.len());
+            let mut vars = Vec::with_capacity(fields.len());
+
+            for f in fields {
+                members.push(match f.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(syn::Index::from(f.index)),
+                });
+
+                vars.push(quote::format_ident!("__v{}", f.index));
+            }
+
+            quote! {
+                #option::Some(#path { #(#members: #vars),* }) => {
+                    let _ = #path { #(#members: #vars),* };
+                }
+            }
+        }
+
+        let Tokens { option, .. } = tokens;
+
+        let arms = match &self.data {
+            Data::Struct(st) => vec![arm(option, quote!(Self), &st.fields)],
+            Data::Enum(en) => en
+                .variants
+                .iter()
+                .map(|v| {
+                    let ident = v.ident;
+                    arm(option, quote!(Self::#ident), &v.fields)
+                })
+                .collect(),
+            Data::Union => Vec::new(),
+        };
+
+        quote! {
+            match #option::None::<Self> {
+                #(#arms)*
+                _ => {}
+            }
+        }
+    }
+
     /// Expand Encode implementation.
     pub(crate) fn expand_encode(&self) -> Result<TokenStream> {
         let modes = self.cx.modes();
@@ -362,6 +438,10 @@ impl<'a> Expander<'a> {
             out.extend(crate::en::expand_encode_entry(&build)?);
         }
 
+        if let Some((_, remote)) = self.type_attr.remote_type() {
+            out.extend(crate::en::expand_remote_encode(self, &tokens, remote));
+        }
+
         Ok(out)
     }
 
@@ -377,6 +457,10 @@ impl<'a> Expander<'a> {
             out.extend(crate::de::expand_decode_entry(&build)?);
         }
 
+        if let Some((_, remote)) = self.type_attr.remote_type() {
+            out.extend(crate::de::expand_remote_decode(self, &tokens, remote));
+        }
+
         Ok(out)
     }
 }
@@ -423,6 +507,76 @@ pub(crate) fn expand_name(
     (expr, None)
 }
 
+/// Find the representation specified through `#[repr(..)]`.
+fn repr_type(attrs: &[syn::Attribute]) -> Repr {
+    const INTEGERS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    let mut integer = None;
+    let mut other = None;
+
+    for a in attrs {
+        if !a.path().is_ident("repr") {
+            continue;
+        }
+
+        _ = a.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                if INTEGERS.iter().any(|integer| ident == integer) {
+                    integer = Some(ident.clone());
+                } else if ident != "align" {
+                    other = Some(ident.span());
+                }
+            }
+
+            if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<TokenStream>()?;
+            }
+
+            Ok(())
+        });
+    }
+
+    match (integer, other) {
+        (Some(integer), _) => Repr::Integer(integer),
+        (None, Some(span)) => Repr::Other(span),
+        (None, None) => Repr::Default,
+    }
+}
+
+/// Evaluate a discriminant consisting of an integer literal, which might be
+/// negated.
+fn literal_discriminant(expr: &syn::Expr) -> Option<i128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(..),
+            expr,
+            ..
+        }) => literal_discriminant(expr)?.checked_neg(),
+        syn::Expr::Paren(syn::ExprParen { expr, .. })
+        | syn::Expr::Group(syn::ExprGroup { expr, .. }) => literal_discriminant(expr),
+        _ => None,
+    }
+}
+
+/// Construct an unsuffixed literal expression for a discriminant.
+fn discriminant_expr(value: i128, span: Span) -> syn::Expr {
+    let lit = syn::LitInt::new(&value.unsigned_abs().to_string(), span);
+
+    if value < 0 {
+        syn::parse_quote!(-#lit)
+    } else {
+        syn::parse_quote!(#lit)
+    }
+}
+
 /// Ensure that the given integer is usize-suffixed so that it is treated as the
 /// appropriate type.
 pub(crate) fn usize_suffixed(index: usize, span: Span) -> syn::LitInt {
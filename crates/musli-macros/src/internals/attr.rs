@@ -285,7 +285,31 @@ layer! {
         content_format_with: syn::Path,
         /// `#[musli(packed)]` or `#[musli(transparent)]`.
         packing: Packing,
+        /// `#[musli(deny_unknown_fields)]`.
+        deny_unknown_fields: (),
+        /// `#[musli(flattenable)]`.
+        flattenable: (),
+        /// `#[musli(into = <type>)]`.
+        #[example = "into = <type>"]
+        convert_into: syn::Type,
+        /// `#[musli(from = <type>)]`.
+        #[example = "from = <type>"]
+        convert_from: syn::Type,
+        /// `#[musli(try_from = <type>)]`.
+        #[example = "try_from = <type>"]
+        convert_try_from: syn::Type,
+        /// `#[musli(validate = <path>)]`.
+        #[example = "validate = <path>"]
+        validate: syn::Path,
+        /// `#[musli(remote = <type>)]`.
+        #[example = "remote = <type>"]
+        remote: syn::Path,
+        /// `#[musli(version = <int>)]`.
+        #[example = "version = <int>"]
+        version: syn::LitInt,
         @multiple
+        /// `#[musli(migrate_from(<int> = <path>))]`.
+        migrations: Migration,
         /// Bounds in a where predicate.
         bounds: MusliBound,
         /// Bounds to require for a `Decode` implementation.
@@ -351,6 +375,12 @@ impl TypeAttr {
         Some(enum_tagging)
     }
 
+    /// Get the remote type this is a definition of through `#[musli(remote =
+    /// <type>)]`.
+    pub(crate) fn remote_type(&self) -> Option<&(Span, syn::Path)> {
+        self.root.remote.any.as_ref()
+    }
+
     /// Get the configured crate, or fallback to default.
     pub(crate) fn crate_or_default(&self, default: &str) -> syn::Path {
         if let Some((_, krate)) = self.root.krate.any.as_ref() {
@@ -460,6 +490,70 @@ pub(crate) fn type_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> TypeAttr {
                 return Ok(());
             }
 
+            if meta.path.is_ident("deny_unknown_fields") {
+                new.deny_unknown_fields.push((meta.path.span(), ()));
+                return Ok(());
+            }
+
+            if meta.path.is_ident("flattenable") {
+                new.flattenable.push((meta.path.span(), ()));
+                return Ok(());
+            }
+
+            if meta.path.is_ident("into") {
+                meta.input.parse::<Token![=]>()?;
+                new.convert_into
+                    .push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            if meta.path.is_ident("from") {
+                meta.input.parse::<Token![=]>()?;
+                new.convert_from
+                    .push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            if meta.path.is_ident("try_from") {
+                meta.input.parse::<Token![=]>()?;
+                new.convert_try_from
+                    .push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            if meta.path.is_ident("validate") {
+                meta.input.parse::<Token![=]>()?;
+                new.validate.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            if meta.path.is_ident("remote") {
+                meta.input.parse::<Token![=]>()?;
+                new.remote.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            if meta.path.is_ident("version") {
+                meta.input.parse::<Token![=]>()?;
+                let version: syn::LitInt = meta.input.parse()?;
+                version.base10_parse::<u32>()?;
+                new.version.push((meta.path.span(), version));
+                return Ok(());
+            }
+
+            if meta.path.is_ident("migrate_from") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+
+                let migrations = content.parse_terminated(Migration::parse, Token![,])?;
+
+                for migration in migrations {
+                    new.migrations.push((migration.version.span(), migration));
+                }
+
+                return Ok(());
+            }
+
             if let Some(m) = parse_path_mode(&meta) {
                 mode = Some(m);
                 return Ok(());
@@ -475,6 +569,17 @@ pub(crate) fn type_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> TypeAttr {
             cx.syn_error(error);
         }
 
+        if mode.is_some() || only.is_some() {
+            for &(span, _) in &new.remote {
+                cx.error_span(
+                    span,
+                    format_args!(
+                        "#[{ATTR}(remote = <type>)] cannot be specified for a particular mode or only for encoding or decoding"
+                    ),
+                );
+            }
+        }
+
         let attr = match mode {
             Some(mode) => {
                 let modes = attr.modes.entry(mode.kind.clone()).or_default();
@@ -550,6 +655,26 @@ fn parse_bound_types(
     Ok(())
 }
 
+/// A migration from an older version of a type through `#[musli(migrate_from(<int>
+/// = <path>))]`.
+pub(crate) struct Migration {
+    /// The version being migrated from.
+    pub(crate) version: syn::LitInt,
+    /// The function which converts the older version into the current one.
+    pub(crate) path: syn::Path,
+}
+
+impl Parse for Migration {
+    #[inline]
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let version: syn::LitInt = input.parse()?;
+        version.base10_parse::<u32>()?;
+        input.parse::<Token![=]>()?;
+        let path = input.parse()?;
+        Ok(Self { version, path })
+    }
+}
+
 pub(crate) enum MusliBound {
     Excluded(Box<syn::Path>),
     Predicate(Box<syn::WherePredicate>),
@@ -645,6 +770,9 @@ layer! {
         packing: Packing,
         /// `#[musli(default)]`.
         default_variant: (),
+        @multiple
+        /// `#[musli(alias = ..)]`.
+        alias: syn::Expr,
     }
 }
 
@@ -693,6 +821,12 @@ pub(crate) fn variant_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> VariantAttr
                 ));
             }
 
+            if meta.path.is_ident("alias") {
+                meta.input.parse::<Token![=]>()?;
+                new.alias.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
             if meta.path.is_ident("pattern") {
                 meta.input.parse::<Token![=]>()?;
 
@@ -791,6 +925,15 @@ layer! {
         skip: (),
         /// Field encoding to use.
         encoding: FieldEncoding,
+        /// Merge the fields of the value into the parent.
+        flatten: (),
+        /// Function used to validate the field once it's been decoded.
+        validate: syn::Path,
+        /// Function used to access the field of a remote type.
+        getter: syn::Path,
+        @multiple
+        /// Additional names accepted for the field when decoding.
+        alias: syn::Expr,
     }
 }
 
@@ -925,6 +1068,12 @@ pub(crate) fn field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> Field {
                 return Ok(());
             }
 
+            if meta.path.is_ident("alias") {
+                meta.input.parse::<Token![=]>()?;
+                new.alias.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
             if meta.path.is_ident("pattern") {
                 meta.input.parse::<Token![=]>()?;
 
@@ -957,6 +1106,23 @@ pub(crate) fn field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> Field {
                 return Ok(());
             }
 
+            if meta.path.is_ident("flatten") {
+                new.flatten.push((meta.path.span(), ()));
+                return Ok(());
+            }
+
+            if meta.path.is_ident("validate") {
+                meta.input.parse::<Token![=]>()?;
+                new.validate.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            if meta.path.is_ident("getter") {
+                meta.input.parse::<Token![=]>()?;
+                new.getter.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
             if meta.path.is_ident("trace") {
                 new.encoding.push((meta.path.span(), FieldEncoding::Trace));
                 return Ok(());
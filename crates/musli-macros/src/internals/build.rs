@@ -7,11 +7,13 @@ use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 
 use crate::expander::{
-    self, Data, EnumData, Expander, FieldData, Name, NameMethod, StructData, StructKind,
+    self, Data, EnumData, Expander, FieldData, Name, NameMethod, Repr, StructData, StructKind,
     UnsizedMethod, VariantData,
 };
 
-use super::attr::{DefaultOrCustom, EnumTagging, FieldEncoding, ModeKind, MusliBound, Packing};
+use super::attr::{
+    DefaultOrCustom, EnumTagging, FieldEncoding, Migration, ModeKind, MusliBound, Packing,
+};
 use super::mode::ImportedMethod;
 use super::name::NameAll;
 use super::{ATTR, Ctxt, Expansion, Mode, Only, Result, Tokens};
@@ -35,6 +37,26 @@ pub(crate) struct Build<'a> {
     pub(crate) encode_t_encode: ImportedMethod<'a>,
     pub(crate) enum_tag: Option<Span>,
     pub(crate) enum_content: Option<Span>,
+    /// Type to encode through with `#[musli(into = <type>)]`.
+    pub(crate) into: Option<&'a (Span, syn::Type)>,
+    /// Type to decode through with `#[musli(from = <type>)]`.
+    pub(crate) from: Option<&'a (Span, syn::Type)>,
+    /// Type to decode through with `#[musli(try_from = <type>)]`.
+    pub(crate) try_from: Option<&'a (Span, syn::Type)>,
+    /// Function to validate the decoded value with through `#[musli(validate
+    /// = <path>)]`.
+    pub(crate) validate: Option<&'a (Span, syn::Path)>,
+    /// The remote type this is a definition of through `#[musli(remote =
+    /// <type>)]`.
+    pub(crate) remote: Option<&'a (Span, syn::Path)>,
+    /// The version prefix written through `#[musli(version = <int>)]`.
+    pub(crate) version: Option<&'a (Span, syn::LitInt)>,
+    /// If the flatten traits should be implemented through
+    /// `#[musli(flattenable)]`.
+    pub(crate) flattenable: Option<Span>,
+    /// Migrations from older versions through `#[musli(migrate_from(<int> =
+    /// <path>))]`.
+    pub(crate) migrations: &'a [(Span, Migration)],
     pub(crate) tokens: &'a Tokens<'a>,
     pub(crate) p: Parameters,
 }
@@ -53,8 +75,150 @@ impl Build<'_> {
     fn validate(&self, only: Only) -> Result<()> {
         self.data
             .validate(only, self.cx, &self.mode, self.enum_tag, self.enum_content);
+
+        if let (Some(_), Some(&(span, _))) = (self.from, self.try_from) {
+            let mode = &self.mode;
+
+            self.cx.error_span(
+                span,
+                format_args!(
+                    "In {mode} #[{ATTR}(from)] and #[{ATTR}(try_from)] cannot be used together"
+                ),
+            );
+        }
+
+        self.validate_versioning();
+        self.validate_flattenable(only);
+
         Ok(())
     }
+
+    fn validate_flattenable(&self, only: Only) {
+        let mode = &self.mode;
+
+        let Some(span) = self.flattenable else {
+            return;
+        };
+
+        let st = match &self.data {
+            BuildData::Struct(st) => st,
+            BuildData::Enum(..) => {
+                self.cx.error_span(
+                    span,
+                    format_args!("In {mode} #[{ATTR}(flattenable)] is only supported on structs"),
+                );
+
+                return;
+            }
+        };
+
+        if let StructKind::Empty = st.kind {
+            self.cx.error_span(
+                span,
+                format_args!("In {mode} #[{ATTR}(flattenable)] requires a struct with fields"),
+            );
+        }
+
+        if let (_, packing @ (Packing::Packed | Packing::Transparent | Packing::Untagged)) =
+            st.packing
+        {
+            self.cx.error_span(
+                span,
+                format_args!(
+                    "In {mode} a #[{ATTR}({packing})] struct cannot be #[{ATTR}(flattenable)]"
+                ),
+            );
+        }
+
+        // Custom name types might elide lifetimes, which is not permitted in
+        // the header of the `DecodeFlatten` implementation.
+        if let (Only::Decode, true) = (only, st.custom_name_type) {
+            self.cx.error_span(
+                span,
+                format_args!(
+                    "In {mode} a struct with #[{ATTR}(name(type = ..))] cannot be #[{ATTR}(flattenable)]"
+                ),
+            );
+        }
+
+        let conflicts = [
+            ("remote", self.remote.is_some()),
+            ("version", self.version.is_some()),
+            ("into", matches!(only, Only::Encode) && self.into.is_some()),
+            ("from", matches!(only, Only::Decode) && self.from.is_some()),
+            (
+                "try_from",
+                matches!(only, Only::Decode) && self.try_from.is_some(),
+            ),
+        ];
+
+        for (name, conflicts) in conflicts {
+            if conflicts {
+                self.cx.error_span(
+                    span,
+                    format_args!(
+                        "In {mode} #[{ATTR}(flattenable)] cannot be used in combination with #[{ATTR}({name})]"
+                    ),
+                );
+            }
+        }
+    }
+
+    fn validate_versioning(&self) {
+        let mode = &self.mode;
+
+        let Some((span, version)) = self.version else {
+            for &(span, _) in self.migrations {
+                self.cx.error_span(
+                    span,
+                    format_args!(
+                        "In {mode} #[{ATTR}(migrate_from)] requires #[{ATTR}(version = <int>)]"
+                    ),
+                );
+            }
+
+            return;
+        };
+
+        if self.remote.is_some() {
+            self.cx.error_span(
+                *span,
+                format_args!(
+                    "In {mode} #[{ATTR}(version)] cannot be used in combination with #[{ATTR}(remote)]"
+                ),
+            );
+        }
+
+        let Ok(current) = version.base10_parse::<u32>() else {
+            return;
+        };
+
+        let mut seen = HashSet::new();
+
+        for (span, migration) in self.migrations {
+            let Ok(from) = migration.version.base10_parse::<u32>() else {
+                continue;
+            };
+
+            if from >= current {
+                self.cx.error_span(
+                    *span,
+                    format_args!(
+                        "In {mode} #[{ATTR}(migrate_from)] can only migrate from versions older than {current}"
+                    ),
+                );
+            }
+
+            if !seen.insert(from) {
+                self.cx.error_span(
+                    *span,
+                    format_args!(
+                        "In {mode} #[{ATTR}(migrate_from)] specifies version {from} more than once"
+                    ),
+                );
+            }
+        }
+    }
 }
 
 /// Build model for enums and structs.
@@ -144,6 +308,11 @@ impl BuildData<'_> {
 
 pub(crate) struct Body<'a> {
     pub(crate) name: Name<'a, syn::LitStr>,
+    /// If the name type has been specified through `#[musli(name(type = ..))]`.
+    pub(crate) custom_name_type: bool,
+    /// If decoding should fail on unknown fields through
+    /// `#[musli(deny_unknown_fields)]`.
+    pub(crate) deny_unknown_fields: bool,
     pub(crate) all_fields: Vec<Field<'a>>,
     pub(crate) packing: (Span, Packing),
     pub(crate) kind: StructKind,
@@ -277,6 +446,72 @@ impl<'a> Body<'a> {
                         format_args!("A #[{ATTR}({packing})]{singular} cannot have field patterns"),
                     );
                 }
+
+                for &(span, _) in f.aliases {
+                    cx.error_span(
+                        span,
+                        format_args!("A #[{ATTR}({packing})]{singular} cannot have field aliases"),
+                    );
+                }
+            }
+        }
+
+        for f in &self.all_fields {
+            let Some(span) = f.flatten else {
+                continue;
+            };
+
+            if let (_, packing @ (Packing::Transparent | Packing::Packed)) = self.packing {
+                cx.error_span(
+                    span,
+                    format_args!("A #[{ATTR}({packing})] {singular} cannot have flattened fields"),
+                );
+            }
+
+            if let NameMethod::Unsized(UnsizedMethod::Bytes) = self.name.method {
+                cx.error_span(
+                    span,
+                    format_args!(
+                        "In {mode} a {singular} named by bytes cannot have flattened fields"
+                    ),
+                );
+            }
+
+            if let Some(span) = f.name_span {
+                cx.error_span(
+                    span,
+                    format_args!("A #[{ATTR}(flatten)] field cannot be named"),
+                );
+            }
+
+            if let Some(pattern) = f.pattern {
+                cx.error_span(
+                    pattern.span(),
+                    format_args!("A #[{ATTR}(flatten)] field cannot have a pattern"),
+                );
+            }
+
+            for &(span, _) in f.aliases {
+                cx.error_span(
+                    span,
+                    format_args!("A #[{ATTR}(flatten)] field cannot have aliases"),
+                );
+            }
+
+            if let Some(&(span, _)) = f.validate {
+                cx.error_span(
+                    span,
+                    format_args!("A #[{ATTR}(flatten)] field cannot be validated"),
+                );
+            }
+        }
+
+        for f in &self.all_fields {
+            if let (Some(_), Some(&(span, _))) = (f.skip, f.validate) {
+                cx.error_span(
+                    span,
+                    format_args!("A #[{ATTR}(skip)] field cannot be validated"),
+                );
             }
         }
 
@@ -308,6 +543,7 @@ pub(crate) struct Variant<'a> {
     pub(crate) index: usize,
     pub(crate) name: syn::Expr,
     pub(crate) pattern: Option<&'a syn::Pat>,
+    pub(crate) aliases: &'a [(Span, syn::Expr)],
     pub(crate) st: Body<'a>,
     pub(crate) patterns: Punctuated<syn::FieldPat, Token![,]>,
 }
@@ -321,10 +557,18 @@ pub(crate) struct Field<'a> {
     pub(crate) name: syn::Expr,
     pub(crate) name_span: Option<Span>,
     pub(crate) pattern: Option<&'a syn::Pat>,
+    /// Additional names accepted when decoding.
+    pub(crate) aliases: &'a [(Span, syn::Expr)],
     /// Skip field entirely and always initialize with the specified expresion,
     /// or default value through `default_attr`.
     pub(crate) skip: Option<Span>,
     pub(crate) skip_encoding_if: Option<&'a (Span, syn::Path)>,
+    /// Merge the fields of the value into the parent.
+    pub(crate) flatten: Option<Span>,
+    /// Function to validate the decoded value of the field with.
+    pub(crate) validate: Option<&'a (Span, syn::Path)>,
+    /// Function used to access the field of a remote type.
+    pub(crate) getter: Option<&'a (Span, syn::Path)>,
     /// Fill with default value, if missing.
     pub(crate) default_attr: Option<(Span, Option<&'a syn::Path>)>,
     pub(crate) access: syn::Expr,
@@ -386,6 +630,14 @@ pub(crate) fn setup<'a>(
     let decode_bounds = e.type_attr.decode_bounds(&mode);
     let enum_tag = e.type_attr.tag_value(&mode).map(|(s, _)| *s);
     let enum_content = e.type_attr.content_value(&mode).map(|(s, _)| *s);
+    let into = e.type_attr.convert_into(&mode);
+    let from = e.type_attr.convert_from(&mode);
+    let try_from = e.type_attr.convert_try_from(&mode);
+    let validate = e.type_attr.validate(&mode);
+    let remote = e.type_attr.remote_type();
+    let version = e.type_attr.version(&mode);
+    let migrations = e.type_attr.migrations(&mode);
+    let flattenable = e.type_attr.flattenable(&mode).map(|(s, _)| *s);
 
     Ok(Build {
         mode,
@@ -399,6 +651,14 @@ pub(crate) fn setup<'a>(
         encode_t_encode,
         enum_tag,
         enum_content,
+        into,
+        from,
+        try_from,
+        validate,
+        remote,
+        version,
+        flattenable,
+        migrations,
         tokens,
         p,
     })
@@ -431,7 +691,28 @@ fn setup_struct<'a>(
         ),
     };
 
-    let path = syn::Path::from(syn::Ident::new("Self", e.input.ident.span()));
+    if let (NameAll::Discriminant, Some(&(span, _))) = (name_all, e.type_attr.name_all(mode)) {
+        e.cx.error_span(
+            span,
+            format_args!(
+                "In {mode} #[{ATTR}(name_all = \"{name_all}\")] can only be used on enums to name variants"
+            ),
+        );
+    }
+
+    let has_getter = e.type_attr.remote_type().is_some()
+        && data.fields.iter().any(|f| f.attr.getter(mode).is_some());
+
+    // A remote type with private fields is constructed by converting from the
+    // local definition.
+    let path = if has_getter {
+        let ident = &e.input.ident;
+        let (_, type_generics, _) = e.input.generics.split_for_impl();
+        let turbofish = type_generics.as_turbofish();
+        syn::parse_quote!(#ident #turbofish)
+    } else {
+        syn::Path::from(syn::Ident::new("Self", e.input.ident.span()))
+    };
 
     for f in &data.fields {
         all_fields.push(setup_field(e, mode, f, name_all, None, allocator_ident));
@@ -445,6 +726,8 @@ fn setup_struct<'a>(
             method: name_method,
             format_with: e.type_attr.name_format_with(mode),
         },
+        custom_name_type: e.type_attr.name_type(mode).is_some(),
+        deny_unknown_fields: e.type_attr.deny_unknown_fields(mode).is_some(),
         all_fields,
         packing,
         kind: data.kind,
@@ -482,13 +765,30 @@ fn setup_enum<'a>(
         .map(|&(span, p)| (span, p))
         .unwrap_or_else(|| (Span::call_site(), Packing::default()));
 
-    let (_, name_type, name_method, name_span) = split_name(
+    let (name_all, mut name_type, name_method, name_span) = split_name(
         mode.kind,
         e.type_attr.name_type(mode),
         e.type_attr.name_all(mode),
         e.type_attr.name_method(mode),
     );
 
+    if let (NameAll::Discriminant, None) = (name_all, e.type_attr.name_type(mode)) {
+        match &data.repr {
+            Repr::Default => {}
+            Repr::Integer(repr) => {
+                name_type = syn::parse_quote!(#repr);
+            }
+            Repr::Other(span) => {
+                e.cx.error_span(
+                    *span,
+                    format_args!(
+                        "In {mode} #[{ATTR}(name_all = \"{name_all}\")] requires #[repr(..)] to specify an integer type like #[repr(u8)], or #[{ATTR}(name(type = ..))] to be used"
+                    ),
+                );
+            }
+        }
+    }
+
     for v in &data.variants {
         variants.push(setup_variant(e, mode, v, &mut fallback, allocator_ident));
     }
@@ -543,7 +843,32 @@ fn setup_variant<'a>(
         e.type_attr.name_method(mode),
     );
 
-    let (name, _) = expander::expand_name(data, mode, type_name_all, Some(data.ident));
+    if let NameAll::Discriminant = name_all {
+        if let Some(&(span, _)) = data.attr.name_all(mode) {
+            e.cx.error_span(
+                span,
+                format_args!(
+                    "In {mode} #[{ATTR}(name_all = \"{name_all}\")] can only be used on enums to name variants"
+                ),
+            );
+        }
+    }
+
+    let name = match (type_name_all, data.attr.name_expr(mode), &data.discriminant) {
+        (NameAll::Discriminant, None, Some(discriminant)) => discriminant.clone(),
+        (NameAll::Discriminant, None, None) => {
+            e.cx.error_span(
+                data.span,
+                format_args!(
+                    "In {mode} #[{ATTR}(name_all = \"{type_name_all}\")] requires the variant to have a literal integer discriminant, or to follow a variant which does"
+                ),
+            );
+
+            let index = expander::usize_suffixed(data.index, data.span);
+            syn::parse_quote!(#index)
+        }
+        _ => expander::expand_name(data, mode, type_name_all, Some(data.ident)).0,
+    };
 
     let pattern = data.attr.pattern(mode).map(|(_, p)| p);
 
@@ -582,6 +907,8 @@ fn setup_variant<'a>(
             method: name_method,
             format_with: data.attr.name_format_with(mode),
         },
+        custom_name_type: data.attr.name_type(mode).is_some(),
+        deny_unknown_fields: e.type_attr.deny_unknown_fields(mode).is_some(),
         all_fields,
         packing,
         kind: data.kind,
@@ -593,6 +920,7 @@ fn setup_variant<'a>(
         index: data.index,
         name,
         pattern,
+        aliases: data.attr.alias(mode),
         patterns,
         st,
     }
@@ -617,6 +945,9 @@ fn setup_field<'a>(
 
     let skip = data.attr.skip(mode).map(|&(s, ())| s);
     let skip_encoding_if = data.attr.skip_encoding_if(mode);
+    let flatten = data.attr.flatten(mode).map(|&(s, ())| s);
+    let validate = data.attr.validate(mode);
+    let getter = data.attr.getter(mode);
     let default_attr = data
         .attr
         .is_default(mode)
@@ -632,6 +963,8 @@ fn setup_field<'a>(
 
     let access;
 
+    let patterns_used = patterns.is_some();
+
     if let Some(patterns) = patterns {
         match data.ident {
             Some(ident) => {
@@ -686,10 +1019,28 @@ fn setup_field<'a>(
                 });
             }
         }
+    } else if let (Some((_, getter)), Some(_)) = (getter, e.type_attr.remote_type()) {
+        access = syn::parse_quote!(&#getter(self));
     } else {
         access = syn::parse_quote!(&self.#member);
     };
 
+    if let Some(&(span, _)) = getter {
+        if e.type_attr.remote_type().is_none() {
+            e.cx.error_span(
+                span,
+                format_args!(
+                    "In {mode} #[{ATTR}(getter)] can only be used in combination with #[{ATTR}(remote)]"
+                ),
+            );
+        } else if patterns_used {
+            e.cx.error_span(
+                span,
+                format_args!("In {mode} #[{ATTR}(getter)] is not supported in enum variants"),
+            );
+        }
+    }
+
     let var = match &member {
         syn::Member::Named(ident) => e.cx.ident_with_span(&ident.to_string(), ident.span(), "_f"),
         syn::Member::Unnamed(index) => {
@@ -706,8 +1057,12 @@ fn setup_field<'a>(
         name,
         name_span,
         pattern: pattern.map(|(_, p)| p),
+        aliases: data.attr.alias(mode),
         skip,
         skip_encoding_if,
+        flatten,
+        validate,
+        getter,
         default_attr,
         access,
         member,
@@ -22,20 +22,29 @@ impl ToTokens for Import<'_> {
 pub(crate) struct Tokens<'a> {
     pub(crate) allocator_t: Import<'a>,
     pub(crate) as_decoder_t: Import<'a>,
+    pub(crate) clone_t: Import<'a>,
+    pub(crate) collect_bytes: Import<'a>,
     pub(crate) collect_string: Import<'a>,
     pub(crate) context_t: Import<'a>,
     pub(crate) decode_bytes_t: Import<'a>,
+    pub(crate) decode_flatten_t: Import<'a>,
     pub(crate) decode_packed_t: Import<'a>,
+    pub(crate) decode_remote_t: Import<'a>,
     pub(crate) decode_t: Import<'a>,
     pub(crate) decoder_t: Import<'a>,
     pub(crate) default_function: Import<'a>,
     pub(crate) encode_bytes_t: Import<'a>,
+    pub(crate) encode_flatten_t: Import<'a>,
     pub(crate) encode_packed_t: Import<'a>,
+    pub(crate) encode_remote_t: Import<'a>,
     pub(crate) encode_t: Import<'a>,
     pub(crate) encoder_t: Import<'a>,
     pub(crate) entry_decoder_t: Import<'a>,
     pub(crate) entry_encoder_t: Import<'a>,
+    pub(crate) error_t: Import<'a>,
     pub(crate) fmt: Import<'a>,
+    pub(crate) from_t: Import<'a>,
+    pub(crate) into_t: Import<'a>,
     pub(crate) map_decoder_t: Import<'a>,
     pub(crate) map_encoder_t: Import<'a>,
     pub(crate) map_hint: Import<'a>,
@@ -44,6 +53,7 @@ pub(crate) struct Tokens<'a> {
     pub(crate) offset_of: Import<'a>,
     pub(crate) option: Import<'a>,
     pub(crate) pack_decoder_t: Import<'a>,
+    pub(crate) remote_map_hint: Import<'a>,
     pub(crate) result: Import<'a>,
     pub(crate) sequence_encoder_t: Import<'a>,
     pub(crate) size_of: Import<'a>,
@@ -53,6 +63,7 @@ pub(crate) struct Tokens<'a> {
     pub(crate) trace_encode_t: Import<'a>,
     pub(crate) try_fast_decode: Import<'a>,
     pub(crate) try_fast_encode: Import<'a>,
+    pub(crate) try_from_t: Import<'a>,
     pub(crate) variant_decoder_t: Import<'a>,
     pub(crate) variant_encoder_t: Import<'a>,
     pub(crate) prefix: &'a syn::Path,
@@ -63,20 +74,29 @@ impl<'a> Tokens<'a> {
         Self {
             allocator_t: Import(prefix, "Allocator"),
             as_decoder_t: Import(prefix, "AsDecoder"),
+            clone_t: Import(prefix, "Clone"),
+            collect_bytes: Import(prefix, "collect_bytes"),
             collect_string: Import(prefix, "collect_string"),
             context_t: Import(prefix, "Context"),
             decode_bytes_t: Import(prefix, "DecodeBytes"),
+            decode_flatten_t: Import(prefix, "DecodeFlatten"),
             decode_packed_t: Import(prefix, "DecodePacked"),
+            decode_remote_t: Import(prefix, "DecodeRemote"),
             decode_t: Import(prefix, "Decode"),
             decoder_t: Import(prefix, "Decoder"),
             default_function: Import(prefix, "default"),
             encode_bytes_t: Import(prefix, "EncodeBytes"),
+            encode_flatten_t: Import(prefix, "EncodeFlatten"),
             encode_packed_t: Import(prefix, "EncodePacked"),
+            encode_remote_t: Import(prefix, "EncodeRemote"),
             encode_t: Import(prefix, "Encode"),
             encoder_t: Import(prefix, "Encoder"),
             entry_decoder_t: Import(prefix, "EntryDecoder"),
             entry_encoder_t: Import(prefix, "EntryEncoder"),
+            error_t: Import(prefix, "Error"),
             fmt: Import(prefix, "fmt"),
+            from_t: Import(prefix, "From"),
+            into_t: Import(prefix, "Into"),
             map_decoder_t: Import(prefix, "MapDecoder"),
             map_encoder_t: Import(prefix, "MapEncoder"),
             map_hint: Import(prefix, "map_hint"),
@@ -85,6 +105,7 @@ impl<'a> Tokens<'a> {
             offset_of: Import(prefix, "offset_of"),
             option: Import(prefix, "Option"),
             pack_decoder_t: Import(prefix, "SequenceDecoder"),
+            remote_map_hint: Import(prefix, "remote_map_hint"),
             result: Import(prefix, "Result"),
             sequence_encoder_t: Import(prefix, "SequenceEncoder"),
             size_of: Import(prefix, "size_of"),
@@ -94,6 +115,7 @@ impl<'a> Tokens<'a> {
             trace_encode_t: Import(prefix, "EncodeTrace"),
             try_fast_decode: Import(prefix, "TryFastDecode"),
             try_fast_encode: Import(prefix, "TryFastEncode"),
+            try_from_t: Import(prefix, "TryFrom"),
             variant_decoder_t: Import(prefix, "VariantDecoder"),
             variant_encoder_t: Import(prefix, "VariantEncoder"),
             prefix,
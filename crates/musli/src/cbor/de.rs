@@ -0,0 +1,1196 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::Context;
+use crate::Reader;
+use crate::alloc::Vec;
+use crate::de::{
+    Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder, SizeHint, Skip,
+    UnsizedVisitor, VariantDecoder, Visitor,
+};
+use crate::options::{self, Options};
+use crate::value::{IntoValueDecoder, Value};
+
+use super::tag::{self, Kind, Marker};
+
+const BUFFER_OPTIONS: Options = options::new().build();
+
+/// A decoder for CBOR.
+pub struct CborDecoder<R, C, M> {
+    cx: C,
+    reader: R,
+    /// A semantic tag which has already been read from the input. If this is
+    /// set the decoder yields the tag number as an unsigned integer.
+    tag: Option<u64>,
+    _marker: PhantomData<M>,
+}
+
+impl<R, C, M> CborDecoder<R, C, M> {
+    /// Construct a new CBOR decoder.
+    #[inline]
+    pub(crate) fn new(cx: C, reader: R) -> Self {
+        Self::with_tag(cx, reader, None)
+    }
+
+    #[inline]
+    fn with_tag(cx: C, reader: R, tag: Option<u64>) -> Self {
+        Self {
+            cx,
+            reader,
+            tag,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The head of a data item.
+struct Head {
+    /// The initial byte.
+    byte: u8,
+    /// The argument of the head, or `None` if the item has an indefinite
+    /// length.
+    argument: Option<u64>,
+}
+
+impl Head {
+    #[inline]
+    fn major(&self) -> u8 {
+        self.byte >> 5
+    }
+}
+
+/// A decoded integer, before it's been converted into the requested type.
+enum Integer {
+    /// The integer `n`.
+    Positive(u128),
+    /// The integer `-1 - n`.
+    Negative(u128),
+}
+
+impl<'de, R, C, M> CborDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    /// Skip over any value, including nested containers.
+    ///
+    /// This doesn't recurse, so deeply nested input can't overflow the stack.
+    /// Like in MessagePack the items of nested definite-length containers are
+    /// added to a single counter, and the enclosing frame is only saved when
+    /// entering or leaving an indefinite-length container.
+    pub(crate) fn skip_any(mut self) -> Result<(), C::Error> {
+        if self.tag.take().is_some() {
+            return Ok(());
+        }
+
+        // The number of items remaining in the current frame, or `None` if
+        // it's an indefinite-length container which ends with a break.
+        let mut current = Some(1usize);
+        let mut frames = Vec::new_in(self.cx.alloc());
+
+        loop {
+            let pending = match &mut current {
+                Some(0) => false,
+                Some(n) => {
+                    *n -= 1;
+                    true
+                }
+                None => !self.try_break()?,
+            };
+
+            if !pending {
+                match frames.pop() {
+                    Some(frame) => {
+                        current = frame;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+
+            let pos = self.cx.mark();
+            let head = self.read_head()?;
+
+            let nested = match head.major() {
+                tag::UNSIGNED | tag::NEGATIVE => {
+                    self.definite(&head)?;
+                    None
+                }
+                tag::BYTES | tag::TEXT => {
+                    if let Some(len) = head.argument {
+                        let len = self.to_len(len)?;
+                        self.reader.skip(self.cx, len)?;
+                    } else {
+                        while !self.try_break()? {
+                            let len = self.decode_chunk(head.major())?;
+                            self.reader.skip(self.cx, len)?;
+                        }
+                    }
+
+                    None
+                }
+                tag::ARRAY => match head.argument {
+                    Some(len) => Some(Some(self.to_len(len)?)),
+                    None => Some(None),
+                },
+                tag::MAP => match head.argument {
+                    Some(len) => {
+                        let Some(len) = self.to_len(len)?.checked_mul(2) else {
+                            return Err(self.cx.message_at(&pos, "Map length overflow"));
+                        };
+
+                        Some(Some(len))
+                    }
+                    None => Some(None),
+                },
+                tag::TAG => {
+                    self.definite(&head)?;
+                    Some(Some(1))
+                }
+                _ => {
+                    if head.byte == tag::BREAK {
+                        return Err(self.cx.message_at(
+                            &pos,
+                            format_args!("Cannot skip over {}", Marker(head.byte)),
+                        ));
+                    }
+
+                    None
+                }
+            };
+
+            let Some(len) = nested else {
+                continue;
+            };
+
+            match (&mut current, len) {
+                (Some(n), Some(len)) => {
+                    let Some(sum) = n.checked_add(len) else {
+                        return Err(self.cx.message_at(&pos, "Length overflow"));
+                    };
+
+                    *n = sum;
+                }
+                _ => {
+                    frames.push(current).map_err(self.cx.map())?;
+                    current = len;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the head of a data item.
+    #[inline]
+    fn read_head(&mut self) -> Result<Head, C::Error> {
+        let pos = self.cx.mark();
+
+        if let Some(tag) = self.tag {
+            return Err(self.cx.message_at(
+                &pos,
+                format_args!("Expected value, but got tag number {tag}"),
+            ));
+        }
+
+        let byte = self.reader.read_byte(self.cx)?;
+
+        let argument = match byte & 0x1f {
+            n if n < tag::ARGUMENT_U8 => Some(u64::from(n)),
+            tag::ARGUMENT_U8 => Some(u64::from(self.reader.read_byte(self.cx)?)),
+            tag::ARGUMENT_U16 => Some(u16::from_be_bytes(self.reader.read_array(self.cx)?).into()),
+            tag::ARGUMENT_U32 => Some(u32::from_be_bytes(self.reader.read_array(self.cx)?).into()),
+            tag::ARGUMENT_U64 => Some(u64::from_be_bytes(self.reader.read_array(self.cx)?)),
+            tag::INDEFINITE => None,
+            _ => {
+                return Err(self.cx.message_at(
+                    &pos,
+                    format_args!("Reserved additional information in {}", Marker(byte)),
+                ));
+            }
+        };
+
+        Ok(Head { byte, argument })
+    }
+
+    /// Read the head of a data item of the given major type.
+    #[inline]
+    fn read_head_of(&mut self, major: u8, kind: Kind) -> Result<Head, C::Error> {
+        let pos = self.cx.mark();
+        let head = self.read_head()?;
+
+        if head.major() != major {
+            return Err(self.cx.message_at(
+                &pos,
+                format_args!("Expected {kind}, but got {}", Marker(head.byte)),
+            ));
+        }
+
+        Ok(head)
+    }
+
+    /// Get the argument of a head which must have a definite length.
+    #[inline]
+    fn definite(&self, head: &Head) -> Result<u64, C::Error> {
+        match head.argument {
+            Some(argument) => Ok(argument),
+            None => Err(self.cx.message(format_args!(
+                "Unexpected indefinite length for {}",
+                Marker(head.byte)
+            ))),
+        }
+    }
+
+    #[inline]
+    fn to_len(&self, len: u64) -> Result<usize, C::Error> {
+        match usize::try_from(len) {
+            Ok(len) => Ok(len),
+            Err(..) => Err(self.cx.message(format_args!(
+                "Length {len} does not fit in a usize on this platform"
+            ))),
+        }
+    }
+
+    /// Test if the next byte is a break, consuming it if it is.
+    #[inline]
+    fn try_break(&mut self) -> Result<bool, C::Error> {
+        if self.reader.peek() == Some(tag::BREAK) {
+            self.reader.skip(self.cx, 1)?;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// Decode the head of a chunk of an indefinite-length string and return
+    /// its length.
+    #[inline]
+    fn decode_chunk(&mut self, major: u8) -> Result<usize, C::Error> {
+        let pos = self.cx.mark();
+        let head = self.read_head()?;
+
+        let (true, Some(len)) = (head.major() == major, head.argument) else {
+            return Err(self.cx.message_at(
+                &pos,
+                format_args!(
+                    "Expected definite-length chunk, but got {}",
+                    Marker(head.byte)
+                ),
+            ));
+        };
+
+        self.to_len(len)
+    }
+
+    /// Construct a decoder for the remaining items of an array or map.
+    #[inline]
+    fn remaining(self, head: &Head) -> Result<RemainingCborDecoder<R, C, M>, C::Error> {
+        let remaining = match head.argument {
+            Some(len) => Some(self.to_len(len)?),
+            None => None,
+        };
+
+        Ok(RemainingCborDecoder::new(self.cx, self.reader, remaining))
+    }
+
+    /// Decode a byte or text string, concatenating the chunks of an
+    /// indefinite-length string.
+    #[inline]
+    fn decode_string_of<V>(mut self, major: u8, kind: Kind, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8], Error = C::Error, Allocator = C::Allocator>,
+    {
+        let head = self.read_head_of(major, kind)?;
+
+        if let Some(len) = head.argument {
+            let len = self.to_len(len)?;
+            return self.reader.read_bytes(self.cx, len, visitor);
+        }
+
+        let mut buffer = Vec::new_in(self.cx.alloc());
+
+        while !self.try_break()? {
+            let len = self.decode_chunk(major)?;
+            self.reader
+                .read_bytes(self.cx, len, ExtendVisitor(&mut buffer))?;
+        }
+
+        visitor.visit_owned(self.cx, buffer)
+    }
+
+    #[inline]
+    fn decode_integer(&mut self) -> Result<Integer, C::Error> {
+        if let Some(tag) = self.tag.take() {
+            return Ok(Integer::Positive(u128::from(tag)));
+        }
+
+        let pos = self.cx.mark();
+        let head = self.read_head()?;
+
+        let integer = match (head.major(), head.argument) {
+            (tag::UNSIGNED, Some(value)) => Integer::Positive(u128::from(value)),
+            (tag::NEGATIVE, Some(value)) => Integer::Negative(u128::from(value)),
+            (tag::TAG, Some(tag::POSITIVE_BIGNUM)) => Integer::Positive(self.decode_bignum()?),
+            (tag::TAG, Some(tag::NEGATIVE_BIGNUM)) => Integer::Negative(self.decode_bignum()?),
+            _ => {
+                return Err(self.cx.message_at(
+                    &pos,
+                    format_args!("Expected integer, but got {}", Marker(head.byte)),
+                ));
+            }
+        };
+
+        Ok(integer)
+    }
+
+    /// Decode the content of a bignum.
+    #[inline]
+    fn decode_bignum(&mut self) -> Result<u128, C::Error> {
+        struct Bignum;
+
+        #[crate::trait_defaults(crate)]
+        impl<'de, C> UnsizedVisitor<'de, C, [u8]> for Bignum
+        where
+            C: Context,
+        {
+            type Ok = u128;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "bignum")
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: C, bytes: &[u8]) -> Result<Self::Ok, Self::Error> {
+                let mut value = 0u128;
+
+                for &b in bytes {
+                    if value >> 120 != 0 {
+                        return Err(cx.message("Bignum does not fit in 128 bits"));
+                    }
+
+                    value = (value << 8) | u128::from(b);
+                }
+
+                Ok(value)
+            }
+        }
+
+        self.decode_string_of_mut(Bignum)
+    }
+
+    /// Decode a byte string through a mutable reference.
+    #[inline]
+    fn decode_string_of_mut<V>(&mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8], Error = C::Error, Allocator = C::Allocator>,
+    {
+        CborDecoder::<_, _, M>::new(self.cx, self.reader.borrow_mut()).decode_string_of(
+            tag::BYTES,
+            Kind::Bytes,
+            visitor,
+        )
+    }
+
+    #[inline]
+    fn decode_unsigned<T>(mut self) -> Result<T, C::Error>
+    where
+        T: TryFrom<u128>,
+    {
+        let pos = self.cx.mark();
+
+        let value = match self.decode_integer()? {
+            Integer::Positive(value) => T::try_from(value).ok(),
+            Integer::Negative(..) => None,
+        };
+
+        match value {
+            Some(value) => Ok(value),
+            None => Err(self.cx.message_at(&pos, "Integer out of range")),
+        }
+    }
+
+    #[inline]
+    fn decode_signed<T>(mut self) -> Result<T, C::Error>
+    where
+        T: TryFrom<i128>,
+    {
+        let pos = self.cx.mark();
+
+        let value = match self.decode_integer()? {
+            Integer::Positive(value) => i128::try_from(value).ok(),
+            Integer::Negative(value) => i128::try_from(value).ok().map(|value| -1 - value),
+        };
+
+        match value.and_then(|value| T::try_from(value).ok()) {
+            Some(value) => Ok(value),
+            None => Err(self.cx.message_at(&pos, "Integer out of range")),
+        }
+    }
+
+    #[inline]
+    fn decode_float(mut self) -> Result<f64, C::Error> {
+        let pos = self.cx.mark();
+        let head = self.read_head()?;
+
+        let value = match (head.byte, head.argument) {
+            (tag::FLOAT16, Some(bits)) => f64::from(tag::f16_to_f32(bits as u16)),
+            (tag::FLOAT32, Some(bits)) => f64::from(f32::from_bits(bits as u32)),
+            (tag::FLOAT64, Some(bits)) => f64::from_bits(bits),
+            _ => {
+                return Err(self.cx.message_at(
+                    &pos,
+                    format_args!("Expected float, but got {}", Marker(head.byte)),
+                ));
+            }
+        };
+
+        Ok(value)
+    }
+
+    #[inline]
+    fn shared_decode_sequence(mut self) -> Result<RemainingCborDecoder<R, C, M>, C::Error> {
+        let head = self.read_head_of(tag::ARRAY, Kind::Array)?;
+        self.remaining(&head)
+    }
+
+    #[inline]
+    fn shared_decode_map(mut self) -> Result<RemainingCborDecoder<R, C, M>, C::Error> {
+        let head = self.read_head_of(tag::MAP, Kind::Map)?;
+        self.remaining(&head)
+    }
+}
+
+/// A visitor which appends the chunks of an indefinite-length string to a
+/// buffer.
+struct ExtendVisitor<'a, A>(&'a mut Vec<u8, A>)
+where
+    A: crate::alloc::Allocator;
+
+#[crate::trait_defaults(crate)]
+impl<'de, C> UnsizedVisitor<'de, C, [u8]> for ExtendVisitor<'_, C::Allocator>
+where
+    C: Context,
+{
+    type Ok = ();
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "string chunk")
+    }
+
+    #[inline]
+    fn visit_ref(self, cx: C, bytes: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.0.extend_from_slice(bytes).map_err(cx.map())
+    }
+}
+
+/// A decoder for the items of an array or map, which might have an indefinite
+/// length.
+pub struct RemainingCborDecoder<R, C, M> {
+    cx: C,
+    reader: R,
+    /// The number of remaining items, or `None` if the container has an
+    /// indefinite length and the break has not yet been reached.
+    remaining: Option<usize>,
+    _marker: PhantomData<M>,
+}
+
+impl<'de, R, C, M> RemainingCborDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    #[inline]
+    fn new(cx: C, reader: R, remaining: Option<usize>) -> Self {
+        Self {
+            cx,
+            reader,
+            remaining,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Advance to the next item, returning `false` if there are no more items.
+    #[inline]
+    fn advance(&mut self) -> Result<bool, C::Error> {
+        match &mut self.remaining {
+            Some(0) => Ok(false),
+            Some(remaining) => {
+                *remaining -= 1;
+                Ok(true)
+            }
+            None => {
+                if self.reader.peek() == Some(tag::BREAK) {
+                    self.reader.skip(self.cx, 1)?;
+                    self.remaining = Some(0);
+                    return Ok(false);
+                }
+
+                Ok(true)
+            }
+        }
+    }
+
+    #[inline]
+    fn skip_sequence_remaining(mut self) -> Result<(), C::Error> {
+        while let Some(item) = self.try_decode_next()? {
+            item.skip()?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn skip_map_remaining(mut self) -> Result<(), C::Error> {
+        loop {
+            let Some(key) = self.decode_entry_key()? else {
+                break;
+            };
+
+            key.skip()?;
+            self.decode_entry_value()?.skip()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[crate::trait_defaults(crate)]
+impl<'de, R, C, M> Decoder<'de> for CborDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type TryClone = CborDecoder<R::TryClone, C, M>;
+    type DecodeBuffer = IntoValueDecoder<BUFFER_OPTIONS, C, C::Allocator, M>;
+    type DecodePack = RemainingCborDecoder<R, C, M>;
+    type DecodeSome = Self;
+    type DecodeSequence = RemainingCborDecoder<R, C, M>;
+    type DecodeMap = RemainingCborDecoder<R, C, M>;
+    type DecodeMapEntries = RemainingCborDecoder<R, C, M>;
+    type DecodeVariant = Self;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the CBOR decoder")
+    }
+
+    #[inline]
+    fn try_clone(&self) -> Option<Self::TryClone> {
+        Some(CborDecoder::with_tag(
+            self.cx,
+            self.reader.try_clone()?,
+            self.tag,
+        ))
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), Self::Error> {
+        self.skip_any()
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, Self::Error> {
+        self.skip()?;
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_buffer(self) -> Result<Self::DecodeBuffer, Self::Error> {
+        let cx = self.cx;
+        let value = self.decode::<Value<Self::Allocator>>()?;
+        Ok(value.into_decoder(cx))
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), Self::Error> {
+        self.skip()
+    }
+
+    #[inline]
+    fn decode_pack<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodePack) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.shared_decode_sequence()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_sequence_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_array<const N: usize>(mut self) -> Result<[u8; N], Self::Error> {
+        let pos = self.cx.mark();
+        let head = self.read_head_of(tag::BYTES, Kind::Bytes)?;
+
+        if head.argument != Some(N as u64) {
+            return Err(self.cx.message_at(
+                &pos,
+                format_args!("Bad length, expected byte string of length {N}"),
+            ));
+        }
+
+        self.reader.read_array(self.cx)
+    }
+
+    #[inline]
+    fn decode_bytes<V>(self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8], Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        self.decode_string_of(tag::BYTES, Kind::Bytes, visitor)
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: UnsizedVisitor<'de, C, str, Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        struct Visitor<V>(V);
+
+        #[crate::trait_defaults(crate)]
+        impl<'de, C, V> UnsizedVisitor<'de, C, [u8]> for Visitor<V>
+        where
+            C: Context,
+            V: UnsizedVisitor<'de, C, str, Error = C::Error, Allocator = C::Allocator>,
+        {
+            type Ok = V::Ok;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.expecting(f)
+            }
+
+            #[inline]
+            fn visit_owned(
+                self,
+                cx: C,
+                bytes: Vec<u8, Self::Allocator>,
+            ) -> Result<Self::Ok, Self::Error> {
+                let string = crate::str::from_utf8_owned(bytes).map_err(cx.map())?;
+                self.0.visit_owned(cx, string)
+            }
+
+            #[inline]
+            fn visit_borrowed(self, cx: C, bytes: &'de [u8]) -> Result<Self::Ok, Self::Error> {
+                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                self.0.visit_borrowed(cx, string)
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: C, bytes: &[u8]) -> Result<Self::Ok, Self::Error> {
+                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                self.0.visit_ref(cx, string)
+            }
+        }
+
+        self.decode_string_of(tag::TEXT, Kind::String, Visitor(visitor))
+    }
+
+    #[inline]
+    fn decode_number<V>(mut self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: Visitor<'de, C, Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        let cx = self.cx;
+
+        if self.tag.is_none() {
+            match self.reader.peek() {
+                Some(tag::FLOAT16 | tag::FLOAT32) => {
+                    let value = self.decode_f32()?;
+                    return visitor.visit_f32(cx, value);
+                }
+                Some(tag::FLOAT64) => {
+                    let value = self.decode_f64()?;
+                    return visitor.visit_f64(cx, value);
+                }
+                _ => {}
+            }
+        }
+
+        match self.decode_integer()? {
+            Integer::Positive(value) => match u64::try_from(value) {
+                Ok(value) => visitor.visit_u64(cx, value),
+                Err(..) => visitor.visit_u128(cx, value),
+            },
+            Integer::Negative(value) => match i64::try_from(value) {
+                Ok(value) => visitor.visit_i64(cx, -1 - value),
+                Err(..) => match i128::try_from(value) {
+                    Ok(value) => visitor.visit_i128(cx, -1 - value),
+                    Err(..) => Err(cx.message("Integer out of range")),
+                },
+            },
+        }
+    }
+
+    #[inline]
+    fn decode_bool(mut self) -> Result<bool, Self::Error> {
+        let pos = self.cx.mark();
+        let head = self.read_head()?;
+
+        match head.byte {
+            tag::FALSE => Ok(false),
+            tag::TRUE => Ok(true),
+            b => Err(self.cx.message_at(
+                &pos,
+                format_args!("Expected boolean, but got {}", Marker(b)),
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_char(mut self) -> Result<char, Self::Error> {
+        let pos = self.cx.mark();
+        let head = self.read_head_of(tag::TEXT, Kind::String)?;
+
+        let len = match head.argument {
+            Some(len @ 0..=4) => len as usize,
+            _ => {
+                return Err(self
+                    .cx
+                    .message_at(&pos, "Expected character, but got longer string"));
+            }
+        };
+
+        let mut buf = [0; 4];
+        self.reader.read(self.cx, &mut buf[..len])?;
+
+        let string = crate::str::from_utf8(&buf[..len]).map_err(self.cx.map())?;
+        let mut chars = string.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(self.cx.message_at(&pos, "Expected exactly one character")),
+        }
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u128(self) -> Result<u128, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i128(self) -> Result<i128, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_f32(self) -> Result<f32, Self::Error> {
+        Ok(self.decode_float()? as f32)
+    }
+
+    #[inline]
+    fn decode_f64(self) -> Result<f64, Self::Error> {
+        self.decode_float()
+    }
+
+    #[inline]
+    fn decode_option(mut self) -> Result<Option<Self::DecodeSome>, Self::Error> {
+        if self.tag.is_none() && matches!(self.reader.peek(), Some(tag::NULL | tag::UNDEFINED)) {
+            self.reader.skip(self.cx, 1)?;
+            return Ok(None);
+        }
+
+        Ok(Some(self))
+    }
+
+    #[inline]
+    fn decode_sequence<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeSequence) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.shared_decode_sequence()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_sequence_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.shared_decode_map()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_map_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.shared_decode_map()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_map_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_variant<F, O>(mut self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeVariant) -> Result<O, Self::Error>,
+    {
+        let pos = self.cx.mark();
+        let head = self.read_head()?;
+
+        match (head.major(), head.argument) {
+            (tag::MAP, Some(1)) => f(&mut self),
+            (tag::TAG, Some(tag)) => {
+                self.tag = Some(tag);
+                f(&mut self)
+            }
+            _ => Err(self.cx.message_at(
+                &pos,
+                format_args!(
+                    "Expected variant as tag or map with one entry, but got {}",
+                    Marker(head.byte)
+                ),
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_any<V>(mut self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: Visitor<'de, C, Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        let cx = self.cx;
+
+        if self.tag.is_some() {
+            return self.decode_number(visitor);
+        }
+
+        let Some(b) = self.reader.peek() else {
+            return Err(cx.message("Expected value in input"));
+        };
+
+        match Kind::from_byte(b) {
+            Kind::Unsigned | Kind::Negative | Kind::Float => self.decode_number(visitor),
+            Kind::Bool => {
+                let value = self.decode_bool()?;
+                visitor.visit_bool(cx, value)
+            }
+            Kind::Null | Kind::Undefined => {
+                self.reader.skip(cx, 1)?;
+                visitor.visit_empty(cx)
+            }
+            Kind::String => {
+                let visitor = visitor.visit_string(cx, SizeHint::any())?;
+                self.decode_string(visitor)
+            }
+            Kind::Bytes => {
+                let visitor = visitor.visit_bytes(cx, SizeHint::any())?;
+                self.decode_bytes(visitor)
+            }
+            Kind::Array => self.decode_sequence(|decoder| visitor.visit_sequence(decoder)),
+            Kind::Map => self.decode_map(|decoder| visitor.visit_map(decoder)),
+            Kind::Tag => {
+                let head = self.read_head()?;
+
+                match self.definite(&head)? {
+                    tag::POSITIVE_BIGNUM => {
+                        let value = self.decode_bignum()?;
+                        visitor.visit_u128(cx, value)
+                    }
+                    tag::NEGATIVE_BIGNUM => {
+                        let value = self.decode_bignum()?;
+
+                        match i128::try_from(value) {
+                            Ok(value) => visitor.visit_i128(cx, -1 - value),
+                            Err(..) => Err(cx.message("Integer out of range")),
+                        }
+                    }
+                    tag => {
+                        self.tag = Some(tag);
+                        visitor.visit_variant(&mut self)
+                    }
+                }
+            }
+            kind => Err(cx.message(format_args!("Unsupported {kind} {}", Marker(b)))),
+        }
+    }
+}
+
+impl<'de, R, C, M> SequenceDecoder<'de> for RemainingCborDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeNext<'this>
+        = CborDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        match self.remaining {
+            Some(remaining) => SizeHint::exact(remaining),
+            None => SizeHint::any(),
+        }
+    }
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, Self::Error> {
+        if !self.advance()? {
+            return Ok(None);
+        }
+
+        Ok(Some(CborDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, Self::Error> {
+        let cx = self.cx;
+
+        let Some(decoder) = self.try_decode_next()? else {
+            return Err(cx.message("No remaining elements"));
+        };
+
+        Ok(decoder)
+    }
+}
+
+impl<'de, R, C, M> MapDecoder<'de> for RemainingCborDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeEntry<'this>
+        = CborDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+    type DecodeRemainingEntries<'this>
+        = RemainingCborDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        match self.remaining {
+            Some(remaining) => SizeHint::exact(remaining),
+            None => SizeHint::any(),
+        }
+    }
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, Self::Error> {
+        if !self.advance()? {
+            return Ok(None);
+        }
+
+        Ok(Some(CborDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_remaining_entries(
+        &mut self,
+    ) -> Result<Self::DecodeRemainingEntries<'_>, Self::Error> {
+        Ok(RemainingCborDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            self.remaining.replace(0),
+        ))
+    }
+}
+
+impl<'de, R, C, M> EntriesDecoder<'de> for RemainingCborDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeEntryKey<'this>
+        = CborDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+    type DecodeEntryValue<'this>
+        = CborDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, Self::Error> {
+        if !self.advance()? {
+            return Ok(None);
+        }
+
+        Ok(Some(CborDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, Self::Error> {
+        Ok(CborDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), Self::Error> {
+        self.skip_map_remaining()?;
+        Ok(())
+    }
+}
+
+impl<'de, R, C, M> EntryDecoder<'de> for CborDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeKey<'this>
+        = CborDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+    type DecodeValue = Self;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, Self::Error> {
+        Ok(CborDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn decode_value(self) -> Result<Self::DecodeValue, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl<'de, R, C, M> VariantDecoder<'de> for CborDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeTag<'this>
+        = CborDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+    type DecodeValue<'this>
+        = CborDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, Self::Error> {
+        Ok(CborDecoder::with_tag(
+            self.cx,
+            self.reader.borrow_mut(),
+            self.tag.take(),
+        ))
+    }
+
+    #[inline]
+    fn decode_value(&mut self) -> Result<Self::DecodeValue<'_>, Self::Error> {
+        self.tag = None;
+        Ok(CborDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+}
@@ -0,0 +1,829 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::alloc::Vec;
+use crate::en::{
+    Encode, Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder,
+};
+use crate::hint::{MapHint, SequenceHint};
+use crate::options::{self, Options};
+use crate::writer::Sink;
+use crate::{Context, Writer};
+
+use super::tag;
+
+/// An encoder for CBOR.
+pub struct CborEncoder<'a, const OPT: Options, W, C, M>
+where
+    C: Context,
+{
+    cx: C,
+    writer: Sink<'a, W, C::Allocator>,
+    _marker: PhantomData<M>,
+}
+
+impl<const OPT: Options, W, C, M> CborEncoder<'_, OPT, W, C, M>
+where
+    C: Context,
+{
+    /// Construct a new CBOR encoder.
+    #[inline]
+    pub(crate) fn new(cx: C, writer: W) -> Self {
+        Self::with_sink(cx, Sink::Owned(writer))
+    }
+}
+
+impl<'a, const OPT: Options, W, C, M> CborEncoder<'a, OPT, W, C, M>
+where
+    C: Context,
+{
+    #[inline]
+    fn with_sink(cx: C, writer: Sink<'a, W, C::Allocator>) -> Self {
+        Self {
+            cx,
+            writer,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Encoder for packed values.
+///
+/// Packs are encoded as indefinite-length arrays, unless canonical encoding is
+/// enabled in which case elements are buffered so that the array can be
+/// prefixed with its length.
+pub struct CborPackEncoder<'a, const OPT: Options, W, C, M>
+where
+    C: Context,
+{
+    cx: C,
+    writer: Sink<'a, W, C::Allocator>,
+    buffer: Vec<u8, C::Allocator>,
+    len: usize,
+    _marker: PhantomData<M>,
+}
+
+/// The location of a buffered map entry.
+struct Entry {
+    start: usize,
+    key_end: usize,
+    end: usize,
+}
+
+/// Encoder for maps.
+///
+/// If canonical encoding is enabled, entries are buffered and sorted by their
+/// encoded keys once the map is finished.
+pub struct CborMapEncoder<'a, const OPT: Options, W, C, M>
+where
+    C: Context,
+{
+    cx: C,
+    writer: Sink<'a, W, C::Allocator>,
+    buffer: Vec<u8, C::Allocator>,
+    entries: Vec<Entry, C::Allocator>,
+    _marker: PhantomData<M>,
+}
+
+impl<'a, const OPT: Options, W, C, M> CborMapEncoder<'a, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+{
+    #[inline]
+    fn new(cx: C, mut writer: Sink<'a, W, C::Allocator>, len: usize) -> Result<Self, C::Error> {
+        if !options::is_canonical::<OPT>() {
+            encode_head(cx, writer.borrow_mut(), tag::MAP, len as u64)?;
+        }
+
+        Ok(Self {
+            cx,
+            writer,
+            buffer: Vec::new_in(cx.alloc()),
+            entries: Vec::new_in(cx.alloc()),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Start a new entry, returning the sink to write it to.
+    #[inline]
+    fn start_entry(&mut self) -> Result<Sink<'_, W, C::Allocator>, C::Error> {
+        if !options::is_canonical::<OPT>() {
+            return Ok(self.writer.borrow_mut());
+        }
+
+        let start = self.buffer.len();
+
+        self.entries
+            .push(Entry {
+                start,
+                key_end: start,
+                end: start,
+            })
+            .map_err(self.cx.map())?;
+
+        Ok(Sink::Buffer(&mut self.buffer))
+    }
+
+    /// Mark the end of the key of the current entry, returning the sink to
+    /// write its value to.
+    #[inline]
+    fn start_value(&mut self) -> Sink<'_, W, C::Allocator> {
+        if !options::is_canonical::<OPT>() {
+            return self.writer.borrow_mut();
+        }
+
+        if let Some(entry) = self.entries.as_mut_slice().last_mut() {
+            entry.key_end = self.buffer.len();
+        }
+
+        Sink::Buffer(&mut self.buffer)
+    }
+
+    #[inline]
+    fn finish(mut self) -> Result<(), C::Error> {
+        if !options::is_canonical::<OPT>() {
+            return Ok(());
+        }
+
+        let buffer = self.buffer.as_slice();
+        let entries = self.entries.as_mut_slice();
+        let mut end = buffer.len();
+
+        for entry in entries.iter_mut().rev() {
+            entry.end = end;
+            end = entry.start;
+        }
+
+        entries
+            .sort_unstable_by(|a, b| buffer[a.start..a.key_end].cmp(&buffer[b.start..b.key_end]));
+
+        encode_head(
+            self.cx,
+            self.writer.borrow_mut(),
+            tag::MAP,
+            entries.len() as u64,
+        )?;
+
+        for entry in entries.iter() {
+            self.writer
+                .write_bytes(self.cx, &buffer[entry.start..entry.end])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Encoder for a single map entry.
+pub struct CborEntryEncoder<'a, const OPT: Options, W, C, M>
+where
+    C: Context,
+{
+    cx: C,
+    writer: Sink<'a, W, C::Allocator>,
+    key_end: Option<&'a mut usize>,
+    _marker: PhantomData<M>,
+}
+
+#[crate::trait_defaults(crate)]
+impl<'a, const OPT: Options, W, C, M> Encoder for CborEncoder<'a, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodePack = CborPackEncoder<'a, OPT, W, C, M>;
+    type EncodeSome = Self;
+    type EncodeSequence = Self;
+    type EncodeMap = CborMapEncoder<'a, OPT, W, C, M>;
+    type EncodeMapEntries = CborMapEncoder<'a, OPT, W, C, M>;
+    type EncodeVariant = Self;
+    type EncodeSequenceVariant = Self;
+    type EncodeMapVariant = CborMapEncoder<'a, OPT, W, C, M>;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the CBOR encoder")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<(), Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self)
+    }
+
+    #[inline]
+    fn encode_empty(mut self) -> Result<(), Self::Error> {
+        self.writer.write_byte(self.cx, tag::NULL)
+    }
+
+    #[inline]
+    fn encode_pack(mut self) -> Result<Self::EncodePack, Self::Error> {
+        if !options::is_canonical::<OPT>() {
+            encode_indefinite(self.cx, self.writer.borrow_mut(), tag::ARRAY)?;
+        }
+
+        Ok(CborPackEncoder {
+            cx: self.cx,
+            writer: self.writer,
+            buffer: Vec::new_in(self.cx.alloc()),
+            len: 0,
+            _marker: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn encode_array<const N: usize>(self, array: &[u8; N]) -> Result<(), Self::Error> {
+        self.encode_bytes(array)
+    }
+
+    #[inline]
+    fn encode_bytes(mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        encode_head(
+            self.cx,
+            self.writer.borrow_mut(),
+            tag::BYTES,
+            bytes.len() as u64,
+        )?;
+        self.writer.write_bytes(self.cx, bytes)
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<I>(mut self, len: usize, vectors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item: AsRef<[u8]>>,
+    {
+        encode_head(self.cx, self.writer.borrow_mut(), tag::BYTES, len as u64)?;
+
+        for bytes in vectors {
+            self.writer.write_bytes(self.cx, bytes.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_string(mut self, string: &str) -> Result<(), Self::Error> {
+        encode_head(
+            self.cx,
+            self.writer.borrow_mut(),
+            tag::TEXT,
+            string.len() as u64,
+        )?;
+        self.writer.write_bytes(self.cx, string.as_bytes())
+    }
+
+    #[inline]
+    fn encode_bool(mut self, value: bool) -> Result<(), Self::Error> {
+        self.writer
+            .write_byte(self.cx, if value { tag::TRUE } else { tag::FALSE })
+    }
+
+    #[inline]
+    fn encode_char(self, value: char) -> Result<(), Self::Error> {
+        self.encode_string(value.encode_utf8(&mut [0; 4]))
+    }
+
+    #[inline]
+    fn encode_u8(self, value: u8) -> Result<(), Self::Error> {
+        self.encode_u64(u64::from(value))
+    }
+
+    #[inline]
+    fn encode_u16(self, value: u16) -> Result<(), Self::Error> {
+        self.encode_u64(u64::from(value))
+    }
+
+    #[inline]
+    fn encode_u32(self, value: u32) -> Result<(), Self::Error> {
+        self.encode_u64(u64::from(value))
+    }
+
+    #[inline]
+    fn encode_u64(mut self, value: u64) -> Result<(), Self::Error> {
+        encode_head(self.cx, self.writer.borrow_mut(), tag::UNSIGNED, value)
+    }
+
+    #[inline]
+    fn encode_u128(mut self, value: u128) -> Result<(), Self::Error> {
+        if let Ok(value) = u64::try_from(value) {
+            return self.encode_u64(value);
+        }
+
+        encode_bignum(
+            self.cx,
+            self.writer.borrow_mut(),
+            tag::POSITIVE_BIGNUM,
+            value,
+        )
+    }
+
+    #[inline]
+    fn encode_i8(self, value: i8) -> Result<(), Self::Error> {
+        self.encode_i64(i64::from(value))
+    }
+
+    #[inline]
+    fn encode_i16(self, value: i16) -> Result<(), Self::Error> {
+        self.encode_i64(i64::from(value))
+    }
+
+    #[inline]
+    fn encode_i32(self, value: i32) -> Result<(), Self::Error> {
+        self.encode_i64(i64::from(value))
+    }
+
+    #[inline]
+    fn encode_i64(mut self, value: i64) -> Result<(), Self::Error> {
+        if value >= 0 {
+            encode_head(
+                self.cx,
+                self.writer.borrow_mut(),
+                tag::UNSIGNED,
+                value as u64,
+            )
+        } else {
+            encode_head(
+                self.cx,
+                self.writer.borrow_mut(),
+                tag::NEGATIVE,
+                !value as u64,
+            )
+        }
+    }
+
+    #[inline]
+    fn encode_i128(mut self, value: i128) -> Result<(), Self::Error> {
+        if value >= 0 {
+            return self.encode_u128(value as u128);
+        }
+
+        let value = !value as u128;
+
+        if let Ok(value) = u64::try_from(value) {
+            return encode_head(self.cx, self.writer.borrow_mut(), tag::NEGATIVE, value);
+        }
+
+        encode_bignum(
+            self.cx,
+            self.writer.borrow_mut(),
+            tag::NEGATIVE_BIGNUM,
+            value,
+        )
+    }
+
+    #[inline]
+    fn encode_f32(mut self, value: f32) -> Result<(), Self::Error> {
+        if options::is_canonical::<OPT>() {
+            return encode_canonical_f32(self.cx, self.writer.borrow_mut(), value);
+        }
+
+        self.writer.write_byte(self.cx, tag::FLOAT32)?;
+        self.writer.write_bytes(self.cx, &value.to_be_bytes())
+    }
+
+    #[inline]
+    fn encode_f64(mut self, value: f64) -> Result<(), Self::Error> {
+        if options::is_canonical::<OPT>() {
+            let narrow = value as f32;
+
+            if value.is_nan() || f64::from(narrow) == value {
+                return encode_canonical_f32(self.cx, self.writer.borrow_mut(), narrow);
+            }
+        }
+
+        self.writer.write_byte(self.cx, tag::FLOAT64)?;
+        self.writer.write_bytes(self.cx, &value.to_be_bytes())
+    }
+
+    #[inline]
+    fn encode_usize(self, value: usize) -> Result<(), Self::Error> {
+        self.encode_u64(value as u64)
+    }
+
+    #[inline]
+    fn encode_isize(self, value: isize) -> Result<(), Self::Error> {
+        self.encode_i64(value as i64)
+    }
+
+    #[inline]
+    fn encode_some(self) -> Result<Self::EncodeSome, Self::Error> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_none(self) -> Result<(), Self::Error> {
+        self.encode_empty()
+    }
+
+    #[inline]
+    fn encode_sequence(
+        mut self,
+        hint: impl SequenceHint,
+    ) -> Result<Self::EncodeSequence, Self::Error> {
+        let len = hint.require(self.cx)?;
+        encode_head(self.cx, self.writer.borrow_mut(), tag::ARRAY, len as u64)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_map(self, hint: impl MapHint) -> Result<Self::EncodeMap, Self::Error> {
+        let len = hint.require(self.cx)?;
+        CborMapEncoder::new(self.cx, self.writer, len)
+    }
+
+    #[inline]
+    fn encode_map_entries(self, hint: impl MapHint) -> Result<Self::EncodeMapEntries, Self::Error> {
+        let len = hint.require(self.cx)?;
+        CborMapEncoder::new(self.cx, self.writer, len)
+    }
+
+    #[inline]
+    fn encode_variant(mut self) -> Result<Self::EncodeVariant, Self::Error> {
+        encode_head(self.cx, self.writer.borrow_mut(), tag::MAP, 1)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_sequence_variant<T>(
+        mut self,
+        tag: &T,
+        hint: impl SequenceHint,
+    ) -> Result<Self::EncodeSequenceVariant, Self::Error>
+    where
+        T: ?Sized + Encode<Self::Mode>,
+    {
+        encode_head(self.cx, self.writer.borrow_mut(), tag::MAP, 1)?;
+        CborEncoder::<OPT, _, _, M>::with_sink(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        self.encode_sequence(hint)
+    }
+
+    #[inline]
+    fn encode_map_variant<T>(
+        mut self,
+        tag: &T,
+        hint: impl MapHint,
+    ) -> Result<Self::EncodeMapVariant, Self::Error>
+    where
+        T: ?Sized + Encode<Self::Mode>,
+    {
+        encode_head(self.cx, self.writer.borrow_mut(), tag::MAP, 1)?;
+        CborEncoder::<OPT, _, _, M>::with_sink(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        self.encode_map(hint)
+    }
+
+    #[inline]
+    fn encode_tagged<T>(mut self, tag: u64, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Encode<Self::Mode>,
+    {
+        encode_head(self.cx, self.writer.borrow_mut(), tag::TAG, tag)?;
+        self.encode(value)
+    }
+}
+
+impl<const OPT: Options, W, C, M> SequenceEncoder for CborPackEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeNext<'this>
+        = CborEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, Self::Error> {
+        if !options::is_canonical::<OPT>() {
+            return Ok(CborEncoder::with_sink(self.cx, self.writer.borrow_mut()));
+        }
+
+        self.len += 1;
+        Ok(CborEncoder::with_sink(
+            self.cx,
+            Sink::Buffer(&mut self.buffer),
+        ))
+    }
+
+    #[inline]
+    fn finish_sequence(mut self) -> Result<(), Self::Error> {
+        if !options::is_canonical::<OPT>() {
+            return self.writer.write_byte(self.cx, tag::BREAK);
+        }
+
+        encode_head(
+            self.cx,
+            self.writer.borrow_mut(),
+            tag::ARRAY,
+            self.len as u64,
+        )?;
+        self.writer.extend(self.cx, self.buffer)
+    }
+}
+
+impl<const OPT: Options, W, C, M> SequenceEncoder for CborEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeNext<'this>
+        = CborEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, Self::Error> {
+        Ok(CborEncoder::with_sink(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_sequence(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<const OPT: Options, W, C, M> MapEncoder for CborMapEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeEntry<'this>
+        = CborEntryEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_entry(&mut self) -> Result<Self::EncodeEntry<'_>, Self::Error> {
+        let cx = self.cx;
+
+        if !options::is_canonical::<OPT>() {
+            return Ok(CborEntryEncoder {
+                cx,
+                writer: self.writer.borrow_mut(),
+                key_end: None,
+                _marker: PhantomData,
+            });
+        }
+
+        let start = self.buffer.len();
+
+        self.entries
+            .push(Entry {
+                start,
+                key_end: start,
+                end: start,
+            })
+            .map_err(cx.map())?;
+
+        let key_end = self
+            .entries
+            .as_mut_slice()
+            .last_mut()
+            .map(|entry| &mut entry.key_end);
+
+        Ok(CborEntryEncoder {
+            cx,
+            writer: Sink::Buffer(&mut self.buffer),
+            key_end,
+            _marker: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn finish_map(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<const OPT: Options, W, C, M> EntryEncoder for CborEntryEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeKey<'this>
+        = CborEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+    type EncodeValue<'this>
+        = CborEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_key(&mut self) -> Result<Self::EncodeKey<'_>, Self::Error> {
+        Ok(CborEncoder::with_sink(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn encode_value(&mut self) -> Result<Self::EncodeValue<'_>, Self::Error> {
+        if let Some(key_end) = &mut self.key_end {
+            **key_end = self.writer.buffered();
+        }
+
+        Ok(CborEncoder::with_sink(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_entry(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<const OPT: Options, W, C, M> EntriesEncoder for CborMapEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeEntryKey<'this>
+        = CborEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+    type EncodeEntryValue<'this>
+        = CborEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_entry_key(&mut self) -> Result<Self::EncodeEntryKey<'_>, Self::Error> {
+        let cx = self.cx;
+        let writer = self.start_entry()?;
+        Ok(CborEncoder::with_sink(cx, writer))
+    }
+
+    #[inline]
+    fn encode_entry_value(&mut self) -> Result<Self::EncodeEntryValue<'_>, Self::Error> {
+        let cx = self.cx;
+        let writer = self.start_value();
+        Ok(CborEncoder::with_sink(cx, writer))
+    }
+
+    #[inline]
+    fn finish_entries(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<const OPT: Options, W, C, M> VariantEncoder for CborEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeTag<'this>
+        = CborEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+    type EncodeData<'this>
+        = CborEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_tag(&mut self) -> Result<Self::EncodeTag<'_>, Self::Error> {
+        Ok(CborEncoder::with_sink(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn encode_data(&mut self) -> Result<Self::EncodeData<'_>, Self::Error> {
+        Ok(CborEncoder::with_sink(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Encode the head of a data item using the shortest possible argument.
+#[inline]
+fn encode_head<W, C>(cx: C, mut writer: W, major: u8, value: u64) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    let major = major << 5;
+
+    if value < u64::from(tag::ARGUMENT_U8) {
+        writer.write_byte(cx, major | value as u8)
+    } else if let Ok(value) = u8::try_from(value) {
+        writer.write_bytes(cx, &[major | tag::ARGUMENT_U8, value])
+    } else if let Ok(value) = u16::try_from(value) {
+        writer.write_byte(cx, major | tag::ARGUMENT_U16)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    } else if let Ok(value) = u32::try_from(value) {
+        writer.write_byte(cx, major | tag::ARGUMENT_U32)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    } else {
+        writer.write_byte(cx, major | tag::ARGUMENT_U64)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    }
+}
+
+/// Encode the head of an indefinite-length data item.
+#[inline]
+fn encode_indefinite<W, C>(cx: C, mut writer: W, major: u8) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    writer.write_byte(cx, (major << 5) | tag::INDEFINITE)
+}
+
+/// Encode a bignum with the given tag, without leading zero bytes.
+#[inline]
+fn encode_bignum<W, C>(cx: C, mut writer: W, tag: u64, value: u128) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    let bytes = value.to_be_bytes();
+    let bytes = &bytes[(value.leading_zeros() / 8) as usize..];
+    encode_head(cx, writer.borrow_mut(), tag::TAG, tag)?;
+    encode_head(cx, writer.borrow_mut(), tag::BYTES, bytes.len() as u64)?;
+    writer.write_bytes(cx, bytes)
+}
+
+/// Encode a float using the shortest representation which preserves its value.
+///
+/// NaN is always encoded as the canonical half precision NaN.
+#[inline]
+fn encode_canonical_f32<W, C>(cx: C, mut writer: W, value: f32) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    if value.is_nan() {
+        return writer.write_bytes(cx, &[tag::FLOAT16, 0x7e, 0x00]);
+    }
+
+    if let Some(bits) = tag::f32_to_f16(value) {
+        writer.write_byte(cx, tag::FLOAT16)?;
+        return writer.write_bytes(cx, &bits.to_be_bytes());
+    }
+
+    writer.write_byte(cx, tag::FLOAT32)?;
+    writer.write_bytes(cx, &value.to_be_bytes())
+}
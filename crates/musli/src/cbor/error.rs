@@ -0,0 +1,4 @@
+crate::macros::implement_error! {
+    /// Error raised during CBOR encoding.
+    pub struct Error;
+}
@@ -0,0 +1,160 @@
+//! Support for the [CBOR] format for [Müsli].
+//!
+//! CBOR, the Concise Binary Object Representation, is a fully self-descriptive
+//! binary format standardized in [RFC 8949]. It is used by protocols such as
+//! COSE and on constrained devices:
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`.
+//! * ✔ Can skip over unknown fields.
+//! * ✔ Can be decoded into dynamic containers such as the [`Value`] type.
+//! * ✔ Integers are always encoded using their smallest possible
+//!   representation, and can be decoded into any type which fits the value.
+//!
+//! By default the [`Binary`] mode is used, which means that fields and variants
+//! are identified by their numerical indexes.
+//!
+//! [Müsli]: https://docs.rs/musli
+//! [CBOR]: https://cbor.io
+//! [RFC 8949]: https://www.rfc-editor.org/rfc/rfc8949.html
+//! [`Value`]: crate::value
+//! [`Binary`]: crate::mode::Binary
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Version1 {
+//!     name: String,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Version2 {
+//!     name: String,
+//!     #[musli(default)]
+//!     age: Option<u32>,
+//! }
+//!
+//! let version2 = musli::cbor::to_vec(&Version2 {
+//!     name: String::from("Aristotle"),
+//!     age: Some(61),
+//! })?;
+//!
+//! assert_eq!(version2, b"\xa2\x00\x69Aristotle\x01\x18\x3d");
+//!
+//! let version1: Version1 = musli::cbor::from_slice(version2.as_slice())?;
+//!
+//! assert_eq!(version1, Version1 {
+//!     name: String::from("Aristotle"),
+//! });
+//! # Ok::<_, musli::cbor::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Canonical encoding
+//!
+//! Enabling [`canonical`] in the options of an [`Encoding`] causes the
+//! deterministic encoding described in [RFC 8949 section 4.2] to be used:
+//!
+//! * Floats are encoded using the shortest representation which preserves
+//!   their value, and NaN is always encoded as a half precision float.
+//! * Map entries are sorted by the bytewise order of their encoded keys.
+//! * Indefinite-length items are never produced.
+//!
+//! [`canonical`]: crate::options::Builder::canonical
+//! [RFC 8949 section 4.2]: https://www.rfc-editor.org/rfc/rfc8949.html#name-deterministically-encoded-c
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use musli::cbor::Encoding;
+//! use musli::options::{self, Options};
+//!
+//! const OPTIONS: Options = options::new().canonical().build();
+//! const CONFIG: Encoding<OPTIONS> = Encoding::new().with_options();
+//!
+//! let mut map = HashMap::new();
+//! map.insert(String::from("bb"), 1.5f64);
+//! map.insert(String::from("a"), 0.1f64);
+//!
+//! let bytes = CONFIG.to_vec(&map)?;
+//!
+//! assert_eq!(
+//!     bytes,
+//!     b"\xa2\x61a\xfb\x3f\xb9\x99\x99\x99\x99\x99\x9a\x62bb\xf9\x3e\x00"
+//! );
+//! # Ok::<_, musli::cbor::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Implementation details
+//!
+//! Müsli types map onto CBOR as follows:
+//!
+//! * Structs and maps are encoded as maps, and sequences and tuples as arrays.
+//! * Packed types are encoded as indefinite-length arrays, or as
+//!   definite-length arrays if canonical encoding is enabled.
+//! * Byte arrays are encoded as byte strings, and strings and characters as
+//!   text strings.
+//! * Empty values and `None` are encoded as `null`, while `Some(value)` is
+//!   encoded as `value`.
+//! * Enum variants are encoded as a map with a single entry, from the variant
+//!   tag to its content.
+//! * 128-bit integers which don't fit in 64 bits are encoded as bignums.
+//! * Values wrapped in [`Tagged`] are encoded with a semantic tag. When decoded
+//!   dynamically, any other tag is treated as a variant with the tag number as
+//!   its tag.
+//!
+//! Indefinite-length arrays, maps, byte strings and text strings are supported
+//! when decoding.
+
+#![cfg(feature = "cbor")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "cbor")))]
+
+#[cfg(test)]
+mod tests;
+
+mod de;
+mod en;
+mod encoding;
+mod error;
+mod tag;
+mod tagged;
+
+#[cfg(feature = "test")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "test")))]
+#[doc(hidden)]
+pub mod test;
+
+/// Convenient result alias for use with `musli::cbor`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::DecodeIter;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::to_vec;
+#[cfg(all(feature = "std", feature = "alloc"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "alloc"))))]
+#[doc(inline)]
+pub use self::encoding::to_writer;
+#[doc(inline)]
+pub use self::encoding::{Encoding, OPTIONS};
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, to_slice};
+#[cfg(feature = "tokio")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+#[doc(inline)]
+pub use self::encoding::{decode_async, encode_async};
+#[doc(inline)]
+pub use self::error::Error;
+#[doc(inline)]
+pub use self::tagged::Tagged;
@@ -0,0 +1,181 @@
+//! Initial bytes and major types used by `musli::cbor`.
+//!
+//! See [RFC 8949] for details.
+//!
+//! [RFC 8949]: https://www.rfc-editor.org/rfc/rfc8949.html
+
+use core::fmt;
+
+/// Unsigned integer.
+pub(crate) const UNSIGNED: u8 = 0;
+/// Negative integer, encoding `-1 - n`.
+pub(crate) const NEGATIVE: u8 = 1;
+/// Byte string.
+pub(crate) const BYTES: u8 = 2;
+/// Text string.
+pub(crate) const TEXT: u8 = 3;
+/// Array of data items.
+pub(crate) const ARRAY: u8 = 4;
+/// Map of pairs of data items.
+pub(crate) const MAP: u8 = 5;
+/// Semantic tag.
+pub(crate) const TAG: u8 = 6;
+
+/// Additional information indicating that the argument follows in one byte.
+pub(crate) const ARGUMENT_U8: u8 = 24;
+/// Additional information indicating that the argument follows in two bytes.
+pub(crate) const ARGUMENT_U16: u8 = 25;
+/// Additional information indicating that the argument follows in four bytes.
+pub(crate) const ARGUMENT_U32: u8 = 26;
+/// Additional information indicating that the argument follows in eight bytes.
+pub(crate) const ARGUMENT_U64: u8 = 27;
+/// Additional information indicating an indefinite length.
+pub(crate) const INDEFINITE: u8 = 31;
+
+/// The value false.
+pub(crate) const FALSE: u8 = 0xf4;
+/// The value true.
+pub(crate) const TRUE: u8 = 0xf5;
+/// The value null.
+pub(crate) const NULL: u8 = 0xf6;
+/// The value undefined.
+pub(crate) const UNDEFINED: u8 = 0xf7;
+/// Half precision floating point number.
+pub(crate) const FLOAT16: u8 = 0xf9;
+/// Single precision floating point number.
+pub(crate) const FLOAT32: u8 = 0xfa;
+/// Double precision floating point number.
+pub(crate) const FLOAT64: u8 = 0xfb;
+/// The break which terminates an indefinite-length item.
+pub(crate) const BREAK: u8 = 0xff;
+
+/// Tag number of an unsigned bignum.
+pub(crate) const POSITIVE_BIGNUM: u64 = 2;
+/// Tag number of a negative bignum.
+pub(crate) const NEGATIVE_BIGNUM: u64 = 3;
+
+/// The kind of value an initial byte introduces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Unsigned,
+    Negative,
+    Bytes,
+    String,
+    Array,
+    Map,
+    Tag,
+    Bool,
+    Null,
+    Undefined,
+    Float,
+    Simple,
+    Break,
+}
+
+impl Kind {
+    /// Classify the given initial byte.
+    #[inline]
+    pub(crate) const fn from_byte(b: u8) -> Self {
+        match b >> 5 {
+            UNSIGNED => Kind::Unsigned,
+            NEGATIVE => Kind::Negative,
+            BYTES => Kind::Bytes,
+            TEXT => Kind::String,
+            ARRAY => Kind::Array,
+            MAP => Kind::Map,
+            TAG => Kind::Tag,
+            _ => match b {
+                FALSE | TRUE => Kind::Bool,
+                NULL => Kind::Null,
+                UNDEFINED => Kind::Undefined,
+                FLOAT16 | FLOAT32 | FLOAT64 => Kind::Float,
+                BREAK => Kind::Break,
+                _ => Kind::Simple,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Unsigned => write!(f, "unsigned integer"),
+            Kind::Negative => write!(f, "negative integer"),
+            Kind::Bytes => write!(f, "byte string"),
+            Kind::String => write!(f, "text string"),
+            Kind::Array => write!(f, "array"),
+            Kind::Map => write!(f, "map"),
+            Kind::Tag => write!(f, "tag"),
+            Kind::Bool => write!(f, "boolean"),
+            Kind::Null => write!(f, "null"),
+            Kind::Undefined => write!(f, "undefined"),
+            Kind::Float => write!(f, "float"),
+            Kind::Simple => write!(f, "simple value"),
+            Kind::Break => write!(f, "break"),
+        }
+    }
+}
+
+/// Helper to display an initial byte in diagnostics.
+#[derive(Clone, Copy)]
+pub(crate) struct Marker(pub(crate) u8);
+
+impl fmt::Display for Marker {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (0x{:02x})", Kind::from_byte(self.0), self.0)
+    }
+}
+
+/// Convert a half precision float into a single precision float.
+///
+/// Every half precision float can be represented exactly.
+pub(crate) fn f16_to_f32(bits: u16) -> f32 {
+    let sign = u32::from(bits >> 15) << 31;
+    let exp = u32::from((bits >> 10) & 0x1f);
+    let mant = u32::from(bits & 0x3ff);
+
+    match exp {
+        // Zero and subnormals, which are `mant * 2^-24`.
+        0 => {
+            let value = mant as f32 * f32::from_bits(0x3380_0000);
+            f32::from_bits(sign | value.to_bits())
+        }
+        // Infinities and NaN.
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (mant << 13)),
+        _ => f32::from_bits(sign | ((exp + 112) << 23) | (mant << 13)),
+    }
+}
+
+/// Convert a single precision float into a half precision float, if it can be
+/// done without losing precision.
+pub(crate) fn f32_to_f16(value: f32) -> Option<u16> {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32 - 127;
+    let mant = bits & 0x7f_ffff;
+
+    match exp {
+        // Zero.
+        -127 if mant == 0 => Some(sign),
+        // Infinities.
+        128 if mant == 0 => Some(sign | 0x7c00),
+        // Normal half precision numbers.
+        -14..=15 if mant & 0x1fff == 0 => {
+            Some(sign | (((exp + 15) as u16) << 10) | (mant >> 13) as u16)
+        }
+        // Subnormal half precision numbers.
+        -24..=-15 => {
+            let mant = mant | 0x80_0000;
+            let shift = (-1 - exp) as u32;
+
+            if mant & ((1 << shift) - 1) != 0 {
+                return None;
+            }
+
+            Some(sign | (mant >> shift) as u16)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,81 @@
+use crate::alloc::Allocator;
+use crate::de::VariantDecoder;
+use crate::{Decode, Decoder, Encode, Encoder};
+
+/// A value annotated with a CBOR semantic tag.
+///
+/// This is encoded as a [tag] in CBOR, such as the tag `1` used for epoch-based
+/// date and times. Other formats encode this as a variant, where the tag is the
+/// variant tag.
+///
+/// When decoding, any variant with a numerical tag can be decoded into this
+/// type.
+///
+/// [tag]: https://www.rfc-editor.org/rfc/rfc8949.html#name-tagging-of-items
+///
+/// # Examples
+///
+/// ```
+/// use musli::cbor::Tagged;
+///
+/// let value = Tagged::new(1, 1363896240u32);
+/// let bytes = musli::cbor::to_vec(&value)?;
+/// assert_eq!(bytes, b"\xc1\x1a\x51\x4b\x67\xb0");
+///
+/// let decoded: Tagged<u32> = musli::cbor::from_slice(&bytes)?;
+/// assert_eq!(decoded, value);
+/// # Ok::<_, musli::cbor::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tagged<T> {
+    /// The tag number.
+    pub tag: u64,
+    /// The tagged value.
+    pub value: T,
+}
+
+impl<T> Tagged<T> {
+    /// Construct a new tagged value.
+    #[inline]
+    pub const fn new(tag: u64, value: T) -> Self {
+        Self { tag, value }
+    }
+}
+
+impl<M, T> Encode<M> for Tagged<T>
+where
+    T: Encode<M>,
+{
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_tagged(self.tag, &self.value)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A, T> Decode<'de, M, A> for Tagged<T>
+where
+    A: Allocator,
+    T: Decode<'de, M, A>,
+{
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        decoder.decode_variant(|variant| {
+            let tag = variant.decode_tag()?.decode_u64()?;
+            let value = variant.decode_value()?.decode()?;
+            Ok(Self { tag, value })
+        })
+    }
+}
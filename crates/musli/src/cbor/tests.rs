@@ -0,0 +1,384 @@
+use rust_alloc::collections::BTreeMap;
+use rust_alloc::string::String;
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::compat::Bytes;
+use crate::mode::Text;
+use crate::options::{self, Options};
+use crate::{Decode, Encode};
+
+use super::{Encoding, Tagged};
+
+const CANONICAL: Options = options::new().canonical().build();
+const TEXT: Encoding<{ super::OPTIONS }, Text> = Encoding::new().with_mode();
+const CANONICAL_TEXT: Encoding<CANONICAL, Text> = Encoding::new().with_options().with_mode();
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+enum Shape {
+    Empty,
+    Circle(u32),
+    Rectangle { width: u32, height: u32 },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, packed)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Before {
+    name: String,
+    skipped: Vec<Option<Point>>,
+    extra: Shape,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct After {
+    name: String,
+}
+
+macro_rules! assert_bytes {
+    (with $encoding:expr, $ty:ty, $value:expr, $expected:expr) => {{
+        let value: $ty = $value;
+        let bytes = $encoding.to_vec(&value).unwrap();
+        assert_eq!(bytes, $expected, "{value:?}");
+        let actual: $ty = $encoding.from_slice(&bytes).unwrap();
+        assert_eq!(actual, value);
+    }};
+
+    ($ty:ty, $value:expr, $expected:expr) => {
+        assert_bytes!(with Encoding::new(), $ty, $value, $expected)
+    };
+}
+
+#[test]
+fn integers() {
+    assert_bytes!(u8, 0, [0x00]);
+    assert_bytes!(u8, 23, [0x17]);
+    assert_bytes!(u8, 24, [0x18, 0x18]);
+    assert_bytes!(u16, 1000, [0x19, 0x03, 0xe8]);
+    assert_bytes!(u32, 1000000, [0x1a, 0x00, 0x0f, 0x42, 0x40]);
+    assert_bytes!(
+        u64,
+        u64::MAX,
+        [0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+    );
+    assert_bytes!(i8, -1, [0x20]);
+    assert_bytes!(i8, -10, [0x29]);
+    assert_bytes!(i16, -1000, [0x39, 0x03, 0xe7]);
+    assert_bytes!(
+        i128,
+        -18446744073709551616,
+        [0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+    );
+    assert_bytes!(
+        u128,
+        18446744073709551616,
+        [
+            0xc2, 0x49, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+        ]
+    );
+    assert_bytes!(
+        i128,
+        -18446744073709551617,
+        [
+            0xc3, 0x49, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+        ]
+    );
+
+    let mut wide = vec![0xc3, 0x50];
+    wide.extend_from_slice(&(!i128::MIN as u128).to_be_bytes());
+    assert_bytes!(i128, i128::MIN, wide);
+
+    // Integers can be decoded into any type which fits them.
+    assert_eq!(super::from_slice::<i8>(&[0x18, 0x7f]).unwrap(), 127);
+    assert_eq!(super::from_slice::<u128>(&[0x01]).unwrap(), 1);
+    assert!(super::from_slice::<u8>(&[0x20]).is_err());
+    assert!(super::from_slice::<i8>(&[0x18, 0x80]).is_err());
+}
+
+#[test]
+fn scalars() {
+    assert_bytes!(bool, true, [0xf5]);
+    assert_bytes!(bool, false, [0xf4]);
+    assert_bytes!(Option<u32>, None, [0xf6]);
+    assert_bytes!(Option<u32>, Some(1), [0x01]);
+    assert_bytes!(f32, 1.5, [0xfa, 0x3f, 0xc0, 0x00, 0x00]);
+    assert_bytes!(
+        f64,
+        1.5,
+        [0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+    );
+    assert_bytes!(char, 'ü', [0x62, 0xc3, 0xbc]);
+    assert_bytes!(String, String::from("IETF"), *b"\x64IETF");
+    assert_bytes!(Bytes<Vec<u8>>, Bytes(vec![1, 2]), [0x42, 0x01, 0x02]);
+
+    // Undefined is treated like null.
+    assert_eq!(super::from_slice::<Option<u32>>(&[0xf7]).unwrap(), None);
+}
+
+#[test]
+fn half_floats() {
+    assert_eq!(super::from_slice::<f64>(&[0xf9, 0x3c, 0x00]).unwrap(), 1.0);
+    assert_eq!(super::from_slice::<f32>(&[0xf9, 0xc4, 0x00]).unwrap(), -4.0);
+    assert_eq!(
+        super::from_slice::<f64>(&[0xf9, 0x00, 0x01]).unwrap(),
+        5.960464477539063e-8
+    );
+    assert_eq!(
+        super::from_slice::<f64>(&[0xf9, 0x7b, 0xff]).unwrap(),
+        65504.0
+    );
+    assert_eq!(
+        super::from_slice::<f64>(&[0xf9, 0xfc, 0x00]).unwrap(),
+        f64::NEG_INFINITY
+    );
+    assert!(
+        super::from_slice::<f64>(&[0xf9, 0x7e, 0x00])
+            .unwrap()
+            .is_nan()
+    );
+}
+
+#[test]
+fn canonical_floats() {
+    const CONFIG: Encoding<CANONICAL> = Encoding::new().with_options();
+
+    assert_bytes!(with CONFIG, f64, 0.0, [0xf9, 0x00, 0x00]);
+    assert_bytes!(with CONFIG, f64, -0.0, [0xf9, 0x80, 0x00]);
+    assert_bytes!(with CONFIG, f64, 1.0, [0xf9, 0x3c, 0x00]);
+    assert_bytes!(with CONFIG, f32, 1.5, [0xf9, 0x3e, 0x00]);
+    assert_bytes!(with CONFIG, f64, 65504.0, [0xf9, 0x7b, 0xff]);
+    assert_bytes!(with CONFIG, f64, 100000.0, [0xfa, 0x47, 0xc3, 0x50, 0x00]);
+    assert_bytes!(
+        with CONFIG,
+        f64,
+        3.4028234663852886e38,
+        [0xfa, 0x7f, 0x7f, 0xff, 0xff]
+    );
+    assert_bytes!(
+        with CONFIG,
+        f64,
+        1.1,
+        [0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]
+    );
+    assert_bytes!(with CONFIG, f64, 5.960464477539063e-8, [0xf9, 0x00, 0x01]);
+    assert_bytes!(with CONFIG, f64, 0.00006103515625, [0xf9, 0x04, 0x00]);
+    assert_bytes!(with CONFIG, f64, -4.0, [0xf9, 0xc4, 0x00]);
+    assert_bytes!(with CONFIG, f64, f64::INFINITY, [0xf9, 0x7c, 0x00]);
+    assert_bytes!(with CONFIG, f32, f32::NEG_INFINITY, [0xf9, 0xfc, 0x00]);
+    assert_eq!(CONFIG.to_vec(&f64::NAN).unwrap(), [0xf9, 0x7e, 0x00]);
+}
+
+#[test]
+fn canonical_maps() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate)]
+    struct Unsorted {
+        bb: u32,
+        a: u32,
+        c: Vec<u32>,
+        #[musli(Text, name = "aa")]
+        nested: BTreeMap<i32, u32>,
+    }
+
+    let value = Unsorted {
+        bb: 1,
+        a: 2,
+        c: vec![3],
+        nested: [(-1, 4), (10, 5)].into_iter().collect(),
+    };
+
+    // Shorter keys sort first, and negative integers after positive ones.
+    assert_bytes!(
+        with CANONICAL_TEXT,
+        Unsorted,
+        value,
+        *b"\xa4\x61a\x02\x61c\x81\x03\x62aa\xa2\x0a\x05\x20\x04\x62bb\x01"
+    );
+
+    let value = Unsorted {
+        bb: 1,
+        a: 2,
+        c: vec![3],
+        nested: BTreeMap::new(),
+    };
+
+    assert_eq!(
+        TEXT.to_vec(&value).unwrap(),
+        *b"\xa4\x62bb\x01\x61a\x02\x61c\x81\x03\x62aa\xa0"
+    );
+}
+
+#[test]
+fn containers() {
+    assert_bytes!(Vec<u8>, vec![1, 2, 3], [0x83, 0x01, 0x02, 0x03]);
+
+    let mut expected = vec![0x98, 0x19];
+    expected.extend(0..25u8);
+    expected[2 + 24] = 0x18;
+    expected.push(0x18);
+    assert_bytes!(Vec<u8>, (0..25).collect(), expected);
+
+    // Packs are encoded as indefinite arrays, unless the encoding is canonical.
+    assert_bytes!(Point, Point { x: 1, y: -1 }, [0x9f, 0x01, 0x20, 0xff]);
+    assert_bytes!(
+        with CANONICAL_TEXT,
+        Point,
+        Point { x: 1, y: -1 },
+        [0x82, 0x01, 0x20]
+    );
+
+    assert_bytes!(with TEXT, Shape, Shape::Circle(2), *b"\xa1\x66Circle\xa1\x00\x02");
+    assert_bytes!(
+        with TEXT,
+        Shape,
+        Shape::Rectangle {
+            width: 1,
+            height: 2
+        },
+        *b"\xa1\x69Rectangle\xa2\x65width\x01\x66height\x02"
+    );
+    assert_bytes!(with TEXT, Shape, Shape::Empty, *b"\xa1\x65Empty\xa0");
+}
+
+#[test]
+fn indefinite_lengths() {
+    let value: Vec<Vec<u32>> =
+        super::from_slice(b"\x9f\x81\x01\x82\x02\x03\x9f\x04\x05\xff\xff").unwrap();
+    assert_eq!(value, vec![vec![1], vec![2, 3], vec![4, 5]]);
+
+    let value: BTreeMap<String, u32> = super::from_slice(b"\xbf\x61a\x01\x61b\x02\xff").unwrap();
+    assert_eq!(
+        value,
+        [(String::from("a"), 1), (String::from("b"), 2)]
+            .into_iter()
+            .collect()
+    );
+
+    let value: String = super::from_slice(b"\x7f\x65strea\x64ming\xff").unwrap();
+    assert_eq!(value, "streaming");
+
+    let value: Bytes<Vec<u8>> = super::from_slice(b"\x5f\x42\x01\x02\x43\x03\x04\x05\xff").unwrap();
+    assert_eq!(value, Bytes(vec![1, 2, 3, 4, 5]));
+
+    // Chunks must be definite strings of the same type.
+    assert!(super::from_slice::<String>(b"\x7f\x41a\xff").is_err());
+
+    // Unknown fields with indefinite lengths can be skipped.
+    let after: After = TEXT
+        .from_slice(b"\xbf\x65extra\x9f\x5f\x41a\xff\xbf\x01\x02\xff\xff\x64name\x61a\xff")
+        .unwrap();
+
+    assert_eq!(
+        after,
+        After {
+            name: String::from("a")
+        }
+    );
+}
+
+#[test]
+fn tags() {
+    let value = Tagged::new(1, 1363896240u32);
+    assert_bytes!(Tagged<u32>, value, [0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0]);
+
+    let value = Tagged::new(32, String::from("http://www.example.com"));
+    assert_bytes!(
+        with TEXT,
+        Tagged<String>,
+        value,
+        *b"\xd8\x20\x76http://www.example.com"
+    );
+
+    // Tagged values can also be decoded from single-entry maps.
+    let value: Tagged<bool> = super::from_slice(b"\xa1\x18\x64\xf5").unwrap();
+    assert_eq!(value, Tagged::new(100, true));
+
+    // Tags are skipped along with their content.
+    let after: After = TEXT
+        .from_slice(b"\xa2\x65extra\xc1\x82\x01\x02\x64name\x61a")
+        .unwrap();
+
+    assert_eq!(
+        after,
+        After {
+            name: String::from("a")
+        }
+    );
+}
+
+#[test]
+fn dynamic_values() {
+    use crate::alloc::Global;
+    use crate::value::Value;
+
+    // Tags are converted into variants when decoded dynamically.
+    let bytes = b"\xd8\x20\x61a";
+    let value: Value<Global> = super::from_slice(bytes).unwrap();
+    let tagged: Tagged<String> = crate::value::decode(&value).unwrap();
+    assert_eq!(tagged, Tagged::new(32, String::from("a")));
+
+    let bytes = b"\xc2\x49\x01\x00\x00\x00\x00\x00\x00\x00\x00";
+    let value: Value<Global> = super::from_slice(bytes).unwrap();
+    let number: u128 = crate::value::decode(&value).unwrap();
+    assert_eq!(number, 18446744073709551616);
+}
+
+#[test]
+fn skip_unknown() {
+    let before = Before {
+        name: String::from("Aristotle"),
+        skipped: vec![Some(Point { x: 1, y: 2 }), None],
+        extra: Shape::Rectangle {
+            width: 300,
+            height: 70000,
+        },
+    };
+
+    let bytes = TEXT.to_vec(&before).unwrap();
+    let after: After = TEXT.from_slice(&bytes).unwrap();
+
+    assert_eq!(
+        after,
+        After {
+            name: String::from("Aristotle")
+        }
+    );
+}
+
+#[test]
+fn skip_deeply_nested() {
+    const DEPTH: usize = 100_000;
+
+    let mut bytes = vec![0xa2, 0x64];
+    bytes.extend_from_slice(b"deep");
+
+    // A definite-length array containing an indefinite-length array
+    // containing a tagged value, repeated.
+    for _ in 0..DEPTH {
+        bytes.extend_from_slice(&[0x81, 0x9f, 0xc0]);
+    }
+
+    bytes.push(0x00);
+    bytes.extend((0..DEPTH).map(|_| 0xff));
+
+    bytes.push(0x64);
+    bytes.extend_from_slice(b"name");
+    bytes.extend_from_slice(&[0x61, b'x']);
+
+    let after: After = TEXT.from_slice(&bytes).unwrap();
+
+    assert_eq!(
+        after,
+        After {
+            name: String::from("x")
+        }
+    );
+}
@@ -38,9 +38,10 @@ pub use musli_core::__macros::Decode;
 
 #[doc(inline)]
 pub use musli_core::de::__traits::{
-    AsDecoder, Decode, DecodeBytes, DecodeOwned, DecodePacked, DecodeSliceBuilder, DecodeTrace,
-    DecodeUnsized, DecodeUnsizedBytes, Decoder, EntriesDecoder, EntryDecoder, MapDecoder,
-    SequenceDecoder, SizeHint, Skip, TryFastDecode, UnsizedVisitor, VariantDecoder, Visitor,
+    AsDecoder, Decode, DecodeBytes, DecodeFlatten, DecodeOwned, DecodePacked, DecodeSliceBuilder,
+    DecodeTrace, DecodeUnsized, DecodeUnsizedBytes, Decoder, EntriesDecoder, EntryDecoder, LazyMap,
+    LazyMapEncoding, LazySeq, LazySeqEncoding, MapDecoder, SequenceDecoder, SizeHint, Skip,
+    TryFastDecode, UnsizedVisitor, VariantDecoder, Visitor,
 };
 
 #[cfg(any(
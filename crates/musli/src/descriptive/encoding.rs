@@ -1,5 +1,7 @@
 use core::marker;
 
+#[cfg(feature = "alloc")]
+use crate::Reader;
 #[cfg(feature = "alloc")]
 use crate::alloc::Global;
 use crate::mode::Binary;
@@ -129,6 +131,18 @@ where
     );
 }
 
+crate::macros::decode_iter!(
+    descriptive,
+    [const OPT: Options, M] Encoding<OPT, M>,
+    M,
+    SelfDecoder::<OPT, _, _, M>::new,
+    IntoReader::into_reader -> Reader,
+    Reader,
+    |_cx, reader| reader.is_eof(),
+);
+
+crate::macros::lazy_encoding!([const OPT: Options, M] Encoding<OPT, M>, M);
+
 impl<const OPT: Options, M> Clone for Encoding<OPT, M> {
     #[inline]
     fn clone(&self) -> Self {
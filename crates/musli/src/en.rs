@@ -50,8 +50,8 @@ pub use musli_core::__macros::Encode;
 
 #[doc(inline)]
 pub use musli_core::en::__traits::{
-    Encode, EncodeBytes, EncodePacked, EncodeTrace, Encoder, EntriesEncoder, EntryEncoder,
-    MapEncoder, SequenceEncoder, TryFastEncode, VariantEncoder,
+    Encode, EncodeBytes, EncodeFlatten, EncodePacked, EncodeTrace, Encoder, EntriesEncoder,
+    EntryEncoder, IterEncode, MapEncoder, SequenceEncoder, TryFastEncode, VariantEncoder,
 };
 
 #[cfg(any(
@@ -0,0 +1,105 @@
+//! Length-delimited framing used by asynchronous encoding helpers.
+//!
+//! Each frame consists of a 32-bit big-endian length prefix followed by that
+//! many bytes of encoded payload. Since binary formats are not necessarily
+//! self-delimiting, this allows a whole value to be read off an [`AsyncRead`]
+//! before it is decoded.
+//!
+//! [`AsyncRead`]: tokio::io::AsyncRead
+
+#![cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "protobuf",
+    feature = "descriptive",
+    feature = "value"
+))]
+
+use rust_alloc::vec::Vec;
+use std::io;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Write a single length-delimited frame to the given writer.
+pub(crate) async fn write_frame<W>(mut writer: W, bytes: &[u8]) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let Ok(len) = u32::try_from(bytes.len()) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            FrameTooLarge { len: bytes.len() },
+        ));
+    };
+
+    writer.write_all(&len.to_be_bytes()).await?;
+    writer.write_all(bytes).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Read a single length-delimited frame from the given reader.
+///
+/// The buffer grows as data arrives, so a bogus length prefix cannot cause a
+/// large up-front allocation.
+pub(crate) async fn read_frame<R>(mut reader: R) -> io::Result<Vec<u8>>
+where
+    R: AsyncRead + Unpin,
+{
+    let len = reader.read_u32().await?;
+
+    let mut bytes = Vec::new();
+    (&mut reader)
+        .take(u64::from(len))
+        .read_to_end(&mut bytes)
+        .await?;
+
+    if bytes.len() != len as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            TruncatedFrame {
+                expected: len as usize,
+                actual: bytes.len(),
+            },
+        ));
+    }
+
+    Ok(bytes)
+}
+
+#[derive(Debug)]
+struct FrameTooLarge {
+    len: usize,
+}
+
+impl core::fmt::Display for FrameTooLarge {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let FrameTooLarge { len } = self;
+        write!(
+            f,
+            "Frame of {len} bytes does not fit in a 32-bit length prefix"
+        )
+    }
+}
+
+impl core::error::Error for FrameTooLarge {}
+
+#[derive(Debug)]
+struct TruncatedFrame {
+    expected: usize,
+    actual: usize,
+}
+
+impl core::fmt::Display for TruncatedFrame {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let TruncatedFrame { expected, actual } = self;
+        write!(
+            f,
+            "Frame truncated, expected {expected} bytes but only {actual} were available"
+        )
+    }
+}
+
+impl core::error::Error for TruncatedFrame {}
@@ -0,0 +1,222 @@
+//! Textual representations of bytes.
+
+use crate::alloc::Vec;
+use crate::{Context, Writer};
+
+/// How bytes are represented in JSON.
+///
+/// This applies to values encoded through [`Encoder::encode_bytes`] or
+/// [`Encoder::encode_bytes_vectored`], such as fields marked with
+/// `#[musli(bytes)]`. Use [`Encoding::with_bytes`] to configure it.
+///
+/// [`Encoder::encode_bytes`]: crate::Encoder::encode_bytes
+/// [`Encoder::encode_bytes_vectored`]: crate::Encoder::encode_bytes_vectored
+/// [`Encoding::with_bytes`]: super::Encoding::with_bytes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BytesFormat {
+    /// An array of numbers, like `[104,105]`.
+    #[default]
+    Array,
+    /// A string using the standard base64 alphabet with padding, like
+    /// `"aGk="`.
+    ///
+    /// When decoding, padding is optional.
+    Base64,
+    /// A string using the URL and filename safe base64 alphabet without
+    /// padding, like `"aGk"`.
+    ///
+    /// When decoding, padding is optional.
+    Base64Url,
+    /// A string of lowercase hexadecimal digits, like `"6869"`.
+    ///
+    /// When decoding, uppercase digits are also accepted.
+    Hex,
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// Marks bytes which are not part of an alphabet in a reverse lookup table.
+const INVALID: u8 = 0xff;
+
+static BASE64_DECODE: [u8; 256] = decode_table(BASE64);
+static BASE64_URL_DECODE: [u8; 256] = decode_table(BASE64_URL);
+
+static HEX_DECODE: [u8; 256] = {
+    let mut table = decode_table(HEX);
+    let mut i = 10;
+
+    while i < HEX.len() {
+        table[HEX[i].to_ascii_uppercase() as usize] = i as u8;
+        i += 1;
+    }
+
+    table
+};
+
+/// Construct a table which maps every byte to its position in the given
+/// alphabet, or [`INVALID`] if it isn't a part of it.
+const fn decode_table(alphabet: &[u8]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+
+    while i < alphabet.len() {
+        table[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+
+    table
+}
+
+/// Encode a sequence of byte slices as a single string.
+///
+/// This must not be called with [`BytesFormat::Array`].
+pub(crate) fn encode<W, C, I>(
+    cx: C,
+    mut writer: W,
+    format: BytesFormat,
+    vectors: I,
+) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+    I: IntoIterator<Item: AsRef<[u8]>>,
+{
+    let (alphabet, pad) = match format {
+        BytesFormat::Base64 => (BASE64, true),
+        BytesFormat::Base64Url => (BASE64_URL, false),
+        _ => {
+            writer.write_byte(cx, b'"')?;
+
+            for bytes in vectors {
+                for &b in bytes.as_ref() {
+                    let digits = [HEX[usize::from(b >> 4)], HEX[usize::from(b & 0xf)]];
+                    writer.write_bytes(cx, &digits)?;
+                }
+            }
+
+            return writer.write_byte(cx, b'"');
+        }
+    };
+
+    writer.write_byte(cx, b'"')?;
+
+    // Bytes which didn't make up a complete group of three in the preceding
+    // slices.
+    let mut carry = [0u8; 3];
+    let mut len = 0;
+
+    for bytes in vectors {
+        for &b in bytes.as_ref() {
+            carry[len] = b;
+            len += 1;
+
+            if len == 3 {
+                writer.write_bytes(cx, &base64_group(alphabet, carry))?;
+                len = 0;
+            }
+        }
+    }
+
+    if len > 0 {
+        carry[len..].fill(0);
+        let group = base64_group(alphabet, carry);
+        writer.write_bytes(cx, &group[..len + 1])?;
+
+        if pad {
+            writer.write_bytes(cx, &b"=="[..3 - len])?;
+        }
+    }
+
+    writer.write_byte(cx, b'"')
+}
+
+#[inline]
+fn base64_group(alphabet: &[u8; 64], [a, b, c]: [u8; 3]) -> [u8; 4] {
+    let n = u32::from(a) << 16 | u32::from(b) << 8 | u32::from(c);
+
+    [
+        alphabet[(n >> 18) as usize & 0x3f],
+        alphabet[(n >> 12) as usize & 0x3f],
+        alphabet[(n >> 6) as usize & 0x3f],
+        alphabet[n as usize & 0x3f],
+    ]
+}
+
+/// Decode the contents of a string into `out`.
+///
+/// This must not be called with [`BytesFormat::Array`].
+pub(crate) fn decode<C>(
+    cx: C,
+    format: BytesFormat,
+    input: &[u8],
+    out: &mut Vec<u8, C::Allocator>,
+    start: &C::Mark,
+) -> Result<(), C::Error>
+where
+    C: Context,
+{
+    let table = match format {
+        BytesFormat::Base64 => &BASE64_DECODE,
+        BytesFormat::Base64Url => &BASE64_URL_DECODE,
+        _ => {
+            if input.len() % 2 != 0 {
+                return Err(cx.message_at(start, "Expected an even number of hex digits"));
+            }
+
+            for pair in input.chunks_exact(2) {
+                let hi = HEX_DECODE[usize::from(pair[0])];
+                let lo = HEX_DECODE[usize::from(pair[1])];
+
+                if hi == INVALID || lo == INVALID {
+                    return Err(cx.message_at(start, "Invalid hex digit"));
+                }
+
+                out.push(hi << 4 | lo).map_err(cx.map())?;
+            }
+
+            return Ok(());
+        }
+    };
+
+    let input = match input {
+        [rest @ .., b'=', b'='] if input.len() % 4 == 0 => rest,
+        [rest @ .., b'='] if input.len() % 4 == 0 => rest,
+        input => input,
+    };
+
+    let mut acc = 0u32;
+    let mut bits = 0;
+
+    for &b in input {
+        let value = table[usize::from(b)];
+
+        if value == INVALID {
+            return Err(cx.message_at(start, "Invalid base64 character"));
+        }
+
+        acc = acc << 6 | u32::from(value);
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8).map_err(cx.map())?;
+            acc &= (1 << bits) - 1;
+        }
+    }
+
+    // A single trailing character doesn't make up a whole byte.
+    if bits >= 6 {
+        return Err(cx.message_at(start, "Invalid base64 length"));
+    }
+
+    // Bits left over after the last byte must be zero so that every sequence
+    // of bytes has exactly one encoding.
+    if acc != 0 {
+        return Err(cx.message_at(start, "Invalid trailing bits in base64"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::json::BytesFormat;
+
+/// How JSON is decoded.
+///
+/// This is passed by value to every nested decoder.
+#[derive(Clone, Copy)]
+pub(crate) struct Config {
+    /// Provide numbers in their textual form when decoding dynamic values.
+    arbitrary_precision: bool,
+    /// How bytes are represented.
+    bytes: BytesFormat,
+    /// Accept a relaxed grammar, which is implemented by wrapping the parser
+    /// at the root of decoding.
+    relaxed: bool,
+}
+
+impl Config {
+    /// Construct the default configuration.
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            arbitrary_precision: false,
+            bytes: BytesFormat::Array,
+            relaxed: false,
+        }
+    }
+
+    /// Configure whether numbers are decoded with arbitrary precision.
+    #[inline]
+    pub(crate) const fn with_arbitrary_precision(self, arbitrary_precision: bool) -> Self {
+        Self {
+            arbitrary_precision,
+            ..self
+        }
+    }
+
+    /// Test if numbers are decoded with arbitrary precision.
+    #[inline]
+    pub(crate) const fn is_arbitrary_precision(&self) -> bool {
+        self.arbitrary_precision
+    }
+
+    /// Expect bytes in the given format.
+    #[inline]
+    pub(crate) const fn with_bytes(self, bytes: BytesFormat) -> Self {
+        Self { bytes, ..self }
+    }
+
+    /// How bytes are represented.
+    #[inline]
+    pub(crate) const fn bytes(&self) -> BytesFormat {
+        self.bytes
+    }
+
+    /// Accept a relaxed grammar.
+    #[inline]
+    pub(crate) const fn with_relaxed(self) -> Self {
+        Self {
+            relaxed: true,
+            ..self
+        }
+    }
+
+    /// Test if a relaxed grammar is accepted.
+    #[inline]
+    pub(crate) const fn is_relaxed(&self) -> bool {
+        self.relaxed
+    }
+}
@@ -6,12 +6,13 @@ use crate::alloc::Vec;
 use crate::de::{Decoder, SizeHint, Skip, UnsizedVisitor, Visitor};
 
 use super::super::parser::{Parser, Token};
-use super::{JsonDecoder, KeySignedVisitor, KeyUnsignedVisitor, StringReference};
+use super::{Config, JsonDecoder, KeySignedVisitor, KeyUnsignedVisitor, StringReference};
 
 /// A JSON object key decoder for Müsli.
 pub(crate) struct JsonKeyDecoder<P, C, M> {
     cx: C,
     parser: P,
+    config: Config,
     _marker: PhantomData<M>,
 }
 
@@ -23,10 +24,11 @@ where
 {
     /// Construct a new fixed width message encoder.
     #[inline]
-    pub(crate) fn new(cx: C, parser: P) -> Self {
+    pub(crate) fn new(cx: C, parser: P, config: Config) -> Self {
         Self {
             cx,
             parser,
+            config,
             _marker: PhantomData,
         }
     }
@@ -38,7 +40,7 @@ where
     {
         let mut scratch = Vec::new_in(self.cx.alloc());
 
-        match self.parser.parse_string(self.cx, true, &mut scratch)? {
+        match self.parser.parse_key(self.cx, true, &mut scratch)? {
             StringReference::Borrowed(string) => visitor.visit_borrowed(self.cx, string.as_bytes()),
             StringReference::Scratch(string) => visitor.visit_ref(self.cx, string.as_bytes()),
         }
@@ -70,12 +72,20 @@ where
 
     #[inline]
     fn try_clone(&self) -> Option<Self::TryClone> {
-        Some(JsonKeyDecoder::new(self.cx, self.parser.try_clone()?))
+        Some(JsonKeyDecoder::new(
+            self.cx,
+            self.parser.try_clone()?,
+            self.config,
+        ))
     }
 
     #[inline]
-    fn skip(self) -> Result<(), Self::Error> {
-        JsonDecoder::<_, _, M>::new(self.cx, self.parser).skip()
+    fn skip(mut self) -> Result<(), Self::Error> {
+        if matches!(self.parser.lex_key(self.cx), Token::String) {
+            return self.parser.skip_key(self.cx);
+        }
+
+        JsonDecoder::<_, _, M>::new(self.cx, self.parser, self.config).skip()
     }
 
     #[inline]
@@ -145,11 +155,16 @@ where
     }
 
     #[inline]
-    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, V::Error>
+    fn decode_string<V>(mut self, visitor: V) -> Result<V::Ok, V::Error>
     where
         V: UnsizedVisitor<'de, C, str, Error = Self::Error, Allocator = Self::Allocator>,
     {
-        JsonDecoder::<_, _, M>::new(self.cx, self.parser).decode_string(visitor)
+        let mut scratch = Vec::new_in(self.cx.alloc());
+
+        match self.parser.parse_key(self.cx, true, &mut scratch)? {
+            StringReference::Borrowed(borrowed) => visitor.visit_borrowed(self.cx, borrowed),
+            StringReference::Scratch(string) => visitor.visit_ref(self.cx, string),
+        }
     }
 
     #[inline]
@@ -157,7 +172,7 @@ where
     where
         V: Visitor<'de, C, Error = Self::Error, Allocator = Self::Allocator>,
     {
-        match self.parser.lex(self.cx) {
+        match self.parser.lex_key(self.cx) {
             Token::String => {
                 let visitor = visitor.visit_string(self.cx, SizeHint::any())?;
                 self.decode_string(visitor)
@@ -19,6 +19,11 @@ use self::sequence_decoder::JsonSequenceDecoder;
 mod variant_decoder;
 use self::variant_decoder::JsonVariantDecoder;
 
+mod config;
+pub(crate) use self::config::Config;
+
+mod pointer;
+
 use core::fmt;
 use core::marker::PhantomData;
 use core::str;
@@ -26,11 +31,12 @@ use core::str;
 use crate::Context;
 use crate::Options;
 use crate::alloc::Vec;
-use crate::de::{Decoder, SequenceDecoder, SizeHint, Skip, UnsizedVisitor, Visitor};
+use crate::de::{Decode, Decoder, SequenceDecoder, SizeHint, Skip, UnsizedVisitor, Visitor};
 use crate::hint::{MapHint, SequenceHint};
 use crate::options;
 use crate::value::{IntoValueDecoder, Value};
 
+use super::bytes::{self, BytesFormat};
 #[cfg(not(feature = "parse-full"))]
 use super::parser::integer::{
     parse_signed_base as parse_signed, parse_unsigned_base as parse_unsigned,
@@ -39,7 +45,7 @@ use super::parser::integer::{
 use super::parser::integer::{
     parse_signed_full as parse_signed, parse_unsigned_full as parse_unsigned,
 };
-use super::parser::{Parser, StringReference, Token, integer};
+use super::parser::{Parser, RelaxedParser, StringReference, Token, integer};
 
 const BUFFER_OPTIONS: Options = options::new().map_keys_as_numbers().build();
 
@@ -47,9 +53,33 @@ const BUFFER_OPTIONS: Options = options::new().map_keys_as_numbers().build();
 pub(crate) struct JsonDecoder<P, C, M> {
     cx: C,
     parser: P,
+    config: Config,
     _marker: PhantomData<M>,
 }
 
+/// Decode a value at the root of the given parser.
+///
+/// If a relaxed grammar is accepted, the parser is wrapped in a
+/// [`RelaxedParser`] so that strict parsing doesn't have to account for it.
+#[inline]
+pub(crate) fn decode<'de, P, C, M, T>(cx: C, parser: P, config: Config) -> Result<T, C::Error>
+where
+    P: Parser<'de>,
+    C: Context,
+    M: 'static,
+    T: Decode<'de, M, C::Allocator>,
+{
+    if config.is_relaxed() {
+        return T::decode(JsonDecoder::<_, _, M>::new(
+            cx,
+            RelaxedParser::new(parser),
+            config,
+        ));
+    }
+
+    T::decode(JsonDecoder::<_, _, M>::new(cx, parser, config))
+}
+
 impl<'de, P, C, M> JsonDecoder<P, C, M>
 where
     P: Parser<'de>,
@@ -58,10 +88,11 @@ where
 {
     /// Construct a new fixed width message encoder.
     #[inline]
-    pub(crate) fn new(cx: C, parser: P) -> Self {
+    pub(crate) fn new(cx: C, parser: P, config: Config) -> Self {
         Self {
             cx,
             parser,
+            config,
             _marker: PhantomData,
         }
     }
@@ -78,11 +109,7 @@ where
             Token::True => self.parse_true(),
             Token::False => self.parse_false(),
             Token::Number => integer::skip_number(self.cx, self.parser.borrow_mut()),
-            Token::String => {
-                // Skip over opening quote.
-                self.parser.skip(self.cx, 1)?;
-                self.parser.skip_string_inner(self.cx)
-            }
+            Token::String => self.parser.skip_string(self.cx),
             actual => Err(self
                 .cx
                 .message_at(&start, format_args!("Expected value, found {actual}"))),
@@ -137,7 +164,11 @@ where
 
     #[inline]
     fn try_clone(&self) -> Option<Self::TryClone> {
-        Some(JsonDecoder::new(self.cx, self.parser.try_clone()?))
+        Some(JsonDecoder::new(
+            self.cx,
+            self.parser.try_clone()?,
+            self.config,
+        ))
     }
 
     #[inline]
@@ -274,9 +305,28 @@ where
     }
 
     #[inline]
-    fn decode_array<const N: usize>(self) -> Result<[u8; N], Self::Error> {
+    fn decode_array<const N: usize>(mut self) -> Result<[u8; N], Self::Error> {
         let cx = self.cx;
         let mark = cx.mark();
+        let format = self.config.bytes();
+
+        if !matches!(format, BytesFormat::Array) {
+            let mut scratch = Vec::new_in(cx.alloc());
+            let string = self.parser.parse_string(cx, true, &mut scratch)?;
+            let mut bytes = Vec::new_in(cx.alloc());
+            bytes::decode(cx, format, string.as_str().as_bytes(), &mut bytes, &mark)?;
+
+            return match <[u8; N]>::try_from(bytes.as_slice()) {
+                Ok(array) => Ok(array),
+                Err(..) => Err(cx.message_at(
+                    &mark,
+                    format_args!(
+                        "Bytes with length {} does not have the expected {N} number of elements",
+                        bytes.len()
+                    ),
+                )),
+            };
+        }
 
         self.decode_sequence(|seq| {
             let mut bytes = [0; N];
@@ -304,11 +354,21 @@ where
     }
 
     #[inline]
-    fn decode_bytes<V>(self, visitor: V) -> Result<V::Ok, V::Error>
+    fn decode_bytes<V>(mut self, visitor: V) -> Result<V::Ok, V::Error>
     where
         V: UnsizedVisitor<'de, C, [u8], Error = Self::Error, Allocator = Self::Allocator>,
     {
         let cx = self.cx;
+        let format = self.config.bytes();
+
+        if !matches!(format, BytesFormat::Array) {
+            let start = cx.mark();
+            let mut scratch = Vec::new_in(cx.alloc());
+            let string = self.parser.parse_string(cx, true, &mut scratch)?;
+            let mut bytes = Vec::new_in(cx.alloc());
+            bytes::decode(cx, format, string.as_str().as_bytes(), &mut bytes, &start)?;
+            return visitor.visit_owned(cx, bytes);
+        }
 
         self.decode_sequence(|seq| {
             let mut bytes = Vec::with_capacity_in(seq.size_hint().or_default(), cx.alloc())
@@ -351,7 +411,7 @@ where
     where
         F: FnOnce(&mut Self::DecodePack) -> Result<O, Self::Error>,
     {
-        let mut decoder = JsonSequenceDecoder::new(self.cx, None, self.parser)?;
+        let mut decoder = JsonSequenceDecoder::new(self.cx, None, self.parser, self.config)?;
         let output = f(&mut decoder)?;
         decoder.skip_sequence_remaining()?;
         Ok(output)
@@ -362,7 +422,7 @@ where
     where
         F: FnOnce(&mut Self::DecodeSequence) -> Result<O, Self::Error>,
     {
-        let mut decoder = JsonSequenceDecoder::new(self.cx, None, self.parser)?;
+        let mut decoder = JsonSequenceDecoder::new(self.cx, None, self.parser, self.config)?;
         let output = f(&mut decoder)?;
         decoder.skip_sequence_remaining()?;
         Ok(output)
@@ -374,7 +434,7 @@ where
         F: FnOnce(&mut Self::DecodeSequence) -> Result<O, Self::Error>,
     {
         let size = hint.get();
-        let mut decoder = JsonSequenceDecoder::new(self.cx, size, self.parser)?;
+        let mut decoder = JsonSequenceDecoder::new(self.cx, size, self.parser, self.config)?;
         let output = f(&mut decoder)?;
         decoder.skip_sequence_remaining()?;
         Ok(output)
@@ -385,7 +445,7 @@ where
     where
         F: FnOnce(&mut Self::DecodeMap) -> Result<O, Self::Error>,
     {
-        let mut decoder = JsonObjectDecoder::new(self.cx, None, self.parser)?;
+        let mut decoder = JsonObjectDecoder::new(self.cx, None, self.parser, self.config)?;
         let output = f(&mut decoder)?;
         decoder.skip_object_remaining()?;
         Ok(output)
@@ -396,7 +456,7 @@ where
     where
         F: FnOnce(&mut Self::DecodeMap) -> Result<O, Self::Error>,
     {
-        let mut decoder = JsonObjectDecoder::new(self.cx, hint.get(), self.parser)?;
+        let mut decoder = JsonObjectDecoder::new(self.cx, hint.get(), self.parser, self.config)?;
         let output = f(&mut decoder)?;
         decoder.skip_object_remaining()?;
         Ok(output)
@@ -415,7 +475,7 @@ where
     where
         F: FnOnce(&mut Self::DecodeVariant) -> Result<O, Self::Error>,
     {
-        let mut decoder = JsonVariantDecoder::new(self.cx, self.parser)?;
+        let mut decoder = JsonVariantDecoder::new(self.cx, self.parser, self.config)?;
         let output = f(&mut decoder)?;
         decoder.end()?;
         Ok(output)
@@ -426,7 +486,24 @@ where
     where
         V: Visitor<'de, C, Error = Self::Error, Allocator = Self::Allocator>,
     {
-        self.parser.parse_number(self.cx, visitor)
+        let cx = self.cx;
+
+        self.parser.skip_whitespace(cx);
+
+        let start = cx.mark();
+        let before = self.parser.remaining();
+        integer::skip_number(cx, self.parser.borrow_mut())?;
+        let after = self.parser.remaining();
+
+        let number = &before[..before.len() - after.len()];
+
+        if !integer::is_number(number) {
+            return Err(cx.message_at(&start, "Invalid number"));
+        }
+
+        // SAFETY: Numbers are validated to only consist of ASCII characters.
+        let number = unsafe { str::from_utf8_unchecked(number) };
+        visitor.visit_number(cx, number)
     }
 
     #[inline]
@@ -443,7 +520,8 @@ where
                 let visitor = visitor.visit_string(cx, SizeHint::any())?;
                 self.decode_string(visitor)
             }
-            Token::Number => self.decode_number(visitor),
+            Token::Number if self.config.is_arbitrary_precision() => self.decode_number(visitor),
+            Token::Number => self.parser.parse_number(cx, visitor),
             Token::Null => {
                 self.parse_null()?;
                 visitor.visit_empty(cx)
@@ -459,4 +537,27 @@ where
             token => Err(cx.message(format_args!("Expected value, found {token:?}"))),
         }
     }
+
+    #[inline]
+    fn decode_raw<V>(mut self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: UnsizedVisitor<'de, C, str, Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        let cx = self.cx;
+
+        self.parser.skip_whitespace(cx);
+
+        let start = cx.mark();
+        let before = self.parser.remaining();
+        JsonDecoder::<_, _, M>::new(cx, self.parser.borrow_mut(), self.config).skip_any()?;
+        let after = self.parser.remaining();
+
+        let raw = &before[..before.len() - after.len()];
+
+        let Ok(raw) = crate::str::from_utf8(raw) else {
+            return Err(cx.message_at(&start, "Invalid unicode in raw value"));
+        };
+
+        visitor.visit_borrowed(cx, raw)
+    }
 }
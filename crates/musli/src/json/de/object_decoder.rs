@@ -5,7 +5,7 @@ use crate::Context;
 use crate::de::{Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SizeHint};
 use crate::json::parser::{Parser, Token};
 
-use super::{JsonDecoder, JsonKeyDecoder, JsonObjectPairDecoder};
+use super::{Config, JsonDecoder, JsonKeyDecoder, JsonObjectPairDecoder};
 
 #[must_use = "Must call skip_object_remaining to complete decoding"]
 pub(crate) struct JsonObjectDecoder<P, C, M> {
@@ -13,6 +13,7 @@ pub(crate) struct JsonObjectDecoder<P, C, M> {
     first: bool,
     len: Option<usize>,
     parser: P,
+    config: Config,
     finalized: bool,
     _marker: PhantomData<M>,
 }
@@ -23,19 +24,31 @@ where
     C: Context,
     M: 'static,
 {
-    pub(super) fn new_in(cx: C, first: bool, len: Option<usize>, parser: P) -> Self {
+    pub(super) fn new_in(
+        cx: C,
+        first: bool,
+        len: Option<usize>,
+        parser: P,
+        config: Config,
+    ) -> Self {
         Self {
             cx,
             first,
             len,
             parser,
+            config,
             finalized: false,
             _marker: PhantomData,
         }
     }
 
     #[inline]
-    pub(super) fn new(cx: C, len: Option<usize>, mut parser: P) -> Result<Self, C::Error> {
+    pub(super) fn new(
+        cx: C,
+        len: Option<usize>,
+        mut parser: P,
+        config: Config,
+    ) -> Result<Self, C::Error> {
         let actual = parser.lex(cx);
 
         if !matches!(actual, Token::OpenBrace) {
@@ -49,6 +62,7 @@ where
             first: true,
             len,
             parser,
+            config,
             finalized: false,
             _marker: PhantomData,
         })
@@ -62,7 +76,7 @@ where
         let first = take(&mut self.first);
 
         loop {
-            let token = self.parser.lex(self.cx);
+            let token = self.parser.lex_key(self.cx);
 
             match token {
                 Token::String => {
@@ -148,6 +162,7 @@ where
         Ok(Some(JsonObjectPairDecoder::new(
             self.cx,
             self.parser.borrow_mut(),
+            self.config,
         )))
     }
 
@@ -166,6 +181,7 @@ where
             self.first,
             self.len,
             self.parser.borrow_mut(),
+            self.config,
         ))
     }
 }
@@ -200,7 +216,11 @@ where
             return Ok(None);
         }
 
-        Ok(Some(JsonKeyDecoder::new(self.cx, self.parser.borrow_mut())))
+        Ok(Some(JsonKeyDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.config,
+        )))
     }
 
     #[inline]
@@ -214,7 +234,11 @@ where
         }
 
         self.parser.skip(self.cx, 1)?;
-        Ok(JsonDecoder::new(self.cx, self.parser.borrow_mut()))
+        Ok(JsonDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.config,
+        ))
     }
 
     #[inline]
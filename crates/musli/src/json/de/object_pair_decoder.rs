@@ -4,20 +4,22 @@ use crate::Context;
 use crate::de::EntryDecoder;
 use crate::json::parser::{Parser, Token};
 
-use super::{JsonDecoder, JsonKeyDecoder};
+use super::{Config, JsonDecoder, JsonKeyDecoder};
 
 pub(crate) struct JsonObjectPairDecoder<P, C, M> {
     cx: C,
     parser: P,
+    config: Config,
     _marker: PhantomData<M>,
 }
 
 impl<P, C, M> JsonObjectPairDecoder<P, C, M> {
     #[inline]
-    pub(super) fn new(cx: C, parser: P) -> Self {
+    pub(super) fn new(cx: C, parser: P, config: Config) -> Self {
         Self {
             cx,
             parser,
+            config,
             _marker: PhantomData,
         }
     }
@@ -46,7 +48,11 @@ where
 
     #[inline]
     fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, C::Error> {
-        Ok(JsonKeyDecoder::new(self.cx, self.parser.borrow_mut()))
+        Ok(JsonKeyDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.config,
+        ))
     }
 
     #[inline]
@@ -60,6 +66,6 @@ where
         }
 
         self.parser.skip(self.cx, 1)?;
-        Ok(JsonDecoder::new(self.cx, self.parser))
+        Ok(JsonDecoder::new(self.cx, self.parser, self.config))
     }
 }
@@ -0,0 +1,367 @@
+use core::fmt;
+
+use crate::Context;
+use crate::alloc::Vec;
+use crate::json::parser::{Parser, Token};
+
+use super::JsonDecoder;
+
+/// A single reference token in a JSON pointer, as specified in [RFC 6901].
+///
+/// The token is stored in its escaped form, where `~1` and `~0` stand in for
+/// `/` and `~` respectively.
+///
+/// [RFC 6901]: https://www.rfc-editor.org/rfc/rfc6901
+#[derive(Clone, Copy)]
+struct Segment<'a> {
+    escaped: &'a str,
+}
+
+impl<'a> Segment<'a> {
+    /// Construct a new segment, validating its escape sequences.
+    fn new(escaped: &'a str) -> Option<Self> {
+        let mut it = escaped.bytes();
+
+        while let Some(b) = it.next() {
+            if b == b'~' && !matches!(it.next(), Some(b'0' | b'1')) {
+                return None;
+            }
+        }
+
+        Some(Self { escaped })
+    }
+
+    /// Iterate over the unescaped characters of the segment.
+    fn chars(self) -> impl Iterator<Item = char> + 'a {
+        let mut it = self.escaped.chars();
+
+        core::iter::from_fn(move || match it.next()? {
+            '~' => match it.next()? {
+                '0' => Some('~'),
+                _ => Some('/'),
+            },
+            c => Some(c),
+        })
+    }
+
+    /// Iterate over the unescaped bytes of the segment.
+    fn bytes(self) -> impl Iterator<Item = u8> + 'a {
+        let mut it = self.escaped.bytes();
+
+        core::iter::from_fn(move || match it.next()? {
+            b'~' => match it.next()? {
+                b'0' => Some(b'~'),
+                _ => Some(b'/'),
+            },
+            b => Some(b),
+        })
+    }
+
+    /// Test if the segment matches the given object key.
+    fn matches(self, key: &str) -> bool {
+        self.bytes().eq(key.bytes())
+    }
+
+    /// Test if the segment matches the JSON string at the start of `raw`,
+    /// which follows an opening `quote`.
+    ///
+    /// The string is unescaped as it's being compared, so this never needs to
+    /// buffer it. Malformed strings never match, and are expected to be
+    /// reported when they are skipped over.
+    fn matches_quoted(self, raw: &[u8], quote: u8) -> bool {
+        let mut expected = self.bytes();
+        let mut it = raw.iter().copied();
+
+        loop {
+            let mut buf = [0; 4];
+
+            let actual: &[u8] = match it.next() {
+                Some(b) if b == quote => return expected.next().is_none(),
+                Some(b'\\') => match it.next() {
+                    Some(b'b') => b"\x08",
+                    Some(b'f') => b"\x0c",
+                    Some(b'n') => b"\n",
+                    Some(b'r') => b"\r",
+                    Some(b't') => b"\t",
+                    Some(b'u') => {
+                        let Some(c) = unescape_unicode(&mut it) else {
+                            return false;
+                        };
+
+                        c.encode_utf8(&mut buf).as_bytes()
+                    }
+                    Some(b) => {
+                        buf[0] = b;
+                        &buf[..1]
+                    }
+                    None => return false,
+                },
+                Some(b) => {
+                    buf[0] = b;
+                    &buf[..1]
+                }
+                None => return false,
+            };
+
+            if !actual.iter().all(|&b| expected.next() == Some(b)) {
+                return false;
+            }
+        }
+    }
+
+    /// Interpret the segment as an array index.
+    ///
+    /// Leading zeros are not permitted.
+    fn index(self) -> Option<usize> {
+        match self.escaped.as_bytes() {
+            [b'0'] => Some(0),
+            [b'1'..=b'9', rest @ ..] if rest.iter().all(u8::is_ascii_digit) => {
+                self.escaped.parse().ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Decode the character of a `\u` escape, which might be a surrogate pair.
+fn unescape_unicode(it: &mut impl Iterator<Item = u8>) -> Option<char> {
+    fn hex(it: &mut impl Iterator<Item = u8>) -> Option<u32> {
+        let mut n = 0;
+
+        for _ in 0..4 {
+            n = (n << 4) | char::from(it.next()?).to_digit(16)?;
+        }
+
+        Some(n)
+    }
+
+    let n = match hex(it)? {
+        n1 @ 0xD800..=0xDBFF => {
+            let (b'\\', b'u') = (it.next()?, it.next()?) else {
+                return None;
+            };
+
+            let n2 = hex(it)?;
+
+            if !(0xDC00..=0xDFFF).contains(&n2) {
+                return None;
+            }
+
+            (((n1 - 0xD800) << 10) | (n2 - 0xDC00)) + 0x1_0000
+        }
+        n => n,
+    };
+
+    char::from_u32(n)
+}
+
+impl fmt::Display for Segment<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use core::fmt::Write;
+
+        for c in self.chars() {
+            f.write_char(c)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de, P, C, M> JsonDecoder<P, C, M>
+where
+    P: Parser<'de>,
+    C: Context,
+    M: 'static,
+{
+    /// Walk the document to the value referenced by the given JSON pointer
+    /// and decode it using `decode`.
+    ///
+    /// Values which are not on the path are skipped without being decoded.
+    /// Each traversed key and index is traced through the context, so that
+    /// errors are reported at the location of the pointer.
+    pub(crate) fn decode_at<O>(
+        self,
+        pointer: &str,
+        decode: impl FnOnce(Self) -> Result<O, C::Error>,
+    ) -> Result<O, C::Error> {
+        if pointer.is_empty() {
+            return decode(self);
+        }
+
+        let Some(rest) = pointer.strip_prefix('/') else {
+            return Err(self.cx.message(format_args!(
+                "JSON pointer `{pointer}` must be empty or start with `/`"
+            )));
+        };
+
+        self.decode_segments(rest, decode)
+    }
+
+    fn decode_segments<O>(
+        mut self,
+        pointer: &str,
+        decode: impl FnOnce(Self) -> Result<O, C::Error>,
+    ) -> Result<O, C::Error> {
+        let cx = self.cx;
+
+        let (head, tail) = match pointer.split_once('/') {
+            Some((head, tail)) => (head, Some(tail)),
+            None => (pointer, None),
+        };
+
+        let Some(segment) = Segment::new(head) else {
+            return Err(cx.message(format_args!(
+                "Invalid escape sequence in JSON pointer segment `{head}`"
+            )));
+        };
+
+        let start = cx.mark();
+
+        match self.parser.lex(cx) {
+            Token::OpenBrace => {
+                self.parser.skip(cx, 1)?;
+                self.seek_key(segment)?;
+
+                cx.enter_map_key(segment);
+
+                let out = match tail {
+                    Some(tail) => self.decode_segments(tail, decode)?,
+                    None => decode(self)?,
+                };
+
+                cx.leave_map_key();
+                Ok(out)
+            }
+            Token::OpenBracket => {
+                let Some(index) = segment.index() else {
+                    return Err(cx.message_at(
+                        &start,
+                        format_args!("Expected array index in JSON pointer, found `{segment}`"),
+                    ));
+                };
+
+                self.parser.skip(cx, 1)?;
+                self.seek_index(index)?;
+
+                cx.enter_sequence_index(index);
+
+                let out = match tail {
+                    Some(tail) => self.decode_segments(tail, decode)?,
+                    None => decode(self)?,
+                };
+
+                cx.leave_sequence_index();
+                Ok(out)
+            }
+            actual => Err(cx.message_at(
+                &start,
+                format_args!("Cannot look up `{segment}` in {actual}"),
+            )),
+        }
+    }
+
+    /// Advance the parser to the value of the given key in the current
+    /// object, skipping over every other entry.
+    fn seek_key(&mut self, segment: Segment<'_>) -> Result<(), C::Error> {
+        let cx = self.cx;
+
+        // Only used for unquoted keys in the relaxed grammar, which are always
+        // borrowed from the input so this never allocates.
+        let mut scratch = Vec::new_in(cx.alloc());
+        let mut first = true;
+
+        loop {
+            let start = cx.mark();
+            let mut actual = self.parser.lex_key(cx);
+
+            if matches!(actual, Token::CloseBrace) {
+                return Err(cx.message_at(&start, format_args!("Missing key `{segment}`")));
+            }
+
+            if !first {
+                if !matches!(actual, Token::Comma) {
+                    return Err(cx.message_at(
+                        &start,
+                        format_args!("Expected comma `,` or closing brace `}}`, was {actual}"),
+                    ));
+                }
+
+                self.parser.skip(cx, 1)?;
+                actual = self.parser.lex_key(cx);
+            }
+
+            if !matches!(actual, Token::String) {
+                return Err(cx.message_at(
+                    &start,
+                    format_args!("Expected key, or closing brace `}}` but found {actual}"),
+                ));
+            }
+
+            first = false;
+
+            let found = match self.parser.peek() {
+                Some(quote @ (b'"' | b'\'')) => {
+                    let found = match self.parser.remaining() {
+                        [_, raw @ ..] => segment.matches_quoted(raw, quote),
+                        [] => false,
+                    };
+
+                    self.parser.skip_string(cx)?;
+                    found
+                }
+                _ => segment.matches(self.parser.parse_key(cx, true, &mut scratch)?.as_str()),
+            };
+
+            let actual = self.parser.lex(cx);
+
+            if !matches!(actual, Token::Colon) {
+                return Err(cx.message(format_args!("Expected colon `:`, was {actual}")));
+            }
+
+            self.parser.skip(cx, 1)?;
+
+            if found {
+                return Ok(());
+            }
+
+            JsonDecoder::<_, _, M>::new(cx, self.parser.borrow_mut(), self.config).skip_any()?;
+        }
+    }
+
+    /// Advance the parser to the element at the given index in the current
+    /// array, skipping over every preceding element.
+    fn seek_index(&mut self, index: usize) -> Result<(), C::Error> {
+        let cx = self.cx;
+
+        for n in 0..=index {
+            let start = cx.mark();
+            let actual = self.parser.lex(cx);
+
+            if matches!(actual, Token::CloseBracket) {
+                return Err(cx.message_at(
+                    &start,
+                    format_args!("Index {index} is out of bounds for array of length {n}"),
+                ));
+            }
+
+            if n > 0 {
+                if !matches!(actual, Token::Comma) {
+                    return Err(cx.message_at(
+                        &start,
+                        format_args!("Expected comma `,` or closing bracket `]`, was {actual}"),
+                    ));
+                }
+
+                self.parser.skip(cx, 1)?;
+            }
+
+            if n < index {
+                JsonDecoder::<_, _, M>::new(cx, self.parser.borrow_mut(), self.config)
+                    .skip_any()?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -5,7 +5,7 @@ use crate::Context;
 use crate::de::{Decoder, SequenceDecoder, SizeHint};
 use crate::json::parser::{Parser, Token};
 
-use super::JsonDecoder;
+use super::{Config, JsonDecoder};
 
 #[must_use = "Must call skip_sequence_remaining"]
 pub(crate) struct JsonSequenceDecoder<P, C, M> {
@@ -13,6 +13,7 @@ pub(crate) struct JsonSequenceDecoder<P, C, M> {
     len: Option<usize>,
     first: bool,
     parser: P,
+    config: Config,
     finalized: bool,
     _marker: PhantomData<M>,
 }
@@ -24,7 +25,12 @@ where
     M: 'static,
 {
     #[inline]
-    pub(super) fn new(cx: C, len: Option<usize>, mut parser: P) -> Result<Self, C::Error> {
+    pub(super) fn new(
+        cx: C,
+        len: Option<usize>,
+        mut parser: P,
+        config: Config,
+    ) -> Result<Self, C::Error> {
         let actual = parser.lex(cx);
 
         if !matches!(actual, Token::OpenBracket) {
@@ -38,6 +44,7 @@ where
             len,
             first: true,
             parser,
+            config,
             finalized: false,
             _marker: PhantomData,
         })
@@ -124,7 +131,11 @@ where
             return Ok(None);
         }
 
-        Ok(Some(JsonDecoder::new(self.cx, self.parser.borrow_mut())))
+        Ok(Some(JsonDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.config,
+        )))
     }
 
     #[inline]
@@ -133,6 +144,10 @@ where
             return Err(self.cx.message(format_args!("Encountered short array")));
         }
 
-        Ok(JsonDecoder::new(self.cx, self.parser.borrow_mut()))
+        Ok(JsonDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.config,
+        ))
     }
 }
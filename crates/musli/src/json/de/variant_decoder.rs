@@ -4,11 +4,12 @@ use crate::Context;
 use crate::de::VariantDecoder;
 use crate::json::parser::{Parser, Token};
 
-use super::{JsonDecoder, JsonKeyDecoder};
+use super::{Config, JsonDecoder, JsonKeyDecoder};
 
 pub(crate) struct JsonVariantDecoder<P, C, M> {
     cx: C,
     parser: P,
+    config: Config,
     _marker: PhantomData<M>,
 }
 
@@ -19,7 +20,7 @@ where
     M: 'static,
 {
     #[inline]
-    pub(super) fn new(cx: C, mut parser: P) -> Result<Self, C::Error> {
+    pub(super) fn new(cx: C, mut parser: P, config: Config) -> Result<Self, C::Error> {
         let actual = parser.lex(cx);
 
         if !matches!(actual, Token::OpenBrace) {
@@ -30,6 +31,7 @@ where
         Ok(Self {
             cx,
             parser,
+            config,
             _marker: PhantomData,
         })
     }
@@ -75,7 +77,11 @@ where
 
     #[inline]
     fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, C::Error> {
-        Ok(JsonKeyDecoder::new(self.cx, self.parser.borrow_mut()))
+        Ok(JsonKeyDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.config,
+        ))
     }
 
     #[inline]
@@ -89,6 +95,10 @@ where
         }
 
         self.parser.skip(self.cx, 1)?;
-        Ok(JsonDecoder::new(self.cx, self.parser.borrow_mut()))
+        Ok(JsonDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.config,
+        ))
     }
 }
@@ -2,45 +2,73 @@ use core::marker::PhantomData;
 use core::mem::take;
 
 use crate::en::SequenceEncoder;
+use crate::writer::Sink;
 use crate::{Context, Writer};
 
-use super::JsonEncoder;
+use super::{Format, JsonEncoder};
 
 /// Encoder for a JSON array.
-pub(crate) struct JsonArrayEncoder<W, C, M> {
+pub(crate) struct JsonArrayEncoder<'a, W, C, M>
+where
+    C: Context,
+{
     cx: C,
     first: bool,
-    end: &'static [u8],
-    writer: W,
+    format: Format,
+    /// The format of the variant object the array is the content of, if any.
+    variant: Option<Format>,
+    writer: Sink<'a, W, C::Allocator>,
     _marker: PhantomData<M>,
 }
 
-impl<W, C, M> JsonArrayEncoder<W, C, M>
+impl<'a, W, C, M> JsonArrayEncoder<'a, W, C, M>
 where
     W: Writer,
     C: Context,
     M: 'static,
 {
     #[inline]
-    pub(super) fn new(cx: C, writer: W) -> Result<Self, C::Error> {
-        Self::with_end(cx, writer, b"]")
+    pub(super) fn new(
+        cx: C,
+        writer: Sink<'a, W, C::Allocator>,
+        format: Format,
+    ) -> Result<Self, C::Error> {
+        Self::start(cx, writer, format, None)
+    }
+
+    /// Construct an array encoder for the content of a variant, which closes
+    /// the variant object once finished.
+    #[inline]
+    pub(super) fn with_variant(
+        cx: C,
+        writer: Sink<'a, W, C::Allocator>,
+        format: Format,
+        variant: Format,
+    ) -> Result<Self, C::Error> {
+        Self::start(cx, writer, format, Some(variant))
     }
 
     #[inline]
-    pub(super) fn with_end(cx: C, mut writer: W, end: &'static [u8]) -> Result<Self, C::Error> {
+    fn start(
+        cx: C,
+        mut writer: Sink<'a, W, C::Allocator>,
+        format: Format,
+        variant: Option<Format>,
+    ) -> Result<Self, C::Error> {
         writer.write_byte(cx, b'[')?;
 
         Ok(Self {
             cx,
             first: true,
-            end,
+            format,
+            variant,
             writer,
             _marker: PhantomData,
         })
     }
 }
 
-impl<W, C, M> SequenceEncoder for JsonArrayEncoder<W, C, M>
+impl<W, C, M> SequenceEncoder for JsonArrayEncoder<'_, W, C, M>
 where
     W: Writer,
     C: Context,
@@ -50,7 +78,7 @@ where
     type Error = C::Error;
     type Mode = M;
     type EncodeNext<'this>
-        = JsonEncoder<W::Mut<'this>, C, M>
+        = JsonEncoder<'this, W, C, M>
     where
         Self: 'this;
 
@@ -65,11 +93,28 @@ where
             self.writer.write_byte(self.cx, b',')?;
         }
 
-        Ok(JsonEncoder::new(self.cx, self.writer.borrow_mut()))
+        let format = self.format.nested();
+        format.newline(self.cx, self.writer.borrow_mut())?;
+        Ok(JsonEncoder::with_sink(
+            self.cx,
+            self.writer.borrow_mut(),
+            format,
+        ))
     }
 
     #[inline]
     fn finish_sequence(mut self) -> Result<(), C::Error> {
-        self.writer.write_bytes(self.cx, self.end)
+        if !self.first {
+            self.format.newline(self.cx, self.writer.borrow_mut())?;
+        }
+
+        self.writer.write_byte(self.cx, b']')?;
+
+        if let Some(variant) = self.variant {
+            variant.newline(self.cx, self.writer.borrow_mut())?;
+            self.writer.write_byte(self.cx, b'}')?;
+        }
+
+        Ok(())
     }
 }
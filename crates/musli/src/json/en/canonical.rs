@@ -0,0 +1,387 @@
+//! Canonical JSON output as specified by [RFC 8785].
+//!
+//! Objects buffer their entries through the context while they are being
+//! encoded, and write them out sorted by key once they are finished. This
+//! module provides how keys are compared and how numbers are formatted, as
+//! well as the rewriting of raw values.
+//!
+//! # Deviations
+//!
+//! RFC 8785 requires every number to be serialized as an IEEE 754 double.
+//! Integers are instead written out in full, so that values which can't be
+//! represented exactly as an `f64` such as `u64::MAX` don't lose precision.
+//! This only affects integers outside of the range `-2^53..=2^53`, which are
+//! written identically by both.
+//!
+//! [RFC 8785]: https://www.rfc-editor.org/rfc/rfc8785
+
+use core::cmp::Ordering;
+use core::iter;
+use core::str;
+
+use crate::alloc::Vec;
+use crate::en::{Encoder, EntriesEncoder, SequenceEncoder};
+use crate::json::parser::{Parser, SliceParser, Token, integer};
+use crate::{Context, Writer};
+
+use super::{JsonArrayEncoder, JsonEncoder, JsonObjectEncoder};
+
+/// Encode a raw value by rewriting it in its canonical form.
+pub(super) fn encode_raw<W, C, M>(
+    encoder: JsonEncoder<'_, W, C, M>,
+    raw: &str,
+) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    let mut parser = SliceParser::new(raw.as_bytes());
+    write_value(&mut parser, encoder)
+}
+
+/// Rewrite the next value in the parser.
+fn write_value<W, C, M>(
+    parser: &mut SliceParser<'_>,
+    encoder: JsonEncoder<'_, W, C, M>,
+) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    let cx = encoder.cx;
+    let start = cx.mark();
+
+    match parser.lex(cx) {
+        Token::OpenBrace => write_object(parser, encoder),
+        Token::OpenBracket => write_array(parser, encoder),
+        Token::String => {
+            let mut scratch = Vec::new_in(cx.alloc());
+            let string = parser.parse_string(cx, true, &mut scratch)?;
+            encoder.encode_string(string.as_str())
+        }
+        Token::Number => {
+            let before = parser.remaining();
+            integer::skip_number(cx, &mut *parser)?;
+            let number = &before[..before.len() - parser.remaining().len()];
+            encoder.encode_number(str::from_utf8(number).unwrap_or_default())
+        }
+        Token::Null => {
+            parser.parse_exact(cx, "null")?;
+            encoder.encode_empty()
+        }
+        Token::True => {
+            parser.parse_exact(cx, "true")?;
+            encoder.encode_bool(true)
+        }
+        Token::False => {
+            parser.parse_exact(cx, "false")?;
+            encoder.encode_bool(false)
+        }
+        actual => Err(cx.message_at(&start, format_args!("Expected value, found {actual}"))),
+    }
+}
+
+fn write_array<W, C, M>(
+    parser: &mut SliceParser<'_>,
+    encoder: JsonEncoder<'_, W, C, M>,
+) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    let cx = encoder.cx;
+    parser.skip(cx, 1)?;
+
+    let mut array = JsonArrayEncoder::<_, _, M>::new(cx, encoder.writer, encoder.format)?;
+
+    if matches!(parser.lex(cx), Token::CloseBracket) {
+        parser.skip(cx, 1)?;
+        return array.finish_sequence();
+    }
+
+    loop {
+        write_value(parser, array.encode_next()?)?;
+
+        let start = cx.mark();
+
+        match parser.lex(cx) {
+            Token::Comma => {
+                parser.skip(cx, 1)?;
+            }
+            Token::CloseBracket => {
+                parser.skip(cx, 1)?;
+                break;
+            }
+            actual => {
+                return Err(cx.message_at(
+                    &start,
+                    format_args!("Expected comma `,` or closing bracket `]`, was {actual}"),
+                ));
+            }
+        }
+    }
+
+    array.finish_sequence()
+}
+
+fn write_object<W, C, M>(
+    parser: &mut SliceParser<'_>,
+    encoder: JsonEncoder<'_, W, C, M>,
+) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    let cx = encoder.cx;
+    parser.skip(cx, 1)?;
+
+    let mut object = JsonObjectEncoder::<_, _, M>::new(cx, encoder.writer, encoder.format)?;
+
+    if matches!(parser.lex(cx), Token::CloseBrace) {
+        parser.skip(cx, 1)?;
+        return object.finish_entries();
+    }
+
+    let mut scratch = Vec::new_in(cx.alloc());
+
+    loop {
+        let key = parser.parse_string(cx, true, &mut scratch)?;
+        object.encode_entry_key()?.encode_string(key.as_str())?;
+
+        let start = cx.mark();
+        let actual = parser.lex(cx);
+
+        if !matches!(actual, Token::Colon) {
+            return Err(cx.message_at(&start, format_args!("Expected colon `:`, was {actual}")));
+        }
+
+        parser.skip(cx, 1)?;
+        write_value(parser, object.encode_entry_value()?)?;
+
+        let start = cx.mark();
+
+        match parser.lex(cx) {
+            Token::Comma => {
+                parser.skip(cx, 1)?;
+            }
+            Token::CloseBrace => {
+                parser.skip(cx, 1)?;
+                break;
+            }
+            actual => {
+                return Err(cx.message_at(
+                    &start,
+                    format_args!("Expected comma `,` or closing brace `}}`, was {actual}"),
+                ));
+            }
+        }
+    }
+
+    object.finish_entries()
+}
+
+/// Compare encoded object keys by the UTF-16 code units of their contents.
+///
+/// UTF-8 sorts by code point, which only differs from UTF-16 in that the code
+/// points `U+E000` to `U+FFFF` sort after the supplementary planes since those
+/// are encoded as surrogates. These are exactly the characters whose UTF-8
+/// lead byte is `0xEE` or `0xEF`, so moving those bytes above the lead bytes of
+/// supplementary characters allows keys to be compared byte by byte.
+pub(super) fn compare_keys(a: &[u8], b: &[u8]) -> Ordering {
+    let order = |b: u8| match b {
+        0xee | 0xef => b + 0x10,
+        b => b,
+    };
+
+    unescape(a).map(order).cmp(unescape(b).map(order))
+}
+
+/// Iterate over the unescaped bytes of a string as written by
+/// [`encode_string`].
+///
+/// [`encode_string`]: super::encode_string
+fn unescape(string: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    let string = match string {
+        [b'"', string @ .., b'"'] => string,
+        string => string,
+    };
+
+    let mut it = string.iter().copied();
+
+    iter::from_fn(move || {
+        let b = it.next()?;
+
+        if b != b'\\' {
+            return Some(b);
+        }
+
+        let b = match it.next()? {
+            b'b' => 0x08,
+            b't' => b'\t',
+            b'n' => b'\n',
+            b'f' => 0x0c,
+            b'r' => b'\r',
+            // Only control characters are escaped as `\u00xx`.
+            b'u' => it.by_ref().take(4).fold(0, |n, b| {
+                n << 4 | (b as char).to_digit(16).unwrap_or_default() as u8
+            }),
+            b => b,
+        };
+
+        Some(b)
+    })
+}
+
+/// Write a number in its canonical form.
+///
+/// Integers are written as-is, so that they retain their full precision.
+/// Numbers with a fraction or an exponent are normalized to the shortest
+/// representation of the closest `f64`, formatted like ECMAScript does.
+pub(super) fn write_number<W, C>(cx: C, mut writer: W, number: &str) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    if !number.bytes().any(|b| matches!(b, b'.' | b'e' | b'E')) {
+        if number == "-0" {
+            return writer.write_byte(cx, b'0');
+        }
+
+        return writer.write_bytes(cx, number.as_bytes());
+    }
+
+    match number.parse::<f64>() {
+        Ok(value) if value.is_finite() => write_f64(cx, writer, value),
+        _ => Err(cx.message(format_args!(
+            "Number `{number}` is out of range for canonical JSON"
+        ))),
+    }
+}
+
+/// Write an `f32` using its shortest representation, so that for example
+/// `0.1f32` is written as `0.1` rather than `0.10000000149011612`.
+pub(super) fn write_f32<W, C>(cx: C, writer: W, value: f32) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    if !value.is_finite() {
+        return Err(non_finite(cx));
+    }
+
+    let mut buffer = ryu::Buffer::new();
+    let value = buffer
+        .format_finite(value)
+        .parse::<f64>()
+        .unwrap_or_default();
+    write_f64(cx, writer, value)
+}
+
+/// Write an `f64` using the ECMAScript `Number::toString` algorithm.
+pub(super) fn write_f64<W, C>(cx: C, mut writer: W, value: f64) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    if !value.is_finite() {
+        return Err(non_finite(cx));
+    }
+
+    if value == 0.0 {
+        return writer.write_byte(cx, b'0');
+    }
+
+    let mut buffer = ryu::Buffer::new();
+    let repr = buffer.format_finite(value);
+
+    let (negative, repr) = match repr.strip_prefix('-') {
+        Some(repr) => (true, repr),
+        None => (false, repr),
+    };
+
+    let (mantissa, exponent) = match repr.split_once('e') {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().unwrap_or_default()),
+        None => (repr, 0),
+    };
+
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    // Collect the significant digits, where `point` is the position of the
+    // decimal point relative to the first digit.
+    let mut digits = [0u8; 32];
+    let mut len = 0;
+
+    for &b in whole.as_bytes().iter().chain(fraction.as_bytes()) {
+        if let Some(d) = digits.get_mut(len) {
+            *d = b;
+            len += 1;
+        }
+    }
+
+    let mut point = whole.len() as i32 + exponent;
+    let mut digits = &digits[..len];
+
+    while let [b'0', rest @ ..] = digits {
+        digits = rest;
+        point -= 1;
+    }
+
+    while let [rest @ .., b'0'] = digits {
+        digits = rest;
+    }
+
+    if negative {
+        writer.write_byte(cx, b'-')?;
+    }
+
+    let k = digits.len() as i32;
+
+    if k <= point && point <= 21 {
+        writer.write_bytes(cx, digits)?;
+
+        for _ in k..point {
+            writer.write_byte(cx, b'0')?;
+        }
+    } else if 0 < point && point <= 21 {
+        let (head, tail) = digits.split_at(point as usize);
+        writer.write_bytes(cx, head)?;
+        writer.write_byte(cx, b'.')?;
+        writer.write_bytes(cx, tail)?;
+    } else if -6 < point && point <= 0 {
+        writer.write_bytes(cx, b"0.")?;
+
+        for _ in point..0 {
+            writer.write_byte(cx, b'0')?;
+        }
+
+        writer.write_bytes(cx, digits)?;
+    } else {
+        let (head, tail) = digits.split_at(1);
+        writer.write_bytes(cx, head)?;
+
+        if !tail.is_empty() {
+            writer.write_byte(cx, b'.')?;
+            writer.write_bytes(cx, tail)?;
+        }
+
+        let exponent = point - 1;
+        writer.write_bytes(cx, if exponent < 0 { b"e-" } else { b"e+" })?;
+
+        let mut buffer = itoa::Buffer::new();
+        writer.write_bytes(cx, buffer.format(exponent.unsigned_abs()).as_bytes())?;
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn non_finite<C>(cx: C) -> C::Error
+where
+    C: Context,
+{
+    cx.message("Non-finite numbers can't be represented in canonical JSON")
+}
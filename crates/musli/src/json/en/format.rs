@@ -0,0 +1,132 @@
+use crate::json::BytesFormat;
+use crate::{Context, Writer};
+
+/// How JSON is formatted by the encoder.
+///
+/// This is passed by value to every nested encoder, where `level` is the level
+/// of nesting of the value currently being encoded.
+#[derive(Clone, Copy)]
+pub(crate) struct Format {
+    /// The indentation used for each level of nesting, or `None` if output is
+    /// compact.
+    indent: Option<&'static str>,
+    /// The newline inserted before each element when pretty printing.
+    newline: &'static str,
+    /// Whether output is canonical, in which case it is never pretty printed.
+    canonical: bool,
+    /// How bytes are represented.
+    bytes: BytesFormat,
+    level: usize,
+}
+
+impl Format {
+    /// Construct a compact format.
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            indent: None,
+            newline: "\n",
+            canonical: false,
+            bytes: BytesFormat::Array,
+            level: 0,
+        }
+    }
+
+    /// Pretty print using the given indentation.
+    #[inline]
+    pub(crate) const fn with_indent(self, indent: &'static str) -> Self {
+        Self {
+            indent: Some(indent),
+            ..self
+        }
+    }
+
+    /// Use the given newline when pretty printing.
+    #[inline]
+    pub(crate) const fn with_newline(self, newline: &'static str) -> Self {
+        Self { newline, ..self }
+    }
+
+    /// Produce canonical output.
+    #[inline]
+    pub(crate) const fn with_canonical(self) -> Self {
+        Self {
+            canonical: true,
+            ..self
+        }
+    }
+
+    /// Represent bytes using the given format.
+    #[inline]
+    pub(crate) const fn with_bytes(self, bytes: BytesFormat) -> Self {
+        Self { bytes, ..self }
+    }
+
+    /// How bytes are represented.
+    #[inline]
+    pub(crate) const fn bytes(&self) -> BytesFormat {
+        self.bytes
+    }
+
+    /// Test if output is canonical.
+    #[inline]
+    pub(crate) const fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Test if output is pretty printed.
+    #[inline]
+    pub(crate) const fn is_pretty(&self) -> bool {
+        self.indent().is_some()
+    }
+
+    /// The indentation in use, which is always `None` for canonical output.
+    #[inline]
+    const fn indent(&self) -> Option<&'static str> {
+        if self.canonical { None } else { self.indent }
+    }
+
+    /// The format of a value nested one level deeper.
+    #[inline]
+    pub(crate) const fn nested(self) -> Self {
+        Self {
+            level: self.level + 1,
+            ..self
+        }
+    }
+
+    /// Write a newline followed by the indentation of the current level, if
+    /// pretty printing is enabled.
+    #[inline]
+    pub(crate) fn newline<W, C>(&self, cx: C, mut writer: W) -> Result<(), C::Error>
+    where
+        W: Writer,
+        C: Context,
+    {
+        let Some(indent) = self.indent() else {
+            return Ok(());
+        };
+
+        writer.write_bytes(cx, self.newline.as_bytes())?;
+
+        for _ in 0..self.level {
+            writer.write_bytes(cx, indent.as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the separator between an object key and its value.
+    #[inline]
+    pub(crate) fn colon<W, C>(&self, cx: C, mut writer: W) -> Result<(), C::Error>
+    where
+        W: Writer,
+        C: Context,
+    {
+        if self.is_pretty() {
+            writer.write_bytes(cx, b": ")
+        } else {
+            writer.write_byte(cx, b':')
+        }
+    }
+}
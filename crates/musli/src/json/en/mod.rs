@@ -1,6 +1,11 @@
 mod array_encoder;
 use self::array_encoder::JsonArrayEncoder;
 
+mod canonical;
+
+mod format;
+pub(crate) use self::format::Format;
+
 mod object_encoder;
 use self::object_encoder::JsonObjectEncoder;
 
@@ -16,31 +21,54 @@ use self::variant_encoder::JsonVariantEncoder;
 use core::fmt;
 use core::marker::PhantomData;
 
-use crate::en::{Encode, Encoder, SequenceEncoder};
+use crate::en::{Encode, Encoder, MapEncoder, SequenceEncoder};
 use crate::hint::{MapHint, SequenceHint};
+use crate::json::parser::integer;
+use crate::writer::Sink;
 use crate::{Context, Writer};
 
+use super::bytes::{self, BytesFormat};
+
 /// A JSON encoder for Müsli.
-pub(crate) struct JsonEncoder<W, C, M> {
+pub(crate) struct JsonEncoder<'a, W, C, M>
+where
+    C: Context,
+{
     cx: C,
-    writer: W,
+    writer: Sink<'a, W, C::Allocator>,
+    format: Format,
     _marker: PhantomData<M>,
 }
 
-impl<W, C, M> JsonEncoder<W, C, M> {
-    /// Construct a new fixed width message encoder.
+impl<W, C, M> JsonEncoder<'_, W, C, M>
+where
+    C: Context,
+{
+    /// Construct a new encoder which formats its output using the given
+    /// [`Format`].
+    #[inline]
+    pub(crate) fn new(cx: C, writer: W, format: Format) -> Self {
+        Self::with_sink(cx, Sink::Owned(writer), format)
+    }
+}
+
+impl<'a, W, C, M> JsonEncoder<'a, W, C, M>
+where
+    C: Context,
+{
     #[inline]
-    pub(crate) fn new(cx: C, writer: W) -> Self {
+    fn with_sink(cx: C, writer: Sink<'a, W, C::Allocator>, format: Format) -> Self {
         Self {
             cx,
             writer,
+            format,
             _marker: PhantomData,
         }
     }
 }
 
 #[crate::trait_defaults(crate)]
-impl<W, C, M> Encoder for JsonEncoder<W, C, M>
+impl<'a, W, C, M> Encoder for JsonEncoder<'a, W, C, M>
 where
     W: Writer,
     C: Context,
@@ -49,14 +77,14 @@ where
     type Cx = C;
     type Error = C::Error;
     type Mode = M;
-    type EncodePack = JsonArrayEncoder<W, C, M>;
+    type EncodePack = JsonArrayEncoder<'a, W, C, M>;
     type EncodeSome = Self;
-    type EncodeSequence = JsonArrayEncoder<W, C, M>;
-    type EncodeMap = JsonObjectEncoder<W, C, M>;
-    type EncodeMapEntries = JsonObjectEncoder<W, C, M>;
-    type EncodeVariant = JsonVariantEncoder<W, C, M>;
-    type EncodeSequenceVariant = JsonArrayEncoder<W, C, M>;
-    type EncodeMapVariant = JsonObjectEncoder<W, C, M>;
+    type EncodeSequence = JsonArrayEncoder<'a, W, C, M>;
+    type EncodeMap = JsonObjectEncoder<'a, W, C, M>;
+    type EncodeMapEntries = JsonObjectEncoder<'a, W, C, M>;
+    type EncodeVariant = JsonVariantEncoder<'a, W, C, M>;
+    type EncodeSequenceVariant = JsonArrayEncoder<'a, W, C, M>;
+    type EncodeMapVariant = JsonObjectEncoder<'a, W, C, M>;
 
     #[inline]
     fn cx(&self) -> Self::Cx {
@@ -182,6 +210,10 @@ where
 
     #[inline]
     fn encode_f32(mut self, value: f32) -> Result<(), Self::Error> {
+        if self.format.is_canonical() {
+            return canonical::write_f32(self.cx, self.writer, value);
+        }
+
         let mut buffer = ryu::Buffer::new();
         self.writer
             .write_bytes(self.cx, buffer.format(value).as_bytes())
@@ -189,11 +221,28 @@ where
 
     #[inline]
     fn encode_f64(mut self, value: f64) -> Result<(), Self::Error> {
+        if self.format.is_canonical() {
+            return canonical::write_f64(self.cx, self.writer, value);
+        }
+
         let mut buffer = ryu::Buffer::new();
         self.writer
             .write_bytes(self.cx, buffer.format(value).as_bytes())
     }
 
+    #[inline]
+    fn encode_number(mut self, number: &str) -> Result<(), Self::Error> {
+        if !integer::is_number(number.as_bytes()) {
+            return Err(self.cx.message(format_args!("Invalid number `{number}`")));
+        }
+
+        if self.format.is_canonical() {
+            return canonical::write_number(self.cx, self.writer, number);
+        }
+
+        self.writer.write_bytes(self.cx, number.as_bytes())
+    }
+
     #[inline]
     fn encode_array<const N: usize>(self, bytes: &[u8; N]) -> Result<(), Self::Error> {
         self.encode_bytes(bytes)
@@ -201,6 +250,14 @@ where
 
     #[inline]
     fn encode_bytes(mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        if !matches!(self.format.bytes(), BytesFormat::Array) {
+            return bytes::encode(self.cx, self.writer, self.format.bytes(), [bytes]);
+        }
+
+        if self.format.is_pretty() {
+            return self.encode_sequence_iter(bytes);
+        }
+
         let mut buf = itoa::Buffer::new();
         let mut it = bytes.iter();
         let last = it.next_back();
@@ -227,7 +284,11 @@ where
     where
         I: IntoIterator<Item: AsRef<[u8]>>,
     {
-        let mut seq = JsonArrayEncoder::<_, _, M>::new(self.cx, self.writer)?;
+        if !matches!(self.format.bytes(), BytesFormat::Array) {
+            return bytes::encode(self.cx, self.writer, self.format.bytes(), vectors);
+        }
+
+        let mut seq = JsonArrayEncoder::<_, _, M>::new(self.cx, self.writer, self.format)?;
 
         for bb in vectors {
             for &b in bb.as_ref() {
@@ -243,6 +304,15 @@ where
         encode_string(self.cx, self.writer.borrow_mut(), string.as_bytes())
     }
 
+    #[inline]
+    fn encode_raw(mut self, raw: &str) -> Result<(), Self::Error> {
+        if self.format.is_canonical() {
+            return canonical::encode_raw(self, raw);
+        }
+
+        self.writer.write_bytes(self.cx, raw.as_bytes())
+    }
+
     #[inline]
     fn encode_some(self) -> Result<Self::EncodeSome, Self::Error> {
         Ok(self)
@@ -255,27 +325,60 @@ where
 
     #[inline]
     fn encode_pack(self) -> Result<Self::EncodePack, Self::Error> {
-        JsonArrayEncoder::new(self.cx, self.writer)
+        JsonArrayEncoder::new(self.cx, self.writer, self.format)
     }
 
     #[inline]
     fn encode_sequence(self, _: impl SequenceHint) -> Result<Self::EncodeSequence, Self::Error> {
-        JsonArrayEncoder::new(self.cx, self.writer)
+        JsonArrayEncoder::new(self.cx, self.writer, self.format)
+    }
+
+    #[inline]
+    fn encode_sequence_iter<I>(self, iter: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item: Encode<Self::Mode>>,
+    {
+        let cx = self.cx;
+        let mut seq = JsonArrayEncoder::new(self.cx, self.writer, self.format)?;
+
+        for (index, item) in iter.into_iter().enumerate() {
+            cx.enter_sequence_index(index);
+            seq.push(item)?;
+            cx.leave_sequence_index();
+        }
+
+        seq.finish_sequence()
     }
 
     #[inline]
     fn encode_map(self, _: impl MapHint) -> Result<Self::EncodeMap, Self::Error> {
-        JsonObjectEncoder::new(self.cx, self.writer)
+        JsonObjectEncoder::new(self.cx, self.writer, self.format)
+    }
+
+    #[inline]
+    fn encode_map_iter<I, K, V>(self, iter: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Encode<Self::Mode>,
+        V: Encode<Self::Mode>,
+    {
+        let mut map = JsonObjectEncoder::new(self.cx, self.writer, self.format)?;
+
+        for (key, value) in iter {
+            map.insert_entry(key, value)?;
+        }
+
+        map.finish_map()
     }
 
     #[inline]
     fn encode_map_entries(self, _: impl MapHint) -> Result<Self::EncodeMapEntries, Self::Error> {
-        JsonObjectEncoder::new(self.cx, self.writer)
+        JsonObjectEncoder::new(self.cx, self.writer, self.format)
     }
 
     #[inline]
     fn encode_variant(self) -> Result<Self::EncodeVariant, Self::Error> {
-        JsonVariantEncoder::new(self.cx, self.writer)
+        JsonVariantEncoder::new(self.cx, self.writer, self.format)
     }
 
     #[inline]
@@ -287,10 +390,12 @@ where
     where
         T: ?Sized + Encode<Self::Mode>,
     {
+        let format = self.format.nested();
         self.writer.write_byte(self.cx, b'{')?;
+        format.newline(self.cx, self.writer.borrow_mut())?;
         JsonObjectKeyEncoder::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
-        self.writer.write_byte(self.cx, b':')?;
-        JsonArrayEncoder::with_end(self.cx, self.writer, b"]}")
+        format.colon(self.cx, self.writer.borrow_mut())?;
+        JsonArrayEncoder::with_variant(self.cx, self.writer, format, self.format)
     }
 
     #[inline]
@@ -302,10 +407,12 @@ where
     where
         T: ?Sized + Encode<Self::Mode>,
     {
+        let format = self.format.nested();
         self.writer.write_byte(self.cx, b'{')?;
+        format.newline(self.cx, self.writer.borrow_mut())?;
         JsonObjectKeyEncoder::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
-        self.writer.write_byte(self.cx, b':')?;
-        JsonObjectEncoder::with_end(self.cx, self.writer, b"}}")
+        format.colon(self.cx, self.writer.borrow_mut())?;
+        JsonObjectEncoder::with_variant(self.cx, self.writer, format, self.format)
     }
 }
 
@@ -1,45 +1,174 @@
 use core::marker::PhantomData;
 
+use crate::alloc::Vec;
 use crate::en::{EntriesEncoder, MapEncoder};
+use crate::writer::Sink;
 use crate::{Context, Writer};
 
-use super::{JsonEncoder, JsonObjectKeyEncoder, JsonObjectPairEncoder};
+use super::{Format, JsonEncoder, JsonObjectKeyEncoder, JsonObjectPairEncoder, canonical};
+
+/// The location of a buffered object entry.
+struct Entry {
+    start: usize,
+    key_end: usize,
+    end: usize,
+}
 
 /// An object encoder for JSON.
-pub(crate) struct JsonObjectEncoder<W, C, M> {
+///
+/// If canonical output is enabled, entries are buffered and sorted by their
+/// keys once the object is finished.
+pub(crate) struct JsonObjectEncoder<'a, W, C, M>
+where
+    C: Context,
+{
     cx: C,
     len: usize,
-    end: &'static [u8],
-    writer: W,
+    format: Format,
+    /// The format of the variant object the object is the content of, if any.
+    variant: Option<Format>,
+    writer: Sink<'a, W, C::Allocator>,
+    buffer: Vec<u8, C::Allocator>,
+    entries: Vec<Entry, C::Allocator>,
     _marker: PhantomData<M>,
 }
 
-impl<W, C, M> JsonObjectEncoder<W, C, M>
+impl<'a, W, C, M> JsonObjectEncoder<'a, W, C, M>
 where
     W: Writer,
     C: Context,
     M: 'static,
 {
     #[inline]
-    pub(super) fn new(cx: C, writer: W) -> Result<Self, C::Error> {
-        Self::with_end(cx, writer, b"}")
+    pub(super) fn new(
+        cx: C,
+        writer: Sink<'a, W, C::Allocator>,
+        format: Format,
+    ) -> Result<Self, C::Error> {
+        Self::start(cx, writer, format, None)
+    }
+
+    /// Construct an object encoder for the content of a variant, which closes
+    /// the variant object once finished.
+    #[inline]
+    pub(super) fn with_variant(
+        cx: C,
+        writer: Sink<'a, W, C::Allocator>,
+        format: Format,
+        variant: Format,
+    ) -> Result<Self, C::Error> {
+        Self::start(cx, writer, format, Some(variant))
     }
 
     #[inline]
-    pub(super) fn with_end(cx: C, mut writer: W, end: &'static [u8]) -> Result<Self, C::Error> {
+    fn start(
+        cx: C,
+        mut writer: Sink<'a, W, C::Allocator>,
+        format: Format,
+        variant: Option<Format>,
+    ) -> Result<Self, C::Error> {
         writer.write_byte(cx, b'{')?;
 
         Ok(Self {
             cx,
             len: 0,
-            end,
+            format,
+            variant,
             writer,
+            buffer: Vec::new_in(cx.alloc()),
+            entries: Vec::new_in(cx.alloc()),
             _marker: PhantomData,
         })
     }
+
+    /// Start a new entry.
+    #[inline]
+    fn start_entry(&mut self) -> Result<(), C::Error> {
+        self.len += 1;
+
+        if !self.format.is_canonical() {
+            if self.len > 1 {
+                self.writer.write_byte(self.cx, b',')?;
+            }
+
+            return self
+                .format
+                .nested()
+                .newline(self.cx, self.writer.borrow_mut());
+        }
+
+        let start = self.buffer.len();
+
+        self.entries
+            .push(Entry {
+                start,
+                key_end: start,
+                end: start,
+            })
+            .map_err(self.cx.map())
+    }
+
+    /// Mark the end of the key of the current entry.
+    #[inline]
+    fn start_value(&mut self) {
+        if let Some(entry) = self.entries.as_mut_slice().last_mut() {
+            entry.key_end = self.buffer.len();
+        }
+    }
+
+    /// The sink to write the current entry to.
+    #[inline]
+    fn sink(&mut self) -> Sink<'_, W, C::Allocator> {
+        if !self.format.is_canonical() {
+            return self.writer.borrow_mut();
+        }
+
+        Sink::Buffer(&mut self.buffer)
+    }
+
+    #[inline]
+    fn finish(mut self) -> Result<(), C::Error> {
+        if self.format.is_canonical() {
+            let buffer = self.buffer.as_slice();
+            let entries = self.entries.as_mut_slice();
+            let mut end = buffer.len();
+
+            for entry in entries.iter_mut().rev() {
+                entry.end = end;
+                end = entry.start;
+            }
+
+            // Ties between duplicate keys are broken by the order in which
+            // they were encoded, so that the output is fully determined by it.
+            entries.sort_unstable_by(|a, b| {
+                canonical::compare_keys(&buffer[a.start..a.key_end], &buffer[b.start..b.key_end])
+                    .then(a.start.cmp(&b.start))
+            });
+
+            for (index, entry) in entries.iter().enumerate() {
+                if index > 0 {
+                    self.writer.write_byte(self.cx, b',')?;
+                }
+
+                self.writer
+                    .write_bytes(self.cx, &buffer[entry.start..entry.end])?;
+            }
+        } else if self.len > 0 {
+            self.format.newline(self.cx, self.writer.borrow_mut())?;
+        }
+
+        self.writer.write_byte(self.cx, b'}')?;
+
+        if let Some(variant) = self.variant {
+            variant.newline(self.cx, self.writer.borrow_mut())?;
+            self.writer.write_byte(self.cx, b'}')?;
+        }
+
+        Ok(())
+    }
 }
 
-impl<W, C, M> MapEncoder for JsonObjectEncoder<W, C, M>
+impl<W, C, M> MapEncoder for JsonObjectEncoder<'_, W, C, M>
 where
     W: Writer,
     C: Context,
@@ -49,7 +178,7 @@ where
     type Error = C::Error;
     type Mode = M;
     type EncodeEntry<'this>
-        = JsonObjectPairEncoder<W::Mut<'this>, C, M>
+        = JsonObjectPairEncoder<'this, W, C, M>
     where
         Self: 'this;
 
@@ -60,22 +189,40 @@ where
 
     #[inline]
     fn encode_entry(&mut self) -> Result<Self::EncodeEntry<'_>, Self::Error> {
-        self.len += 1;
+        self.start_entry()?;
+
+        let format = self.format.nested();
+
+        if !format.is_canonical() {
+            return Ok(JsonObjectPairEncoder::new(
+                self.cx,
+                format,
+                self.writer.borrow_mut(),
+                None,
+            ));
+        }
+
+        let key_end = self
+            .entries
+            .as_mut_slice()
+            .last_mut()
+            .map(|entry| &mut entry.key_end);
 
         Ok(JsonObjectPairEncoder::new(
             self.cx,
-            self.len == 1,
-            self.writer.borrow_mut(),
+            format,
+            Sink::Buffer(&mut self.buffer),
+            key_end,
         ))
     }
 
     #[inline]
-    fn finish_map(mut self) -> Result<(), Self::Error> {
-        self.writer.write_bytes(self.cx, self.end)
+    fn finish_map(self) -> Result<(), Self::Error> {
+        self.finish()
     }
 }
 
-impl<W, C, M> EntriesEncoder for JsonObjectEncoder<W, C, M>
+impl<W, C, M> EntriesEncoder for JsonObjectEncoder<'_, W, C, M>
 where
     W: Writer,
     C: Context,
@@ -85,11 +232,11 @@ where
     type Error = C::Error;
     type Mode = M;
     type EncodeEntryKey<'this>
-        = JsonObjectKeyEncoder<W::Mut<'this>, C, M>
+        = JsonObjectKeyEncoder<Sink<'this, W, C::Allocator>, C, M>
     where
         Self: 'this;
     type EncodeEntryValue<'this>
-        = JsonEncoder<W::Mut<'this>, C, M>
+        = JsonEncoder<'this, W, C, M>
     where
         Self: 'this;
 
@@ -100,22 +247,23 @@ where
 
     #[inline]
     fn encode_entry_key(&mut self) -> Result<Self::EncodeEntryKey<'_>, Self::Error> {
-        if self.len > 0 {
-            self.writer.write_byte(self.cx, b',')?;
-        }
-
-        self.len += 1;
-        Ok(JsonObjectKeyEncoder::new(self.cx, self.writer.borrow_mut()))
+        self.start_entry()?;
+        let cx = self.cx;
+        Ok(JsonObjectKeyEncoder::new(cx, self.sink()))
     }
 
     #[inline]
     fn encode_entry_value(&mut self) -> Result<Self::EncodeEntryValue<'_>, Self::Error> {
-        self.writer.write_byte(self.cx, b':')?;
-        Ok(JsonEncoder::new(self.cx, self.writer.borrow_mut()))
+        let cx = self.cx;
+        let format = self.format.nested();
+        self.start_value();
+        let mut writer = self.sink();
+        format.colon(cx, writer.borrow_mut())?;
+        Ok(JsonEncoder::with_sink(cx, writer, format))
     }
 
     #[inline]
-    fn finish_entries(mut self) -> Result<(), Self::Error> {
-        self.writer.write_byte(self.cx, b'}')
+    fn finish_entries(self) -> Result<(), Self::Error> {
+        self.finish()
     }
 }
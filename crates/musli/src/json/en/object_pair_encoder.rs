@@ -1,31 +1,46 @@
 use core::marker::PhantomData;
 
 use crate::en::EntryEncoder;
+use crate::writer::Sink;
 use crate::{Context, Writer};
 
-use super::{JsonEncoder, JsonObjectKeyEncoder};
+use super::{Format, JsonEncoder, JsonObjectKeyEncoder};
 
 /// Encoder for a JSON object pair.
-pub(crate) struct JsonObjectPairEncoder<W, C, M> {
+pub(crate) struct JsonObjectPairEncoder<'a, W, C, M>
+where
+    C: Context,
+{
     cx: C,
-    empty: bool,
-    writer: W,
+    format: Format,
+    writer: Sink<'a, W, C::Allocator>,
+    /// Where to record the end of the key when the entry is buffered.
+    key_end: Option<&'a mut usize>,
     _marker: PhantomData<M>,
 }
 
-impl<W, C, M> JsonObjectPairEncoder<W, C, M> {
+impl<'a, W, C, M> JsonObjectPairEncoder<'a, W, C, M>
+where
+    C: Context,
+{
     #[inline]
-    pub(super) const fn new(cx: C, empty: bool, writer: W) -> Self {
+    pub(super) const fn new(
+        cx: C,
+        format: Format,
+        writer: Sink<'a, W, C::Allocator>,
+        key_end: Option<&'a mut usize>,
+    ) -> Self {
         Self {
             cx,
-            empty,
+            format,
             writer,
+            key_end,
             _marker: PhantomData,
         }
     }
 }
 
-impl<W, C, M> EntryEncoder for JsonObjectPairEncoder<W, C, M>
+impl<W, C, M> EntryEncoder for JsonObjectPairEncoder<'_, W, C, M>
 where
     W: Writer,
     C: Context,
@@ -35,11 +50,11 @@ where
     type Error = C::Error;
     type Mode = M;
     type EncodeKey<'this>
-        = JsonObjectKeyEncoder<W::Mut<'this>, C, M>
+        = JsonObjectKeyEncoder<Sink<'this, W, C::Allocator>, C, M>
     where
         Self: 'this;
     type EncodeValue<'this>
-        = JsonEncoder<W::Mut<'this>, C, M>
+        = JsonEncoder<'this, W, C, M>
     where
         Self: 'this;
 
@@ -50,17 +65,21 @@ where
 
     #[inline]
     fn encode_key(&mut self) -> Result<Self::EncodeKey<'_>, Self::Error> {
-        if !self.empty {
-            self.writer.write_byte(self.cx, b',')?;
-        }
-
         Ok(JsonObjectKeyEncoder::new(self.cx, self.writer.borrow_mut()))
     }
 
     #[inline]
     fn encode_value(&mut self) -> Result<Self::EncodeValue<'_>, Self::Error> {
-        self.writer.write_byte(self.cx, b':')?;
-        Ok(JsonEncoder::new(self.cx, self.writer.borrow_mut()))
+        if let Some(key_end) = &mut self.key_end {
+            **key_end = self.writer.buffered();
+        }
+
+        self.format.colon(self.cx, self.writer.borrow_mut())?;
+        Ok(JsonEncoder::with_sink(
+            self.cx,
+            self.writer.borrow_mut(),
+            self.format,
+        ))
     }
 
     #[inline]
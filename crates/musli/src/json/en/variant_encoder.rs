@@ -1,36 +1,46 @@
 use core::marker::PhantomData;
 
 use crate::en::VariantEncoder;
+use crate::writer::Sink;
 use crate::{Context, Writer};
 
-use super::{JsonEncoder, JsonObjectKeyEncoder};
+use super::{Format, JsonEncoder, JsonObjectKeyEncoder};
 
 /// A JSON variant encoder.
-pub(crate) struct JsonVariantEncoder<W, C, M> {
+pub(crate) struct JsonVariantEncoder<'a, W, C, M>
+where
+    C: Context,
+{
     cx: C,
-    writer: W,
+    format: Format,
+    writer: Sink<'a, W, C::Allocator>,
     _marker: PhantomData<M>,
 }
 
-impl<W, C, M> JsonVariantEncoder<W, C, M>
+impl<'a, W, C, M> JsonVariantEncoder<'a, W, C, M>
 where
     W: Writer,
     C: Context,
     M: 'static,
 {
     #[inline]
-    pub(super) fn new(cx: C, mut writer: W) -> Result<Self, C::Error> {
+    pub(super) fn new(
+        cx: C,
+        mut writer: Sink<'a, W, C::Allocator>,
+        format: Format,
+    ) -> Result<Self, C::Error> {
         writer.write_byte(cx, b'{')?;
 
         Ok(Self {
             cx,
+            format,
             writer,
             _marker: PhantomData,
         })
     }
 }
 
-impl<W, C, M> VariantEncoder for JsonVariantEncoder<W, C, M>
+impl<W, C, M> VariantEncoder for JsonVariantEncoder<'_, W, C, M>
 where
     W: Writer,
     C: Context,
@@ -40,11 +50,11 @@ where
     type Error = C::Error;
     type Mode = M;
     type EncodeTag<'this>
-        = JsonObjectKeyEncoder<W::Mut<'this>, C, M>
+        = JsonObjectKeyEncoder<Sink<'this, W, C::Allocator>, C, M>
     where
         Self: 'this;
     type EncodeData<'this>
-        = JsonEncoder<W::Mut<'this>, C, M>
+        = JsonEncoder<'this, W, C, M>
     where
         Self: 'this;
 
@@ -55,17 +65,26 @@ where
 
     #[inline]
     fn encode_tag(&mut self) -> Result<Self::EncodeTag<'_>, C::Error> {
+        self.format
+            .nested()
+            .newline(self.cx, self.writer.borrow_mut())?;
         Ok(JsonObjectKeyEncoder::new(self.cx, self.writer.borrow_mut()))
     }
 
     #[inline]
     fn encode_data(&mut self) -> Result<Self::EncodeData<'_>, C::Error> {
-        self.writer.write_byte(self.cx, b':')?;
-        Ok(JsonEncoder::new(self.cx, self.writer.borrow_mut()))
+        let format = self.format.nested();
+        format.colon(self.cx, self.writer.borrow_mut())?;
+        Ok(JsonEncoder::with_sink(
+            self.cx,
+            self.writer.borrow_mut(),
+            format,
+        ))
     }
 
     #[inline]
     fn finish_variant(mut self) -> Result<(), C::Error> {
+        self.format.newline(self.cx, self.writer.borrow_mut())?;
         self.writer.write_byte(self.cx, b'}')
     }
 }
@@ -10,11 +10,14 @@ use crate::alloc::Global;
 use crate::mode::Text;
 use crate::{Context, Decode, Encode, IntoWriter};
 
-use super::de::JsonDecoder;
-use super::en::JsonEncoder;
+use super::BytesFormat;
+use super::de::{self, Config, JsonDecoder};
+use super::en::{Format, JsonEncoder};
 #[cfg(feature = "alloc")]
 use super::error::Error;
-use super::parser::IntoParser;
+#[cfg(feature = "alloc")]
+use super::parser::Parser;
+use super::parser::{IntoParser, RelaxedParser};
 
 #[allow(unused)]
 const DEFAULT: Encoding = Encoding::new();
@@ -92,11 +95,62 @@ where
     DEFAULT.from_str(string)
 }
 
+/// Encode the given value to a pretty-printed [`String`] using the default
+/// [`Encoding`].
+///
+/// This indents nested values using two spaces. To configure the indentation,
+/// use [`Encoding::with_pretty`].
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::json;
+/// # use musli::json::Error;
+///
+/// #[derive(Decode, Encode)]
+/// struct Person {
+///     name: String,
+///     age: u32,
+///     tags: Vec<String>,
+/// }
+///
+/// let data = json::to_string_pretty(&Person {
+///     name: "Aristotle".to_string(),
+///     age: 61,
+///     tags: vec!["philosopher".to_string()],
+/// })?;
+///
+/// assert_eq!(data, r#"{
+///   "name": "Aristotle",
+///   "age": 61,
+///   "tags": [
+///     "philosopher"
+///   ]
+/// }"#);
+///
+/// let person: Person = json::from_str(&data)?;
+/// assert_eq!(person.name, "Aristotle");
+/// assert_eq!(person.age, 61);
+/// # Ok::<_, Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[inline]
+pub fn to_string_pretty<T>(value: &T) -> Result<String, Error>
+where
+    T: ?Sized + Encode<Text>,
+{
+    DEFAULT.with_pretty("  ").to_string(value)
+}
+
 /// Setting up encoding with parameters.
 pub struct Encoding<M = Text>
 where
     M: 'static,
 {
+    format: Format,
+    config: Config,
     _marker: marker::PhantomData<M>,
 }
 
@@ -144,6 +198,8 @@ impl Encoding<Text> {
     #[inline]
     pub const fn new() -> Self {
         Encoding {
+            format: Format::new(),
+            config: Config::new(),
             _marker: marker::PhantomData,
         }
     }
@@ -169,10 +225,285 @@ where
         T: 'static,
     {
         Encoding {
+            format: self.format,
+            config: self.config,
             _marker: marker::PhantomData,
         }
     }
 
+    /// Pretty print the output, indenting each level of nesting using the
+    /// given `indent`.
+    ///
+    /// Every element of an array and every field of an object is placed on a
+    /// line of its own, and object keys are separated from their values with
+    /// `": "`. Empty arrays and objects are encoded as `[]` and `{}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::json::Encoding;
+    /// # use musli::json::Error;
+    ///
+    /// const CONFIG: Encoding = Encoding::new().with_pretty("    ");
+    ///
+    /// #[derive(Debug, PartialEq, Decode, Encode)]
+    /// enum Shape {
+    ///     Circle { radius: u32 },
+    ///     Empty,
+    /// }
+    ///
+    /// let shapes = vec![Shape::Circle { radius: 2 }, Shape::Empty];
+    /// let data = CONFIG.to_string(&shapes)?;
+    ///
+    /// assert_eq!(data, r#"[
+    ///     {
+    ///         "Circle": {
+    ///             "radius": 2
+    ///         }
+    ///     },
+    ///     {
+    ///         "Empty": {}
+    ///     }
+    /// ]"#);
+    ///
+    /// let actual: Vec<Shape> = CONFIG.from_str(&data)?;
+    /// assert_eq!(actual, shapes);
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn with_pretty(self, indent: &'static str) -> Self {
+        Encoding {
+            format: self.format.with_indent(indent),
+            ..self
+        }
+    }
+
+    /// Use the given `newline` to separate lines when pretty printing. By
+    /// default this is `"\n"`.
+    ///
+    /// This has no effect unless pretty printing is enabled through
+    /// [`Encoding::with_pretty`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::Encoding;
+    /// # use musli::json::Error;
+    ///
+    /// const CONFIG: Encoding = Encoding::new().with_pretty("\t").with_newline("\r\n");
+    ///
+    /// let data = CONFIG.to_string(&vec![1, 2])?;
+    /// assert_eq!(data, "[\r\n\t1,\r\n\t2\r\n]");
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn with_newline(self, newline: &'static str) -> Self {
+        Encoding {
+            format: self.format.with_newline(newline),
+            ..self
+        }
+    }
+
+    /// Represent bytes using the given [`BytesFormat`].
+    ///
+    /// This applies to values encoded as bytes, such as fields marked with
+    /// `#[musli(bytes)]`. By default bytes are represented as an array of
+    /// numbers. When decoding, only the configured representation is accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::json::{BytesFormat, Encoding};
+    /// # use musli::json::Error;
+    ///
+    /// const CONFIG: Encoding = Encoding::new().with_bytes(BytesFormat::Base64);
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// struct Upload {
+    ///     #[musli(bytes)]
+    ///     digest: [u8; 4],
+    ///     #[musli(bytes)]
+    ///     content: Vec<u8>,
+    /// }
+    ///
+    /// let upload = Upload {
+    ///     digest: [0xde, 0xad, 0xbe, 0xef],
+    ///     content: b"Hello World".to_vec(),
+    /// };
+    ///
+    /// let data = CONFIG.to_string(&upload)?;
+    /// assert_eq!(data, r#"{"digest":"3q2+7w==","content":"SGVsbG8gV29ybGQ="}"#);
+    ///
+    /// let actual: Upload = CONFIG.from_str(&data)?;
+    /// assert_eq!(actual, upload);
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn with_bytes(self, bytes: BytesFormat) -> Self {
+        Encoding {
+            format: self.format.with_bytes(bytes),
+            config: self.config.with_bytes(bytes),
+            ..self
+        }
+    }
+
+    /// Produce canonical JSON as specified by the [JSON Canonicalization
+    /// Scheme] (RFC 8785).
+    ///
+    /// Canonical output is byte-for-byte identical for equal values, which
+    /// makes it suitable for hashing and signing:
+    /// * The entries of every object are sorted by their keys, compared by
+    ///   their UTF-16 code units. This includes maps with an unspecified
+    ///   iteration order such as `HashMap`.
+    /// * Floating point numbers use their shortest round-trip representation,
+    ///   formatted like ECMAScript does. Non-finite numbers cause an error.
+    /// * Strings only escape what is required, using the short escapes where
+    ///   available and lowercase `\u00xx` escapes otherwise.
+    /// * No whitespace is emitted, so this takes precedence over
+    ///   [`Encoding::with_pretty`].
+    ///
+    /// This deviates from RFC 8785 in that integers are written out in full,
+    /// regardless of whether they can be represented exactly as an `f64`. The
+    /// specification instead serializes every number as an `f64`, so integers
+    /// outside of the range `-2^53..=2^53` such as `u64::MAX` would lose
+    /// precision.
+    ///
+    /// To sort object entries, the entries of each object are buffered through
+    /// the [`Context`] in use until the object is finished.
+    ///
+    /// [JSON Canonicalization Scheme]: https://www.rfc-editor.org/rfc/rfc8785
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use musli::{Decode, Encode};
+    /// use musli::json::Encoding;
+    /// # use musli::json::Error;
+    ///
+    /// const CONFIG: Encoding = Encoding::new().with_canonical();
+    ///
+    /// #[derive(Encode, Decode)]
+    /// struct Payment {
+    ///     to: String,
+    ///     amount: f64,
+    ///     meta: HashMap<String, String>,
+    /// }
+    ///
+    /// let payment = Payment {
+    ///     to: "Aristotle".to_string(),
+    ///     amount: 1e21,
+    ///     meta: HashMap::from([
+    ///         ("z".to_string(), "\u{1f}".to_string()),
+    ///         ("a".to_string(), "\n".to_string()),
+    ///     ]),
+    /// };
+    ///
+    /// let data = CONFIG.to_string(&payment)?;
+    /// assert_eq!(data, r#"{"amount":1e+21,"meta":{"a":"\n","z":"\u001f"},"to":"Aristotle"}"#);
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn with_canonical(self) -> Self {
+        Encoding {
+            format: self.format.with_canonical(),
+            ..self
+        }
+    }
+
+    /// Decode numbers with arbitrary precision.
+    ///
+    /// When enabled, numbers decoded into dynamic containers such as
+    /// [`Value`] retain their original textual form instead of being parsed
+    /// into a primitive, so that they can be re-encoded without any loss of
+    /// precision.
+    ///
+    /// Numbers decoded into a [`Number`] always retain their textual form,
+    /// regardless of this option.
+    ///
+    /// [`Value`]: crate::value::Value
+    /// [`Number`]: crate::json::Number
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::alloc::Global;
+    /// use musli::json::Encoding;
+    /// use musli::value::Value;
+    /// # use musli::json::Error;
+    ///
+    /// const CONFIG: Encoding = Encoding::new().with_arbitrary_precision();
+    ///
+    /// let json = r#"[18446744073709551616000,0.10000000000000000001]"#;
+    ///
+    /// let value: Value<Global> = CONFIG.from_str(json)?;
+    /// assert_eq!(CONFIG.to_string(&value)?, json);
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn with_arbitrary_precision(self) -> Self {
+        Encoding {
+            config: self.config.with_arbitrary_precision(true),
+            ..self
+        }
+    }
+
+    /// Accept a relaxed grammar when decoding, which is convenient for
+    /// hand-written input such as configuration files.
+    ///
+    /// In addition to [RFC 8259] JSON, this accepts a subset of [JSON5]:
+    /// * Line comments (`// ...`) and block comments (`/* ... */`).
+    /// * Trailing commas in arrays and objects.
+    /// * Strings delimited by single quotes, like `'hello'`.
+    /// * Identifiers in place of strings, which allows for unquoted object
+    ///   keys like `{ name: "Aristotle" }`. Note that `true`, `false` and
+    ///   `null` are always parsed as literals.
+    ///
+    /// Strict parsing is unaffected by this option existing, since relaxed
+    /// parsing is implemented by a separate parser which is only used when
+    /// this is enabled.
+    ///
+    /// [RFC 8259]: https://www.rfc-editor.org/rfc/rfc8259
+    /// [JSON5]: https://spec.json5.org
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::json::Encoding;
+    /// # use musli::json::Error;
+    ///
+    /// const CONFIG: Encoding = Encoding::new().with_relaxed();
+    ///
+    /// #[derive(Debug, PartialEq, Decode, Encode)]
+    /// struct Config {
+    ///     name: String,
+    ///     ports: Vec<u16>,
+    /// }
+    ///
+    /// let config: Config = CONFIG.from_str(r#"
+    ///     // The service configuration.
+    ///     {
+    ///         name: 'web',
+    ///         /* Ports to listen on. */
+    ///         ports: [80, 443,],
+    ///     }
+    /// "#)?;
+    ///
+    /// assert_eq!(config, Config {
+    ///     name: "web".to_string(),
+    ///     ports: vec![80, 443],
+    /// });
+    ///
+    /// // Strict parsing is the default.
+    /// assert!(musli::json::from_str::<Config>("{name: 'web', ports: []}").is_err());
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn with_relaxed(self) -> Self {
+        Encoding {
+            config: self.config.with_relaxed(),
+            ..self
+        }
+    }
+
     crate::macros::encoding_impls!(
         M,
         json,
@@ -180,6 +511,9 @@ where
         JsonDecoder::<_, _, M>::new,
         IntoParser::into_parser,
         IntoWriter::into_writer,
+        encoder_arg = self.format,
+        decoder_arg = self.config,
+        decode = de::decode::<_, _, M, _>,
     );
 
     /// Encode the given value to the given value to a [`String`] using the
@@ -265,12 +599,129 @@ where
     {
         cx.clear();
         let mut data = Vec::with_capacity(128);
-        T::encode(value, JsonEncoder::<_, _, M>::new(cx, &mut data))?;
+        T::encode(
+            value,
+            JsonEncoder::<_, _, M>::new(cx, &mut data, self.format),
+        )?;
         // SAFETY: Encoder is guaranteed to produce valid UTF-8.
         Ok(unsafe { String::from_utf8_unchecked(data) })
     }
+
+    /// Decode the value referenced by a [JSON Pointer] in the given input
+    /// using the current [`Encoding`].
+    ///
+    /// Values which are not on the path to the target are skipped over without
+    /// being decoded, and anything following the target is left unparsed. An
+    /// empty pointer refers to the whole document.
+    ///
+    /// [JSON Pointer]: https://www.rfc-editor.org/rfc/rfc6901
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::Encoding;
+    /// # use musli::json::Error;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let data = r#"{"items": [{"name": "a"}, {"name": "b"}], "a/b": {"~": 42}}"#;
+    ///
+    /// let name: &str = ENCODING.decode_at(data.as_bytes(), "/items/1/name")?;
+    /// assert_eq!(name, "b");
+    ///
+    /// let number: u32 = ENCODING.decode_at(data.as_bytes(), "/a~1b/~0")?;
+    /// assert_eq!(number, 42);
+    ///
+    /// assert!(ENCODING.decode_at::<_, u32>(data.as_bytes(), "/items/2").is_err());
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn decode_at<'de, R, T>(self, reader: R, pointer: &str) -> Result<T, Error>
+    where
+        R: IntoParser<'de>,
+        T: Decode<'de, M, Global>,
+    {
+        let cx = crate::context::new().with_error();
+        self.decode_at_with(&cx, reader, pointer)
+    }
+
+    /// Decode the value referenced by a [JSON Pointer] in the given input
+    /// using the current [`Encoding`].
+    ///
+    /// This is the same as [`Encoding::decode_at`] but allows for using a
+    /// configurable [`Context`]. Each key and index in the pointer is traced
+    /// through the context, so errors are reported at the location of the
+    /// pointer.
+    ///
+    /// [JSON Pointer]: https://www.rfc-editor.org/rfc/rfc6901
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::context;
+    /// use musli::json::Encoding;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let data = br#"{"items": [{"id": 1}, {"id": "two"}]}"#;
+    ///
+    /// let cx = context::new().with_trace();
+    /// let id: u32 = ENCODING.decode_at_with(&cx, &data[..], "/items/0/id")?;
+    /// assert_eq!(id, 1);
+    ///
+    /// let cx = context::new().with_trace();
+    /// assert!(ENCODING.decode_at_with::<_, _, u32>(&cx, &data[..], "/items/1/id").is_err());
+    /// assert!(cx.report().to_string().starts_with("[items][1][id]"));
+    /// # Ok::<_, musli::context::ErrorMarker>(())
+    /// ```
+    #[inline]
+    pub fn decode_at_with<'de, C, R, T>(
+        self,
+        cx: C,
+        reader: R,
+        pointer: &str,
+    ) -> Result<T, C::Error>
+    where
+        C: Context,
+        R: IntoParser<'de>,
+        T: Decode<'de, M, C::Allocator>,
+    {
+        cx.clear();
+        let parser = IntoParser::into_parser(reader);
+
+        if self.config.is_relaxed() {
+            let parser = RelaxedParser::new(parser);
+            return JsonDecoder::<_, _, M>::new(cx, parser, self.config)
+                .decode_at(pointer, T::decode);
+        }
+
+        JsonDecoder::<_, _, M>::new(cx, parser, self.config).decode_at(pointer, T::decode)
+    }
 }
 
+crate::macros::decode_iter!(
+    json,
+    [M] Encoding<M>,
+    M,
+    JsonDecoder::<_, _, M>::new,
+    IntoParser::into_parser -> Parser,
+    Parser,
+    decoder_arg = self.config,
+    decode = de::decode::<_, _, M, _>,
+    |cx, parser, encoding| {
+        if encoding.config.is_relaxed() {
+            let mut parser = RelaxedParser::new(parser.borrow_mut());
+            parser.skip_whitespace(cx);
+            parser.peek().is_none()
+        } else {
+            parser.skip_whitespace(cx);
+            parser.peek().is_none()
+        }
+    },
+);
+
 impl<M> Clone for Encoding<M> {
     #[inline]
     fn clone(&self) -> Self {
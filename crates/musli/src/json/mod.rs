@@ -40,11 +40,15 @@
 #![cfg(feature = "json")]
 #![cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
 
+mod bytes;
 mod de;
 mod en;
 mod encoding;
 mod error;
+#[cfg(feature = "alloc")]
+mod number;
 mod parser;
+mod raw_value;
 
 #[cfg(feature = "test")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "test")))]
@@ -82,6 +86,12 @@ pub mod test;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
+#[doc(inline)]
+pub use self::bytes::BytesFormat;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::DecodeIter;
 #[doc(inline)]
 pub use self::encoding::Encoding;
 #[cfg(all(feature = "std", feature = "alloc"))]
@@ -91,10 +101,24 @@ pub use self::encoding::to_writer;
 #[cfg(feature = "alloc")]
 #[doc(inline)]
 pub use self::encoding::{decode, encode, from_slice, from_str, to_fixed_bytes, to_slice};
+#[cfg(feature = "tokio")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+#[doc(inline)]
+pub use self::encoding::{decode_async, encode_async};
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 #[doc(inline)]
-pub use self::encoding::{to_string, to_vec};
+pub use self::encoding::{to_string, to_string_pretty, to_vec};
 #[doc(inline)]
 pub use self::error::Error;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::number::Number;
 pub use self::parser::Parser;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::raw_value::OwnedRawValue;
+#[doc(inline)]
+pub use self::raw_value::RawValue;
@@ -0,0 +1,446 @@
+use core::fmt;
+use core::str::FromStr;
+
+use rust_alloc::boxed::Box;
+
+use crate::de::Visitor;
+use crate::{Allocator, Context, Decode, Decoder, Encode, Encoder};
+
+use super::Error;
+use super::parser::integer;
+
+/// A JSON number which retains its original textual form.
+///
+/// Numbers are stored exactly as they appear in the input, so they can hold
+/// integers and decimals of arbitrary size and precision. Encoding a number
+/// writes the text back verbatim.
+///
+/// Checked conversions are provided to the primitive numerical types through
+/// methods such as [`Number::as_u128`], and to any other type implementing
+/// [`FromStr`] through [`Number::parse`].
+///
+/// Numbers can be decoded from any format, but only formats which support
+/// arbitrary-precision numbers such as JSON preserve them without loss of
+/// precision.
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::json::Number;
+///
+/// #[derive(Decode, Encode)]
+/// struct Account {
+///     balance: Number,
+/// }
+///
+/// let json = r#"{"balance":123456789012345678901234567890.000000001}"#;
+///
+/// let account: Account = musli::json::from_str(json)?;
+/// assert_eq!(account.balance.as_str(), "123456789012345678901234567890.000000001");
+/// assert_eq!(account.balance.as_u128(), None);
+///
+/// let out = musli::json::to_string(&account)?;
+/// assert_eq!(out, json);
+/// # Ok::<_, musli::json::Error>(())
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Number {
+    number: Box<str>,
+}
+
+impl Number {
+    /// Construct a number from a finite floating point value.
+    ///
+    /// Returns `None` if the value is NaN or infinite, since these can't be
+    /// represented as JSON numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::Number;
+    ///
+    /// let number = Number::from_f64(1.5).unwrap();
+    /// assert_eq!(number.as_str(), "1.5");
+    ///
+    /// assert!(Number::from_f64(f64::NAN).is_none());
+    /// ```
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        let mut buffer = ryu::Buffer::new();
+
+        Some(Self {
+            number: buffer.format_finite(value).into(),
+        })
+    }
+
+    /// Get the textual form of the number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::Number;
+    ///
+    /// let number: Number = "-1.20e10".parse()?;
+    /// assert_eq!(number.as_str(), "-1.20e10");
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.number
+    }
+
+    /// Convert the number into a `u64` if it is an integer in range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::Number;
+    ///
+    /// let number: Number = "42".parse()?;
+    /// assert_eq!(number.as_u64(), Some(42));
+    ///
+    /// let number: Number = "-42".parse()?;
+    /// assert_eq!(number.as_u64(), None);
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_u64(&self) -> Option<u64> {
+        self.number.parse().ok()
+    }
+
+    /// Convert the number into an `i64` if it is an integer in range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::Number;
+    ///
+    /// let number: Number = "-42".parse()?;
+    /// assert_eq!(number.as_i64(), Some(-42));
+    ///
+    /// let number: Number = "1.5".parse()?;
+    /// assert_eq!(number.as_i64(), None);
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_i64(&self) -> Option<i64> {
+        self.number.parse().ok()
+    }
+
+    /// Convert the number into a `u128` if it is an integer in range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::Number;
+    ///
+    /// let number: Number = "340282366920938463463374607431768211455".parse()?;
+    /// assert_eq!(number.as_u128(), Some(u128::MAX));
+    ///
+    /// let number: Number = "340282366920938463463374607431768211456".parse()?;
+    /// assert_eq!(number.as_u128(), None);
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_u128(&self) -> Option<u128> {
+        self.number.parse().ok()
+    }
+
+    /// Convert the number into an `i128` if it is an integer in range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::Number;
+    ///
+    /// let number: Number = "-170141183460469231731687303715884105728".parse()?;
+    /// assert_eq!(number.as_i128(), Some(i128::MIN));
+    ///
+    /// let number: Number = "-170141183460469231731687303715884105729".parse()?;
+    /// assert_eq!(number.as_i128(), None);
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_i128(&self) -> Option<i128> {
+        self.number.parse().ok()
+    }
+
+    /// Convert the number into the closest `f64`.
+    ///
+    /// Returns `None` if the number is too large to be represented as a finite
+    /// `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::Number;
+    ///
+    /// let number: Number = "1.5e3".parse()?;
+    /// assert_eq!(number.as_f64(), Some(1500.0));
+    ///
+    /// let number: Number = "1e400".parse()?;
+    /// assert_eq!(number.as_f64(), None);
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_f64(&self) -> Option<f64> {
+        let value = self.number.parse::<f64>().ok()?;
+        value.is_finite().then_some(value)
+    }
+
+    /// Parse the number into any type implementing [`FromStr`].
+    ///
+    /// This can be used to convert the number into a decimal type without any
+    /// loss of precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::str::FromStr;
+    ///
+    /// use musli::json::Number;
+    ///
+    /// /// A decimal with two fractional digits, stored in cents.
+    /// #[derive(Debug, PartialEq)]
+    /// struct Cents(i128);
+    ///
+    /// impl FromStr for Cents {
+    ///     type Err = &'static str;
+    ///
+    ///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+    ///         let (whole, fraction) = s.split_once('.').unwrap_or((s, "00"));
+    ///
+    ///         if fraction.len() != 2 {
+    ///             return Err("expected two fractional digits");
+    ///         }
+    ///
+    ///         let whole = whole.parse::<i128>().map_err(|_| "bad whole part")?;
+    ///         let fraction = fraction.parse::<i128>().map_err(|_| "bad fraction")?;
+    ///         Ok(Cents(whole * 100 + whole.signum() * fraction))
+    ///     }
+    /// }
+    ///
+    /// let number: Number = "12345678901234567890.25".parse()?;
+    /// assert_eq!(number.parse::<Cents>(), Ok(Cents(1234567890123456789025)));
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    #[inline]
+    pub fn parse<T>(&self) -> Result<T, T::Err>
+    where
+        T: FromStr,
+    {
+        self.number.parse()
+    }
+}
+
+impl FromStr for Number {
+    type Err = Error;
+
+    /// Parse a number, which must follow the JSON number grammar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::Number;
+    ///
+    /// assert!("-0.5e-3".parse::<Number>().is_ok());
+    /// assert!("+1".parse::<Number>().is_err());
+    /// assert!("01".parse::<Number>().is_err());
+    /// assert!("1.".parse::<Number>().is_err());
+    /// ```
+    fn from_str(number: &str) -> Result<Self, Self::Err> {
+        if !integer::is_number(number.as_bytes()) {
+            return Err(crate::context::ContextError::message(
+                crate::alloc::Global::new(),
+                format_args!("Invalid number `{number}`"),
+            ));
+        }
+
+        Ok(Self {
+            number: number.into(),
+        })
+    }
+}
+
+macro_rules! from_integer {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl From<$ty> for Number {
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    let mut buffer = itoa::Buffer::new();
+
+                    Self {
+                        number: buffer.format(value).into(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+impl AsRef<str> for Number {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.number
+    }
+}
+
+impl fmt::Display for Number {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.number.fmt(f)
+    }
+}
+
+impl fmt::Debug for Number {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Number").field(&self.number).finish()
+    }
+}
+
+impl<M> Encode<M> for Number {
+    type Encode = Self;
+
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_number(&self.number)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A> Decode<'de, M, A> for Number
+where
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        struct NumberVisitor;
+
+        #[crate::trait_defaults(crate)]
+        impl<C> Visitor<'_, C> for NumberVisitor
+        where
+            C: Context,
+        {
+            type Ok = Number;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a number")
+            }
+
+            #[inline]
+            fn visit_number(self, _: C, number: &str) -> Result<Self::Ok, Self::Error> {
+                Ok(Number {
+                    number: number.into(),
+                })
+            }
+
+            #[inline]
+            fn visit_u8(self, _: C, value: u8) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_u16(self, _: C, value: u16) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_u32(self, _: C, value: u32) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_u64(self, _: C, value: u64) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_u128(self, _: C, value: u128) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_usize(self, _: C, value: usize) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_i8(self, _: C, value: i8) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_i16(self, _: C, value: i16) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_i32(self, _: C, value: i32) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_i64(self, _: C, value: i64) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_i128(self, _: C, value: i128) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_isize(self, _: C, value: isize) -> Result<Self::Ok, Self::Error> {
+                Ok(Number::from(value))
+            }
+
+            #[inline]
+            fn visit_f32(self, cx: C, value: f32) -> Result<Self::Ok, Self::Error> {
+                if !value.is_finite() {
+                    return Err(cx.message(format_args!("Non-finite number {value}")));
+                }
+
+                let mut buffer = ryu::Buffer::new();
+
+                Ok(Number {
+                    number: buffer.format_finite(value).into(),
+                })
+            }
+
+            #[inline]
+            fn visit_f64(self, cx: C, value: f64) -> Result<Self::Ok, Self::Error> {
+                match Number::from_f64(value) {
+                    Some(number) => Ok(number),
+                    None => Err(cx.message(format_args!("Non-finite number {value}"))),
+                }
+            }
+        }
+
+        decoder.decode_number(NumberVisitor)
+    }
+}
@@ -257,6 +257,55 @@ where
     Ok(())
 }
 
+/// Test if the given bytes is exactly one number, as specified by the JSON
+/// grammar.
+pub(crate) fn is_number(bytes: &[u8]) -> bool {
+    let bytes = match bytes {
+        [b'-', rest @ ..] => rest,
+        rest => rest,
+    };
+
+    let mut rest = match bytes {
+        [b'0', rest @ ..] => rest,
+        [b, rest @ ..] if is_digit_nonzero(*b) => digits(rest),
+        _ => return false,
+    };
+
+    if let [b'.', tail @ ..] = rest {
+        match tail {
+            [b, tail @ ..] if is_digit(*b) => rest = digits(tail),
+            _ => return false,
+        }
+    }
+
+    if let [b'e' | b'E', tail @ ..] = rest {
+        let tail = match tail {
+            [b'-' | b'+', tail @ ..] => tail,
+            tail => tail,
+        };
+
+        match tail {
+            [b, tail @ ..] if is_digit(*b) => rest = digits(tail),
+            _ => return false,
+        }
+    }
+
+    rest.is_empty()
+}
+
+/// Strip leading digits from the given bytes.
+fn digits(mut bytes: &[u8]) -> &[u8] {
+    while let [b, rest @ ..] = bytes {
+        if !is_digit(*b) {
+            break;
+        }
+
+        bytes = rest;
+    }
+
+    bytes
+}
+
 /// Partially parse an unsigned value.
 #[cfg_attr(feature = "parse-full", allow(unused))]
 #[inline(never)]
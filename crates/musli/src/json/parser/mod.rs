@@ -19,6 +19,9 @@ pub(crate) use self::slice_parser::SliceParser;
 mod mut_slice_parser;
 pub(crate) use self::mut_slice_parser::MutSliceParser;
 
+mod relaxed_parser;
+pub(crate) use self::relaxed_parser::RelaxedParser;
+
 pub(crate) mod string;
 pub(crate) use self::string::StringReference;
 
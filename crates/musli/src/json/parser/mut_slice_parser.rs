@@ -74,6 +74,34 @@ impl<'a, 'de> Parser<'de> for MutSliceParser<'a, 'de> {
         out
     }
 
+    #[inline]
+    fn parse_single_quoted_inner<'scratch, C>(
+        &mut self,
+        cx: C,
+        validate: bool,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
+        start: &C::Mark,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error>
+    where
+        C: Context,
+    {
+        let mut access = SliceAccess::new(cx, self.slice, 0);
+        let out = access.parse_quoted::<b'\''>(validate, start, scratch);
+        *self.slice = &self.slice[access.index..];
+        out
+    }
+
+    #[inline]
+    fn skip_single_quoted_inner<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        let mut access = SliceAccess::new(cx, self.slice, 0);
+        let out = access.skip_quoted::<b'\''>();
+        *self.slice = &self.slice[access.index..];
+        out
+    }
+
     #[inline]
     fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
     where
@@ -132,6 +160,11 @@ impl<'a, 'de> Parser<'de> for MutSliceParser<'a, 'de> {
         self.slice.first().copied()
     }
 
+    #[inline]
+    fn remaining(&self) -> &'de [u8] {
+        self.slice
+    }
+
     fn parse_f32<C>(&mut self, cx: C) -> Result<f32, C::Error>
     where
         C: Context,
@@ -8,6 +8,7 @@ mod private {
     pub trait Sealed {}
     impl Sealed for crate::json::parser::SliceParser<'_> {}
     impl Sealed for crate::json::parser::MutSliceParser<'_, '_> {}
+    impl<'de, P> Sealed for crate::json::parser::RelaxedParser<P> where P: super::Parser<'de> {}
     impl<'de, R> Sealed for &mut R where R: ?Sized + super::Parser<'de> {}
 }
 
@@ -73,6 +74,71 @@ pub trait Parser<'de>: private::Sealed {
     where
         C: Context;
 
+    /// Skip a string, including its opening quote.
+    #[doc(hidden)]
+    fn skip_string<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.skip(cx, 1)?;
+        self.skip_string_inner(cx)
+    }
+
+    /// Lex the next token where an object key is expected.
+    ///
+    /// This is the same as [`Parser::lex`], unless the grammar accepts keys
+    /// which aren't strings.
+    #[doc(hidden)]
+    fn lex_key<C>(&mut self, cx: C) -> Token
+    where
+        C: Context,
+    {
+        self.lex(cx)
+    }
+
+    /// Parse an object key.
+    #[doc(hidden)]
+    fn parse_key<'scratch, C>(
+        &mut self,
+        cx: C,
+        validate: bool,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error>
+    where
+        C: Context,
+    {
+        self.parse_string(cx, validate, scratch)
+    }
+
+    /// Skip an object key.
+    #[doc(hidden)]
+    fn skip_key<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.skip_string(cx)
+    }
+
+    /// Parse a string delimited by single quotes, after its opening quote has
+    /// been consumed.
+    #[doc(hidden)]
+    fn parse_single_quoted_inner<'scratch, C>(
+        &mut self,
+        cx: C,
+        validate: bool,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
+        start: &C::Mark,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error>
+    where
+        C: Context;
+
+    /// Skip a string delimited by single quotes, after its opening quote has
+    /// been consumed.
+    #[doc(hidden)]
+    fn skip_single_quoted_inner<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context;
+
     #[doc(hidden)]
     fn read_byte<C>(&mut self, cx: C) -> Result<u8, C::Error>
     where
@@ -123,6 +189,12 @@ pub trait Parser<'de>: private::Sealed {
     #[doc(hidden)]
     fn peek(&mut self) -> Option<u8>;
 
+    /// The input which remains to be parsed.
+    ///
+    /// This is used to capture the exact source of a value.
+    #[doc(hidden)]
+    fn remaining(&self) -> &'de [u8];
+
     #[doc(hidden)]
     fn lex<C>(&mut self, cx: C) -> Token
     where
@@ -263,6 +335,19 @@ where
         (**self).try_clone()
     }
 
+    #[inline]
+    fn parse_string<'scratch, C>(
+        &mut self,
+        cx: C,
+        validate: bool,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error>
+    where
+        C: Context,
+    {
+        (**self).parse_string(cx, validate, scratch)
+    }
+
     #[inline]
     fn parse_string_inner<'scratch, C>(
         &mut self,
@@ -285,6 +370,65 @@ where
         (**self).skip_string_inner(cx)
     }
 
+    #[inline]
+    fn skip_string<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        (**self).skip_string(cx)
+    }
+
+    #[inline]
+    fn lex_key<C>(&mut self, cx: C) -> Token
+    where
+        C: Context,
+    {
+        (**self).lex_key(cx)
+    }
+
+    #[inline]
+    fn parse_key<'scratch, C>(
+        &mut self,
+        cx: C,
+        validate: bool,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error>
+    where
+        C: Context,
+    {
+        (**self).parse_key(cx, validate, scratch)
+    }
+
+    #[inline]
+    fn skip_key<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        (**self).skip_key(cx)
+    }
+
+    #[inline]
+    fn parse_single_quoted_inner<'scratch, C>(
+        &mut self,
+        cx: C,
+        validate: bool,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
+        start: &C::Mark,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error>
+    where
+        C: Context,
+    {
+        (**self).parse_single_quoted_inner(cx, validate, scratch, start)
+    }
+
+    #[inline]
+    fn skip_single_quoted_inner<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        (**self).skip_single_quoted_inner(cx)
+    }
+
     #[inline]
     fn read_byte<C>(&mut self, cx: C) -> Result<u8, C::Error>
     where
@@ -298,6 +442,11 @@ where
         (**self).peek()
     }
 
+    #[inline]
+    fn remaining(&self) -> &'de [u8] {
+        (**self).remaining()
+    }
+
     #[inline]
     fn lex<C>(&mut self, cx: C) -> Token
     where
@@ -0,0 +1,304 @@
+use core::str;
+
+use crate::Context;
+use crate::alloc::Vec;
+use crate::json::parser::{Parser, StringReference, Token};
+
+/// A [`Parser`] wrapper which accepts a relaxed grammar.
+///
+/// In addition to what the wrapped parser accepts, this permits:
+/// * Line comments (`// ...`) and block comments (`/* ... */`) wherever
+///   whitespace is permitted.
+/// * Strings delimited by single quotes, like `'hello'`.
+/// * Identifiers as object keys, like `{ name: "Aristotle" }`. Values must
+///   still be quoted, so that a typo like `{ enabled: ture }` is an error.
+///
+/// This is only used when relaxed parsing has been enabled, so that the
+/// regular parsers don't pay for it.
+pub(crate) struct RelaxedParser<P> {
+    parser: P,
+}
+
+impl<P> RelaxedParser<P> {
+    /// Wrap the given parser.
+    #[inline]
+    pub(crate) fn new(parser: P) -> Self {
+        Self { parser }
+    }
+}
+
+impl<'de, P> RelaxedParser<P>
+where
+    P: Parser<'de>,
+{
+    /// The length of the identifier at the start of the remaining input.
+    #[inline]
+    fn identifier_len(&self) -> usize {
+        match self.parser.remaining() {
+            [b, rest @ ..] if is_identifier_start(*b) => {
+                1 + rest.iter().take_while(|b| is_identifier(**b)).count()
+            }
+            _ => 0,
+        }
+    }
+
+    /// The length of the comment at the start of the remaining input, if
+    /// there is a complete one.
+    #[inline]
+    fn comment_len(&self) -> Option<usize> {
+        match self.parser.remaining() {
+            [b'/', b'/', rest @ ..] => {
+                Some(2 + rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len()))
+            }
+            [b'/', b'*', rest @ ..] => {
+                let n = rest.windows(2).position(|w| w == b"*/")?;
+                Some(2 + n + 2)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'de, P> Parser<'de> for RelaxedParser<P>
+where
+    P: Parser<'de>,
+{
+    type Mut<'this>
+        = RelaxedParser<P::Mut<'this>>
+    where
+        Self: 'this;
+
+    type TryClone = RelaxedParser<P::TryClone>;
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::Mut<'_> {
+        RelaxedParser::new(self.parser.borrow_mut())
+    }
+
+    #[inline]
+    fn try_clone(&self) -> Option<Self::TryClone> {
+        Some(RelaxedParser::new(self.parser.try_clone()?))
+    }
+
+    fn parse_string<'scratch, C>(
+        &mut self,
+        cx: C,
+        validate: bool,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error>
+    where
+        C: Context,
+    {
+        self.skip_whitespace(cx);
+        let start = cx.mark();
+
+        if self.peek() == Some(b'\'') {
+            self.parser.skip(cx, 1)?;
+            return self
+                .parser
+                .parse_single_quoted_inner(cx, validate, scratch, &start);
+        }
+
+        self.parser.parse_string(cx, validate, scratch)
+    }
+
+    #[inline]
+    fn parse_string_inner<'scratch, C>(
+        &mut self,
+        cx: C,
+        validate: bool,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
+        start: &C::Mark,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error>
+    where
+        C: Context,
+    {
+        self.parser.parse_string_inner(cx, validate, scratch, start)
+    }
+
+    #[inline]
+    fn skip_string_inner<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.parser.skip_string_inner(cx)
+    }
+
+    fn skip_string<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.skip_whitespace(cx);
+
+        if self.peek() == Some(b'\'') {
+            self.parser.skip(cx, 1)?;
+            return self.parser.skip_single_quoted_inner(cx);
+        }
+
+        self.parser.skip_string(cx)
+    }
+
+    #[inline]
+    fn lex_key<C>(&mut self, cx: C) -> Token
+    where
+        C: Context,
+    {
+        let token = self.lex(cx);
+
+        if self.identifier_len() > 0 {
+            return Token::String;
+        }
+
+        token
+    }
+
+    #[inline]
+    fn parse_key<'scratch, C>(
+        &mut self,
+        cx: C,
+        validate: bool,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error>
+    where
+        C: Context,
+    {
+        self.skip_whitespace(cx);
+
+        let len = self.identifier_len();
+
+        if len == 0 {
+            return self.parse_string(cx, validate, scratch);
+        }
+
+        let identifier = &self.parser.remaining()[..len];
+        self.parser.skip(cx, len)?;
+
+        // SAFETY: Identifiers only consist of ASCII characters.
+        let identifier = unsafe { str::from_utf8_unchecked(identifier) };
+        Ok(StringReference::Borrowed(identifier))
+    }
+
+    #[inline]
+    fn skip_key<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.skip_whitespace(cx);
+
+        match self.identifier_len() {
+            0 => self.skip_string(cx),
+            len => self.parser.skip(cx, len),
+        }
+    }
+
+    #[inline]
+    fn parse_single_quoted_inner<'scratch, C>(
+        &mut self,
+        cx: C,
+        validate: bool,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
+        start: &C::Mark,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error>
+    where
+        C: Context,
+    {
+        self.parser
+            .parse_single_quoted_inner(cx, validate, scratch, start)
+    }
+
+    #[inline]
+    fn skip_single_quoted_inner<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.parser.skip_single_quoted_inner(cx)
+    }
+
+    #[inline]
+    fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.parser.skip(cx, n)
+    }
+
+    #[inline]
+    fn read<C>(&mut self, cx: C, buf: &mut [u8]) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.parser.read(cx, buf)
+    }
+
+    fn skip_whitespace<C>(&mut self, cx: C)
+    where
+        C: Context,
+    {
+        loop {
+            self.parser.skip_whitespace(cx);
+
+            // An incomplete comment is left in place, so that it's reported
+            // as an unexpected token.
+            let Some(len) = self.comment_len() else {
+                return;
+            };
+
+            if self.parser.skip(cx, len).is_err() {
+                return;
+            }
+        }
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<u8> {
+        self.parser.peek()
+    }
+
+    #[inline]
+    fn remaining(&self) -> &'de [u8] {
+        self.parser.remaining()
+    }
+
+    fn lex<C>(&mut self, cx: C) -> Token
+    where
+        C: Context,
+    {
+        self.skip_whitespace(cx);
+
+        let Some(b) = self.peek() else {
+            return Token::Eof;
+        };
+
+        if b == b'\'' {
+            return Token::String;
+        }
+
+        Token::from_byte(b)
+    }
+
+    #[inline]
+    fn parse_f32<C>(&mut self, cx: C) -> Result<f32, C::Error>
+    where
+        C: Context,
+    {
+        self.parser.parse_f32(cx)
+    }
+
+    #[inline]
+    fn parse_f64<C>(&mut self, cx: C) -> Result<f64, C::Error>
+    where
+        C: Context,
+    {
+        self.parser.parse_f64(cx)
+    }
+}
+
+#[inline]
+fn is_identifier_start(b: u8) -> bool {
+    matches!(b, b'a'..=b'z' | b'A'..=b'Z' | b'_' | b'$')
+}
+
+#[inline]
+fn is_identifier(b: u8) -> bool {
+    is_identifier_start(b) || b.is_ascii_digit()
+}
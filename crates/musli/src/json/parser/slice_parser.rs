@@ -69,6 +69,34 @@ impl<'de> Parser<'de> for SliceParser<'de> {
         out
     }
 
+    #[inline]
+    fn parse_single_quoted_inner<'scratch, C>(
+        &mut self,
+        cx: C,
+        validate: bool,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
+        start: &C::Mark,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error>
+    where
+        C: Context,
+    {
+        let mut access = SliceAccess::new(cx, self.slice, self.index);
+        let out = access.parse_quoted::<b'\''>(validate, start, scratch);
+        self.index = access.index;
+        out
+    }
+
+    #[inline]
+    fn skip_single_quoted_inner<C>(&mut self, cx: C) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        let mut access = SliceAccess::new(cx, self.slice, self.index);
+        let out = access.skip_quoted::<b'\''>();
+        self.index = access.index;
+        out
+    }
+
     #[inline]
     fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
     where
@@ -124,6 +152,11 @@ impl<'de> Parser<'de> for SliceParser<'de> {
         self.slice.get(self.index).copied()
     }
 
+    #[inline]
+    fn remaining(&self) -> &'de [u8] {
+        self.slice.get(self.index..).unwrap_or_default()
+    }
+
     fn parse_f32<C>(&mut self, cx: C) -> Result<f32, C::Error>
     where
         C: Context,
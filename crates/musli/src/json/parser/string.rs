@@ -107,7 +107,9 @@ where
 
     /// Parses a JSON escape sequence and appends it into the scratch space. Assumes
     /// the previous byte read was a backslash.
-    pub(crate) fn parse_escape(
+    ///
+    /// The `QUOTE` the string is delimited by can always be escaped.
+    fn parse_escape<const QUOTE: u8>(
         &mut self,
         validate: bool,
         scratch: &mut Vec<u8, C::Allocator>,
@@ -117,6 +119,7 @@ where
 
         let extend = match b {
             b'"' => scratch.push(b'"').is_ok(),
+            b'\'' if QUOTE == b'\'' => scratch.push(b'\'').is_ok(),
             b'\\' => scratch.push(b'\\').is_ok(),
             b'/' => scratch.push(b'/').is_ok(),
             b'b' => scratch.push(b'\x08').is_ok(),
@@ -174,7 +177,7 @@ where
                                 // does not blow the stack on malicious input because
                                 // the escape is not \u, so it will be handled by one
                                 // of the easy nonrecursive cases.
-                                self.parse_escape(validate, scratch)
+                                self.parse_escape::<QUOTE>(validate, scratch)
                             };
                         }
 
@@ -213,14 +216,15 @@ where
         Ok(extend)
     }
 
-    /// Parses a JSON escape sequence and appends it into the scratch space. Assumes
-    /// the previous byte read was a backslash.
-    fn skip_escape(&mut self, validate: bool) -> Result<(), C::Error> {
+    /// Skips over a JSON escape sequence. Assumes the previous byte read was a
+    /// backslash.
+    fn skip_escape<const QUOTE: u8>(&mut self, validate: bool) -> Result<(), C::Error> {
         let start = self.cx.mark();
         let b = self.next()?;
 
         match b {
             b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => (),
+            b'\'' if QUOTE == b'\'' => (),
             b'u' => {
                 match self.parse_hex_escape()? {
                     0xDC00..=0xDFFF => {
@@ -257,7 +261,7 @@ where
                                 // does not blow the stack on malicious input because
                                 // the escape is not \u, so it will be handled by one
                                 // of the easy nonrecursive cases.
-                                self.skip_escape(validate)
+                                self.skip_escape::<QUOTE>(validate)
                             };
                         }
 
@@ -290,18 +294,32 @@ where
     }
 
     /// Reader implementation from a slice.
+    #[inline]
     pub(crate) fn parse_string<'scratch>(
         &mut self,
         validate: bool,
         start: &C::Mark,
         scratch: &'scratch mut Vec<u8, C::Allocator>,
+    ) -> Result<StringReference<'de, 'scratch>, C::Error> {
+        self.parse_quoted::<b'"'>(validate, start, scratch)
+    }
+
+    /// Parse a string which is delimited by `QUOTE`.
+    pub(crate) fn parse_quoted<'scratch, const QUOTE: u8>(
+        &mut self,
+        validate: bool,
+        start: &C::Mark,
+        scratch: &'scratch mut Vec<u8, C::Allocator>,
     ) -> Result<StringReference<'de, 'scratch>, C::Error> {
         // Index of the first byte not yet copied into the scratch space.
         let mut open_mark = self.cx.mark();
         let mut open = self.index;
 
         loop {
-            while self.index < self.slice.len() && !ESCAPE[self.slice[self.index] as usize] {
+            while self.index < self.slice.len()
+                && !ESCAPE[self.slice[self.index] as usize]
+                && (QUOTE == b'"' || self.slice[self.index] != QUOTE)
+            {
                 self.index = self.index.wrapping_add(1);
                 self.cx.advance(1);
             }
@@ -311,7 +329,7 @@ where
             }
 
             match self.slice[self.index] {
-                b'"' => {
+                b if b == QUOTE => {
                     if scratch.is_empty() {
                         // Fast path: return a slice of the raw JSON without any
                         // copying.
@@ -352,13 +370,19 @@ where
                     self.index = self.index.wrapping_add(1);
                     self.cx.advance(1);
 
-                    if !self.parse_escape(validate, scratch)? {
+                    if !self.parse_escape::<QUOTE>(validate, scratch)? {
                         return Err(self.cx.message_at(&open_mark, "Buffer overflow"));
                     }
 
                     open = self.index;
                     open_mark = self.cx.mark();
                 }
+                // Only reachable when the string is not delimited by a double
+                // quote, in which case it is an ordinary character.
+                b'"' => {
+                    self.index = self.index.wrapping_add(1);
+                    self.cx.advance(1);
+                }
                 _ => {
                     if validate {
                         return Err(self
@@ -374,10 +398,16 @@ where
     }
 
     /// Reader implementation from a slice.
+    #[inline]
     pub(crate) fn skip_string(&mut self) -> Result<(), C::Error> {
+        self.skip_quoted::<b'"'>()
+    }
+
+    /// Skip a string which is delimited by `QUOTE`.
+    pub(crate) fn skip_quoted<const QUOTE: u8>(&mut self) -> Result<(), C::Error> {
         loop {
-            while let Some(b) = self.slice.get(self.index) {
-                if ESCAPE[*b as usize] {
+            while let Some(&b) = self.slice.get(self.index) {
+                if ESCAPE[b as usize] || (QUOTE != b'"' && b == QUOTE) {
                     break;
                 }
 
@@ -388,12 +418,13 @@ where
             let b = self.next()?;
 
             match b {
-                b'"' => {
+                b if b == QUOTE => {
                     return Ok(());
                 }
                 b'\\' => {
-                    self.skip_escape(true)?;
+                    self.skip_escape::<QUOTE>(true)?;
                 }
+                b'"' => {}
                 _ => {
                     return Err(self.cx.message("Control character while parsing string"));
                 }
@@ -0,0 +1,382 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use rust_alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use rust_alloc::string::String;
+
+use crate::de::UnsizedVisitor;
+use crate::{Allocator, Context, Decode, Decoder, Encode, Encoder};
+
+#[cfg(feature = "alloc")]
+use super::Error;
+
+/// A borrowed fragment of unparsed JSON.
+///
+/// Decoding this skips over the next value and captures the exact text it
+/// occupies in the input, including any whitespace inside of it. Encoding it
+/// writes the text back verbatim.
+///
+/// This can be used to defer or entirely avoid decoding parts of a document,
+/// or to splice already encoded JSON into a value being encoded.
+///
+/// Raw values can only be decoded from and encoded to JSON. Other formats will
+/// raise an error.
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::json::RawValue;
+///
+/// #[derive(Decode, Encode)]
+/// struct Event<'a> {
+///     kind: &'a str,
+///     payload: RawValue<'a>,
+/// }
+///
+/// let json = r#"{"kind": "update", "payload": {"id": 1, "tags": ["a", "b"]}}"#;
+///
+/// let event: Event<'_> = musli::json::from_str(json)?;
+/// assert_eq!(event.kind, "update");
+/// assert_eq!(event.payload.as_str(), r#"{"id": 1, "tags": ["a", "b"]}"#);
+///
+/// let out = musli::json::to_string(&event)?;
+/// assert_eq!(out, r#"{"kind":"update","payload":{"id": 1, "tags": ["a", "b"]}}"#);
+/// # Ok::<_, musli::json::Error>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawValue<'de> {
+    raw: &'de str,
+}
+
+impl<'de> RawValue<'de> {
+    /// Construct a raw value from a string containing a single JSON value.
+    ///
+    /// Whitespace surrounding the value is trimmed.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the string is not a valid JSON value, or if it is followed by
+    /// anything but whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::RawValue;
+    ///
+    /// let raw = RawValue::from_str(" [1, 2, 3] ")?;
+    /// assert_eq!(raw.as_str(), "[1, 2, 3]");
+    ///
+    /// assert!(RawValue::from_str("[1, 2").is_err());
+    /// assert!(RawValue::from_str("1 2").is_err());
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(json: &'de str) -> Result<Self, Error> {
+        let value: RawValue<'de> = super::from_str(json)?;
+
+        if value.raw.len() != json.trim_matches(is_whitespace).len() {
+            return Err(crate::context::ContextError::message(
+                crate::alloc::Global::new(),
+                "Trailing data after raw value",
+            ));
+        }
+
+        Ok(value)
+    }
+
+    /// Get the unparsed JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::RawValue;
+    ///
+    /// let raw: RawValue<'_> = musli::json::from_str(r#"  {"a": 1}  "#)?;
+    /// assert_eq!(raw.as_str(), r#"{"a": 1}"#);
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'de str {
+        self.raw
+    }
+
+    /// Convert into an owned raw value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::RawValue;
+    ///
+    /// let raw = RawValue::from_str("[1, 2]")?.into_owned();
+    /// assert_eq!(raw.as_str(), "[1, 2]");
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn into_owned(self) -> OwnedRawValue {
+        OwnedRawValue {
+            raw: self.raw.into(),
+        }
+    }
+}
+
+impl AsRef<str> for RawValue<'_> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.raw
+    }
+}
+
+impl fmt::Display for RawValue<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.raw.fmt(f)
+    }
+}
+
+impl fmt::Debug for RawValue<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RawValue").field(&self.raw).finish()
+    }
+}
+
+impl<M> Encode<M> for RawValue<'_> {
+    type Encode = Self;
+
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_raw(self.raw)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A> Decode<'de, M, A> for RawValue<'de>
+where
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        struct Visitor;
+
+        #[crate::trait_defaults(crate)]
+        impl<'de, C> UnsizedVisitor<'de, C, str> for Visitor
+        where
+            C: Context,
+        {
+            type Ok = RawValue<'de>;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a borrowed raw value")
+            }
+
+            #[inline]
+            fn visit_borrowed(self, _: C, raw: &'de str) -> Result<Self::Ok, Self::Error> {
+                Ok(RawValue { raw })
+            }
+        }
+
+        decoder.decode_raw(Visitor)
+    }
+}
+
+/// An owned fragment of unparsed JSON.
+///
+/// This is the owned variant of [`RawValue`], which can be decoded from any
+/// input without borrowing from it.
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::json::OwnedRawValue;
+///
+/// #[derive(Decode, Encode)]
+/// struct Event {
+///     kind: String,
+///     payload: OwnedRawValue,
+/// }
+///
+/// let json = br#"{"kind": "update", "payload": [1, 2, 3]}"#;
+///
+/// let event: Event = musli::json::from_slice(json)?;
+/// assert_eq!(event.payload.as_str(), "[1, 2, 3]");
+///
+/// let out = musli::json::to_string(&event)?;
+/// assert_eq!(out, r#"{"kind":"update","payload":[1, 2, 3]}"#);
+/// # Ok::<_, musli::json::Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedRawValue {
+    raw: Box<str>,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedRawValue {
+    /// Construct an owned raw value from a string containing a single JSON
+    /// value.
+    ///
+    /// Whitespace surrounding the value is trimmed.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the string is not a valid JSON value, or if it is followed by
+    /// anything but whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::OwnedRawValue;
+    ///
+    /// let raw = OwnedRawValue::from_string(String::from("true"))?;
+    /// assert_eq!(raw.as_str(), "true");
+    ///
+    /// assert!(OwnedRawValue::from_string(String::from("tru")).is_err());
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    pub fn from_string(json: String) -> Result<Self, Error> {
+        let raw = RawValue::from_str(&json)?;
+
+        if raw.raw.len() == json.len() {
+            return Ok(Self {
+                raw: json.into_boxed_str(),
+            });
+        }
+
+        Ok(raw.into_owned())
+    }
+
+    /// Get the unparsed JSON.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Borrow as a [`RawValue`].
+    #[inline]
+    pub fn as_raw_value(&self) -> RawValue<'_> {
+        RawValue { raw: &self.raw }
+    }
+
+    /// Convert into the underlying string.
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.raw.into_string()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<str> for OwnedRawValue {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.raw
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for OwnedRawValue {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.raw.fmt(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for OwnedRawValue {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OwnedRawValue").field(&self.raw).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<RawValue<'_>> for OwnedRawValue {
+    #[inline]
+    fn from(value: RawValue<'_>) -> Self {
+        value.into_owned()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M> Encode<M> for OwnedRawValue {
+    type Encode = Self;
+
+    const IS_BITWISE_ENCODE: bool = false;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_raw(&self.raw)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, M, A> Decode<'de, M, A> for OwnedRawValue
+where
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        struct Visitor;
+
+        #[crate::trait_defaults(crate)]
+        impl<C> UnsizedVisitor<'_, C, str> for Visitor
+        where
+            C: Context,
+        {
+            type Ok = OwnedRawValue;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a raw value")
+            }
+
+            #[inline]
+            fn visit_ref(self, _: C, raw: &str) -> Result<Self::Ok, Self::Error> {
+                Ok(OwnedRawValue { raw: raw.into() })
+            }
+        }
+
+        decoder.decode_raw(Visitor)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+fn is_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\n' | '\t' | '\r')
+}
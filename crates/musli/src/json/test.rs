@@ -1,3 +1,182 @@
 //! Helpers for writing tests.
 
 crate::macros::test_fns!(Text, "json");
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::collections::HashMap;
+    use std::string::{String, ToString};
+    use std::vec;
+    use std::vec::Vec;
+
+    use crate::json::{Encoding, RawValue};
+    use crate::{Decode, Encode};
+
+    const CANONICAL: Encoding = Encoding::new().with_canonical();
+
+    #[derive(Encode, Decode)]
+    #[musli(crate)]
+    struct Record {
+        name: String,
+        values: Vec<f64>,
+        attributes: HashMap<String, u32>,
+        nested: Nested,
+    }
+
+    #[derive(Encode, Decode)]
+    #[musli(crate)]
+    struct Nested {
+        z: bool,
+        a: Option<u32>,
+    }
+
+    fn canonical<T>(value: &T) -> String
+    where
+        T: ?Sized + Encode<crate::mode::Text>,
+    {
+        CANONICAL.to_string(value).unwrap()
+    }
+
+    #[test]
+    fn canonical_golden() {
+        let record = Record {
+            name: "example".to_string(),
+            values: vec![1.0, -0.0, 0.5, 1e21, 1e-7],
+            attributes: (0..32).map(|n| (n.to_string(), n)).collect(),
+            nested: Nested { z: true, a: None },
+        };
+
+        let attributes = {
+            let mut keys = (0..32).collect::<Vec<u32>>();
+            keys.sort_by_key(|n| n.to_string());
+
+            keys.iter()
+                .map(|n| std::format!("\"{n}\":{n}"))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        let expected = std::format!(
+            r#"{{"attributes":{{{attributes}}},"name":"example","nested":{{"a":null,"z":true}},"values":[1,0,0.5,1e+21,1e-7]}}"#
+        );
+
+        assert_eq!(canonical(&record), expected);
+
+        // Output doesn't depend on the iteration order of the map.
+        for _ in 0..16 {
+            let attributes = record
+                .attributes
+                .clone()
+                .into_iter()
+                .collect::<HashMap<_, _>>();
+
+            let record = Record {
+                attributes,
+                name: record.name.clone(),
+                values: record.values.clone(),
+                nested: Nested { z: true, a: None },
+            };
+
+            assert_eq!(canonical(&record), expected);
+        }
+    }
+
+    #[test]
+    fn canonical_key_order() {
+        // Keys are sorted by their UTF-16 code units, which places the
+        // surrogate pair of U+1F600 before U+E000. Escaped keys are sorted by
+        // the characters they represent.
+        let map = HashMap::from([
+            ("\u{e000}", 1),
+            ("\u{1f600}", 2),
+            ("b", 3),
+            ("B", 4),
+            ("\u{80}", 5),
+            ("", 6),
+            ("\\", 7),
+            ("\n", 8),
+            ("\u{1f}", 9),
+            ("\"", 10),
+        ]);
+
+        assert_eq!(
+            canonical(&map),
+            "{\"\":6,\"\\n\":8,\"\\u001f\":9,\"\\\"\":10,\"B\":4,\"\\\\\":7,\"b\":3,\"\u{80}\":5,\"\u{1f600}\":2,\"\u{e000}\":1}"
+        );
+    }
+
+    #[test]
+    fn canonical_numbers() {
+        macro_rules! test {
+            ($value:expr, $expected:expr) => {
+                assert_eq!(canonical(&$value), $expected, "{}", stringify!($value));
+            };
+        }
+
+        test!(0.0f64, "0");
+        test!(-0.0f64, "0");
+        test!(1.0f64, "1");
+        test!(-1.5f64, "-1.5");
+        test!(123.456f64, "123.456");
+        test!(0.1f32, "0.1");
+        test!(1e20f64, "100000000000000000000");
+        test!(1e21f64, "1e+21");
+        test!(1.5e300f64, "1.5e+300");
+        test!(0.000001f64, "0.000001");
+        test!(1e-7f64, "1e-7");
+        test!(-2.5e-10f64, "-2.5e-10");
+        test!(f64::MAX, "1.7976931348623157e+308");
+        test!(f64::MIN_POSITIVE, "2.2250738585072014e-308");
+        test!(5e-324f64, "5e-324");
+        test!(u64::MAX, "18446744073709551615");
+        test!(i128::MIN, "-170141183460469231731687303715884105728");
+
+        assert!(CANONICAL.to_string(&f64::NAN).is_err());
+        assert!(CANONICAL.to_string(&[1.0, f64::INFINITY]).is_err());
+        assert!(CANONICAL.to_string(&f32::NEG_INFINITY).is_err());
+    }
+
+    #[test]
+    fn canonical_strings() {
+        assert_eq!(
+            canonical("\u{0}\u{8}\t\n\u{c}\r\u{1f}\"\\/\u{7f}é\u{1f600}"),
+            "\"\\u0000\\b\\t\\n\\f\\r\\u001f\\\"\\\\/\u{7f}é\u{1f600}\""
+        );
+    }
+
+    #[test]
+    fn canonical_raw_value() {
+        let raw = RawValue::from_str(
+            r#" { "b" : [ 1.50 , -0 , "A\/" ] , "a" : { "y" : 1E2 , "x" : null } } "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            canonical(&raw),
+            r#"{"a":{"x":null,"y":100},"b":[1.5,0,"A/"]}"#
+        );
+    }
+
+    #[test]
+    fn canonical_ignores_pretty() {
+        const PRETTY: Encoding = Encoding::new().with_pretty("  ").with_canonical();
+        const CANONICAL_PRETTY: Encoding = Encoding::new().with_canonical().with_pretty("  ");
+
+        let map = HashMap::from([("b", vec![1, 2]), ("a", vec![])]);
+        assert_eq!(PRETTY.to_string(&map).unwrap(), r#"{"a":[],"b":[1,2]}"#);
+        assert_eq!(
+            CANONICAL_PRETTY.to_string(&map).unwrap(),
+            r#"{"a":[],"b":[1,2]}"#
+        );
+    }
+
+    #[test]
+    fn canonical_roundtrip() {
+        let map = HashMap::from([("b".to_string(), 0.1f64), ("a".to_string(), 1e100)]);
+        let data = canonical(&map);
+        assert_eq!(data, r#"{"a":1e+100,"b":0.1}"#);
+
+        let actual: HashMap<String, f64> = CANONICAL.from_str(&data).unwrap();
+        assert_eq!(actual, map);
+    }
+}
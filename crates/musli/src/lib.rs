@@ -173,6 +173,9 @@
 //! | [`musli::wire`]                             | ✔ | ✔ | ✔ | ✗ |
 //! | [`musli::descriptive`]                      | ✔ | ✔ | ✔ | ✔ |
 //! | [`musli::json`] [^json]                     | ✔ | ✔ | ✔ | ✔ |
+//! | [`musli::msgpack`]                          | ✔ | ✔ | ✔ | ✔ |
+//! | [`musli::cbor`]                             | ✔ | ✔ | ✔ | ✔ |
+//! | [`musli::protobuf`]                         | ✔ | ✔ | ✔ | ✗ |
 //!
 //! `reorder` determines whether fields must occur in exactly the order in which
 //! they are specified in their type. Reordering fields in such a type would
@@ -470,9 +473,12 @@
 //! [`derives`]: <https://docs.rs/musli/latest/musli/_help/derives/>
 //! [`Encode`]: <https://docs.rs/musli/latest/musli/en/trait.Encode.html>
 //! [`Encoder`]: <https://docs.rs/musli/latest/musli/trait.Encoder.html>
+//! [`musli::cbor`]: <https://docs.rs/musli/latest/musli/cbor/>
 //! [`musli::descriptive`]: <https://docs.rs/musli/latest/musli/descriptive/>
 //! [`musli::json`]: <https://docs.rs/musli/latest/musli/json/>
+//! [`musli::msgpack`]: <https://docs.rs/musli/latest/musli/msgpack/>
 //! [`musli::packed`]: <https://docs.rs/musli/latest/musli/packed/>
+//! [`musli::protobuf`]: <https://docs.rs/musli/latest/musli/protobuf/>
 //! [`musli::serde`]: <https://docs.rs/musli/latest/musli/serde/>
 //! [`musli::storage`]: <https://docs.rs/musli/latest/musli/storage/>
 //! [`musli::value`]: <https://docs.rs/musli/latest/musli/value/>
@@ -686,9 +692,12 @@ pub mod alloc;
 #[doc(inline)]
 pub use self::alloc::Allocator;
 
+pub mod cbor;
 pub mod descriptive;
 pub mod json;
+pub mod msgpack;
 pub mod packed;
+pub mod protobuf;
 pub mod serde;
 pub mod storage;
 pub mod value;
@@ -712,6 +721,9 @@ pub use self::reader::{IntoReader, Reader};
 
 pub mod wrap;
 
+#[cfg(feature = "tokio")]
+mod frame;
+
 pub mod writer;
 #[doc(inline)]
 pub use self::writer::{IntoWriter, Writer};
@@ -219,14 +219,11 @@ macro_rules! bare_encoding {
         ///     age: 61,
         /// })?;
         ///
-        /// // Add some extra data which will be ignored during decoding.
-        /// data.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
-        ///
-        /// // Note: A slice implements `musli::Reader`.
+        #[doc = $crate::macros::trailing_data_doc!($what, extend)]
         /// let mut slice = &data[..];
         ///
         #[doc = concat!("let person: Person = ", stringify!($what), "::decode(&mut slice)?;")]
-        /// assert_eq!(slice, &[0xde, 0xad, 0xbe, 0xef]);
+        #[doc = $crate::macros::trailing_data_doc!($what, remaining)]
         /// assert_eq!(person.name, "Aristotle");
         /// assert_eq!(person.age, 61);
         /// # Ok::<_, Error>(())
@@ -277,12 +274,140 @@ macro_rules! bare_encoding {
         {
             $default.from_slice(bytes)
         }
+
+        /// Encode the given value as a length-delimited frame to the given
+        /// [`AsyncWrite`] using the default [`Encoding`].
+        ///
+        /// The frame consists of a 32-bit big-endian length prefix followed by
+        /// the encoded value, and can be read back using [`decode_async`].
+        ///
+        /// [`AsyncWrite`]: tokio::io::AsyncWrite
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        #[doc = concat!("use musli::", stringify!($what), ";")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// #[derive(Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        /// # #[tokio::main(flavor = "current_thread")] async fn main() -> Result<(), Error> {
+        /// let mut data = Vec::new();
+        ///
+        #[doc = concat!(stringify!($what), "::encode_async(&mut data, &Person {")]
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// }).await?;
+        ///
+        #[doc = concat!("let person: Person = ", stringify!($what), "::decode_async(&data[..]).await?;")]
+        /// assert_eq!(person.name, "Aristotle");
+        /// assert_eq!(person.age, 61);
+        /// # Ok(()) }
+        /// ```
+        #[cfg(feature = "tokio")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+        #[inline]
+        pub async fn encode_async<W, T>(writer: W, value: &T) -> Result<(), Error>
+        where
+            W: ::tokio::io::AsyncWrite + Unpin,
+            T: ?Sized + Encode<crate::mode::$mode>,
+        {
+            $default.encode_async(writer, value).await
+        }
+
+        /// Decode the given type `T` from a length-delimited frame read from
+        /// the given [`AsyncRead`] using the default [`Encoding`].
+        ///
+        /// This reads back frames written by [`encode_async`].
+        ///
+        /// [`AsyncRead`]: tokio::io::AsyncRead
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        #[doc = concat!("use musli::", stringify!($what), ";")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// #[derive(Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        /// # #[tokio::main(flavor = "current_thread")] async fn main() -> Result<(), Error> {
+        /// let mut data = Vec::new();
+        ///
+        #[doc = concat!(stringify!($what), "::encode_async(&mut data, &Person {")]
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// }).await?;
+        ///
+        #[doc = concat!("let person: Person = ", stringify!($what), "::decode_async(&data[..]).await?;")]
+        /// assert_eq!(person.name, "Aristotle");
+        /// assert_eq!(person.age, 61);
+        /// # Ok(()) }
+        /// ```
+        #[cfg(feature = "tokio")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+        #[inline]
+        pub async fn decode_async<R, T>(reader: R) -> Result<T, Error>
+        where
+            R: ::tokio::io::AsyncRead + Unpin,
+            T: $crate::de::DecodeOwned<$mode, Global>,
+        {
+            $default.decode_async(reader).await
+        }
     };
 }
 
 pub(crate) use bare_encoding;
 
 /// Generate all public encoding helpers.
+/// Documentation for the data left over in a reader after decoding.
+///
+/// Protobuf messages extend until the end of the input, so no extra data can
+/// be added after them.
+#[cfg(feature = "alloc")]
+macro_rules! trailing_data_doc {
+    (protobuf, extend) => {
+        "// Note: A slice implements `musli::Reader`."
+    };
+
+    (protobuf, remaining) => {
+        "assert!(slice.is_empty());"
+    };
+
+    ($what:ident, extend) => {
+        concat!(
+            "// Add some extra data which will be ignored during decoding.\n",
+            "data.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);\n",
+            "\n",
+            "// Note: A slice implements `musli::Reader`."
+        )
+    };
+
+    ($what:ident, remaining) => {
+        "assert_eq!(slice, &[0xde, 0xad, 0xbe, 0xef]);"
+    };
+}
+
+/// Implement the encoding and decoding methods of an `Encoding`.
+///
+/// If `encoder_arg = self.<field>` or `decoder_arg = self.<field>` is
+/// specified, the given field of the encoding is passed as an extra argument
+/// when constructing the encoder or decoder respectively.
+///
+/// If `decode = <path>` is specified, values are decoded by calling it with the
+/// arguments otherwise used to construct the decoder instead of calling
+/// [`Decode::decode`] directly.
+///
+/// [`Decode::decode`]: crate::de::Decode::decode
 macro_rules! encoding_impls {
     (
         $mode:ident,
@@ -290,7 +415,10 @@ macro_rules! encoding_impls {
         $encoder_new:path,
         $decoder_new:path,
         $reader_trait:ident :: $into_reader:ident,
-        $writer_trait:ident :: $into_writer:ident $(,)?
+        $writer_trait:ident :: $into_writer:ident
+        $(, encoder_arg = self.$encoder_arg:ident)?
+        $(, decoder_arg = self.$decoder_arg:ident)?
+        $(, decode = $decode:path)? $(,)?
     ) => {
         /// Encode the given value to the given [`Writer`] using the current
         /// [`Encoding`].
@@ -534,14 +662,11 @@ macro_rules! encoding_impls {
         ///     age: 61,
         /// })?;
         ///
-        /// // Add some extra data which will be ignored during decoding.
-        /// data.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
-        ///
-        /// // Note: A slice implements `musli::Reader`.
+        #[doc = $crate::macros::trailing_data_doc!($what, extend)]
         /// let mut slice = &data[..];
         /// let person: Person = ENCODING.decode(&mut slice)?;
         ///
-        /// assert_eq!(slice, &[0xde, 0xad, 0xbe, 0xef]);
+        #[doc = $crate::macros::trailing_data_doc!($what, remaining)]
         /// assert_eq!(person.name, "Aristotle");
         /// assert_eq!(person.age, 61);
         /// # Ok::<_, Error>(())
@@ -612,6 +737,111 @@ macro_rules! encoding_impls {
             self.from_slice(string.as_bytes())
         }
 
+        /// Encode the given value as a length-delimited frame to the given
+        /// [`AsyncWrite`] using the current [`Encoding`].
+        ///
+        /// The value is encoded into a buffer before the frame is written, so
+        /// the runtime is never blocked on I/O. The frame consists of a 32-bit
+        /// big-endian length prefix followed by the encoded value, and can be
+        /// read back using [`Encoding::decode_async`].
+        ///
+        /// [`AsyncWrite`]: tokio::io::AsyncWrite
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// const ENCODING: Encoding = Encoding::new();
+        ///
+        /// #[derive(Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        /// # #[tokio::main(flavor = "current_thread")] async fn main() -> Result<(), Error> {
+        /// let mut data = Vec::new();
+        ///
+        /// ENCODING.encode_async(&mut data, &Person {
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// }).await?;
+        ///
+        /// let person: Person = ENCODING.decode_async(&data[..]).await?;
+        /// assert_eq!(person.name, "Aristotle");
+        /// assert_eq!(person.age, 61);
+        /// # Ok(()) }
+        /// ```
+        #[cfg(feature = "tokio")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+        #[inline]
+        pub async fn encode_async<W, T>(self, writer: W, value: &T) -> Result<(), Error>
+        where
+            W: ::tokio::io::AsyncWrite + Unpin,
+            T: ?Sized + Encode<$mode>,
+        {
+            let bytes = self.to_vec(value)?;
+
+            $crate::frame::write_frame(writer, &bytes)
+                .await
+                .map_err(|error| $crate::context::ContextError::custom(Global, error))
+        }
+
+        /// Decode the given type `T` from a length-delimited frame read from
+        /// the given [`AsyncRead`] using the current [`Encoding`].
+        ///
+        /// The whole frame is read before decoding starts, which is why `T`
+        /// cannot borrow from the input. This reads back frames written by
+        /// [`Encoding::encode_async`].
+        ///
+        /// [`AsyncRead`]: tokio::io::AsyncRead
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// const ENCODING: Encoding = Encoding::new();
+        ///
+        /// #[derive(Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        /// # #[tokio::main(flavor = "current_thread")] async fn main() -> Result<(), Error> {
+        /// let mut data = Vec::new();
+        ///
+        /// ENCODING.encode_async(&mut data, &Person {
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// }).await?;
+        ///
+        /// let person: Person = ENCODING.decode_async(&data[..]).await?;
+        /// assert_eq!(person.name, "Aristotle");
+        /// assert_eq!(person.age, 61);
+        /// # Ok(()) }
+        /// ```
+        #[cfg(feature = "tokio")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+        #[inline]
+        pub async fn decode_async<R, T>(self, reader: R) -> Result<T, Error>
+        where
+            R: ::tokio::io::AsyncRead + Unpin,
+            T: $crate::de::DecodeOwned<$mode, Global>,
+        {
+            let bytes = $crate::frame::read_frame(reader)
+                .await
+                .map_err(|error| $crate::context::ContextError::custom(Global, error))?;
+
+            self.from_slice(&bytes)
+        }
+
         /// Encode the given value to the given [`Writer`] using the current
         /// [`Encoding`].
         ///
@@ -661,7 +891,11 @@ macro_rules! encoding_impls {
         {
             cx.clear();
             let mut writer = $writer_trait::$into_writer(writer);
-            let encoder = $encoder_new(cx, $crate::writer::Writer::borrow_mut(&mut writer));
+            let encoder = $encoder_new(
+                cx,
+                $crate::writer::Writer::borrow_mut(&mut writer)
+                $(, self.$encoder_arg)?
+            );
             T::encode(value, encoder)?;
             $crate::writer::Writer::finish(&mut writer, cx)
         }
@@ -921,7 +1155,7 @@ macro_rules! encoding_impls {
         {
             cx.clear();
             let reader = $reader_trait::$into_reader(reader);
-            T::decode($decoder_new(cx, reader))
+            $crate::macros::decode_value!($decoder_new $(, $decode)?; cx, reader $(, self.$decoder_arg)?)
         }
 
         /// Decode the given type `T` from the given slice using the current
@@ -988,6 +1222,570 @@ macro_rules! encoding_impls {
         {
             self.from_slice_with(cx, string.as_bytes())
         }
+
+        /// Encode the given value as a length-delimited frame to the given
+        /// [`AsyncWrite`] using the current [`Encoding`].
+        ///
+        /// This is the same as [`Encoding::encode_async`] but allows for using
+        /// a configurable [`Context`]. I/O errors are reported through the
+        /// context.
+        ///
+        /// [`AsyncWrite`]: tokio::io::AsyncWrite
+        /// [`Context`]: crate::Context
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        /// use musli::context;
+        #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// const ENCODING: Encoding = Encoding::new();
+        ///
+        /// #[derive(Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        /// # #[tokio::main(flavor = "current_thread")] async fn main() -> Result<(), Error> {
+        /// let cx = context::new().with_error();
+        ///
+        /// let mut data = Vec::new();
+        ///
+        /// ENCODING.encode_async_with(&cx, &mut data, &Person {
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// }).await?;
+        ///
+        /// let person: Person = ENCODING.decode_async_with(&cx, &data[..]).await?;
+        /// assert_eq!(person.name, "Aristotle");
+        /// assert_eq!(person.age, 61);
+        /// # Ok(()) }
+        /// ```
+        #[cfg(feature = "tokio")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+        #[inline]
+        pub async fn encode_async_with<C, W, T>(
+            self,
+            cx: C,
+            writer: W,
+            value: &T,
+        ) -> Result<(), C::Error>
+        where
+            C: Context,
+            W: ::tokio::io::AsyncWrite + Unpin,
+            T: ?Sized + Encode<$mode>,
+        {
+            let bytes = self.to_vec_with(cx, value)?;
+
+            $crate::frame::write_frame(writer, &bytes)
+                .await
+                .map_err(cx.map())
+        }
+
+        /// Decode the given type `T` from a length-delimited frame read from
+        /// the given [`AsyncRead`] using the current [`Encoding`].
+        ///
+        /// This is the same as [`Encoding::decode_async`] but allows for using
+        /// a configurable [`Context`]. I/O errors are reported through the
+        /// context.
+        ///
+        /// [`AsyncRead`]: tokio::io::AsyncRead
+        /// [`Context`]: crate::Context
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        /// use musli::context;
+        #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// const ENCODING: Encoding = Encoding::new();
+        ///
+        /// #[derive(Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        /// # #[tokio::main(flavor = "current_thread")] async fn main() -> Result<(), Error> {
+        /// let cx = context::new().with_error();
+        ///
+        /// let mut data = Vec::new();
+        ///
+        /// ENCODING.encode_async_with(&cx, &mut data, &Person {
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// }).await?;
+        ///
+        /// let person: Person = ENCODING.decode_async_with(&cx, &data[..]).await?;
+        /// assert_eq!(person.name, "Aristotle");
+        /// assert_eq!(person.age, 61);
+        /// # Ok(()) }
+        /// ```
+        #[cfg(feature = "tokio")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+        #[inline]
+        pub async fn decode_async_with<C, R, T>(self, cx: C, reader: R) -> Result<T, C::Error>
+        where
+            C: Context,
+            R: ::tokio::io::AsyncRead + Unpin,
+            T: $crate::de::DecodeOwned<$mode, C::Allocator>,
+        {
+            let bytes = $crate::frame::read_frame(reader).await.map_err(cx.map())?;
+
+            self.from_slice_with(cx, &bytes)
+        }
+    };
+}
+
+/// Decode a value at the root of an encoding, using the hook specified in
+/// [`encoding_impls!`] if there is one.
+macro_rules! decode_value {
+    ($decoder_new:path; $($arg:expr),* $(,)?) => {
+        $crate::de::Decode::decode($decoder_new($($arg),*))
+    };
+
+    ($decoder_new:path, $decode:path; $($arg:expr),* $(,)?) => {
+        $decode($($arg),*)
+    };
+}
+
+/// Generate a streaming iterator over values stored back to back.
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+macro_rules! decode_iter {
+    (
+        $what:ident,
+        [$($generics:tt)*] $encoding:ty,
+        $mode:ident,
+        $decoder_new:path,
+        $into_trait:ident :: $into_fn:ident -> $assoc:ident,
+        $trait:ident,
+        $(decoder_arg = self.$decoder_arg:ident,)?
+        $(decode = $decode:path,)?
+        |$cx:ident, $source:ident $(, $this:ident)?| $is_eof:expr $(,)?
+    ) => {
+        /// An iterator over values of type `T` which are stored back to back in
+        /// a single source.
+        ///
+        /// Iteration stops cleanly once the source has been exhausted. If
+        /// decoding a value fails no further values are produced, and the
+        /// error returned by an iterator constructed through
+        /// [`Encoding::decode_iter`] is prefixed with the index of the record
+        /// which failed to decode, like `Record 2: ..`.
+        ///
+        /// While a value is being decoded the context has entered the sequence
+        /// index of the record, so a custom context which traces errors reports
+        /// which record failed to decode.
+        ///
+        /// Constructed through [`Encoding::decode_iter`] or
+        /// [`Encoding::decode_iter_with`].
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        pub struct DecodeIter<'de, R, T, C = DefaultIterContext, E = Encoding> {
+            cx: C,
+            source: R,
+            // Only read by encodings which pass configuration to their decoder.
+            #[allow(dead_code)]
+            encoding: E,
+            index: usize,
+            done: bool,
+            _marker: marker::PhantomData<fn(&'de ()) -> T>,
+        }
+
+        /// The context used by [`Encoding::decode_iter`].
+        #[cfg(feature = "alloc")]
+        type DefaultIterContext = $crate::context::DefaultContext<
+            Global,
+            $crate::context::NoTrace,
+            $crate::context::Emit<Error>,
+        >;
+
+        #[cfg(feature = "alloc")]
+        impl<$($generics)*> $encoding
+        where
+            $mode: 'static,
+        {
+            /// Construct an iterator decoding values of type `T` which are
+            /// stored back to back in the given source using the current
+            /// [`Encoding`].
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use musli::{Decode, Encode};
+            #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+            #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+            ///
+            /// const ENCODING: Encoding = Encoding::new();
+            ///
+            /// #[derive(Debug, PartialEq, Decode, Encode)]
+            /// struct Person {
+            ///     name: String,
+            ///     age: u32,
+            /// }
+            ///
+            /// let mut data = Vec::new();
+            ///
+            /// ENCODING.to_writer(&mut data, &Person { name: "Aristotle".to_string(), age: 61 })?;
+            /// ENCODING.to_writer(&mut data, &Person { name: "Plato".to_string(), age: 80 })?;
+            ///
+            /// let people = ENCODING.decode_iter::<_, Person>(&data[..]).collect::<Result<Vec<_>, _>>()?;
+            ///
+            /// assert_eq!(people, [
+            ///     Person { name: "Aristotle".to_string(), age: 61 },
+            ///     Person { name: "Plato".to_string(), age: 80 },
+            /// ]);
+            /// # Ok::<_, Error>(())
+            /// ```
+            #[inline]
+            pub fn decode_iter<'de, R, T>(self, source: R) -> DecodeIter<'de, R::$assoc, T, DefaultIterContext, Self>
+            where
+                R: $into_trait<'de>,
+                T: Decode<'de, $mode, Global>,
+            {
+                DecodeIter {
+                    cx: $crate::context::new().with_error(),
+                    source: $into_trait::$into_fn(source),
+                    encoding: self,
+                    index: 0,
+                    done: false,
+                    _marker: marker::PhantomData,
+                }
+            }
+
+            /// Construct an iterator decoding values of type `T` which are
+            /// stored back to back in the given source using the current
+            /// [`Encoding`].
+            ///
+            /// This is the same as [`Encoding::decode_iter`], but allows for
+            /// using a configurable [`Context`].
+            ///
+            /// [`Context`]: crate::Context
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use musli::{Decode, Encode};
+            /// use musli::context;
+            #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+            #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+            ///
+            /// const ENCODING: Encoding = Encoding::new();
+            ///
+            /// #[derive(Debug, PartialEq, Decode, Encode)]
+            /// struct Person {
+            ///     name: String,
+            ///     age: u32,
+            /// }
+            ///
+            /// let cx = context::new().with_error();
+            ///
+            /// let mut data = Vec::new();
+            ///
+            /// ENCODING.to_writer_with(&cx, &mut data, &Person { name: "Aristotle".to_string(), age: 61 })?;
+            /// ENCODING.to_writer_with(&cx, &mut data, &Person { name: "Plato".to_string(), age: 80 })?;
+            ///
+            /// let people = ENCODING.decode_iter_with::<_, _, Person>(&cx, &data[..]).collect::<Result<Vec<_>, _>>()?;
+            ///
+            /// assert_eq!(people, [
+            ///     Person { name: "Aristotle".to_string(), age: 61 },
+            ///     Person { name: "Plato".to_string(), age: 80 },
+            /// ]);
+            /// # Ok::<_, Error>(())
+            /// ```
+            #[inline]
+            pub fn decode_iter_with<'de, C, R, T>(self, cx: C, source: R) -> DecodeIter<'de, R::$assoc, T, C, Self>
+            where
+                C: Context,
+                R: $into_trait<'de>,
+                T: Decode<'de, $mode, C::Allocator>,
+            {
+                DecodeIter {
+                    cx,
+                    source: $into_trait::$into_fn(source),
+                    encoding: self,
+                    index: 0,
+                    done: false,
+                    _marker: marker::PhantomData,
+                }
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'de, R, T, C, $($generics)*> DecodeIter<'de, R, T, C, $encoding>
+        where
+            $mode: 'static,
+        {
+            /// Get the number of values which have been decoded so far.
+            #[inline]
+            pub fn index(&self) -> usize {
+                self.index
+            }
+
+            /// Coerce the iterator into the underlying source, which is
+            /// positioned right after the last decoded value.
+            #[inline]
+            pub fn into_inner(self) -> R {
+                self.source
+            }
+        }
+
+        /// Decode the next value of a [`DecodeIter`].
+        #[cfg(feature = "alloc")]
+        #[inline]
+        fn decode_iter_next<'de, R, T, C, $($generics)*>(
+            cx: C,
+            source: &mut R,
+            // Only read by encodings which pass configuration to their decoder.
+            #[allow(unused_variables)] encoding: &$encoding,
+            index: &mut usize,
+            done: &mut bool,
+        ) -> Option<Result<T, C::Error>>
+        where
+            C: Context,
+            R: $trait<'de>,
+            T: Decode<'de, $mode, C::Allocator>,
+            $mode: 'static,
+        {
+            if *done {
+                return None;
+            }
+
+            let is_eof = {
+                let $cx = cx;
+                let $source = &mut *source;
+                $(let $this = encoding;)?
+                $is_eof
+            };
+
+            if is_eof {
+                *done = true;
+                return None;
+            }
+
+            cx.enter_sequence_index(*index);
+
+            let value = $crate::macros::decode_value!(
+                $decoder_new $(, $decode)?;
+                cx,
+                $trait::borrow_mut(source)
+                $(, encoding.$decoder_arg)?
+            );
+
+            if value.is_err() {
+                *done = true;
+                return Some(value);
+            }
+
+            cx.leave_sequence_index();
+            *index += 1;
+            Some(value)
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'de, R, T, C, $($generics)*> Iterator for DecodeIter<'de, R, T, C, $encoding>
+        where
+            C: Context,
+            R: $trait<'de>,
+            T: Decode<'de, $mode, C::Allocator>,
+            $mode: 'static,
+        {
+            type Item = Result<T, C::Error>;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                decode_iter_next(
+                    self.cx,
+                    &mut self.source,
+                    &self.encoding,
+                    &mut self.index,
+                    &mut self.done,
+                )
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'de, R, T, $($generics)*> Iterator for DecodeIter<'de, R, T, DefaultIterContext, $encoding>
+        where
+            R: $trait<'de>,
+            T: Decode<'de, $mode, Global>,
+            $mode: 'static,
+        {
+            type Item = Result<T, Error>;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let index = self.index;
+
+                let value = decode_iter_next(
+                    &self.cx,
+                    &mut self.source,
+                    &self.encoding,
+                    &mut self.index,
+                    &mut self.done,
+                )?;
+
+                Some(value.map_err(|error| {
+                    $crate::context::ContextError::message(
+                        Global,
+                        format_args!("Record {index}: {error}"),
+                    )
+                }))
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'de, R, T, C, $($generics)*> core::iter::FusedIterator for DecodeIter<'de, R, T, C, $encoding>
+        where
+            Self: Iterator,
+            $mode: 'static,
+        {
+        }
+    };
+}
+
+/// Implement [`LazySeqEncoding`] and [`LazyMapEncoding`] for an encoding which
+/// supports lazy sequences and maps, decoding values through [`DecodeIter`].
+///
+/// This is only implemented by formats which can skip over values without
+/// decoding them.
+///
+/// [`LazySeqEncoding`]: crate::de::LazySeqEncoding
+/// [`LazyMapEncoding`]: crate::de::LazyMapEncoding
+#[cfg(any(feature = "wire", feature = "descriptive"))]
+macro_rules! lazy_encoding {
+    ([$($generics:tt)*] $encoding:ty, $mode:ident) => {
+        /// An iterator over the entries of a [`LazyMap`].
+        ///
+        /// If decoding an entry fails no further entries are produced, and the
+        /// error is prefixed with the index of the entry which failed to
+        /// decode, like `Entry 2: ..`.
+        ///
+        /// Constructed through [`LazyMap::iter`].
+        ///
+        /// [`LazyMap`]: crate::de::LazyMap
+        /// [`LazyMap::iter`]: crate::de::LazyMap::iter
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        pub struct LazyMapIter<'de, K, V, E = Encoding> {
+            cx: DefaultIterContext,
+            source: &'de [u8],
+            encoding: E,
+            index: usize,
+            done: bool,
+            _marker: marker::PhantomData<fn() -> (K, V)>,
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'de, T, $($generics)*> $crate::de::LazySeqEncoding<'de, T> for $encoding
+        where
+            T: Decode<'de, $mode, Global>,
+            $mode: 'static,
+        {
+            type Error = Error;
+            type Iter = DecodeIter<'de, &'de [u8], T, DefaultIterContext, Self>;
+
+            #[inline]
+            fn decode_lazy(bytes: &'de [u8]) -> Self::Iter {
+                let encoding = Self {
+                    _marker: marker::PhantomData,
+                };
+
+                encoding.decode_iter(bytes)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'de, K, V, $($generics)*> $crate::de::LazyMapEncoding<'de, K, V> for $encoding
+        where
+            K: Decode<'de, $mode, Global>,
+            V: Decode<'de, $mode, Global>,
+            $mode: 'static,
+        {
+            type Error = Error;
+            type Iter = LazyMapIter<'de, K, V, Self>;
+
+            #[inline]
+            fn decode_lazy_map(bytes: &'de [u8]) -> Self::Iter {
+                LazyMapIter {
+                    cx: $crate::context::new().with_error(),
+                    source: bytes,
+                    encoding: Self {
+                        _marker: marker::PhantomData,
+                    },
+                    index: 0,
+                    done: false,
+                    _marker: marker::PhantomData,
+                }
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'de, K, V, $($generics)*> Iterator for LazyMapIter<'de, K, V, $encoding>
+        where
+            K: Decode<'de, $mode, Global>,
+            V: Decode<'de, $mode, Global>,
+            $mode: 'static,
+        {
+            type Item = Result<(K, V), Error>;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let index = self.index;
+
+                let key: Result<K, Error> = decode_iter_next(
+                    &self.cx,
+                    &mut self.source,
+                    &self.encoding,
+                    &mut { index },
+                    &mut self.done,
+                )?;
+
+                let entry = key.and_then(|key| {
+                    let value = decode_iter_next(
+                        &self.cx,
+                        &mut self.source,
+                        &self.encoding,
+                        &mut { index },
+                        &mut self.done,
+                    );
+
+                    match value {
+                        Some(value) => Ok((key, value?)),
+                        None => Err($crate::context::ContextError::message(
+                            Global,
+                            "Missing value of entry",
+                        )),
+                    }
+                });
+
+                self.index += 1;
+
+                Some(entry.map_err(|error| {
+                    $crate::context::ContextError::message(
+                        Global,
+                        format_args!("Entry {index}: {error}"),
+                    )
+                }))
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'de, K, V, $($generics)*> core::iter::FusedIterator for LazyMapIter<'de, K, V, $encoding>
+        where
+            Self: Iterator,
+            $mode: 'static,
+        {
+        }
     };
 }
 
@@ -1120,5 +1918,17 @@ macro_rules! implement_error {
     };
 }
 
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+pub(crate) use decode_iter;
+pub(crate) use decode_value;
 pub(crate) use encoding_impls;
 pub(crate) use implement_error;
+#[cfg(any(feature = "wire", feature = "descriptive"))]
+pub(crate) use lazy_encoding;
+#[cfg(feature = "alloc")]
+pub(crate) use trailing_data_doc;
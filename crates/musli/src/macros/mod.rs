@@ -3,6 +3,7 @@
 #[cfg(any(
     feature = "storage",
     feature = "wire",
+    feature = "protobuf",
     feature = "descriptive",
     feature = "value"
 ))]
@@ -13,13 +14,35 @@ mod internal;
     feature = "descriptive",
     feature = "value"
 ))]
-pub(crate) use self::internal::{bare_encoding, encoding_impls, implement_error};
+pub(crate) use self::internal::decode_iter;
+#[cfg(any(feature = "wire", feature = "descriptive"))]
+pub(crate) use self::internal::lazy_encoding;
+#[cfg(all(
+    feature = "alloc",
+    any(
+        feature = "storage",
+        feature = "wire",
+        feature = "protobuf",
+        feature = "descriptive",
+        feature = "value"
+    )
+))]
+pub(crate) use self::internal::trailing_data_doc;
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "protobuf",
+    feature = "descriptive",
+    feature = "value"
+))]
+pub(crate) use self::internal::{bare_encoding, decode_value, encoding_impls, implement_error};
 
 #[cfg(all(
     feature = "test",
     any(
         feature = "storage",
         feature = "wire",
+        feature = "protobuf",
         feature = "descriptive",
         feature = "value"
     )
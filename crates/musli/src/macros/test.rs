@@ -389,6 +389,9 @@ macro_rules! __test_matrix {
         $call!(descriptive, Binary);
         $call!(descriptive, Text);
         $call!(json, Text);
+        $call!(msgpack, Text);
+        $call!(cbor, Binary);
+        $call!(cbor, Text);
     };
 
     (not_packed, $call:path) => {
@@ -396,6 +399,8 @@ macro_rules! __test_matrix {
         $call!(wire, Binary);
         $call!(descriptive, Binary);
         $call!(json, Text);
+        $call!(msgpack, Text);
+        $call!(cbor, Binary);
     };
 
     (text_mode, $call:path) => {
@@ -403,6 +408,8 @@ macro_rules! __test_matrix {
         $call!(wire, Text);
         $call!(descriptive, Text);
         $call!(json, Text);
+        $call!(msgpack, Text);
+        $call!(cbor, Text);
     };
 
     (binary_mode, $call:path) => {
@@ -411,6 +418,8 @@ macro_rules! __test_matrix {
         $call!(wire, Binary);
         $call!(descriptive, Binary);
         $call!(json, Binary);
+        $call!(msgpack, Binary);
+        $call!(cbor, Binary);
     };
 
     (no_json, $call:path) => {
@@ -423,6 +432,8 @@ macro_rules! __test_matrix {
     (descriptive, $call:path) => {
         $call!(descriptive, Binary);
         $call!(json, Text);
+        $call!(msgpack, Binary);
+        $call!(cbor, Binary);
     };
 
     (json, $call:path) => {
@@ -437,6 +448,8 @@ macro_rules! __test_matrix {
         $call!(wire, Binary);
         $call!(descriptive, Binary);
         $call!(json, Text);
+        $call!(msgpack, Binary);
+        $call!(cbor, Binary);
     };
 }
 
@@ -0,0 +1,933 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::take;
+
+use crate::Context;
+use crate::Reader;
+use crate::alloc::Vec;
+use crate::de::{
+    Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder, SizeHint, Skip,
+    UnsizedVisitor, VariantDecoder, Visitor,
+};
+use crate::options::{self, Options};
+use crate::value::{IntoValueDecoder, Value};
+
+use super::tag::{self, Kind, Marker};
+
+const BUFFER_OPTIONS: Options = options::new().build();
+
+/// A decoder for MessagePack.
+pub struct MsgPackDecoder<R, C, M> {
+    cx: C,
+    reader: R,
+    _marker: PhantomData<M>,
+}
+
+impl<R, C, M> MsgPackDecoder<R, C, M> {
+    /// Construct a new MessagePack decoder.
+    #[inline]
+    pub(crate) fn new(cx: C, reader: R) -> Self {
+        Self {
+            cx,
+            reader,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A decoded integer, before it's been converted into the requested type.
+enum Integer {
+    Unsigned(u64),
+    Signed(i64),
+    Unsigned128(u128),
+    Signed128(i128),
+}
+
+impl<'de, R, C, M> MsgPackDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    /// Skip over any value, including nested containers.
+    pub(crate) fn skip_any(mut self) -> Result<(), C::Error> {
+        let mut remaining = 1usize;
+
+        while remaining > 0 {
+            remaining -= 1;
+
+            let pos = self.cx.mark();
+            let b = self.reader.read_byte(self.cx)?;
+
+            let skip = match b {
+                0x00..=tag::POSITIVE_FIXINT_MAX
+                | tag::NEGATIVE_FIXINT_MIN..=0xff
+                | tag::NIL
+                | tag::FALSE
+                | tag::TRUE => 0,
+                0x80..=0x8f | tag::MAP16 | tag::MAP32 => {
+                    let len = self.decode_len(b)?;
+
+                    let Some(n) = len.checked_mul(2).and_then(|n| remaining.checked_add(n)) else {
+                        return Err(self.cx.message_at(&pos, "Map length overflow"));
+                    };
+
+                    remaining = n;
+                    0
+                }
+                0x90..=0x9f | tag::ARRAY16 | tag::ARRAY32 => {
+                    let len = self.decode_len(b)?;
+
+                    let Some(n) = remaining.checked_add(len) else {
+                        return Err(self.cx.message_at(&pos, "Array length overflow"));
+                    };
+
+                    remaining = n;
+                    0
+                }
+                0xa0..=0xbf
+                | tag::STR8
+                | tag::STR16
+                | tag::STR32
+                | tag::BIN8
+                | tag::BIN16
+                | tag::BIN32 => self.decode_len(b)?,
+                tag::EXT8 | tag::EXT16 | tag::EXT32 => {
+                    let Some(n) = self.decode_len(b)?.checked_add(1) else {
+                        return Err(self.cx.message_at(&pos, "Extension length overflow"));
+                    };
+
+                    n
+                }
+                tag::UINT8 | tag::INT8 => 1,
+                tag::UINT16 | tag::INT16 => 2,
+                tag::UINT32 | tag::INT32 | tag::FLOAT32 => 4,
+                tag::UINT64 | tag::INT64 | tag::FLOAT64 => 8,
+                tag::FIXEXT1 => 2,
+                tag::FIXEXT2 => 3,
+                tag::FIXEXT4 => 5,
+                tag::FIXEXT8 => 9,
+                tag::FIXEXT16 => 17,
+                b => {
+                    return Err(self
+                        .cx
+                        .message_at(&pos, format_args!("Cannot skip over {}", Marker(b))));
+                }
+            };
+
+            if skip > 0 {
+                self.reader.skip(self.cx, skip)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decode the length which follows the given marker.
+    #[inline]
+    fn decode_len(&mut self, b: u8) -> Result<usize, C::Error> {
+        let len = match b {
+            0x80..=0x9f => usize::from(b & 0x0f),
+            0xa0..=0xbf => usize::from(b & 0x1f),
+            tag::STR8 | tag::BIN8 | tag::EXT8 => usize::from(self.reader.read_byte(self.cx)?),
+            tag::STR16 | tag::BIN16 | tag::EXT16 | tag::ARRAY16 | tag::MAP16 => {
+                usize::from(u16::from_be_bytes(self.reader.read_array(self.cx)?))
+            }
+            tag::STR32 | tag::BIN32 | tag::EXT32 | tag::ARRAY32 | tag::MAP32 => {
+                let len = u32::from_be_bytes(self.reader.read_array(self.cx)?);
+
+                let Ok(len) = usize::try_from(len) else {
+                    return Err(self.cx.message(format_args!(
+                        "Length {len} does not fit in a usize on this platform"
+                    )));
+                };
+
+                len
+            }
+            b => {
+                return Err(self
+                    .cx
+                    .message(format_args!("Expected length, but got {}", Marker(b))));
+            }
+        };
+
+        Ok(len)
+    }
+
+    /// Decode a header of the given kind and return its length.
+    #[inline]
+    fn decode_header(&mut self, kind: Kind) -> Result<usize, C::Error> {
+        let pos = self.cx.mark();
+        let b = self.reader.read_byte(self.cx)?;
+
+        if Kind::from_byte(b) != kind {
+            return Err(self
+                .cx
+                .message_at(&pos, format_args!("Expected {kind}, but got {}", Marker(b))));
+        }
+
+        self.decode_len(b)
+    }
+
+    #[inline]
+    fn decode_integer(&mut self) -> Result<Integer, C::Error> {
+        let pos = self.cx.mark();
+        let b = self.reader.read_byte(self.cx)?;
+
+        let integer = match b {
+            0x00..=tag::POSITIVE_FIXINT_MAX => Integer::Unsigned(u64::from(b)),
+            tag::NEGATIVE_FIXINT_MIN..=0xff => Integer::Signed(i64::from(b as i8)),
+            tag::UINT8 => Integer::Unsigned(u64::from(self.reader.read_byte(self.cx)?)),
+            tag::UINT16 => {
+                Integer::Unsigned(u16::from_be_bytes(self.reader.read_array(self.cx)?).into())
+            }
+            tag::UINT32 => {
+                Integer::Unsigned(u32::from_be_bytes(self.reader.read_array(self.cx)?).into())
+            }
+            tag::UINT64 => Integer::Unsigned(u64::from_be_bytes(self.reader.read_array(self.cx)?)),
+            tag::INT8 => Integer::Signed(i64::from(self.reader.read_byte(self.cx)? as i8)),
+            tag::INT16 => {
+                Integer::Signed(i16::from_be_bytes(self.reader.read_array(self.cx)?).into())
+            }
+            tag::INT32 => {
+                Integer::Signed(i32::from_be_bytes(self.reader.read_array(self.cx)?).into())
+            }
+            tag::INT64 => Integer::Signed(i64::from_be_bytes(self.reader.read_array(self.cx)?)),
+            tag::FIXEXT16 => match self.reader.read_byte(self.cx)? {
+                tag::EXT_U128 => {
+                    Integer::Unsigned128(u128::from_be_bytes(self.reader.read_array(self.cx)?))
+                }
+                tag::EXT_I128 => {
+                    Integer::Signed128(i128::from_be_bytes(self.reader.read_array(self.cx)?))
+                }
+                ty => {
+                    return Err(self.cx.message_at(
+                        &pos,
+                        format_args!("Expected integer, but got extension type {ty}"),
+                    ));
+                }
+            },
+            b => {
+                return Err(self.cx.message_at(
+                    &pos,
+                    format_args!("Expected integer, but got {}", Marker(b)),
+                ));
+            }
+        };
+
+        Ok(integer)
+    }
+
+    #[inline]
+    fn decode_unsigned<T>(mut self) -> Result<T, C::Error>
+    where
+        T: TryFrom<u128>,
+    {
+        let pos = self.cx.mark();
+
+        let value = match self.decode_integer()? {
+            Integer::Unsigned(value) => Some(u128::from(value)),
+            Integer::Signed(value) => u128::try_from(value).ok(),
+            Integer::Unsigned128(value) => Some(value),
+            Integer::Signed128(value) => u128::try_from(value).ok(),
+        };
+
+        match value.and_then(|value| T::try_from(value).ok()) {
+            Some(value) => Ok(value),
+            None => Err(self.cx.message_at(&pos, "Integer out of range")),
+        }
+    }
+
+    #[inline]
+    fn decode_signed<T>(mut self) -> Result<T, C::Error>
+    where
+        T: TryFrom<i128>,
+    {
+        let pos = self.cx.mark();
+
+        let value = match self.decode_integer()? {
+            Integer::Unsigned(value) => Some(i128::from(value)),
+            Integer::Signed(value) => Some(i128::from(value)),
+            Integer::Unsigned128(value) => i128::try_from(value).ok(),
+            Integer::Signed128(value) => Some(value),
+        };
+
+        match value.and_then(|value| T::try_from(value).ok()) {
+            Some(value) => Ok(value),
+            None => Err(self.cx.message_at(&pos, "Integer out of range")),
+        }
+    }
+
+    #[inline]
+    fn shared_decode_sequence(mut self) -> Result<RemainingMsgPackDecoder<R, C, M>, C::Error> {
+        let len = self.decode_header(Kind::Array)?;
+        Ok(RemainingMsgPackDecoder::new(self.cx, self.reader, len))
+    }
+
+    #[inline]
+    fn shared_decode_map(mut self) -> Result<RemainingMsgPackDecoder<R, C, M>, C::Error> {
+        let len = self.decode_header(Kind::Map)?;
+        Ok(RemainingMsgPackDecoder::new(self.cx, self.reader, len))
+    }
+}
+
+/// A length-prefixed decode wrapper used for arrays and maps.
+pub struct RemainingMsgPackDecoder<R, C, M> {
+    cx: C,
+    reader: R,
+    remaining: usize,
+    _marker: PhantomData<M>,
+}
+
+impl<'de, R, C, M> RemainingMsgPackDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    #[inline]
+    fn new(cx: C, reader: R, remaining: usize) -> Self {
+        Self {
+            cx,
+            reader,
+            remaining,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn skip_sequence_remaining(mut self) -> Result<(), C::Error> {
+        while let Some(item) = self.try_decode_next()? {
+            item.skip()?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn skip_map_remaining(mut self) -> Result<(), C::Error> {
+        loop {
+            let Some(key) = self.decode_entry_key()? else {
+                break;
+            };
+
+            key.skip()?;
+            self.decode_entry_value()?.skip()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[crate::trait_defaults(crate)]
+impl<'de, R, C, M> Decoder<'de> for MsgPackDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type TryClone = MsgPackDecoder<R::TryClone, C, M>;
+    type DecodeBuffer = IntoValueDecoder<BUFFER_OPTIONS, C, C::Allocator, M>;
+    type DecodePack = RemainingMsgPackDecoder<R, C, M>;
+    type DecodeSome = Self;
+    type DecodeSequence = RemainingMsgPackDecoder<R, C, M>;
+    type DecodeMap = RemainingMsgPackDecoder<R, C, M>;
+    type DecodeMapEntries = RemainingMsgPackDecoder<R, C, M>;
+    type DecodeVariant = Self;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the MessagePack decoder")
+    }
+
+    #[inline]
+    fn try_clone(&self) -> Option<Self::TryClone> {
+        Some(MsgPackDecoder::new(self.cx, self.reader.try_clone()?))
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), Self::Error> {
+        self.skip_any()
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, Self::Error> {
+        self.skip()?;
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_buffer(self) -> Result<Self::DecodeBuffer, Self::Error> {
+        let cx = self.cx;
+        let value = self.decode::<Value<Self::Allocator>>()?;
+        Ok(value.into_decoder(cx))
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), Self::Error> {
+        self.skip()
+    }
+
+    #[inline]
+    fn decode_pack<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodePack) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.shared_decode_sequence()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_sequence_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_array<const N: usize>(mut self) -> Result<[u8; N], Self::Error> {
+        let pos = self.cx.mark();
+        let len = self.decode_header(Kind::Bytes)?;
+
+        if len != N {
+            return Err(self.cx.message_at(
+                &pos,
+                format_args! {
+                    "Bad length, got {len} but expect {N}"
+                },
+            ));
+        }
+
+        self.reader.read_array(self.cx)
+    }
+
+    #[inline]
+    fn decode_bytes<V>(mut self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8], Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        let pos = self.cx.mark();
+        let b = self.reader.read_byte(self.cx)?;
+
+        if !matches!(Kind::from_byte(b), Kind::Bytes | Kind::String) {
+            return Err(self
+                .cx
+                .message_at(&pos, format_args!("Expected bytes, but got {}", Marker(b))));
+        }
+
+        let len = self.decode_len(b)?;
+        self.reader.read_bytes(self.cx, len, visitor)
+    }
+
+    #[inline]
+    fn decode_string<V>(mut self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: UnsizedVisitor<'de, C, str, Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        struct Visitor<V>(V);
+
+        #[crate::trait_defaults(crate)]
+        impl<'de, C, V> UnsizedVisitor<'de, C, [u8]> for Visitor<V>
+        where
+            C: Context,
+            V: UnsizedVisitor<'de, C, str, Error = C::Error, Allocator = C::Allocator>,
+        {
+            type Ok = V::Ok;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.expecting(f)
+            }
+
+            #[inline]
+            fn visit_owned(
+                self,
+                cx: C,
+                bytes: Vec<u8, Self::Allocator>,
+            ) -> Result<Self::Ok, Self::Error> {
+                let string = crate::str::from_utf8_owned(bytes).map_err(cx.map())?;
+                self.0.visit_owned(cx, string)
+            }
+
+            #[inline]
+            fn visit_borrowed(self, cx: C, bytes: &'de [u8]) -> Result<Self::Ok, Self::Error> {
+                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                self.0.visit_borrowed(cx, string)
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: C, bytes: &[u8]) -> Result<Self::Ok, Self::Error> {
+                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                self.0.visit_ref(cx, string)
+            }
+        }
+
+        let len = self.decode_header(Kind::String)?;
+        self.reader.read_bytes(self.cx, len, Visitor(visitor))
+    }
+
+    #[inline]
+    fn decode_number<V>(mut self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: Visitor<'de, C, Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        let cx = self.cx;
+
+        let Some(b) = self.reader.peek() else {
+            return Err(cx.message("Expected number in input"));
+        };
+
+        match b {
+            tag::FLOAT32 => {
+                let value = self.decode_f32()?;
+                visitor.visit_f32(cx, value)
+            }
+            tag::FLOAT64 => {
+                let value = self.decode_f64()?;
+                visitor.visit_f64(cx, value)
+            }
+            _ => match self.decode_integer()? {
+                Integer::Unsigned(value) => visitor.visit_u64(cx, value),
+                Integer::Signed(value) => visitor.visit_i64(cx, value),
+                Integer::Unsigned128(value) => visitor.visit_u128(cx, value),
+                Integer::Signed128(value) => visitor.visit_i128(cx, value),
+            },
+        }
+    }
+
+    #[inline]
+    fn decode_bool(mut self) -> Result<bool, Self::Error> {
+        let pos = self.cx.mark();
+
+        match self.reader.read_byte(self.cx)? {
+            tag::FALSE => Ok(false),
+            tag::TRUE => Ok(true),
+            b => Err(self.cx.message_at(
+                &pos,
+                format_args!("Expected boolean, but got {}", Marker(b)),
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_char(mut self) -> Result<char, Self::Error> {
+        let pos = self.cx.mark();
+        let len = self.decode_header(Kind::String)?;
+
+        if len > 4 {
+            return Err(self.cx.message_at(
+                &pos,
+                format_args!("Expected character, but got string of length {len}"),
+            ));
+        }
+
+        let mut buf = [0; 4];
+        self.reader.read(self.cx, &mut buf[..len])?;
+
+        let string = crate::str::from_utf8(&buf[..len]).map_err(self.cx.map())?;
+        let mut chars = string.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(self.cx.message_at(&pos, "Expected exactly one character")),
+        }
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u128(self) -> Result<u128, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i128(self) -> Result<i128, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, Self::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, Self::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_f32(mut self) -> Result<f32, Self::Error> {
+        let pos = self.cx.mark();
+
+        match self.reader.read_byte(self.cx)? {
+            tag::FLOAT32 => Ok(f32::from_be_bytes(self.reader.read_array(self.cx)?)),
+            tag::FLOAT64 => Ok(f64::from_be_bytes(self.reader.read_array(self.cx)?) as f32),
+            b => Err(self
+                .cx
+                .message_at(&pos, format_args!("Expected float, but got {}", Marker(b)))),
+        }
+    }
+
+    #[inline]
+    fn decode_f64(mut self) -> Result<f64, Self::Error> {
+        let pos = self.cx.mark();
+
+        match self.reader.read_byte(self.cx)? {
+            tag::FLOAT32 => Ok(f32::from_be_bytes(self.reader.read_array(self.cx)?).into()),
+            tag::FLOAT64 => Ok(f64::from_be_bytes(self.reader.read_array(self.cx)?)),
+            b => Err(self
+                .cx
+                .message_at(&pos, format_args!("Expected float, but got {}", Marker(b)))),
+        }
+    }
+
+    #[inline]
+    fn decode_option(mut self) -> Result<Option<Self::DecodeSome>, Self::Error> {
+        if self.reader.peek() == Some(tag::NIL) {
+            self.reader.skip(self.cx, 1)?;
+            return Ok(None);
+        }
+
+        Ok(Some(self))
+    }
+
+    #[inline]
+    fn decode_sequence<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeSequence) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.shared_decode_sequence()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_sequence_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.shared_decode_map()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_map_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.shared_decode_map()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_map_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_variant<F, O>(mut self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeVariant) -> Result<O, Self::Error>,
+    {
+        let pos = self.cx.mark();
+        let len = self.decode_header(Kind::Map)?;
+
+        if len != 1 {
+            return Err(self.cx.message_at(
+                &pos,
+                format_args!("Expected variant as map with one entry, but got {len} entries"),
+            ));
+        }
+
+        f(&mut self)
+    }
+
+    #[inline]
+    fn decode_any<V>(mut self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: Visitor<'de, C, Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        let cx = self.cx;
+
+        let Some(b) = self.reader.peek() else {
+            return Err(cx.message("Expected value in input"));
+        };
+
+        match Kind::from_byte(b) {
+            Kind::Nil => {
+                self.reader.skip(cx, 1)?;
+                visitor.visit_empty(cx)
+            }
+            Kind::Bool => {
+                let value = self.decode_bool()?;
+                visitor.visit_bool(cx, value)
+            }
+            Kind::Unsigned | Kind::Signed | Kind::Float => self.decode_number(visitor),
+            Kind::Extension if b == tag::FIXEXT16 => self.decode_number(visitor),
+            Kind::String => {
+                let visitor = visitor.visit_string(cx, SizeHint::any())?;
+                self.decode_string(visitor)
+            }
+            Kind::Bytes => {
+                let visitor = visitor.visit_bytes(cx, SizeHint::any())?;
+                self.decode_bytes(visitor)
+            }
+            Kind::Array => self.decode_sequence(|decoder| visitor.visit_sequence(decoder)),
+            Kind::Map => self.decode_map(|decoder| visitor.visit_map(decoder)),
+            kind => Err(cx.message(format_args!("Unsupported {kind} {}", Marker(b)))),
+        }
+    }
+}
+
+impl<'de, R, C, M> SequenceDecoder<'de> for RemainingMsgPackDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeNext<'this>
+        = MsgPackDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::exact(self.remaining)
+    }
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, Self::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        Ok(Some(MsgPackDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, Self::Error> {
+        let cx = self.cx;
+
+        let Some(decoder) = self.try_decode_next()? else {
+            return Err(cx.message("No remaining elements"));
+        };
+
+        Ok(decoder)
+    }
+}
+
+impl<'de, R, C, M> MapDecoder<'de> for RemainingMsgPackDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeEntry<'this>
+        = MsgPackDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+    type DecodeRemainingEntries<'this>
+        = RemainingMsgPackDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::exact(self.remaining)
+    }
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, Self::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        Ok(Some(MsgPackDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_remaining_entries(
+        &mut self,
+    ) -> Result<Self::DecodeRemainingEntries<'_>, Self::Error> {
+        Ok(RemainingMsgPackDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            take(&mut self.remaining),
+        ))
+    }
+}
+
+impl<'de, R, C, M> EntriesDecoder<'de> for RemainingMsgPackDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeEntryKey<'this>
+        = MsgPackDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+    type DecodeEntryValue<'this>
+        = MsgPackDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, Self::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        Ok(Some(MsgPackDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, Self::Error> {
+        Ok(MsgPackDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), Self::Error> {
+        self.skip_map_remaining()?;
+        Ok(())
+    }
+}
+
+impl<'de, R, C, M> EntryDecoder<'de> for MsgPackDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeKey<'this>
+        = MsgPackDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+    type DecodeValue = Self;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, Self::Error> {
+        Ok(MsgPackDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn decode_value(self) -> Result<Self::DecodeValue, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl<'de, R, C, M> VariantDecoder<'de> for MsgPackDecoder<R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeTag<'this>
+        = MsgPackDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+    type DecodeValue<'this>
+        = MsgPackDecoder<R::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, Self::Error> {
+        Ok(MsgPackDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn decode_value(&mut self) -> Result<Self::DecodeValue<'_>, Self::Error> {
+        Ok(MsgPackDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+}
@@ -0,0 +1,665 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::en::{
+    Encode, Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder,
+};
+use crate::hint::{MapHint, SequenceHint};
+use crate::writer::BufWriter;
+use crate::{Context, Writer};
+
+use super::tag;
+
+/// An encoder for MessagePack.
+pub struct MsgPackEncoder<W, C, M> {
+    cx: C,
+    writer: W,
+    _marker: PhantomData<M>,
+}
+
+impl<W, C, M> MsgPackEncoder<W, C, M> {
+    /// Construct a new MessagePack encoder.
+    #[inline]
+    pub(crate) fn new(cx: C, writer: W) -> Self {
+        Self {
+            cx,
+            writer,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Encoder for packed values.
+///
+/// Since MessagePack arrays are prefixed with the number of elements they
+/// contain, elements are buffered until the pack is finished.
+pub struct MsgPackPackEncoder<W, C, M>
+where
+    C: Context,
+{
+    cx: C,
+    writer: W,
+    buffer: BufWriter<C::Allocator>,
+    len: usize,
+    _marker: PhantomData<M>,
+}
+
+impl<W, C, M> MsgPackPackEncoder<W, C, M>
+where
+    C: Context,
+{
+    #[inline]
+    fn new(cx: C, writer: W) -> Self {
+        Self {
+            cx,
+            writer,
+            buffer: BufWriter::new(cx.alloc()),
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[crate::trait_defaults(crate)]
+impl<W, C, M> Encoder for MsgPackEncoder<W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodePack = MsgPackPackEncoder<W, C, M>;
+    type EncodeSome = Self;
+    type EncodeSequence = Self;
+    type EncodeMap = Self;
+    type EncodeMapEntries = Self;
+    type EncodeVariant = Self;
+    type EncodeSequenceVariant = Self;
+    type EncodeMapVariant = Self;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the MessagePack encoder")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<(), Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self)
+    }
+
+    #[inline]
+    fn encode_empty(mut self) -> Result<(), Self::Error> {
+        self.writer.write_byte(self.cx, tag::NIL)
+    }
+
+    #[inline]
+    fn encode_pack(self) -> Result<Self::EncodePack, Self::Error> {
+        Ok(MsgPackPackEncoder::new(self.cx, self.writer))
+    }
+
+    #[inline]
+    fn encode_array<const N: usize>(self, array: &[u8; N]) -> Result<(), Self::Error> {
+        self.encode_bytes(array)
+    }
+
+    #[inline]
+    fn encode_bytes(mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        encode_bin_header(self.cx, self.writer.borrow_mut(), bytes.len())?;
+        self.writer.write_bytes(self.cx, bytes)
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<I>(mut self, len: usize, vectors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item: AsRef<[u8]>>,
+    {
+        encode_bin_header(self.cx, self.writer.borrow_mut(), len)?;
+
+        for bytes in vectors {
+            self.writer.write_bytes(self.cx, bytes.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_string(mut self, string: &str) -> Result<(), Self::Error> {
+        encode_str_header(self.cx, self.writer.borrow_mut(), string.len())?;
+        self.writer.write_bytes(self.cx, string.as_bytes())
+    }
+
+    #[inline]
+    fn encode_bool(mut self, value: bool) -> Result<(), Self::Error> {
+        self.writer
+            .write_byte(self.cx, if value { tag::TRUE } else { tag::FALSE })
+    }
+
+    #[inline]
+    fn encode_char(self, value: char) -> Result<(), Self::Error> {
+        self.encode_string(value.encode_utf8(&mut [0; 4]))
+    }
+
+    #[inline]
+    fn encode_u8(mut self, value: u8) -> Result<(), Self::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), u64::from(value))
+    }
+
+    #[inline]
+    fn encode_u16(mut self, value: u16) -> Result<(), Self::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), u64::from(value))
+    }
+
+    #[inline]
+    fn encode_u32(mut self, value: u32) -> Result<(), Self::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), u64::from(value))
+    }
+
+    #[inline]
+    fn encode_u64(mut self, value: u64) -> Result<(), Self::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value)
+    }
+
+    #[inline]
+    fn encode_u128(mut self, value: u128) -> Result<(), Self::Error> {
+        if let Ok(value) = u64::try_from(value) {
+            return encode_unsigned(self.cx, self.writer.borrow_mut(), value);
+        }
+
+        self.writer
+            .write_bytes(self.cx, &[tag::FIXEXT16, tag::EXT_U128])?;
+        self.writer.write_bytes(self.cx, &value.to_be_bytes())
+    }
+
+    #[inline]
+    fn encode_i8(mut self, value: i8) -> Result<(), Self::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), i64::from(value))
+    }
+
+    #[inline]
+    fn encode_i16(mut self, value: i16) -> Result<(), Self::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), i64::from(value))
+    }
+
+    #[inline]
+    fn encode_i32(mut self, value: i32) -> Result<(), Self::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), i64::from(value))
+    }
+
+    #[inline]
+    fn encode_i64(mut self, value: i64) -> Result<(), Self::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value)
+    }
+
+    #[inline]
+    fn encode_i128(mut self, value: i128) -> Result<(), Self::Error> {
+        if let Ok(value) = i64::try_from(value) {
+            return encode_signed(self.cx, self.writer.borrow_mut(), value);
+        }
+
+        self.writer
+            .write_bytes(self.cx, &[tag::FIXEXT16, tag::EXT_I128])?;
+        self.writer.write_bytes(self.cx, &value.to_be_bytes())
+    }
+
+    #[inline]
+    fn encode_f32(mut self, value: f32) -> Result<(), Self::Error> {
+        self.writer.write_byte(self.cx, tag::FLOAT32)?;
+        self.writer.write_bytes(self.cx, &value.to_be_bytes())
+    }
+
+    #[inline]
+    fn encode_f64(mut self, value: f64) -> Result<(), Self::Error> {
+        self.writer.write_byte(self.cx, tag::FLOAT64)?;
+        self.writer.write_bytes(self.cx, &value.to_be_bytes())
+    }
+
+    #[inline]
+    fn encode_usize(mut self, value: usize) -> Result<(), Self::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value as u64)
+    }
+
+    #[inline]
+    fn encode_isize(mut self, value: isize) -> Result<(), Self::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value as i64)
+    }
+
+    #[inline]
+    fn encode_some(self) -> Result<Self::EncodeSome, Self::Error> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_none(self) -> Result<(), Self::Error> {
+        self.encode_empty()
+    }
+
+    #[inline]
+    fn encode_sequence(
+        mut self,
+        hint: impl SequenceHint,
+    ) -> Result<Self::EncodeSequence, Self::Error> {
+        let len = hint.require(self.cx)?;
+        encode_array_header(self.cx, self.writer.borrow_mut(), len)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_map(mut self, hint: impl MapHint) -> Result<Self::EncodeMap, Self::Error> {
+        let len = hint.require(self.cx)?;
+        encode_map_header(self.cx, self.writer.borrow_mut(), len)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_map_entries(
+        mut self,
+        hint: impl MapHint,
+    ) -> Result<Self::EncodeMapEntries, Self::Error> {
+        let len = hint.require(self.cx)?;
+        encode_map_header(self.cx, self.writer.borrow_mut(), len)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_variant(mut self) -> Result<Self::EncodeVariant, Self::Error> {
+        encode_map_header(self.cx, self.writer.borrow_mut(), 1)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_sequence_variant<T>(
+        mut self,
+        tag: &T,
+        hint: impl SequenceHint,
+    ) -> Result<Self::EncodeSequenceVariant, Self::Error>
+    where
+        T: ?Sized + Encode<Self::Mode>,
+    {
+        encode_map_header(self.cx, self.writer.borrow_mut(), 1)?;
+        MsgPackEncoder::<_, _, M>::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        self.encode_sequence(hint)
+    }
+
+    #[inline]
+    fn encode_map_variant<T>(
+        mut self,
+        tag: &T,
+        hint: impl MapHint,
+    ) -> Result<Self::EncodeMapVariant, Self::Error>
+    where
+        T: ?Sized + Encode<Self::Mode>,
+    {
+        encode_map_header(self.cx, self.writer.borrow_mut(), 1)?;
+        MsgPackEncoder::<_, _, M>::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        self.encode_map(hint)
+    }
+}
+
+impl<W, C, M> SequenceEncoder for MsgPackPackEncoder<W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeNext<'this>
+        = MsgPackEncoder<&'this mut BufWriter<C::Allocator>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, Self::Error> {
+        self.len += 1;
+        Ok(MsgPackEncoder::new(self.cx, &mut self.buffer))
+    }
+
+    #[inline]
+    fn finish_sequence(mut self) -> Result<(), Self::Error> {
+        encode_array_header(self.cx, self.writer.borrow_mut(), self.len)?;
+        self.writer.extend(self.cx, self.buffer.into_inner())?;
+        Ok(())
+    }
+}
+
+impl<W, C, M> SequenceEncoder for MsgPackEncoder<W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeNext<'this>
+        = MsgPackEncoder<W::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, Self::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_sequence(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W, C, M> MapEncoder for MsgPackEncoder<W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeEntry<'this>
+        = MsgPackEncoder<W::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_entry(&mut self) -> Result<Self::EncodeEntry<'_>, Self::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_map(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W, C, M> EntryEncoder for MsgPackEncoder<W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeKey<'this>
+        = MsgPackEncoder<W::Mut<'this>, C, M>
+    where
+        Self: 'this;
+    type EncodeValue<'this>
+        = MsgPackEncoder<W::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_key(&mut self) -> Result<Self::EncodeKey<'_>, Self::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn encode_value(&mut self) -> Result<Self::EncodeValue<'_>, Self::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_entry(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W, C, M> EntriesEncoder for MsgPackEncoder<W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeEntryKey<'this>
+        = MsgPackEncoder<W::Mut<'this>, C, M>
+    where
+        Self: 'this;
+    type EncodeEntryValue<'this>
+        = MsgPackEncoder<W::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_entry_key(&mut self) -> Result<Self::EncodeEntryKey<'_>, Self::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn encode_entry_value(&mut self) -> Result<Self::EncodeEntryValue<'_>, Self::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_entries(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W, C, M> VariantEncoder for MsgPackEncoder<W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeTag<'this>
+        = MsgPackEncoder<W::Mut<'this>, C, M>
+    where
+        Self: 'this;
+    type EncodeData<'this>
+        = MsgPackEncoder<W::Mut<'this>, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_tag(&mut self) -> Result<Self::EncodeTag<'_>, Self::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn encode_data(&mut self) -> Result<Self::EncodeData<'_>, Self::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Encode an unsigned integer using the smallest available representation.
+#[inline]
+fn encode_unsigned<W, C>(cx: C, mut writer: W, value: u64) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    if value <= u64::from(tag::POSITIVE_FIXINT_MAX) {
+        writer.write_byte(cx, value as u8)
+    } else if let Ok(value) = u8::try_from(value) {
+        writer.write_bytes(cx, &[tag::UINT8, value])
+    } else if let Ok(value) = u16::try_from(value) {
+        writer.write_byte(cx, tag::UINT16)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    } else if let Ok(value) = u32::try_from(value) {
+        writer.write_byte(cx, tag::UINT32)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    } else {
+        writer.write_byte(cx, tag::UINT64)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    }
+}
+
+/// Encode a signed integer using the smallest available representation.
+///
+/// Non-negative values are encoded as unsigned integers.
+#[inline]
+fn encode_signed<W, C>(cx: C, mut writer: W, value: i64) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    if value >= 0 {
+        encode_unsigned(cx, writer, value as u64)
+    } else if value >= -32 {
+        writer.write_byte(cx, value as u8)
+    } else if let Ok(value) = i8::try_from(value) {
+        writer.write_bytes(cx, &[tag::INT8, value as u8])
+    } else if let Ok(value) = i16::try_from(value) {
+        writer.write_byte(cx, tag::INT16)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    } else if let Ok(value) = i32::try_from(value) {
+        writer.write_byte(cx, tag::INT32)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    } else {
+        writer.write_byte(cx, tag::INT64)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    }
+}
+
+/// Encode a length header using either an embedded length or one of the
+/// given 8, 16 or 32-bit markers.
+#[inline]
+fn encode_len<W, C>(
+    cx: C,
+    mut writer: W,
+    len: usize,
+    fixed: Option<(u8, usize)>,
+    [m8, m16, m32]: [Option<u8>; 3],
+) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    if let Some((base, max)) = fixed {
+        if len <= max {
+            return writer.write_byte(cx, base | len as u8);
+        }
+    }
+
+    if let (Some(m8), Ok(len)) = (m8, u8::try_from(len)) {
+        return writer.write_bytes(cx, &[m8, len]);
+    }
+
+    if let (Some(m16), Ok(len)) = (m16, u16::try_from(len)) {
+        writer.write_byte(cx, m16)?;
+        return writer.write_bytes(cx, &len.to_be_bytes());
+    }
+
+    if let (Some(m32), Ok(len)) = (m32, u32::try_from(len)) {
+        writer.write_byte(cx, m32)?;
+        return writer.write_bytes(cx, &len.to_be_bytes());
+    }
+
+    Err(cx.message(format_args!(
+        "Length {len} is too large to be encoded in MessagePack"
+    )))
+}
+
+#[inline]
+fn encode_bin_header<W, C>(cx: C, writer: W, len: usize) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    encode_len(
+        cx,
+        writer,
+        len,
+        None,
+        [Some(tag::BIN8), Some(tag::BIN16), Some(tag::BIN32)],
+    )
+}
+
+#[inline]
+fn encode_str_header<W, C>(cx: C, writer: W, len: usize) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    encode_len(
+        cx,
+        writer,
+        len,
+        Some((tag::FIXSTR, tag::FIXSTR_LEN_MAX)),
+        [Some(tag::STR8), Some(tag::STR16), Some(tag::STR32)],
+    )
+}
+
+#[inline]
+fn encode_array_header<W, C>(cx: C, writer: W, len: usize) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    encode_len(
+        cx,
+        writer,
+        len,
+        Some((tag::FIXARRAY, tag::FIX_LEN_MAX)),
+        [None, Some(tag::ARRAY16), Some(tag::ARRAY32)],
+    )
+}
+
+#[inline]
+fn encode_map_header<W, C>(cx: C, writer: W, len: usize) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    encode_len(
+        cx,
+        writer,
+        len,
+        Some((tag::FIXMAP, tag::FIX_LEN_MAX)),
+        [None, Some(tag::MAP16), Some(tag::MAP32)],
+    )
+}
@@ -0,0 +1,123 @@
+use core::marker;
+
+#[cfg(feature = "alloc")]
+use crate::Reader;
+#[cfg(feature = "alloc")]
+use crate::alloc::Global;
+use crate::mode::Text;
+use crate::{Context, Decode, Encode, IntoReader, IntoWriter};
+
+use super::de::MsgPackDecoder;
+use super::en::MsgPackEncoder;
+#[cfg(feature = "alloc")]
+use super::error::Error;
+
+#[allow(unused)]
+const DEFAULT: Encoding = Encoding::new();
+
+crate::macros::bare_encoding!(Text, DEFAULT, msgpack, IntoReader, IntoWriter);
+
+/// Setting up encoding with parameters.
+pub struct Encoding<M = Text>
+where
+    M: 'static,
+{
+    _marker: marker::PhantomData<M>,
+}
+
+impl Default for Encoding<Text> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Encoding<Text> {
+    /// Construct a new [`Encoding`].
+    ///
+    /// ```
+    /// use musli::{Encode, Decode};
+    /// use musli::msgpack::Encoding;
+    /// # use musli::msgpack::Error;
+    ///
+    /// const CONFIG: Encoding = Encoding::new();
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// struct Person<'a> {
+    ///     name: &'a str,
+    ///     age: u32,
+    /// }
+    ///
+    /// let mut out = Vec::new();
+    ///
+    /// let expected = Person {
+    ///     name: "Aristotle",
+    ///     age: 61,
+    /// };
+    ///
+    /// CONFIG.encode(&mut out, &expected)?;
+    /// let actual = CONFIG.decode(&out[..])?;
+    ///
+    /// assert_eq!(expected, actual);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M> Encoding<M>
+where
+    M: 'static,
+{
+    /// Change the mode of the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::msgpack::Encoding;
+    ///
+    /// enum Custom {}
+    ///
+    /// const CONFIG: Encoding<Custom> = Encoding::new().with_mode();
+    /// ```
+    pub const fn with_mode<T>(self) -> Encoding<T>
+    where
+        T: 'static,
+    {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    crate::macros::encoding_impls!(
+        M,
+        msgpack,
+        MsgPackEncoder::<_, _, M>::new,
+        MsgPackDecoder::<_, _, M>::new,
+        IntoReader::into_reader,
+        IntoWriter::into_writer,
+    );
+}
+
+crate::macros::decode_iter!(
+    msgpack,
+    [M] Encoding<M>,
+    M,
+    MsgPackDecoder::<_, _, M>::new,
+    IntoReader::into_reader -> Reader,
+    Reader,
+    |_cx, reader| reader.is_eof(),
+);
+
+impl<M> Clone for Encoding<M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for Encoding<M> {}
@@ -0,0 +1,4 @@
+crate::macros::implement_error! {
+    /// Error raised during MessagePack encoding.
+    pub struct Error;
+}
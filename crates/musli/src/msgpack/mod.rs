@@ -0,0 +1,117 @@
+//! Support for the [MessagePack] format for [Müsli].
+//!
+//! MessagePack is a compact, self-descriptive binary format which is widely
+//! supported across languages. The encoding produced here follows the
+//! [MessagePack specification], so data can be exchanged with other
+//! implementations:
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`.
+//! * ✔ Can skip over unknown fields.
+//! * ✔ Integers are always encoded using their smallest possible
+//!   representation, and can be decoded into any type which fits the value.
+//!
+//! By default the [`Text`] mode is used, which means that fields and variants
+//! are identified by their names the same way they are in [`musli::json`].
+//!
+//! [Müsli]: https://docs.rs/musli
+//! [MessagePack]: https://msgpack.org
+//! [MessagePack specification]: https://github.com/msgpack/msgpack/blob/master/spec.md
+//! [`Text`]: crate::mode::Text
+//! [`musli::json`]: crate::json
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Version1 {
+//!     name: String,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Version2 {
+//!     name: String,
+//!     #[musli(default)]
+//!     age: Option<u32>,
+//! }
+//!
+//! let version2 = musli::msgpack::to_vec(&Version2 {
+//!     name: String::from("Aristotle"),
+//!     age: Some(61),
+//! })?;
+//!
+//! assert_eq!(version2, b"\x82\xa4name\xa9Aristotle\xa3age\x3d");
+//!
+//! let version1: Version1 = musli::msgpack::from_slice(version2.as_slice())?;
+//!
+//! assert_eq!(version1, Version1 {
+//!     name: String::from("Aristotle"),
+//! });
+//! # Ok::<_, musli::msgpack::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Implementation details
+//!
+//! Müsli types map onto MessagePack as follows:
+//!
+//! * Structs and maps are encoded as maps, and sequences and tuples as arrays.
+//! * Packed types are encoded as arrays.
+//! * Byte arrays are encoded as `bin`, and strings and characters as `str`.
+//! * Empty values and `None` are encoded as `nil`, while `Some(value)` is
+//!   encoded as `value`.
+//! * Enum variants are encoded as a map with a single entry, from the variant
+//!   tag to its content.
+//! * 128-bit integers which don't fit in 64 bits are encoded as a `fixext 16`
+//!   containing the big-endian integer, with extension type `1` for unsigned
+//!   and `2` for signed integers.
+//!
+//! Other extension types are skipped over as unknown fields, but will result
+//! in an error if they are decoded.
+
+#![cfg(feature = "msgpack")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "msgpack")))]
+
+#[cfg(test)]
+mod tests;
+
+mod de;
+mod en;
+mod encoding;
+mod error;
+mod tag;
+
+#[cfg(feature = "test")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "test")))]
+#[doc(hidden)]
+pub mod test;
+
+/// Convenient result alias for use with `musli::msgpack`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::DecodeIter;
+#[doc(inline)]
+pub use self::encoding::Encoding;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::to_vec;
+#[cfg(all(feature = "std", feature = "alloc"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "alloc"))))]
+#[doc(inline)]
+pub use self::encoding::to_writer;
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, to_slice};
+#[cfg(feature = "tokio")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+#[doc(inline)]
+pub use self::encoding::{decode_async, encode_async};
+#[doc(inline)]
+pub use self::error::Error;
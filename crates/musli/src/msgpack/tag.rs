@@ -0,0 +1,161 @@
+//! Format markers used by `musli::msgpack`.
+//!
+//! See the [MessagePack specification] for details.
+//!
+//! [MessagePack specification]: https://github.com/msgpack/msgpack/blob/master/spec.md
+
+use core::fmt;
+
+/// The maximum value of a positive fixint.
+pub(crate) const POSITIVE_FIXINT_MAX: u8 = 0x7f;
+/// The start of the fixmap range, with the length in the low 4 bits.
+pub(crate) const FIXMAP: u8 = 0x80;
+/// The start of the fixarray range, with the length in the low 4 bits.
+pub(crate) const FIXARRAY: u8 = 0x90;
+/// The start of the fixstr range, with the length in the low 5 bits.
+pub(crate) const FIXSTR: u8 = 0xa0;
+/// The nil value.
+pub(crate) const NIL: u8 = 0xc0;
+/// The value false.
+pub(crate) const FALSE: u8 = 0xc2;
+/// The value true.
+pub(crate) const TRUE: u8 = 0xc3;
+/// Byte array with an 8-bit length.
+pub(crate) const BIN8: u8 = 0xc4;
+/// Byte array with a 16-bit length.
+pub(crate) const BIN16: u8 = 0xc5;
+/// Byte array with a 32-bit length.
+pub(crate) const BIN32: u8 = 0xc6;
+/// Extension type with an 8-bit length.
+pub(crate) const EXT8: u8 = 0xc7;
+/// Extension type with a 16-bit length.
+pub(crate) const EXT16: u8 = 0xc8;
+/// Extension type with a 32-bit length.
+pub(crate) const EXT32: u8 = 0xc9;
+/// Single precision floating point number.
+pub(crate) const FLOAT32: u8 = 0xca;
+/// Double precision floating point number.
+pub(crate) const FLOAT64: u8 = 0xcb;
+/// 8-bit unsigned integer.
+pub(crate) const UINT8: u8 = 0xcc;
+/// 16-bit unsigned integer.
+pub(crate) const UINT16: u8 = 0xcd;
+/// 32-bit unsigned integer.
+pub(crate) const UINT32: u8 = 0xce;
+/// 64-bit unsigned integer.
+pub(crate) const UINT64: u8 = 0xcf;
+/// 8-bit signed integer.
+pub(crate) const INT8: u8 = 0xd0;
+/// 16-bit signed integer.
+pub(crate) const INT16: u8 = 0xd1;
+/// 32-bit signed integer.
+pub(crate) const INT32: u8 = 0xd2;
+/// 64-bit signed integer.
+pub(crate) const INT64: u8 = 0xd3;
+/// Extension type with 1 byte of data.
+pub(crate) const FIXEXT1: u8 = 0xd4;
+/// Extension type with 2 bytes of data.
+pub(crate) const FIXEXT2: u8 = 0xd5;
+/// Extension type with 4 bytes of data.
+pub(crate) const FIXEXT4: u8 = 0xd6;
+/// Extension type with 8 bytes of data.
+pub(crate) const FIXEXT8: u8 = 0xd7;
+/// Extension type with 16 bytes of data.
+pub(crate) const FIXEXT16: u8 = 0xd8;
+/// String with an 8-bit length.
+pub(crate) const STR8: u8 = 0xd9;
+/// String with a 16-bit length.
+pub(crate) const STR16: u8 = 0xda;
+/// String with a 32-bit length.
+pub(crate) const STR32: u8 = 0xdb;
+/// Array with a 16-bit length.
+pub(crate) const ARRAY16: u8 = 0xdc;
+/// Array with a 32-bit length.
+pub(crate) const ARRAY32: u8 = 0xdd;
+/// Map with a 16-bit length.
+pub(crate) const MAP16: u8 = 0xde;
+/// Map with a 32-bit length.
+pub(crate) const MAP32: u8 = 0xdf;
+/// The minimum value of a negative fixint.
+pub(crate) const NEGATIVE_FIXINT_MIN: u8 = 0xe0;
+
+/// Extension type used for unsigned 128-bit integers which don't fit in 64
+/// bits, stored as a `fixext 16`.
+pub(crate) const EXT_U128: u8 = 0x01;
+/// Extension type used for signed 128-bit integers which don't fit in 64 bits,
+/// stored as a `fixext 16`.
+pub(crate) const EXT_I128: u8 = 0x02;
+
+/// The maximum length of a fixmap or fixarray.
+pub(crate) const FIX_LEN_MAX: usize = 0x0f;
+/// The maximum length of a fixstr.
+pub(crate) const FIXSTR_LEN_MAX: usize = 0x1f;
+
+/// The kind of value a format marker introduces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Nil,
+    Bool,
+    Unsigned,
+    Signed,
+    Float,
+    String,
+    Bytes,
+    Array,
+    Map,
+    Extension,
+    Reserved,
+}
+
+impl Kind {
+    /// Classify the given format marker.
+    #[inline]
+    pub(crate) const fn from_byte(b: u8) -> Self {
+        match b {
+            0x00..=POSITIVE_FIXINT_MAX => Kind::Unsigned,
+            0x80..=0x8f | MAP16 | MAP32 => Kind::Map,
+            0x90..=0x9f | ARRAY16 | ARRAY32 => Kind::Array,
+            0xa0..=0xbf | STR8 | STR16 | STR32 => Kind::String,
+            NIL => Kind::Nil,
+            FALSE | TRUE => Kind::Bool,
+            BIN8 | BIN16 | BIN32 => Kind::Bytes,
+            EXT8 | EXT16 | EXT32 | FIXEXT1 | FIXEXT2 | FIXEXT4 | FIXEXT8 | FIXEXT16 => {
+                Kind::Extension
+            }
+            FLOAT32 | FLOAT64 => Kind::Float,
+            UINT8 | UINT16 | UINT32 | UINT64 => Kind::Unsigned,
+            INT8 | INT16 | INT32 | INT64 | NEGATIVE_FIXINT_MIN..=0xff => Kind::Signed,
+            _ => Kind::Reserved,
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Nil => write!(f, "nil"),
+            Kind::Bool => write!(f, "boolean"),
+            Kind::Unsigned => write!(f, "unsigned integer"),
+            Kind::Signed => write!(f, "signed integer"),
+            Kind::Float => write!(f, "float"),
+            Kind::String => write!(f, "string"),
+            Kind::Bytes => write!(f, "bytes"),
+            Kind::Array => write!(f, "array"),
+            Kind::Map => write!(f, "map"),
+            Kind::Extension => write!(f, "extension"),
+            Kind::Reserved => write!(f, "reserved marker"),
+        }
+    }
+}
+
+/// Helper to display a format marker in diagnostics.
+#[derive(Clone, Copy)]
+pub(crate) struct Marker(pub(crate) u8);
+
+impl fmt::Display for Marker {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (0x{:02x})", Kind::from_byte(self.0), self.0)
+    }
+}
@@ -0,0 +1,159 @@
+use rust_alloc::string::String;
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+enum Shape {
+    Empty,
+    Circle(u32),
+    Rectangle { width: u32, height: u32 },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, packed)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Before {
+    name: String,
+    skipped: Vec<Option<Point>>,
+    extra: Shape,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct After {
+    name: String,
+}
+
+macro_rules! assert_bytes {
+    ($ty:ty, $value:expr, $expected:expr) => {{
+        let value: $ty = $value;
+        let bytes = super::to_vec(&value).unwrap();
+        assert_eq!(bytes, $expected, "{value:?}");
+        let actual: $ty = super::from_slice(&bytes).unwrap();
+        assert_eq!(actual, value);
+    }};
+}
+
+#[test]
+fn integers() {
+    assert_bytes!(u8, 0, [0x00]);
+    assert_bytes!(u8, 0x7f, [0x7f]);
+    assert_bytes!(u8, 0x80, [0xcc, 0x80]);
+    assert_bytes!(u16, 0x100, [0xcd, 0x01, 0x00]);
+    assert_bytes!(u32, 0x10000, [0xce, 0x00, 0x01, 0x00, 0x00]);
+    assert_bytes!(
+        u64,
+        u64::MAX,
+        [0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+    );
+    assert_bytes!(i8, -1, [0xff]);
+    assert_bytes!(i8, -32, [0xe0]);
+    assert_bytes!(i8, -33, [0xd0, 0xdf]);
+    assert_bytes!(i16, -129, [0xd1, 0xff, 0x7f]);
+    assert_bytes!(i32, i32::MIN, [0xd2, 0x80, 0x00, 0x00, 0x00]);
+    assert_bytes!(i64, 100, [0x64]);
+    assert_bytes!(i128, -1, [0xff]);
+    assert_bytes!(u128, 1, [0x01]);
+
+    let mut wide = vec![0xd8, 0x01];
+    wide.extend_from_slice(&u128::MAX.to_be_bytes());
+    assert_bytes!(u128, u128::MAX, wide);
+
+    let mut wide = vec![0xd8, 0x02];
+    wide.extend_from_slice(&i128::MIN.to_be_bytes());
+    assert_bytes!(i128, i128::MIN, wide);
+
+    // Integers can be decoded into any type which fits them.
+    assert_eq!(super::from_slice::<i8>(&[0xcc, 0x7f]).unwrap(), 127);
+    assert_eq!(super::from_slice::<u64>(&[0xd0, 0x01]).unwrap(), 1);
+    assert!(super::from_slice::<u8>(&[0xff]).is_err());
+    assert!(super::from_slice::<i8>(&[0xcc, 0x80]).is_err());
+}
+
+#[test]
+fn scalars() {
+    assert_bytes!(bool, true, [0xc3]);
+    assert_bytes!(bool, false, [0xc2]);
+    assert_bytes!(Option<u32>, None, [0xc0]);
+    assert_bytes!(Option<u32>, Some(1), [0x01]);
+    assert_bytes!(f32, 1.5, [0xca, 0x3f, 0xc0, 0x00, 0x00]);
+    assert_bytes!(
+        f64,
+        1.5,
+        [0xcb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+    );
+    assert_bytes!(char, 'ä', [0xa2, 0xc3, 0xa4]);
+    assert_bytes!(String, String::from("hi"), *b"\xa2hi");
+
+    let long = "a".repeat(32);
+    let mut expected = vec![0xd9, 32];
+    expected.extend_from_slice(long.as_bytes());
+    assert_bytes!(String, long, expected);
+}
+
+#[test]
+fn containers() {
+    assert_bytes!(Vec<u8>, vec![1, 2, 3], [0x93, 0x01, 0x02, 0x03]);
+
+    let mut expected = vec![0xdc, 0x00, 0x10];
+    expected.extend(0..16u8);
+    assert_bytes!(Vec<u8>, (0..16).collect(), expected);
+
+    assert_bytes!(Point, Point { x: 1, y: -1 }, [0x92, 0x01, 0xff]);
+    assert_bytes!(Shape, Shape::Circle(2), *b"\x81\xa6Circle\x81\x00\x02");
+    assert_bytes!(
+        Shape,
+        Shape::Rectangle {
+            width: 1,
+            height: 2
+        },
+        *b"\x81\xa9Rectangle\x82\xa5width\x01\xa6height\x02"
+    );
+    assert_bytes!(Shape, Shape::Empty, *b"\x81\xa5Empty\x80");
+}
+
+#[test]
+fn skip_unknown() {
+    let before = Before {
+        name: String::from("Aristotle"),
+        skipped: vec![Some(Point { x: 1, y: 2 }), None],
+        extra: Shape::Rectangle {
+            width: 300,
+            height: 70000,
+        },
+    };
+
+    let bytes = super::to_vec(&before).unwrap();
+    let after: After = super::from_slice(&bytes).unwrap();
+
+    assert_eq!(
+        after,
+        After {
+            name: String::from("Aristotle")
+        }
+    );
+}
+
+#[test]
+fn foreign_markers() {
+    // A map with a fixext, a bin and a float which should be skipped.
+    let bytes = b"\x84\xa4name\xa1a\xa1x\xd4\x01\x00\xa1y\xc4\x02ab\xa1z\xcb\x00\x00\x00\x00\x00\x00\x00\x00";
+    let after: After = super::from_slice(bytes).unwrap();
+    assert_eq!(
+        after,
+        After {
+            name: String::from("a")
+        }
+    );
+
+    assert!(super::from_slice::<u32>(b"\xc1").is_err());
+}
@@ -57,6 +57,7 @@ const INTEGER_BIT: Options = 4;
 const FLOAT_BIT: Options = 8;
 const LENGTH_BIT: Options = 12;
 const MAP_KEYS_AS_NUMBERS_BIT: Options = 16;
+const CANONICAL_BIT: Options = 17;
 
 impl Builder {
     /// Indicates if an integer serialization should be variable.
@@ -153,6 +154,27 @@ impl Builder {
         Self((self.0 & !MASK) | (1 << MAP_KEYS_AS_NUMBERS_BIT))
     }
 
+    /// Configure a format to use canonical encoding.
+    ///
+    /// Formats which have a notion of a deterministic encoding, such as CBOR,
+    /// use this to always produce the same bytes for the same value. For CBOR
+    /// this means that floats are encoded using the shortest representation
+    /// which preserves their value and that map entries are sorted by their
+    /// encoded keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::options::{self, Options};
+    ///
+    /// const OPTIONS: Options = options::new().canonical().build();
+    /// ```
+    #[inline]
+    pub const fn canonical(self) -> Self {
+        const MASK: Options = 0b1 << CANONICAL_BIT;
+        Self((self.0 & !MASK) | (1 << CANONICAL_BIT))
+    }
+
     /// Configure the options to use fixed serialization.
     ///
     /// This causes numerical types to use the default fixed-length
@@ -235,6 +257,7 @@ impl fmt::Debug for Builder {
                 "is_map_keys_as_numbers",
                 &is_map_keys_as_numbers_value(self.0),
             )
+            .field("is_canonical", &is_canonical_value(self.0))
             .finish()
     }
 }
@@ -242,6 +265,7 @@ impl fmt::Debug for Builder {
 #[cfg(any(
     feature = "storage",
     feature = "wire",
+    feature = "protobuf",
     feature = "descriptive",
     feature = "json",
     feature = "value"
@@ -337,6 +361,16 @@ const fn is_map_keys_as_numbers_value(opt: Options) -> bool {
     ((opt >> MAP_KEYS_AS_NUMBERS_BIT) & 0b1) == 1
 }
 
+#[cfg(feature = "cbor")]
+#[inline]
+pub(crate) const fn is_canonical<const OPT: Options>() -> bool {
+    is_canonical_value(OPT)
+}
+
+const fn is_canonical_value(opt: Options) -> bool {
+    ((opt >> CANONICAL_BIT) & 0b1) == 1
+}
+
 #[cfg(any(
     feature = "storage",
     feature = "wire",
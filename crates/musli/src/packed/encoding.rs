@@ -1,5 +1,7 @@
 use core::marker;
 
+#[cfg(feature = "alloc")]
+use crate::Reader;
 #[cfg(feature = "alloc")]
 use crate::alloc::Global;
 use crate::mode::Binary;
@@ -122,6 +124,16 @@ where
     );
 }
 
+crate::macros::decode_iter!(
+    packed,
+    [const OPT: Options, M] Encoding<OPT, M>,
+    M,
+    StorageDecoder::<OPT, true, _, _, M>::new,
+    IntoReader::into_reader -> Reader,
+    Reader,
+    |_cx, reader| reader.is_eof(),
+);
+
 impl<const OPT: Options, M> Clone for Encoding<OPT, M> {
     #[inline]
     fn clone(&self) -> Self {
@@ -0,0 +1,1175 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::alloc::Vec;
+use crate::de::{
+    Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder, Skip, UnsizedVisitor,
+    VariantDecoder,
+};
+use crate::options::{self, Integer, Options};
+use crate::{Context, Reader};
+
+use super::tag::{self, Key, WireType};
+use super::varint;
+
+/// The input being decoded.
+///
+/// This keeps track of the number of bytes read so far, which is used to find
+/// the end of length-delimited values without nesting readers.
+pub(crate) struct Input<R> {
+    reader: R,
+    pos: usize,
+}
+
+impl<'de, R> Input<R>
+where
+    R: Reader<'de>,
+{
+    #[inline]
+    fn read_byte<C>(&mut self, cx: C) -> Result<u8, C::Error>
+    where
+        C: Context,
+    {
+        let b = self.reader.read_byte(cx)?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    #[inline]
+    fn read_array<C, const N: usize>(&mut self, cx: C) -> Result<[u8; N], C::Error>
+    where
+        C: Context,
+    {
+        let array = self.reader.read_array(cx)?;
+        self.pos += N;
+        Ok(array)
+    }
+
+    #[inline]
+    fn read_bytes<C, V>(&mut self, cx: C, n: usize, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        C: Context,
+        V: UnsizedVisitor<'de, C, [u8], Error = C::Error, Allocator = C::Allocator>,
+    {
+        let ok = self.reader.read_bytes(cx, n, visitor)?;
+        self.pos += n;
+        Ok(ok)
+    }
+
+    #[inline]
+    fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.reader.skip(cx, n)?;
+        self.pos += n;
+        Ok(())
+    }
+
+    /// Read a variable-length integer of at most ten bytes.
+    #[inline]
+    fn read_varint<C>(&mut self, cx: C) -> Result<u64, C::Error>
+    where
+        C: Context,
+    {
+        let mut value = 0u64;
+
+        for shift in (0..64).step_by(7) {
+            let b = self.read_byte(cx)?;
+            value |= u64::from(b & 0x7f) << shift;
+
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(cx.message("Varint is longer than ten bytes"))
+    }
+
+    /// Read the length prefix of a length-delimited value.
+    #[inline]
+    fn read_len<C>(&mut self, cx: C) -> Result<usize, C::Error>
+    where
+        C: Context,
+    {
+        let len = self.read_varint(cx)?;
+
+        match usize::try_from(len) {
+            Ok(len) => Ok(len),
+            Err(..) => Err(cx.message(format_args!(
+                "Length {len} does not fit in a usize on this platform"
+            ))),
+        }
+    }
+
+    /// Skip over a value with the given wire type.
+    #[inline]
+    fn skip_value<C>(&mut self, cx: C, wire: u8) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        match wire {
+            tag::VARINT => {
+                self.read_varint(cx)?;
+            }
+            tag::I64 => {
+                self.skip(cx, 8)?;
+            }
+            tag::LEN => {
+                let len = self.read_len(cx)?;
+                self.skip(cx, len)?;
+            }
+            tag::I32 => {
+                self.skip(cx, 4)?;
+            }
+            wire => {
+                return Err(cx.message(format_args!("Cannot skip over {}", WireType(wire))));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The input of a decoder, which is owned by the top-level decoder and
+/// borrowed by every nested decoder.
+///
+/// This ensures that the type of the decoder doesn't grow with each level of
+/// nesting.
+enum Source<'a, R> {
+    Owned(Input<R>),
+    Borrowed(&'a mut Input<R>),
+}
+
+impl<R> Source<'_, R> {
+    #[inline]
+    fn get(&mut self) -> &mut Input<R> {
+        match self {
+            Source::Owned(input) => input,
+            Source::Borrowed(input) => input,
+        }
+    }
+}
+
+/// The state of a message being decoded.
+struct Message {
+    /// The position at which the message ends, or `None` if the message extends
+    /// to the end of the input.
+    end: Option<usize>,
+    /// A key which has been read while looking for further occurrences of a
+    /// repeated field, but which belongs to the next field.
+    pending: Option<Key>,
+}
+
+impl Message {
+    #[inline]
+    fn new(end: Option<usize>) -> Self {
+        Self { end, pending: None }
+    }
+
+    /// Read the key of the next field in the message.
+    #[inline]
+    fn next_key<'de, R, C>(&mut self, cx: C, input: &mut Input<R>) -> Result<Option<Key>, C::Error>
+    where
+        R: Reader<'de>,
+        C: Context,
+    {
+        if let Some(key) = self.pending.take() {
+            return Ok(Some(key));
+        }
+
+        let at_end = match self.end {
+            Some(end) => input.pos >= end,
+            None => input.reader.is_eof(),
+        };
+
+        if at_end {
+            return Ok(None);
+        }
+
+        Ok(Some(Key::from_raw(input.read_varint(cx)?)))
+    }
+
+    /// Skip over the remaining fields of the message and make sure that it
+    /// ends where its length says it should.
+    #[inline]
+    fn finish<'de, R, C>(&mut self, cx: C, input: &mut Input<R>) -> Result<(), C::Error>
+    where
+        R: Reader<'de>,
+        C: Context,
+    {
+        while let Some(key) = self.next_key(cx, input)? {
+            input.skip_value(cx, key.wire)?;
+        }
+
+        match self.end {
+            Some(end) if input.pos != end => Err(cx.message(format_args!(
+                "Message ended at offset {}, but its length says it should end at {end}",
+                input.pos
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// What the value being decoded is.
+enum Frame<'a> {
+    /// The top-level message, which extends to the end of the input.
+    Root,
+    /// A field number or a variant tag.
+    Number(u64),
+    /// The value of a field in the given message.
+    Field(Key, &'a mut Message),
+    /// An element of a repeated field with the given wire type. If the wire
+    /// type is length-delimited, the element ends at or before the given
+    /// position.
+    Element(u8, usize),
+    /// A field which is missing from a pack.
+    Absent,
+}
+
+/// A decoder for protobuf.
+pub struct ProtobufDecoder<'a, const OPT: Options, R, C, M> {
+    cx: C,
+    input: Source<'a, R>,
+    frame: Frame<'a>,
+    _marker: PhantomData<M>,
+}
+
+impl<const OPT: Options, R, C, M> ProtobufDecoder<'_, OPT, R, C, M> {
+    /// Construct a new protobuf decoder.
+    #[inline]
+    pub(crate) fn new(cx: C, reader: R) -> Self {
+        Self::with_frame(cx, Source::Owned(Input { reader, pos: 0 }), Frame::Root)
+    }
+}
+
+impl<'a, const OPT: Options, R, C, M> ProtobufDecoder<'a, OPT, R, C, M> {
+    #[inline]
+    fn with_frame(cx: C, input: Source<'a, R>, frame: Frame<'a>) -> Self {
+        Self {
+            cx,
+            input,
+            frame,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'de, const OPT: Options, R, C, M> ProtobufDecoder<'a, OPT, R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    /// Construct an error for when a value with the given wire type was
+    /// expected.
+    #[inline]
+    fn unexpected(&self, wire: u8) -> C::Error {
+        let expected = WireType(wire);
+
+        match &self.frame {
+            Frame::Root => self.cx.message(format_args!(
+                "Expected {expected}, but the top level is a message"
+            )),
+            Frame::Number(..) => self
+                .cx
+                .message(format_args!("Expected {expected}, but got field number")),
+            Frame::Field(Key { wire, .. }, _) | Frame::Element(wire, _) => self.cx.message(
+                format_args!("Expected {expected}, but got {}", WireType(*wire)),
+            ),
+            Frame::Absent => self
+                .cx
+                .message(format_args!("Expected {expected}, but field is missing")),
+        }
+    }
+
+    /// Check that the value is a scalar with the given wire type.
+    ///
+    /// Elements of packed repeated fields have no wire type of their own, so
+    /// they are read as whatever is requested.
+    #[inline]
+    fn scalar(&self, wire: u8) -> Result<(), C::Error> {
+        match &self.frame {
+            Frame::Field(key, _) if key.wire == wire => Ok(()),
+            Frame::Element(tag::LEN, _) => Ok(()),
+            Frame::Element(actual, _) if *actual == wire => Ok(()),
+            _ => Err(self.unexpected(wire)),
+        }
+    }
+
+    #[inline]
+    fn decode_varint(mut self) -> Result<u64, C::Error> {
+        if let Frame::Number(number) = self.frame {
+            return Ok(number);
+        }
+
+        self.scalar(tag::VARINT)?;
+        self.input.get().read_varint(self.cx)
+    }
+
+    #[inline]
+    fn decode_fixed32(mut self) -> Result<[u8; 4], C::Error> {
+        self.scalar(tag::I32)?;
+        self.input.get().read_array(self.cx)
+    }
+
+    #[inline]
+    fn decode_fixed64(mut self) -> Result<[u8; 8], C::Error> {
+        self.scalar(tag::I64)?;
+        self.input.get().read_array(self.cx)
+    }
+
+    #[inline]
+    fn is_varint(&self) -> bool {
+        matches!(self.frame, Frame::Number(..))
+            || matches!(options::integer::<OPT>(), Integer::Variable)
+    }
+
+    #[inline]
+    fn decode_unsigned32(self) -> Result<u32, C::Error> {
+        let cx = self.cx;
+
+        if self.is_varint() {
+            return narrow(cx, self.decode_varint()?);
+        }
+
+        Ok(u32::from_le_bytes(self.decode_fixed32()?))
+    }
+
+    #[inline]
+    fn decode_unsigned64(self) -> Result<u64, C::Error> {
+        if self.is_varint() {
+            return self.decode_varint();
+        }
+
+        Ok(u64::from_le_bytes(self.decode_fixed64()?))
+    }
+
+    #[inline]
+    fn decode_signed32(self) -> Result<i32, C::Error> {
+        let cx = self.cx;
+
+        if let Frame::Number(number) = self.frame {
+            return narrow(cx, number);
+        }
+
+        if self.is_varint() {
+            let value: u32 = narrow(cx, self.decode_varint()?)?;
+            return Ok(varint::unzigzag(u64::from(value)) as i32);
+        }
+
+        Ok(i32::from_le_bytes(self.decode_fixed32()?))
+    }
+
+    #[inline]
+    fn decode_signed64(self) -> Result<i64, C::Error> {
+        let cx = self.cx;
+
+        if let Frame::Number(number) = self.frame {
+            return narrow(cx, number);
+        }
+
+        if self.is_varint() {
+            return Ok(varint::unzigzag(self.decode_varint()?));
+        }
+
+        Ok(i64::from_le_bytes(self.decode_fixed64()?))
+    }
+
+    /// Read the length of a length-delimited value.
+    #[inline]
+    fn decode_len(&mut self) -> Result<usize, C::Error> {
+        match &self.frame {
+            Frame::Field(key, _) if key.wire == tag::LEN => self.input.get().read_len(self.cx),
+            Frame::Element(tag::LEN, end) => Ok(end.saturating_sub(self.input.get().pos)),
+            _ => Err(self.unexpected(tag::LEN)),
+        }
+    }
+
+    /// Start decoding a message.
+    #[inline]
+    fn decode_message(mut self) -> Result<ProtobufMessageDecoder<'a, OPT, R, C, M>, C::Error> {
+        let end = if let Frame::Root = self.frame {
+            None
+        } else {
+            let len = self.decode_len()?;
+            Some(self.input.get().pos + len)
+        };
+
+        Ok(ProtobufMessageDecoder::new(self.cx, self.input, end))
+    }
+}
+
+#[inline]
+fn narrow<C, T, U>(cx: C, value: T) -> Result<U, C::Error>
+where
+    C: Context,
+    U: TryFrom<T>,
+{
+    match U::try_from(value) {
+        Ok(value) => Ok(value),
+        Err(..) => Err(cx.message("Integer out of range")),
+    }
+}
+
+/// A decoder for the fields of a message.
+pub struct ProtobufMessageDecoder<'a, const OPT: Options, R, C, M> {
+    cx: C,
+    input: Source<'a, R>,
+    message: Message,
+    /// The key of the field currently being decoded.
+    key: Option<Key>,
+    /// The number of the next field when decoding a pack.
+    index: u64,
+    _marker: PhantomData<M>,
+}
+
+impl<'a, 'de, const OPT: Options, R, C, M> ProtobufMessageDecoder<'a, OPT, R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    #[inline]
+    fn new(cx: C, input: Source<'a, R>, end: Option<usize>) -> Self {
+        Self::with_message(cx, input, Message::new(end))
+    }
+
+    #[inline]
+    fn with_message(cx: C, input: Source<'a, R>, message: Message) -> Self {
+        Self {
+            cx,
+            input,
+            message,
+            key: None,
+            index: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn next_key(&mut self) -> Result<Option<Key>, C::Error> {
+        self.message.next_key(self.cx, self.input.get())
+    }
+
+    /// Construct a decoder for the given field.
+    #[inline]
+    fn field(&mut self, key: Key) -> ProtobufDecoder<'_, OPT, R, C, M> {
+        ProtobufDecoder::with_frame(
+            self.cx,
+            Source::Borrowed(self.input.get()),
+            Frame::Field(key, &mut self.message),
+        )
+    }
+
+    /// Construct a decoder for a field number or variant tag.
+    #[inline]
+    fn number(&mut self, number: u64) -> ProtobufDecoder<'_, OPT, R, C, M> {
+        ProtobufDecoder::with_frame(
+            self.cx,
+            Source::Borrowed(self.input.get()),
+            Frame::Number(number),
+        )
+    }
+
+    #[inline]
+    fn take_key(&mut self) -> Result<Key, C::Error> {
+        match self.key.take() {
+            Some(key) => Ok(key),
+            None => Err(self
+                .cx
+                .message("Field number must be decoded before its value")),
+        }
+    }
+
+    #[inline]
+    fn finish(mut self) -> Result<(), C::Error> {
+        self.message.finish(self.cx, self.input.get())
+    }
+}
+
+/// A decoder for the elements of a repeated field.
+///
+/// The elements of a repeated field are either packed into a single
+/// length-delimited value, or spread out over consecutive occurrences of the
+/// field.
+pub struct ProtobufSequenceDecoder<'a, const OPT: Options, R, C, M> {
+    cx: C,
+    input: Source<'a, R>,
+    /// The message the repeated field belongs to, which is used to find
+    /// further occurrences of the field. This is `None` for a sequence nested
+    /// in an element of a repeated field.
+    message: Option<&'a mut Message>,
+    number: u64,
+    /// The wire type of the current occurrence of the field.
+    wire: u8,
+    /// The end of the current occurrence if it's length-delimited.
+    end: usize,
+    /// Indicates that no element has been decoded from the current occurrence
+    /// of the field yet.
+    fresh: bool,
+    /// Indicates that all occurrences of the field have been decoded.
+    done: bool,
+    _marker: PhantomData<M>,
+}
+
+impl<'a, 'de, const OPT: Options, R, C, M> ProtobufSequenceDecoder<'a, OPT, R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    #[inline]
+    fn new(cx: C, input: Source<'a, R>, message: Option<&'a mut Message>, number: u64) -> Self {
+        Self {
+            cx,
+            input,
+            message,
+            number,
+            wire: tag::LEN,
+            end: 0,
+            fresh: false,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Start decoding an occurrence of the field with the given wire type.
+    #[inline]
+    fn start(&mut self, wire: u8) -> Result<(), C::Error> {
+        self.wire = wire;
+        self.fresh = true;
+
+        if wire == tag::LEN {
+            let input = self.input.get();
+            let len = input.read_len(self.cx)?;
+            self.end = input.pos + len;
+        }
+
+        Ok(())
+    }
+
+    /// Advance to the next element, returning `false` if there are no more
+    /// elements.
+    ///
+    /// Every occurrence of the field holds at least one element, which means
+    /// that an empty length-delimited value is an empty string, bytes or
+    /// message and not an empty packed field.
+    #[inline]
+    fn advance(&mut self) -> Result<bool, C::Error> {
+        if self.done {
+            return Ok(false);
+        }
+
+        loop {
+            if self.wire == tag::LEN {
+                let pos = self.input.get().pos;
+
+                if pos > self.end {
+                    return Err(self.cx.message(format_args!(
+                        "Element ended at offset {pos}, but its repeated field ends at {}",
+                        self.end
+                    )));
+                }
+
+                if self.fresh || pos < self.end {
+                    self.fresh = false;
+                    return Ok(true);
+                }
+            } else if self.fresh {
+                self.fresh = false;
+                return Ok(true);
+            }
+
+            self.done = true;
+
+            let Some(message) = self.message.as_deref_mut() else {
+                return Ok(false);
+            };
+
+            let Some(key) = message.next_key(self.cx, self.input.get())? else {
+                return Ok(false);
+            };
+
+            if key.number != self.number {
+                message.pending = Some(key);
+                return Ok(false);
+            }
+
+            self.done = false;
+
+            self.start(key.wire)?;
+        }
+    }
+
+    #[inline]
+    fn skip_remaining(mut self) -> Result<(), C::Error> {
+        while let Some(item) = self.try_decode_next()? {
+            item.skip()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[crate::trait_defaults(crate)]
+impl<'a, 'de, const OPT: Options, R, C, M> Decoder<'de> for ProtobufDecoder<'a, OPT, R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodePack = ProtobufMessageDecoder<'a, OPT, R, C, M>;
+    type DecodeSome = Self;
+    type DecodeSequence = ProtobufSequenceDecoder<'a, OPT, R, C, M>;
+    type DecodeMap = ProtobufMessageDecoder<'a, OPT, R, C, M>;
+    type DecodeMapEntries = ProtobufMessageDecoder<'a, OPT, R, C, M>;
+    type DecodeVariant = ProtobufMessageDecoder<'a, OPT, R, C, M>;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the protobuf decoder")
+    }
+
+    #[inline]
+    fn skip(mut self) -> Result<(), Self::Error> {
+        let cx = self.cx;
+        let input = self.input.get();
+
+        match self.frame {
+            Frame::Root => Message::new(None).finish(cx, input),
+            Frame::Field(key, _) => input.skip_value(cx, key.wire),
+            Frame::Element(tag::LEN, end) => input.skip(cx, end.saturating_sub(input.pos)),
+            Frame::Element(wire, _) => input.skip_value(cx, wire),
+            Frame::Number(..) | Frame::Absent => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, Self::Error> {
+        self.skip()?;
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), Self::Error> {
+        self.skip()
+    }
+
+    #[inline]
+    fn decode_pack<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodePack) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.decode_message()?;
+        let output = f(&mut decoder)?;
+        decoder.finish()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_array<const N: usize>(mut self) -> Result<[u8; N], Self::Error> {
+        let len = self.decode_len()?;
+
+        if len != N {
+            return Err(self.cx.message(format_args!(
+                "Bad length, expected bytes of length {N} but got {len}"
+            )));
+        }
+
+        self.input.get().read_array(self.cx)
+    }
+
+    #[inline]
+    fn decode_bytes<V>(mut self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8], Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        let len = self.decode_len()?;
+        self.input.get().read_bytes(self.cx, len, visitor)
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: UnsizedVisitor<'de, C, str, Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        struct Visitor<V>(V);
+
+        #[crate::trait_defaults(crate)]
+        impl<'de, C, V> UnsizedVisitor<'de, C, [u8]> for Visitor<V>
+        where
+            C: Context,
+            V: UnsizedVisitor<'de, C, str, Error = C::Error, Allocator = C::Allocator>,
+        {
+            type Ok = V::Ok;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.expecting(f)
+            }
+
+            #[inline]
+            fn visit_owned(
+                self,
+                cx: C,
+                bytes: Vec<u8, Self::Allocator>,
+            ) -> Result<Self::Ok, Self::Error> {
+                let string = crate::str::from_utf8_owned(bytes).map_err(cx.map())?;
+                self.0.visit_owned(cx, string)
+            }
+
+            #[inline]
+            fn visit_borrowed(self, cx: C, bytes: &'de [u8]) -> Result<Self::Ok, Self::Error> {
+                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                self.0.visit_borrowed(cx, string)
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: C, bytes: &[u8]) -> Result<Self::Ok, Self::Error> {
+                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                self.0.visit_ref(cx, string)
+            }
+        }
+
+        self.decode_bytes(Visitor(visitor))
+    }
+
+    #[inline]
+    fn decode_bool(self) -> Result<bool, Self::Error> {
+        Ok(self.decode_varint()? != 0)
+    }
+
+    #[inline]
+    fn decode_char(self) -> Result<char, Self::Error> {
+        let cx = self.cx;
+        let value = self.decode_unsigned32()?;
+
+        match char::from_u32(value) {
+            Some(c) => Ok(c),
+            None => Err(cx.message(format_args!("Invalid character code {value}"))),
+        }
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, Self::Error> {
+        let cx = self.cx;
+        narrow(cx, self.decode_unsigned32()?)
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, Self::Error> {
+        let cx = self.cx;
+        narrow(cx, self.decode_unsigned32()?)
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, Self::Error> {
+        self.decode_unsigned32()
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, Self::Error> {
+        self.decode_unsigned64()
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, Self::Error> {
+        let cx = self.cx;
+        narrow(cx, self.decode_signed32()?)
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, Self::Error> {
+        let cx = self.cx;
+        narrow(cx, self.decode_signed32()?)
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, Self::Error> {
+        self.decode_signed32()
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, Self::Error> {
+        self.decode_signed64()
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, Self::Error> {
+        let cx = self.cx;
+        narrow(cx, self.decode_unsigned64()?)
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, Self::Error> {
+        let cx = self.cx;
+        narrow(cx, self.decode_signed64()?)
+    }
+
+    #[inline]
+    fn decode_f32(self) -> Result<f32, Self::Error> {
+        Ok(f32::from_le_bytes(self.decode_fixed32()?))
+    }
+
+    #[inline]
+    fn decode_f64(self) -> Result<f64, Self::Error> {
+        Ok(f64::from_le_bytes(self.decode_fixed64()?))
+    }
+
+    #[inline]
+    fn decode_option(self) -> Result<Option<Self::DecodeSome>, Self::Error> {
+        if let Frame::Absent = self.frame {
+            return Ok(None);
+        }
+
+        Ok(Some(self))
+    }
+
+    #[inline]
+    fn decode_sequence<F, O>(mut self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeSequence) -> Result<O, Self::Error>,
+    {
+        let mut decoder = match self.frame {
+            Frame::Field(key, message) => {
+                let mut decoder =
+                    ProtobufSequenceDecoder::new(self.cx, self.input, Some(message), key.number);
+                decoder.start(key.wire)?;
+                decoder
+            }
+            Frame::Element(tag::LEN, end) => {
+                let mut decoder = ProtobufSequenceDecoder::new(self.cx, self.input, None, 0);
+                decoder.end = end;
+                decoder.fresh = true;
+                decoder
+            }
+            frame => {
+                self.frame = frame;
+                return Err(self.unexpected(tag::LEN));
+            }
+        };
+
+        let output = f(&mut decoder)?;
+        decoder.skip_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.decode_message()?;
+        let output = f(&mut decoder)?;
+        decoder.finish()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.decode_message()?;
+        let output = f(&mut decoder)?;
+        decoder.finish()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_variant<F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        F: FnOnce(&mut Self::DecodeVariant) -> Result<O, Self::Error>,
+    {
+        let mut decoder = self.decode_message()?;
+        let output = f(&mut decoder)?;
+        decoder.finish()?;
+        Ok(output)
+    }
+}
+
+impl<'de, const OPT: Options, R, C, M> SequenceDecoder<'de>
+    for ProtobufMessageDecoder<'_, OPT, R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeNext<'this>
+        = ProtobufDecoder<'this, OPT, R, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, Self::Error> {
+        Ok(Some(self.decode_next()?))
+    }
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, Self::Error> {
+        let index = self.index;
+        self.index += 1;
+
+        match self.next_key()? {
+            Some(key) if key.number == index => Ok(self.field(key)),
+            key => {
+                self.message.pending = key;
+
+                Ok(ProtobufDecoder::with_frame(
+                    self.cx,
+                    Source::Borrowed(self.input.get()),
+                    Frame::Absent,
+                ))
+            }
+        }
+    }
+}
+
+impl<'de, const OPT: Options, R, C, M> SequenceDecoder<'de>
+    for ProtobufSequenceDecoder<'_, OPT, R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeNext<'this>
+        = ProtobufDecoder<'this, OPT, R, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, Self::Error> {
+        if !self.advance()? {
+            return Ok(None);
+        }
+
+        Ok(Some(ProtobufDecoder::with_frame(
+            self.cx,
+            Source::Borrowed(self.input.get()),
+            Frame::Element(self.wire, self.end),
+        )))
+    }
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, Self::Error> {
+        let cx = self.cx;
+
+        let Some(decoder) = self.try_decode_next()? else {
+            return Err(cx.message("No remaining elements"));
+        };
+
+        Ok(decoder)
+    }
+}
+
+impl<'de, const OPT: Options, R, C, M> MapDecoder<'de> for ProtobufMessageDecoder<'_, OPT, R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeEntry<'this>
+        = ProtobufDecoder<'this, OPT, R, C, M>
+    where
+        Self: 'this;
+    type DecodeRemainingEntries<'this>
+        = ProtobufMessageDecoder<'this, OPT, R, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, Self::Error> {
+        let Some(key) = self.next_key()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(self.field(key)))
+    }
+
+    #[inline]
+    fn decode_remaining_entries(
+        &mut self,
+    ) -> Result<Self::DecodeRemainingEntries<'_>, Self::Error> {
+        let message = Message {
+            end: self.message.end,
+            pending: self.message.pending.take(),
+        };
+
+        Ok(ProtobufMessageDecoder::with_message(
+            self.cx,
+            Source::Borrowed(self.input.get()),
+            message,
+        ))
+    }
+}
+
+impl<'de, const OPT: Options, R, C, M> EntriesDecoder<'de>
+    for ProtobufMessageDecoder<'_, OPT, R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeEntryKey<'this>
+        = ProtobufDecoder<'this, OPT, R, C, M>
+    where
+        Self: 'this;
+    type DecodeEntryValue<'this>
+        = ProtobufDecoder<'this, OPT, R, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, Self::Error> {
+        let Some(key) = self.next_key()? else {
+            return Ok(None);
+        };
+
+        self.key = Some(key);
+        Ok(Some(self.number(key.number)))
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, Self::Error> {
+        let key = self.take_key()?;
+        Ok(self.field(key))
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'de, const OPT: Options, R, C, M> EntryDecoder<'de> for ProtobufDecoder<'_, OPT, R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeKey<'this>
+        = ProtobufDecoder<'this, OPT, R, C, M>
+    where
+        Self: 'this;
+    type DecodeValue = Self;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, Self::Error> {
+        let Frame::Field(key, _) = &self.frame else {
+            return Err(self.cx.message("Expected field"));
+        };
+
+        let number = key.number;
+
+        Ok(ProtobufDecoder::with_frame(
+            self.cx,
+            Source::Borrowed(self.input.get()),
+            Frame::Number(number),
+        ))
+    }
+
+    #[inline]
+    fn decode_value(self) -> Result<Self::DecodeValue, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl<'de, const OPT: Options, R, C, M> VariantDecoder<'de>
+    for ProtobufMessageDecoder<'_, OPT, R, C, M>
+where
+    R: Reader<'de>,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Mode = M;
+    type DecodeTag<'this>
+        = ProtobufDecoder<'this, OPT, R, C, M>
+    where
+        Self: 'this;
+    type DecodeValue<'this>
+        = ProtobufDecoder<'this, OPT, R, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, Self::Error> {
+        let Some(key) = self.next_key()? else {
+            return Err(self
+                .cx
+                .message("Expected variant, but got an empty message"));
+        };
+
+        self.key = Some(key);
+        Ok(self.number(key.number))
+    }
+
+    #[inline]
+    fn decode_value(&mut self) -> Result<Self::DecodeValue<'_>, Self::Error> {
+        let key = self.take_key()?;
+        Ok(self.field(key))
+    }
+}
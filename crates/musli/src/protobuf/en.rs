@@ -0,0 +1,936 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::alloc::{Allocator, Vec};
+use crate::en::{
+    Encode, Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder,
+};
+use crate::hint::{MapHint, SequenceHint};
+use crate::options::{self, Integer, Options};
+use crate::writer::Sink;
+use crate::{Context, Writer};
+
+use super::tag::{self, Key, WireType};
+use super::varint;
+
+/// Where an encoded value ends up in the message being encoded.
+enum Target<'a, A>
+where
+    A: Allocator,
+{
+    /// The top-level message, which is neither prefixed with a key nor a
+    /// length.
+    Root,
+    /// The value of the field with the given number.
+    Field(u64),
+    /// An element of the repeated field with the given number. Scalar elements
+    /// are packed into the given buffer.
+    Element(u64, &'a mut Vec<u8, A>),
+}
+
+/// An encoder for protobuf.
+pub struct ProtobufEncoder<'a, const OPT: Options, W, C, M>
+where
+    C: Context,
+{
+    cx: C,
+    writer: Sink<'a, W, C::Allocator>,
+    target: Target<'a, C::Allocator>,
+    _marker: PhantomData<M>,
+}
+
+impl<const OPT: Options, W, C, M> ProtobufEncoder<'_, OPT, W, C, M>
+where
+    C: Context,
+{
+    /// Construct a new protobuf encoder.
+    #[inline]
+    pub(crate) fn new(cx: C, writer: W) -> Self {
+        Self::with_target(cx, Sink::Owned(writer), Target::Root)
+    }
+}
+
+impl<'a, const OPT: Options, W, C, M> ProtobufEncoder<'a, OPT, W, C, M>
+where
+    C: Context,
+{
+    #[inline]
+    fn with_target(
+        cx: C,
+        writer: Sink<'a, W, C::Allocator>,
+        target: Target<'a, C::Allocator>,
+    ) -> Self {
+        Self {
+            cx,
+            writer,
+            target,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, const OPT: Options, W, C, M> ProtobufEncoder<'a, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+{
+    /// Write the key of a scalar with the given wire type, returning the
+    /// writer that the scalar should be written to.
+    ///
+    /// Elements of repeated fields are written to the packed buffer of the
+    /// field without a key.
+    #[inline]
+    fn scalar(&mut self, wire: u8) -> Result<Sink<'_, W, C::Allocator>, C::Error> {
+        match &mut self.target {
+            Target::Root => Err(expected_message(self.cx, wire)),
+            Target::Field(number) => {
+                encode_key(self.cx, self.writer.borrow_mut(), Key::new(*number, wire))?;
+                Ok(self.writer.borrow_mut())
+            }
+            Target::Element(_, packed) => Ok(Sink::Buffer(packed)),
+        }
+    }
+
+    #[inline]
+    fn encode_varint(mut self, value: u64) -> Result<(), C::Error> {
+        let cx = self.cx;
+        let writer = self.scalar(tag::VARINT)?;
+        varint::encode(cx, writer, value)
+    }
+
+    #[inline]
+    fn encode_fixed32(mut self, bytes: [u8; 4]) -> Result<(), C::Error> {
+        let cx = self.cx;
+        let mut writer = self.scalar(tag::I32)?;
+        writer.write_bytes(cx, &bytes)
+    }
+
+    #[inline]
+    fn encode_fixed64(mut self, bytes: [u8; 8]) -> Result<(), C::Error> {
+        let cx = self.cx;
+        let mut writer = self.scalar(tag::I64)?;
+        writer.write_bytes(cx, &bytes)
+    }
+
+    #[inline]
+    fn encode_unsigned32(self, value: u32) -> Result<(), C::Error> {
+        match options::integer::<OPT>() {
+            Integer::Variable => self.encode_varint(u64::from(value)),
+            _ => self.encode_fixed32(value.to_le_bytes()),
+        }
+    }
+
+    #[inline]
+    fn encode_unsigned64(self, value: u64) -> Result<(), C::Error> {
+        match options::integer::<OPT>() {
+            Integer::Variable => self.encode_varint(value),
+            _ => self.encode_fixed64(value.to_le_bytes()),
+        }
+    }
+
+    #[inline]
+    fn encode_signed32(self, value: i32) -> Result<(), C::Error> {
+        match options::integer::<OPT>() {
+            Integer::Variable => self.encode_varint(varint::zigzag(i64::from(value))),
+            _ => self.encode_fixed32(value.to_le_bytes()),
+        }
+    }
+
+    #[inline]
+    fn encode_signed64(self, value: i64) -> Result<(), C::Error> {
+        match options::integer::<OPT>() {
+            Integer::Variable => self.encode_varint(varint::zigzag(value)),
+            _ => self.encode_fixed64(value.to_le_bytes()),
+        }
+    }
+
+    /// Write the key and the length of a length-delimited value.
+    #[inline]
+    fn encode_len(&mut self, len: usize) -> Result<(), C::Error> {
+        let number = match &self.target {
+            Target::Root => return Err(expected_message(self.cx, tag::LEN)),
+            Target::Field(number) | Target::Element(number, _) => *number,
+        };
+
+        encode_key(
+            self.cx,
+            self.writer.borrow_mut(),
+            Key::new(number, tag::LEN),
+        )?;
+        varint::encode(self.cx, self.writer.borrow_mut(), len as u64)
+    }
+
+    /// Start encoding a message.
+    #[inline]
+    fn encode_message(self) -> ProtobufMessageEncoder<'a, OPT, W, C, M> {
+        let frame = match self.target {
+            Target::Root => None,
+            Target::Field(number) | Target::Element(number, _) => Some(number),
+        };
+
+        ProtobufMessageEncoder::new(self.cx, self.writer, frame)
+    }
+}
+
+/// Encoder for messages.
+///
+/// Messages which are the value of a field are buffered, so that they can be
+/// prefixed with their length once they are finished.
+pub struct ProtobufMessageEncoder<'a, const OPT: Options, W, C, M>
+where
+    C: Context,
+{
+    cx: C,
+    writer: Sink<'a, W, C::Allocator>,
+    /// The number of the field the message is the value of, or `None` if the
+    /// message is written as-is.
+    frame: Option<u64>,
+    /// The tag of a variant whose data is the message being encoded. If this is
+    /// set, the message is wrapped in a message with a single field numbered
+    /// by the tag.
+    variant: Option<u64>,
+    buffer: Vec<u8, C::Allocator>,
+    /// The number of the field currently being encoded.
+    number: Option<u64>,
+    /// The number of the next field when encoding a pack.
+    index: u64,
+    _marker: PhantomData<M>,
+}
+
+impl<'a, const OPT: Options, W, C, M> ProtobufMessageEncoder<'a, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+{
+    #[inline]
+    fn new(cx: C, writer: Sink<'a, W, C::Allocator>, frame: Option<u64>) -> Self {
+        Self {
+            cx,
+            writer,
+            frame,
+            variant: None,
+            buffer: Vec::new_in(cx.alloc()),
+            number: None,
+            index: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The sink that fields of the message are written to.
+    #[inline]
+    fn sink(&mut self) -> Sink<'_, W, C::Allocator> {
+        if self.frame.is_some() || self.variant.is_some() {
+            Sink::Buffer(&mut self.buffer)
+        } else {
+            self.writer.borrow_mut()
+        }
+    }
+
+    /// Take the number of the field whose value is about to be encoded.
+    #[inline]
+    fn take_number(&mut self) -> Result<u64, C::Error> {
+        match self.number.take() {
+            Some(number) => Ok(number),
+            None => Err(self
+                .cx
+                .message("Field number must be encoded before its value")),
+        }
+    }
+
+    #[inline]
+    fn finish(self) -> Result<(), C::Error> {
+        let cx = self.cx;
+        let mut buffer = self.buffer;
+
+        if let Some(tag) = self.variant {
+            let mut outer = Vec::new_in(cx.alloc());
+            encode_delimited(cx, Sink::<W, _>::Buffer(&mut outer), tag, &buffer)?;
+            buffer = outer;
+        }
+
+        let mut writer = self.writer;
+
+        match self.frame {
+            Some(number) => encode_delimited(cx, writer, number, &buffer),
+            None => writer.write_bytes(cx, &buffer),
+        }
+    }
+}
+
+/// Encoder for repeated fields.
+///
+/// Scalar elements are packed into a single length-delimited value, which is
+/// written once the sequence is finished. Other elements are written as one
+/// field each.
+pub struct ProtobufSequenceEncoder<'a, const OPT: Options, W, C, M>
+where
+    C: Context,
+{
+    cx: C,
+    message: ProtobufMessageEncoder<'a, OPT, W, C, M>,
+    number: u64,
+    packed: Vec<u8, C::Allocator>,
+}
+
+impl<'a, const OPT: Options, W, C, M> ProtobufSequenceEncoder<'a, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+{
+    #[inline]
+    fn new(cx: C, message: ProtobufMessageEncoder<'a, OPT, W, C, M>, number: u64) -> Self {
+        Self {
+            cx,
+            message,
+            number,
+            packed: Vec::new_in(cx.alloc()),
+        }
+    }
+}
+
+/// Encoder for a single field in a message.
+pub struct ProtobufEntryEncoder<'a, const OPT: Options, W, C, M>
+where
+    C: Context,
+{
+    cx: C,
+    writer: Sink<'a, W, C::Allocator>,
+    number: Option<u64>,
+    _marker: PhantomData<M>,
+}
+
+/// Encoder for field numbers and variant tags.
+pub struct ProtobufKeyEncoder<'a, C, M> {
+    cx: C,
+    number: &'a mut Option<u64>,
+    _marker: PhantomData<M>,
+}
+
+impl<'a, C, M> ProtobufKeyEncoder<'a, C, M>
+where
+    C: Context,
+{
+    #[inline]
+    fn new(cx: C, number: &'a mut Option<u64>) -> Self {
+        Self {
+            cx,
+            number,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn set(self, number: u64) -> Result<(), C::Error> {
+        if number > tag::MAX_FIELD_NUMBER {
+            return Err(self
+                .cx
+                .message(format_args!("Field number {number} is out of range")));
+        }
+
+        *self.number = Some(number);
+        Ok(())
+    }
+
+    #[inline]
+    fn set_signed(self, number: i64) -> Result<(), C::Error> {
+        match u64::try_from(number) {
+            Ok(number) => self.set(number),
+            Err(..) => Err(self
+                .cx
+                .message(format_args!("Field number {number} is negative"))),
+        }
+    }
+}
+
+#[crate::trait_defaults(crate)]
+impl<'a, const OPT: Options, W, C, M> Encoder for ProtobufEncoder<'a, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodePack = ProtobufMessageEncoder<'a, OPT, W, C, M>;
+    type EncodeSome = Self;
+    type EncodeSequence = ProtobufSequenceEncoder<'a, OPT, W, C, M>;
+    type EncodeMap = ProtobufMessageEncoder<'a, OPT, W, C, M>;
+    type EncodeMapEntries = ProtobufMessageEncoder<'a, OPT, W, C, M>;
+    type EncodeVariant = ProtobufMessageEncoder<'a, OPT, W, C, M>;
+    type EncodeSequenceVariant = ProtobufSequenceEncoder<'a, OPT, W, C, M>;
+    type EncodeMapVariant = ProtobufMessageEncoder<'a, OPT, W, C, M>;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the protobuf encoder")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<(), Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self)
+    }
+
+    #[inline]
+    fn encode_empty(mut self) -> Result<(), Self::Error> {
+        if let Target::Root = self.target {
+            return Ok(());
+        }
+
+        self.encode_len(0)
+    }
+
+    #[inline]
+    fn encode_pack(self) -> Result<Self::EncodePack, Self::Error> {
+        Ok(self.encode_message())
+    }
+
+    #[inline]
+    fn encode_array<const N: usize>(self, array: &[u8; N]) -> Result<(), Self::Error> {
+        self.encode_bytes(array)
+    }
+
+    #[inline]
+    fn encode_bytes(mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.encode_len(bytes.len())?;
+        self.writer.write_bytes(self.cx, bytes)
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<I>(mut self, len: usize, vectors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item: AsRef<[u8]>>,
+    {
+        self.encode_len(len)?;
+
+        for bytes in vectors {
+            self.writer.write_bytes(self.cx, bytes.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_string(self, string: &str) -> Result<(), Self::Error> {
+        self.encode_bytes(string.as_bytes())
+    }
+
+    #[inline]
+    fn encode_bool(self, value: bool) -> Result<(), Self::Error> {
+        self.encode_varint(u64::from(value))
+    }
+
+    #[inline]
+    fn encode_char(self, value: char) -> Result<(), Self::Error> {
+        self.encode_unsigned32(u32::from(value))
+    }
+
+    #[inline]
+    fn encode_u8(self, value: u8) -> Result<(), Self::Error> {
+        self.encode_unsigned32(u32::from(value))
+    }
+
+    #[inline]
+    fn encode_u16(self, value: u16) -> Result<(), Self::Error> {
+        self.encode_unsigned32(u32::from(value))
+    }
+
+    #[inline]
+    fn encode_u32(self, value: u32) -> Result<(), Self::Error> {
+        self.encode_unsigned32(value)
+    }
+
+    #[inline]
+    fn encode_u64(self, value: u64) -> Result<(), Self::Error> {
+        self.encode_unsigned64(value)
+    }
+
+    #[inline]
+    fn encode_i8(self, value: i8) -> Result<(), Self::Error> {
+        self.encode_signed32(i32::from(value))
+    }
+
+    #[inline]
+    fn encode_i16(self, value: i16) -> Result<(), Self::Error> {
+        self.encode_signed32(i32::from(value))
+    }
+
+    #[inline]
+    fn encode_i32(self, value: i32) -> Result<(), Self::Error> {
+        self.encode_signed32(value)
+    }
+
+    #[inline]
+    fn encode_i64(self, value: i64) -> Result<(), Self::Error> {
+        self.encode_signed64(value)
+    }
+
+    #[inline]
+    fn encode_f32(self, value: f32) -> Result<(), Self::Error> {
+        self.encode_fixed32(value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_f64(self, value: f64) -> Result<(), Self::Error> {
+        self.encode_fixed64(value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_usize(self, value: usize) -> Result<(), Self::Error> {
+        self.encode_unsigned64(value as u64)
+    }
+
+    #[inline]
+    fn encode_isize(self, value: isize) -> Result<(), Self::Error> {
+        self.encode_signed64(value as i64)
+    }
+
+    #[inline]
+    fn encode_some(self) -> Result<Self::EncodeSome, Self::Error> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_none(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_sequence(self, _: impl SequenceHint) -> Result<Self::EncodeSequence, Self::Error> {
+        let number = match self.target {
+            Target::Root => return Err(expected_message(self.cx, tag::LEN)),
+            Target::Field(number) | Target::Element(number, _) => number,
+        };
+
+        let message = ProtobufMessageEncoder::new(self.cx, self.writer, None);
+        Ok(ProtobufSequenceEncoder::new(self.cx, message, number))
+    }
+
+    #[inline]
+    fn encode_map(self, _: impl MapHint) -> Result<Self::EncodeMap, Self::Error> {
+        Ok(self.encode_message())
+    }
+
+    #[inline]
+    fn encode_map_entries(self, _: impl MapHint) -> Result<Self::EncodeMapEntries, Self::Error> {
+        Ok(self.encode_message())
+    }
+
+    #[inline]
+    fn encode_variant(self) -> Result<Self::EncodeVariant, Self::Error> {
+        Ok(self.encode_message())
+    }
+
+    #[inline]
+    fn encode_sequence_variant<T>(
+        self,
+        tag: &T,
+        _: impl SequenceHint,
+    ) -> Result<Self::EncodeSequenceVariant, Self::Error>
+    where
+        T: ?Sized + Encode<Self::Mode>,
+    {
+        let cx = self.cx;
+        let number = encode_number(cx, tag)?;
+        let message = self.encode_message();
+        Ok(ProtobufSequenceEncoder::new(cx, message, number))
+    }
+
+    #[inline]
+    fn encode_map_variant<T>(
+        self,
+        tag: &T,
+        _: impl MapHint,
+    ) -> Result<Self::EncodeMapVariant, Self::Error>
+    where
+        T: ?Sized + Encode<Self::Mode>,
+    {
+        let number = encode_number(self.cx, tag)?;
+        let mut message = self.encode_message();
+        message.variant = Some(number);
+        Ok(message)
+    }
+}
+
+impl<const OPT: Options, W, C, M> SequenceEncoder for ProtobufMessageEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeNext<'this>
+        = ProtobufEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, Self::Error> {
+        let number = self.index;
+        self.index += 1;
+        Ok(ProtobufEncoder::with_target(
+            self.cx,
+            self.sink(),
+            Target::Field(number),
+        ))
+    }
+
+    #[inline]
+    fn finish_sequence(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<const OPT: Options, W, C, M> SequenceEncoder for ProtobufSequenceEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeNext<'this>
+        = ProtobufEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, Self::Error> {
+        Ok(ProtobufEncoder::with_target(
+            self.cx,
+            self.message.sink(),
+            Target::Element(self.number, &mut self.packed),
+        ))
+    }
+
+    #[inline]
+    fn finish_sequence(mut self) -> Result<(), Self::Error> {
+        if !self.packed.is_empty() {
+            encode_delimited(self.cx, self.message.sink(), self.number, &self.packed)?;
+        }
+
+        self.message.finish()
+    }
+}
+
+impl<const OPT: Options, W, C, M> MapEncoder for ProtobufMessageEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeEntry<'this>
+        = ProtobufEntryEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_entry(&mut self) -> Result<Self::EncodeEntry<'_>, Self::Error> {
+        Ok(ProtobufEntryEncoder {
+            cx: self.cx,
+            writer: self.sink(),
+            number: None,
+            _marker: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn finish_map(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<const OPT: Options, W, C, M> EntryEncoder for ProtobufEntryEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeKey<'this>
+        = ProtobufKeyEncoder<'this, C, M>
+    where
+        Self: 'this;
+    type EncodeValue<'this>
+        = ProtobufEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_key(&mut self) -> Result<Self::EncodeKey<'_>, Self::Error> {
+        Ok(ProtobufKeyEncoder::new(self.cx, &mut self.number))
+    }
+
+    #[inline]
+    fn encode_value(&mut self) -> Result<Self::EncodeValue<'_>, Self::Error> {
+        let Some(number) = self.number.take() else {
+            return Err(self
+                .cx
+                .message("Field number must be encoded before its value"));
+        };
+
+        Ok(ProtobufEncoder::with_target(
+            self.cx,
+            self.writer.borrow_mut(),
+            Target::Field(number),
+        ))
+    }
+
+    #[inline]
+    fn finish_entry(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<const OPT: Options, W, C, M> EntriesEncoder for ProtobufMessageEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeEntryKey<'this>
+        = ProtobufKeyEncoder<'this, C, M>
+    where
+        Self: 'this;
+    type EncodeEntryValue<'this>
+        = ProtobufEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_entry_key(&mut self) -> Result<Self::EncodeEntryKey<'_>, Self::Error> {
+        Ok(ProtobufKeyEncoder::new(self.cx, &mut self.number))
+    }
+
+    #[inline]
+    fn encode_entry_value(&mut self) -> Result<Self::EncodeEntryValue<'_>, Self::Error> {
+        let number = self.take_number()?;
+        Ok(ProtobufEncoder::with_target(
+            self.cx,
+            self.sink(),
+            Target::Field(number),
+        ))
+    }
+
+    #[inline]
+    fn finish_entries(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<const OPT: Options, W, C, M> VariantEncoder for ProtobufMessageEncoder<'_, OPT, W, C, M>
+where
+    W: Writer,
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+    type EncodeTag<'this>
+        = ProtobufKeyEncoder<'this, C, M>
+    where
+        Self: 'this;
+    type EncodeData<'this>
+        = ProtobufEncoder<'this, OPT, W, C, M>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn encode_tag(&mut self) -> Result<Self::EncodeTag<'_>, Self::Error> {
+        Ok(ProtobufKeyEncoder::new(self.cx, &mut self.number))
+    }
+
+    #[inline]
+    fn encode_data(&mut self) -> Result<Self::EncodeData<'_>, Self::Error> {
+        let number = self.take_number()?;
+        Ok(ProtobufEncoder::with_target(
+            self.cx,
+            self.sink(),
+            Target::Field(number),
+        ))
+    }
+
+    #[inline]
+    fn finish_variant(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+#[crate::trait_defaults(crate)]
+impl<C, M> Encoder for ProtobufKeyEncoder<'_, C, M>
+where
+    C: Context,
+    M: 'static,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = M;
+
+    #[inline]
+    fn cx(&self) -> Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field number")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<(), Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self)
+    }
+
+    #[inline]
+    fn encode_u8(self, value: u8) -> Result<(), Self::Error> {
+        self.set(u64::from(value))
+    }
+
+    #[inline]
+    fn encode_u16(self, value: u16) -> Result<(), Self::Error> {
+        self.set(u64::from(value))
+    }
+
+    #[inline]
+    fn encode_u32(self, value: u32) -> Result<(), Self::Error> {
+        self.set(u64::from(value))
+    }
+
+    #[inline]
+    fn encode_u64(self, value: u64) -> Result<(), Self::Error> {
+        self.set(value)
+    }
+
+    #[inline]
+    fn encode_usize(self, value: usize) -> Result<(), Self::Error> {
+        self.set(value as u64)
+    }
+
+    #[inline]
+    fn encode_i8(self, value: i8) -> Result<(), Self::Error> {
+        self.set_signed(i64::from(value))
+    }
+
+    #[inline]
+    fn encode_i16(self, value: i16) -> Result<(), Self::Error> {
+        self.set_signed(i64::from(value))
+    }
+
+    #[inline]
+    fn encode_i32(self, value: i32) -> Result<(), Self::Error> {
+        self.set_signed(i64::from(value))
+    }
+
+    #[inline]
+    fn encode_i64(self, value: i64) -> Result<(), Self::Error> {
+        self.set_signed(value)
+    }
+
+    #[inline]
+    fn encode_isize(self, value: isize) -> Result<(), Self::Error> {
+        self.set_signed(value as i64)
+    }
+}
+
+/// Encode the tag of a variant as a field number.
+#[inline]
+fn encode_number<C, T, M>(cx: C, tag: &T) -> Result<u64, C::Error>
+where
+    C: Context,
+    T: ?Sized + Encode<M>,
+    M: 'static,
+{
+    let mut number = None;
+    tag.encode(ProtobufKeyEncoder::<_, M>::new(cx, &mut number))?;
+
+    match number {
+        Some(number) => Ok(number),
+        None => Err(cx.message("Variant tag must be encoded as a field number")),
+    }
+}
+
+/// Encode the key of a field.
+#[inline]
+fn encode_key<W, C>(cx: C, writer: W, key: Key) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    varint::encode(cx, writer, key.into_raw())
+}
+
+/// Encode a length-delimited field.
+#[inline]
+fn encode_delimited<W, C>(cx: C, mut writer: W, number: u64, bytes: &[u8]) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    encode_key(cx, writer.borrow_mut(), Key::new(number, tag::LEN))?;
+    varint::encode(cx, writer.borrow_mut(), bytes.len() as u64)?;
+    writer.write_bytes(cx, bytes)
+}
+
+#[inline]
+fn expected_message<C>(cx: C, wire: u8) -> C::Error
+where
+    C: Context,
+{
+    cx.message(format_args!(
+        "Expected message at the top level, but got {}",
+        WireType(wire)
+    ))
+}
@@ -0,0 +1,4 @@
+crate::macros::implement_error! {
+    /// Error raised during protobuf encoding.
+    pub struct Error;
+}
@@ -0,0 +1,59 @@
+//! Support for the protobuf `int32` and `int64` types.
+//!
+//! Signed integers are zigzag encoded by default, which corresponds to the
+//! protobuf `sint32` and `sint64` types. Fields declared as `int32` or `int64`
+//! are instead sign-extended to 64 bits and encoded as unsigned varints, which
+//! is what this module does when used through `#[musli(with =
+//! musli::protobuf::int)]`.
+//!
+//! # Examples
+//!
+//! ```
+//! use musli::{Decode, Encode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Temperature {
+//!     #[musli(Binary, name = 1, with = musli::protobuf::int)]
+//!     celsius: i32,
+//! }
+//!
+//! let bytes = musli::protobuf::to_vec(&Temperature { celsius: -1 })?;
+//! assert_eq!(bytes, b"\x08\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01");
+//!
+//! let temperature: Temperature = musli::protobuf::from_slice(&bytes)?;
+//! assert_eq!(temperature, Temperature { celsius: -1 });
+//! # Ok::<_, musli::protobuf::Error>(())
+//! ```
+
+use crate::{Context, Decoder, Encoder};
+
+/// Encode a signed integer like the protobuf `int32` and `int64` types.
+///
+/// See the [module level documentation][self] for more information.
+#[inline]
+pub fn encode<E, T>(value: &T, encoder: E) -> Result<(), E::Error>
+where
+    E: Encoder,
+    T: Copy + Into<i64>,
+{
+    let value: i64 = (*value).into();
+    encoder.encode_u64(value as u64)
+}
+
+/// Decode a signed integer like the protobuf `int32` and `int64` types.
+///
+/// See the [module level documentation][self] for more information.
+#[inline]
+pub fn decode<'de, D, T>(decoder: D) -> Result<T, D::Error>
+where
+    D: Decoder<'de>,
+    T: TryFrom<i64>,
+{
+    let cx = decoder.cx();
+    let value = decoder.decode_u64()? as i64;
+
+    match T::try_from(value) {
+        Ok(value) => Ok(value),
+        Err(..) => Err(cx.message("Integer out of range")),
+    }
+}
@@ -0,0 +1,157 @@
+//! Support for the [Protocol Buffers] wire format for [Müsli].
+//!
+//! This is byte-compatible with protobuf, which means that messages produced
+//! by code generated by `protoc` in other languages can be decoded without a
+//! code generation step, and vice versa. Fields are identified by the numerical
+//! name they are given in the [`Binary`] mode, which corresponds to the field
+//! number in the `.proto` definition:
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`.
+//! * ✔ Can skip over unknown fields.
+//!
+//! Note that protobuf field numbers start at `1`, so structs which are shared
+//! with other protobuf implementations should name their fields explicitly.
+//!
+//! [Müsli]: https://docs.rs/musli
+//! [Protocol Buffers]: https://protobuf.dev
+//! [`Binary`]: crate::mode::Binary
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! // message Person {
+//! //     string name = 1;
+//! //     uint32 age = 2;
+//! //     repeated uint32 scores = 3;
+//! // }
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Person {
+//!     #[musli(Binary, name = 1)]
+//!     name: String,
+//!     #[musli(Binary, name = 2)]
+//!     age: u32,
+//!     #[musli(Binary, name = 3, default)]
+//!     scores: Vec<u32>,
+//! }
+//!
+//! let person = Person {
+//!     name: String::from("Aristotle"),
+//!     age: 61,
+//!     scores: vec![1, 2, 300],
+//! };
+//!
+//! let bytes = musli::protobuf::to_vec(&person)?;
+//! assert_eq!(bytes, b"\x0a\x09Aristotle\x10\x3d\x1a\x04\x01\x02\xac\x02");
+//!
+//! let decoded: Person = musli::protobuf::from_slice(&bytes)?;
+//! assert_eq!(decoded, person);
+//! # Ok::<_, musli::protobuf::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Configuring
+//!
+//! Integers are encoded as varints by default. Using [`Integer::Fixed`]
+//! instead encodes them like the `fixed32`, `fixed64`, `sfixed32` and
+//! `sfixed64` types:
+//!
+//! [`Integer::Fixed`]: crate::options::Integer::Fixed
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//! use musli::options::{self, Integer, Options};
+//! use musli::protobuf::Encoding;
+//!
+//! const OPTIONS: Options = options::new().integer(Integer::Fixed).build();
+//! const CONFIG: Encoding<OPTIONS> = Encoding::new().with_options();
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Point {
+//!     #[musli(Binary, name = 1)]
+//!     x: i32,
+//!     #[musli(Binary, name = 2)]
+//!     y: i32,
+//! }
+//!
+//! let bytes = CONFIG.to_vec(&Point { x: 1, y: -1 })?;
+//! assert_eq!(bytes, b"\x0d\x01\x00\x00\x00\x15\xff\xff\xff\xff");
+//!
+//! let point: Point = CONFIG.from_slice(&bytes)?;
+//! assert_eq!(point, Point { x: 1, y: -1 });
+//! # Ok::<_, musli::protobuf::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Implementation details
+//!
+//! Müsli types map onto protobuf as follows:
+//!
+//! * Structs and maps are encoded as messages, where keys are field numbers.
+//!   Nested messages are length-delimited.
+//! * Unsigned integers are encoded like `uint32` and `uint64`, and signed
+//!   integers like `sint32` and `sint64` using zigzag encoding. Fields declared
+//!   as `int32` or `int64` can use [`int`] instead.
+//! * `bool` is encoded as a varint, and `f32` and `f64` like `float` and
+//!   `double`.
+//! * Strings and bytes are length-delimited.
+//! * Sequences are encoded as repeated fields. Scalar elements are packed
+//!   into a single length-delimited value, while other elements are encoded
+//!   as one field each. Both packed and unpacked repeated fields are supported
+//!   when decoding, as long as all occurrences of the field are adjacent.
+//!   Empty sequences are not encoded at all.
+//! * `None` is not encoded at all, while `Some(value)` is encoded as `value`.
+//! * Enums whose variants are all empty are encoded as their tag, which
+//!   corresponds to protobuf enums when combined with `#[musli(Binary, name =
+//!   N)]` or `name_all = "discriminant"`.
+//! * Other enum variants are encoded as a message with a single field, whose
+//!   number is the variant tag and whose value is the content of the variant.
+//! * Packed structs are encoded as messages, where the fields are numbered by
+//!   their position starting at `0`.
+//!
+//! Since protobuf is not self-descriptive, values can't be decoded into
+//! dynamic containers such as the [`Value`] type. Groups are not supported, and
+//! neither are 128-bit integers.
+//!
+//! [`Value`]: crate::value
+
+#![cfg(feature = "protobuf")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "protobuf")))]
+
+#[cfg(test)]
+mod tests;
+
+mod de;
+mod en;
+mod encoding;
+mod error;
+pub mod int;
+mod tag;
+mod varint;
+
+/// Convenient result alias for use with `musli::protobuf`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::to_vec;
+#[cfg(all(feature = "std", feature = "alloc"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "alloc"))))]
+#[doc(inline)]
+pub use self::encoding::to_writer;
+#[doc(inline)]
+pub use self::encoding::{Encoding, OPTIONS};
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, to_slice};
+#[cfg(feature = "tokio")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+#[doc(inline)]
+pub use self::encoding::{decode_async, encode_async};
+#[doc(inline)]
+pub use self::error::Error;
@@ -0,0 +1,73 @@
+//! Wire types used by `musli::protobuf`.
+
+use core::fmt;
+
+/// A variable-length integer, used for `int32`, `int64`, `uint32`, `uint64`,
+/// `sint32`, `sint64`, `bool` and `enum`.
+pub(crate) const VARINT: u8 = 0;
+/// A little-endian 64-bit value, used for `fixed64`, `sfixed64` and `double`.
+pub(crate) const I64: u8 = 1;
+/// A length-delimited value, used for `string`, `bytes`, embedded messages and
+/// packed repeated fields.
+pub(crate) const LEN: u8 = 2;
+/// The start of a group, which is deprecated.
+pub(crate) const SGROUP: u8 = 3;
+/// The end of a group, which is deprecated.
+pub(crate) const EGROUP: u8 = 4;
+/// A little-endian 32-bit value, used for `fixed32`, `sfixed32` and `float`.
+pub(crate) const I32: u8 = 5;
+
+/// The number of bits in the key of a field which are used for the wire type.
+pub(crate) const WIRE_TYPE_BITS: u32 = 3;
+/// Mask for the wire type in the key of a field.
+pub(crate) const WIRE_TYPE_MASK: u64 = 0b111;
+
+/// The largest field number permitted by protobuf.
+pub(crate) const MAX_FIELD_NUMBER: u64 = (1 << 29) - 1;
+
+/// The key of a field, which is its number and its wire type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Key {
+    pub(crate) number: u64,
+    pub(crate) wire: u8,
+}
+
+impl Key {
+    #[inline]
+    pub(crate) const fn new(number: u64, wire: u8) -> Self {
+        Self { number, wire }
+    }
+
+    /// Decode a key from its raw varint representation.
+    #[inline]
+    pub(crate) const fn from_raw(raw: u64) -> Self {
+        Self {
+            number: raw >> WIRE_TYPE_BITS,
+            wire: (raw & WIRE_TYPE_MASK) as u8,
+        }
+    }
+
+    /// Coerce the key into its raw varint representation.
+    #[inline]
+    pub(crate) const fn into_raw(self) -> u64 {
+        (self.number << WIRE_TYPE_BITS) | self.wire as u64
+    }
+}
+
+/// Helper to display a wire type.
+pub(crate) struct WireType(pub(crate) u8);
+
+impl fmt::Display for WireType {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            VARINT => write!(f, "varint"),
+            I64 => write!(f, "64-bit value"),
+            LEN => write!(f, "length-delimited value"),
+            SGROUP => write!(f, "start of group"),
+            EGROUP => write!(f, "end of group"),
+            I32 => write!(f, "32-bit value"),
+            wire => write!(f, "reserved wire type {wire}"),
+        }
+    }
+}
@@ -0,0 +1,408 @@
+use rust_alloc::collections::BTreeMap;
+use rust_alloc::string::String;
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::compat::Bytes;
+use crate::options::{self, Integer, Options};
+use crate::{Decode, Encode};
+
+use super::Encoding;
+
+const FIXED: Options = options::new().integer(Integer::Fixed).build();
+
+// message Test1 { int32 a = 1; }
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Test1 {
+    #[musli(Binary, name = 1)]
+    a: u32,
+}
+
+// message Test2 { string b = 2; }
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Test2 {
+    #[musli(Binary, name = 2)]
+    b: String,
+}
+
+// message Test3 { Test1 c = 3; }
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Test3 {
+    #[musli(Binary, name = 3)]
+    c: Test1,
+}
+
+// message Test4 { string d = 4; repeated int32 e = 5; }
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Test4 {
+    #[musli(Binary, name = 4, default)]
+    d: String,
+    #[musli(Binary, name = 5, default)]
+    e: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Scalars {
+    #[musli(Binary, name = 1)]
+    signed: i32,
+    #[musli(Binary, name = 2)]
+    wide: i64,
+    #[musli(Binary, name = 3)]
+    flag: bool,
+    #[musli(Binary, name = 4)]
+    float: f32,
+    #[musli(Binary, name = 5)]
+    double: f64,
+    #[musli(Binary, name = 6)]
+    bytes: Bytes<Vec<u8>>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Repeated {
+    #[musli(Binary, name = 1, default)]
+    names: Vec<String>,
+    #[musli(Binary, name = 2, default)]
+    messages: Vec<Test1>,
+    #[musli(Binary, name = 3, default)]
+    numbers: Vec<i32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+enum Color {
+    #[musli(Binary, name = 0)]
+    Red,
+    #[musli(Binary, name = 2)]
+    Blue,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+enum Shape {
+    Empty,
+    Circle(u32),
+    Rectangle { width: u32, height: u32 },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Enums {
+    #[musli(Binary, name = 1)]
+    color: Color,
+    #[musli(Binary, name = 2)]
+    shape: Shape,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Optional {
+    #[musli(Binary, name = 1, default)]
+    first: Option<u32>,
+    #[musli(Binary, name = 2, default)]
+    second: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, packed)]
+struct Point {
+    x: i32,
+    y: Option<i32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct After {
+    #[musli(Binary, name = 2)]
+    b: String,
+}
+
+macro_rules! assert_bytes {
+    (with $encoding:expr, $ty:ty, $value:expr, $expected:expr) => {{
+        let value: $ty = $value;
+        let bytes = $encoding.to_vec(&value).unwrap();
+        assert_eq!(bytes, $expected, "{value:?}");
+        let actual: $ty = $encoding.from_slice(&bytes).unwrap();
+        assert_eq!(actual, value);
+    }};
+
+    ($ty:ty, $value:expr, $expected:expr) => {
+        assert_bytes!(with Encoding::new(), $ty, $value, $expected)
+    };
+}
+
+#[test]
+fn messages() {
+    assert_bytes!(Test1, Test1 { a: 150 }, [0x08, 0x96, 0x01]);
+    assert_bytes!(
+        Test2,
+        Test2 {
+            b: String::from("testing")
+        },
+        *b"\x12\x07testing"
+    );
+    assert_bytes!(
+        Test3,
+        Test3 {
+            c: Test1 { a: 150 }
+        },
+        [0x1a, 0x03, 0x08, 0x96, 0x01]
+    );
+    assert_bytes!(
+        Test4,
+        Test4 {
+            d: String::from("hello"),
+            e: vec![1, 2, 3],
+        },
+        *b"\x22\x05hello\x2a\x03\x01\x02\x03"
+    );
+
+    // Fields can appear in any order.
+    let value: Test4 = super::from_slice(b"\x2a\x01\x01\x22\x01a").unwrap();
+    assert_eq!(
+        value,
+        Test4 {
+            d: String::from("a"),
+            e: vec![1],
+        }
+    );
+
+    // Missing fields use their default.
+    let value: Test4 = super::from_slice(b"").unwrap();
+    assert_eq!(
+        value,
+        Test4 {
+            d: String::new(),
+            e: Vec::new(),
+        }
+    );
+
+    // Top-level values have to be messages.
+    assert!(super::to_vec(&42u32).is_err());
+}
+
+#[test]
+fn scalars() {
+    let value = Scalars {
+        signed: -2,
+        wide: i64::MIN,
+        flag: true,
+        float: 1.0,
+        double: -2.5,
+        bytes: Bytes(vec![1, 2]),
+    };
+
+    assert_bytes!(
+        Scalars,
+        value,
+        [
+            0x08, 0x03, 0x10, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x18,
+            0x01, 0x25, 0x00, 0x00, 0x80, 0x3f, 0x29, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04,
+            0xc0, 0x32, 0x02, 0x01, 0x02
+        ]
+    );
+
+    // Integers can be decoded into any type which fits them.
+    assert_eq!(
+        super::from_slice::<Test1>(&[0x08, 0x7f]).unwrap(),
+        Test1 { a: 127 }
+    );
+    assert!(super::from_slice::<Test1>(&[0x08, 0x80, 0x80, 0x80, 0x80, 0x10]).is_err());
+
+    // Wire types are checked.
+    assert!(super::from_slice::<Test1>(&[0x0a, 0x01, 0x01]).is_err());
+}
+
+#[test]
+fn fixed_integers() {
+    const CONFIG: Encoding<FIXED> = Encoding::new().with_options();
+
+    assert_bytes!(
+        with CONFIG,
+        Test1,
+        Test1 { a: 150 },
+        [0x0d, 0x96, 0x00, 0x00, 0x00]
+    );
+
+    let value = Scalars {
+        signed: -2,
+        wide: 1,
+        flag: false,
+        float: 0.0,
+        double: 0.0,
+        bytes: Bytes(Vec::new()),
+    };
+
+    let bytes = CONFIG.to_vec(&value).unwrap();
+    assert_eq!(
+        &bytes[..10],
+        [0x0d, 0xfe, 0xff, 0xff, 0xff, 0x11, 0x01, 0, 0, 0]
+    );
+    assert_eq!(CONFIG.from_slice::<Scalars>(&bytes).unwrap(), value);
+}
+
+#[test]
+fn repeated() {
+    let value = Repeated {
+        names: vec![String::from("a"), String::new()],
+        messages: vec![Test1 { a: 1 }, Test1 { a: 2 }],
+        numbers: vec![3, -270],
+    };
+
+    // Strings and messages are never packed, while scalars are.
+    assert_bytes!(
+        Repeated,
+        value,
+        *b"\x0a\x01a\x0a\x00\x12\x02\x08\x01\x12\x02\x08\x02\x1a\x03\x06\x9b\x04"
+    );
+
+    // Empty sequences are omitted.
+    assert_bytes!(
+        Repeated,
+        Repeated {
+            names: Vec::new(),
+            messages: Vec::new(),
+            numbers: Vec::new(),
+        },
+        []
+    );
+
+    // Unpacked scalars, and packed scalars split across several fields.
+    let value: Repeated = super::from_slice(b"\x18\x06\x18\x9b\x04\x1a\x01\x02").unwrap();
+    assert_eq!(value.numbers, [3, -270, 1]);
+
+    let value: Test4 = super::from_slice(b"\x28\x03\x28\x8e\x02\x28\x9e\xa7\x05").unwrap();
+    assert_eq!(value.e, [3, 270, 86942]);
+}
+
+#[test]
+fn maps() {
+    let mut map = BTreeMap::new();
+    map.insert(1u32, String::from("a"));
+    map.insert(3u32, String::from("b"));
+    assert_bytes!(BTreeMap<u32, String>, map, *b"\x0a\x01a\x1a\x01b");
+}
+
+#[test]
+fn enums() {
+    assert_bytes!(
+        Enums,
+        Enums {
+            color: Color::Blue,
+            shape: Shape::Circle(2),
+        },
+        [0x08, 0x02, 0x12, 0x04, 0x0a, 0x02, 0x00, 0x02]
+    );
+
+    assert_bytes!(
+        Enums,
+        Enums {
+            color: Color::Red,
+            shape: Shape::Rectangle {
+                width: 1,
+                height: 2,
+            },
+        },
+        [0x08, 0x00, 0x12, 0x06, 0x12, 0x04, 0x00, 0x01, 0x08, 0x02]
+    );
+
+    let value = Enums {
+        color: Color::Red,
+        shape: Shape::Empty,
+    };
+
+    let bytes = super::to_vec(&value).unwrap();
+    assert_eq!(super::from_slice::<Enums>(&bytes).unwrap(), value);
+
+    assert!(super::from_slice::<Enums>(&[0x08, 0x01, 0x12, 0x00]).is_err());
+}
+
+#[test]
+fn optional() {
+    assert_bytes!(
+        Optional,
+        Optional {
+            first: Some(1),
+            second: None,
+        },
+        [0x08, 0x01]
+    );
+
+    assert_bytes!(
+        Optional,
+        Optional {
+            first: None,
+            second: Some(String::from("a")),
+        },
+        *b"\x12\x01a"
+    );
+}
+
+#[test]
+fn packs() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate)]
+    struct Points {
+        #[musli(Binary, name = 1)]
+        points: Vec<Point>,
+    }
+
+    assert_bytes!(
+        Points,
+        Points {
+            points: vec![Point { x: 1, y: Some(-1) }, Point { x: 2, y: None }],
+        },
+        [0x0a, 0x04, 0x00, 0x02, 0x08, 0x01, 0x0a, 0x02, 0x00, 0x04]
+    );
+
+    assert_bytes!(Point, Point { x: 1, y: None }, [0x00, 0x02]);
+}
+
+#[test]
+fn skip_unknown() {
+    // Unknown fields of every wire type are skipped.
+    let bytes = b"\x08\x96\x01\x21\x01\x02\x03\x04\x05\x06\x07\x08\x1a\x02ab\x25\x01\x02\x03\x04\x12\x01c\x30\x01";
+    let after: After = super::from_slice(bytes).unwrap();
+
+    assert_eq!(
+        after,
+        After {
+            b: String::from("c")
+        }
+    );
+
+    // Groups are not supported.
+    assert!(super::from_slice::<After>(b"\x0b\x0c\x12\x01c").is_err());
+    // Neither are truncated messages.
+    assert!(super::from_slice::<After>(b"\x12\x02c").is_err());
+}
+
+#[test]
+fn int() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate)]
+    struct Int {
+        #[musli(Binary, name = 1, with = super::int)]
+        value: i64,
+        #[musli(Binary, name = 2, with = super::int)]
+        small: i32,
+    }
+
+    assert_bytes!(
+        Int,
+        Int {
+            value: -2,
+            small: 1
+        },
+        [
+            0x08, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x10, 0x01
+        ]
+    );
+
+    assert!(super::from_slice::<Int>(&[0x08, 0x01, 0x10, 0x80, 0x80, 0x80, 0x80, 0x10]).is_err());
+}
@@ -0,0 +1,33 @@
+//! Variable-length integers as used by protobuf.
+
+use crate::{Context, Writer};
+
+/// Encode a variable-length integer, which is at most ten bytes long.
+#[inline]
+pub(crate) fn encode<C, W>(cx: C, mut writer: W, mut value: u64) -> Result<(), C::Error>
+where
+    C: Context,
+    W: Writer,
+{
+    while value >= 0x80 {
+        writer.write_byte(cx, (value as u8) | 0x80)?;
+        value >>= 7;
+    }
+
+    writer.write_byte(cx, value as u8)
+}
+
+/// Zigzag encode a signed integer, as used by `sint32` and `sint64`.
+///
+/// Values in the range of `i32` are mapped to values in the range of `u32`, so
+/// this is also used for `sint32`.
+#[inline]
+pub(crate) const fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Decode a zigzag encoded signed integer.
+#[inline]
+pub(crate) const fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
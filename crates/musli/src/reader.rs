@@ -19,6 +19,8 @@ mod sealed {
     impl Sealed for super::SliceReader<'_> {}
     impl<'de, R> Sealed for Limit<R> where R: Reader<'de> {}
     impl<'de, R> Sealed for &mut R where R: ?Sized + Reader<'de> {}
+    #[cfg(feature = "std")]
+    impl<R> Sealed for crate::wrap::Wrap<R> where R: std::io::BufRead {}
 }
 
 /// Coerce a type into a [`Reader`].
@@ -60,6 +62,14 @@ pub trait Reader<'de>: self::sealed::Sealed {
     /// Test if the reader is at end of input.
     fn is_eof(&mut self) -> bool;
 
+    /// Try to borrow the remaining input of the reader.
+    ///
+    /// This is only supported by readers over a borrowed slice of bytes.
+    #[inline]
+    fn try_borrow_remaining(&self) -> Option<&'de [u8]> {
+        None
+    }
+
     /// Skip over the given number of bytes.
     fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
     where
@@ -205,6 +215,11 @@ impl<'de> Reader<'de> for &'de [u8] {
         self.is_empty()
     }
 
+    #[inline]
+    fn try_borrow_remaining(&self) -> Option<&'de [u8]> {
+        Some(self)
+    }
+
     #[inline]
     fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
     where
@@ -425,6 +440,11 @@ impl<'de> Reader<'de> for SliceReader<'de> {
         self.range.start == self.range.end
     }
 
+    #[inline]
+    fn try_borrow_remaining(&self) -> Option<&'de [u8]> {
+        Some(self.as_slice())
+    }
+
     #[inline]
     fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
     where
@@ -622,6 +642,11 @@ where
         self.remaining == 0 || self.reader.is_eof()
     }
 
+    #[inline]
+    fn try_borrow_remaining(&self) -> Option<&'de [u8]> {
+        self.reader.try_borrow_remaining()?.get(..self.remaining)
+    }
+
     #[inline]
     fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
     where
@@ -726,6 +751,11 @@ where
         (**self).is_eof()
     }
 
+    #[inline]
+    fn try_borrow_remaining(&self) -> Option<&'de [u8]> {
+        (**self).try_borrow_remaining()
+    }
+
     #[inline]
     fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
     where
@@ -510,6 +510,11 @@ where
         SizeHint::exact(self.remaining)
     }
 
+    #[inline]
+    fn try_borrow_remaining(&self) -> Option<&'de [u8]> {
+        self.reader.try_borrow_remaining()
+    }
+
     #[inline]
     fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, Self::Error> {
         if self.remaining == 0 {
@@ -562,6 +567,11 @@ where
         SizeHint::exact(self.remaining)
     }
 
+    #[inline]
+    fn try_borrow_remaining(&self) -> Option<&'de [u8]> {
+        self.reader.try_borrow_remaining()
+    }
+
     #[inline]
     fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, Self::Error> {
         if self.remaining == 0 {
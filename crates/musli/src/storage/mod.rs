@@ -110,6 +110,10 @@ pub mod test;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::DecodeIter;
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 #[doc(inline)]
@@ -123,5 +127,9 @@ pub use self::encoding::{Encoding, OPTIONS};
 #[cfg(feature = "alloc")]
 #[doc(inline)]
 pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, to_slice};
+#[cfg(feature = "tokio")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+#[doc(inline)]
+pub use self::encoding::{decode_async, encode_async};
 #[doc(inline)]
 pub use self::error::Error;
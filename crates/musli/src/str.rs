@@ -4,6 +4,7 @@
 #![cfg(any(
     feature = "storage",
     feature = "wire",
+    feature = "protobuf",
     feature = "descriptive",
     feature = "json",
     feature = "value"
@@ -63,6 +63,13 @@ macro_rules! ensure_number {
     ($self:expr, $opt:expr, $hint_type:ident, $value:ident::$variant:ident($v:ident) => $ty:ty) => {
         match &$self.value.kind {
             $value::$variant($v) => <$ty>::from_number($v).map_err(|e| $self.cx.message(e)),
+            ValueKind::RawNumber(number) => match <$ty>::parse_number(number) {
+                Some(value) => Ok(value),
+                None => Err($self.cx.message(ErrorMessage::ExpectedNumber(
+                    NumberHint::$hint_type,
+                    TypeHint::RawNumber,
+                ))),
+            },
             ValueKind::String(string)
                 if crate::options::is_map_keys_as_numbers::<$opt>() && $self.map_key =>
             {
@@ -210,12 +217,34 @@ where
 
     #[inline]
     fn decode_f32(self) -> Result<f32, Self::Error> {
-        ensure!(self, hint, ExpectedNumber(NumberHint::F32, hint), ValueKind::Number(Number::F32(n)) => Ok(*n))
+        match &self.value.kind {
+            ValueKind::RawNumber(number) => match f32::parse_number(number) {
+                Some(value) => Ok(value),
+                None => Err(self.cx.message(ErrorMessage::ExpectedNumber(
+                    NumberHint::F32,
+                    TypeHint::RawNumber,
+                ))),
+            },
+            _ => {
+                ensure!(self, hint, ExpectedNumber(NumberHint::F32, hint), ValueKind::Number(Number::F32(n)) => Ok(*n))
+            }
+        }
     }
 
     #[inline]
     fn decode_f64(self) -> Result<f64, Self::Error> {
-        ensure!(self, hint, ExpectedNumber(NumberHint::F64, hint), ValueKind::Number(Number::F64(n)) => Ok(*n))
+        match &self.value.kind {
+            ValueKind::RawNumber(number) => match f64::parse_number(number) {
+                Some(value) => Ok(value),
+                None => Err(self.cx.message(ErrorMessage::ExpectedNumber(
+                    NumberHint::F64,
+                    TypeHint::RawNumber,
+                ))),
+            },
+            _ => {
+                ensure!(self, hint, ExpectedNumber(NumberHint::F64, hint), ValueKind::Number(Number::F64(n)) => Ok(*n))
+            }
+        }
     }
 
     #[inline]
@@ -320,6 +349,14 @@ where
         })
     }
 
+    #[inline]
+    fn decode_number<V>(self, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        V: Visitor<'de, Self::Cx, Error = Self::Error, Allocator = Self::Allocator>,
+    {
+        self.decode_any(visitor)
+    }
+
     #[inline]
     fn decode_any<V>(self, visitor: V) -> Result<V::Ok, V::Error>
     where
@@ -345,6 +382,7 @@ where
                 Number::F32(value) => visitor.visit_f32(self.cx, *value),
                 Number::F64(value) => visitor.visit_f64(self.cx, *value),
             },
+            ValueKind::RawNumber(number) => visitor.visit_number(self.cx, number),
             ValueKind::Bytes(bytes) => {
                 let visitor = visitor.visit_bytes(self.cx, SizeHint::exact(bytes.len()))?;
                 visitor.visit_borrowed(self.cx, bytes)
@@ -227,6 +227,14 @@ where
         Ok(())
     }
 
+    #[inline]
+    fn encode_number(self, number: &str) -> Result<(), Self::Error> {
+        let mut string = String::new_in(self.cx.alloc());
+        string.push_str(number).map_err(self.cx.map())?;
+        self.output.write(self.cx, ValueKind::RawNumber(string))?;
+        Ok(())
+    }
+
     #[inline]
     fn encode_usize(self, n: usize) -> Result<(), Self::Error> {
         self.output
@@ -14,6 +14,8 @@ pub(crate) enum TypeHint {
     Char,
     /// The type as a number.
     Number(NumberHint),
+    /// A number in its textual form.
+    RawNumber,
     /// A byte array.
     Bytes(SizeHint),
     /// A string with the given length.
@@ -35,6 +37,7 @@ impl fmt::Display for TypeHint {
             TypeHint::Bool => write!(f, "bool"),
             TypeHint::Char => write!(f, "char"),
             TypeHint::Number(number) => number.fmt(f),
+            TypeHint::RawNumber => write!(f, "number"),
             TypeHint::Bytes(size) => write!(f, "bytes with {size}"),
             TypeHint::String(size) => write!(f, "string with {size}"),
             TypeHint::Sequence(size) => write!(f, "sequence with {size}"),
@@ -30,6 +30,9 @@ where
     Char(char),
     /// A number.
     Number(Number),
+    /// A number in its textual form, as produced by formats which support
+    /// arbitrary-precision numbers.
+    RawNumber(String<A>),
     /// An array.
     Bytes(Vec<u8, A>),
     /// A string in a value.
@@ -218,6 +221,7 @@ where
             ValueKind::Bool(..) => TypeHint::Bool,
             ValueKind::Char(..) => TypeHint::Char,
             ValueKind::Number(number) => TypeHint::Number(number.type_hint()),
+            ValueKind::RawNumber(..) => TypeHint::RawNumber,
             ValueKind::Bytes(bytes) => TypeHint::Bytes(SizeHint::exact(bytes.len())),
             ValueKind::String(string) => TypeHint::String(SizeHint::exact(string.len())),
             ValueKind::Sequence(sequence) => TypeHint::Sequence(SizeHint::exact(sequence.len())),
@@ -251,6 +255,7 @@ where
             ValueKind::Bool(value) => value.fmt(f),
             ValueKind::Char(value) => value.fmt(f),
             ValueKind::Number(value) => value.fmt(f),
+            ValueKind::RawNumber(value) => f.write_str(value),
             ValueKind::Bytes(value) => BStr::new(value).fmt(f),
             ValueKind::String(value) => value.fmt(f),
             ValueKind::Sequence(value) => value.fmt(f),
@@ -289,6 +294,7 @@ where
             (ValueKind::Bool(lhs), ValueKind::Bool(rhs)) => lhs == rhs,
             (ValueKind::Char(lhs), ValueKind::Char(rhs)) => lhs == rhs,
             (ValueKind::Number(lhs), ValueKind::Number(rhs)) => lhs == rhs,
+            (ValueKind::RawNumber(lhs), ValueKind::RawNumber(rhs)) => lhs == rhs,
             (ValueKind::Bytes(lhs), ValueKind::Bytes(rhs)) => lhs == rhs,
             (ValueKind::String(lhs), ValueKind::String(rhs)) => lhs == rhs,
             (ValueKind::Sequence(lhs), ValueKind::Sequence(rhs)) => lhs == rhs,
@@ -311,6 +317,9 @@ where
             (ValueKind::Bool(lhs), ValueKind::Bool(rhs)) => lhs.partial_cmp(rhs),
             (ValueKind::Char(lhs), ValueKind::Char(rhs)) => lhs.partial_cmp(rhs),
             (ValueKind::Number(lhs), ValueKind::Number(rhs)) => lhs.partial_cmp(rhs),
+            (ValueKind::RawNumber(lhs), ValueKind::RawNumber(rhs)) if lhs == rhs => {
+                Some(Ordering::Equal)
+            }
             (ValueKind::Bytes(lhs), ValueKind::Bytes(rhs)) => lhs.partial_cmp(rhs),
             (ValueKind::String(lhs), ValueKind::String(rhs)) => lhs.partial_cmp(rhs),
             (ValueKind::Sequence(lhs), ValueKind::Sequence(rhs)) => lhs.partial_cmp(rhs),
@@ -540,6 +549,13 @@ where
         Ok(Value::new(ValueKind::Number(Number::F64(value))))
     }
 
+    #[inline]
+    fn visit_number(self, cx: C, number: &str) -> Result<Self::Ok, Self::Error> {
+        let mut string = String::new_in(cx.alloc());
+        string.push_str(number).map_err(cx.map())?;
+        Ok(Value::new(ValueKind::RawNumber(string)))
+    }
+
     #[inline]
     fn visit_none(self, _: C) -> Result<Self::Ok, Self::Error> {
         Ok(Value::new(ValueKind::Option(None)))
@@ -691,6 +707,7 @@ where
             ValueKind::Bool(b) => encoder.encode_bool(*b),
             ValueKind::Char(c) => encoder.encode_char(*c),
             ValueKind::Number(n) => encoder.encode(n),
+            ValueKind::RawNumber(number) => encoder.encode_number(number),
             ValueKind::Bytes(bytes) => encoder.encode_bytes(bytes),
             ValueKind::String(string) => encoder.encode_string(string),
             ValueKind::Sequence(values) => encoder.encode_sequence_fn(values.len(), |sequence| {
@@ -1,5 +1,7 @@
 use core::marker;
 
+#[cfg(feature = "alloc")]
+use crate::Reader;
 #[cfg(feature = "alloc")]
 use crate::alloc::Global;
 use crate::mode::Binary;
@@ -131,6 +133,18 @@ where
     );
 }
 
+crate::macros::decode_iter!(
+    wire,
+    [const OPT: Options, M] Encoding<OPT, M>,
+    M,
+    WireDecoder::<OPT, _, _, M>::new,
+    IntoReader::into_reader -> Reader,
+    Reader,
+    |_cx, reader| reader.is_eof(),
+);
+
+crate::macros::lazy_encoding!([const OPT: Options, M] Encoding<OPT, M>, M);
+
 impl<const OPT: Options, M> Clone for Encoding<OPT, M> {
     #[inline]
     fn clone(&self) -> Self {
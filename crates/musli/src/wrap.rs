@@ -2,11 +2,25 @@
 //!
 //! The main methods in this module is the [`wrap`] function which constructs an
 //! adapter around an I/O type to work with musli.
+//!
+//! Wrapping a [`std::io::Write`] produces a [`Writer`], while wrapping a
+//! [`std::io::BufRead`] produces a [`Reader`]. Any [`std::io::Read`] can be
+//! used for reading by first wrapping it in a [`std::io::BufReader`].
+//!
+//! [`Reader`]: crate::reader::Reader
+//! [`Writer`]: crate::writer::Writer
+
+#[cfg(feature = "std")]
+use core::fmt;
+#[cfg(feature = "std")]
+use core::ptr;
 
 #[cfg(feature = "std")]
 use crate::Context;
 #[cfg(feature = "std")]
 use crate::alloc::Vec;
+#[cfg(feature = "std")]
+use crate::de::UnsizedVisitor;
 
 /// Wrap a type so that it implements [`Reader`] and [`Writer`].
 ///
@@ -41,6 +55,34 @@ pub struct Wrap<T> {
 /// let buffer: Vec<u8> = Vec::new();
 /// let wrapped = wrap::wrap(buffer);
 /// ```
+///
+/// Decoding from a [`std::io::Read`] through a [`std::io::BufReader`]:
+///
+/// ```
+/// use std::io::BufReader;
+///
+/// use musli::{Decode, Encode};
+/// use musli::storage;
+/// use musli::wrap;
+/// # use musli::storage::Error;
+///
+/// #[derive(Debug, PartialEq, Decode, Encode)]
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let data = storage::to_vec(&Person {
+///     name: "Aristotle".to_string(),
+///     age: 61,
+/// })?;
+///
+/// let reader = wrap::wrap(BufReader::new(&data[..]));
+/// let person: Person = storage::decode(reader)?;
+/// assert_eq!(person.name, "Aristotle");
+/// assert_eq!(person.age, 61);
+/// # Ok::<_, Error>(())
+/// ```
 #[inline]
 pub fn wrap<T>(inner: T) -> Wrap<T> {
     Wrap { inner }
@@ -103,3 +145,207 @@ where
         Ok(())
     }
 }
+
+#[cfg(feature = "std")]
+impl<R> Wrap<R>
+where
+    R: std::io::BufRead,
+{
+    /// Fill the internal buffer of the wrapped reader, erroring if the
+    /// underlying source has reached end of input.
+    #[inline]
+    fn fill<C>(&mut self, cx: C, n: usize, remaining: usize) -> Result<&[u8], C::Error>
+    where
+        C: Context,
+    {
+        let buf = self.inner.fill_buf().map_err(cx.map())?;
+
+        if buf.is_empty() {
+            return Err(cx.custom(ReaderUnderflow { n, remaining }));
+        }
+
+        Ok(buf)
+    }
+
+    /// Copy `n` bytes into the given pointer.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` is valid for writes of `n` bytes.
+    #[inline]
+    unsafe fn copy_to<C>(&mut self, cx: C, mut ptr: *mut u8, n: usize) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        let mut remaining = n;
+
+        while remaining > 0 {
+            let buf = self.fill(cx, n, remaining)?;
+            let len = buf.len().min(remaining);
+
+            // SAFETY: The caller ensures that `ptr` is valid for `n` bytes, and
+            // we never write more than `remaining` out of those.
+            unsafe {
+                ptr::copy_nonoverlapping(buf.as_ptr(), ptr, len);
+                ptr = ptr.add(len);
+            }
+
+            self.inner.consume(len);
+            remaining -= len;
+        }
+
+        cx.advance(n);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, R> crate::reader::IntoReader<'de> for Wrap<R>
+where
+    R: std::io::BufRead,
+{
+    type Reader = Self;
+
+    #[inline]
+    fn into_reader(self) -> Self::Reader {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, R> crate::reader::Reader<'de> for Wrap<R>
+where
+    R: std::io::BufRead,
+{
+    type Mut<'this>
+        = &'this mut Self
+    where
+        Self: 'this;
+
+    type TryClone = Self;
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::Mut<'_> {
+        self
+    }
+
+    #[inline]
+    fn try_clone(&self) -> Option<Self::TryClone> {
+        None
+    }
+
+    #[inline]
+    fn is_eof(&mut self) -> bool {
+        // Errors are deferred until the next read, which will report them.
+        matches!(self.inner.fill_buf(), Ok(buf) if buf.is_empty())
+    }
+
+    #[inline]
+    fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        let mut remaining = n;
+
+        while remaining > 0 {
+            let len = self.fill(cx, n, remaining)?.len().min(remaining);
+            self.inner.consume(len);
+            remaining -= len;
+        }
+
+        cx.advance(n);
+        Ok(())
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<u8> {
+        self.inner.fill_buf().ok()?.first().copied()
+    }
+
+    #[inline]
+    fn read<C>(&mut self, cx: C, buf: &mut [u8]) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        // SAFETY: The buffer is valid for writes of its own length.
+        unsafe { self.copy_to(cx, buf.as_mut_ptr(), buf.len()) }
+    }
+
+    #[inline]
+    fn read_bytes<C, V>(&mut self, cx: C, n: usize, visitor: V) -> Result<V::Ok, V::Error>
+    where
+        C: Context,
+        V: UnsizedVisitor<'de, C, [u8], Error = C::Error, Allocator = C::Allocator>,
+    {
+        let buf = self.inner.fill_buf().map_err(cx.map())?;
+
+        // Fast path where the requested bytes are already buffered.
+        if buf.len() >= n {
+            let ok = visitor.visit_ref(cx, &buf[..n])?;
+            self.inner.consume(n);
+            cx.advance(n);
+            return Ok(ok);
+        }
+
+        // Grow the buffer as data arrives, so that a bogus length prefix
+        // cannot cause a large up-front allocation.
+        let mut bytes = Vec::new_in(cx.alloc());
+        let mut remaining = n;
+
+        while remaining > 0 {
+            let buf = self.fill(cx, n, remaining)?;
+            let len = buf.len().min(remaining);
+            bytes.extend_from_slice(&buf[..len]).map_err(cx.map())?;
+            self.inner.consume(len);
+            remaining -= len;
+        }
+
+        let ok = visitor.visit_owned(cx, bytes)?;
+        cx.advance(n);
+        Ok(ok)
+    }
+
+    #[inline]
+    unsafe fn read_bytes_uninit<C>(&mut self, cx: C, ptr: *mut u8, n: usize) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        // SAFETY: The caller ensures that `ptr` is valid for writes of `n`
+        // bytes.
+        unsafe { self.copy_to(cx, ptr, n) }
+    }
+
+    #[inline]
+    fn read_array<C, const N: usize>(&mut self, cx: C) -> Result<[u8; N], C::Error>
+    where
+        C: Context,
+    {
+        let mut array = [0u8; N];
+        self.read(cx, &mut array)?;
+        Ok(array)
+    }
+}
+
+/// Underflow when trying to read from an I/O source.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct ReaderUnderflow {
+    n: usize,
+    remaining: usize,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ReaderUnderflow {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ReaderUnderflow { n, remaining } = self;
+
+        write!(
+            f,
+            "Unexpected end of input while reading {n} bytes, with {remaining} bytes still missing"
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for ReaderUnderflow {}
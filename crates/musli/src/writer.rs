@@ -7,6 +7,10 @@
 mod slice_mut_writer;
 pub use self::slice_mut_writer::SliceMutWriter;
 
+mod sink;
+#[cfg(any(feature = "cbor", feature = "json", feature = "protobuf"))]
+pub(crate) use self::sink::Sink;
+
 use core::fmt;
 
 use crate::alloc::Vec;
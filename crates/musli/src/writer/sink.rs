@@ -0,0 +1,88 @@
+#![cfg(any(feature = "cbor", feature = "json", feature = "protobuf"))]
+
+use crate::alloc::{Allocator, Vec};
+use crate::{Context, Writer};
+
+/// A writer which is either the underlying writer of an encoder, or a buffer.
+///
+/// Formats which need to buffer parts of their output, such as canonical CBOR
+/// maps, canonical JSON objects or length-delimited protobuf messages, write
+/// either to the underlying writer or to a buffer. This is always expressed in
+/// terms of the underlying writer `W` so that the type of the encoder doesn't
+/// grow with each level of nesting.
+pub(crate) enum Sink<'a, W, A>
+where
+    A: Allocator,
+{
+    Owned(W),
+    Borrowed(&'a mut W),
+    Buffer(&'a mut Vec<u8, A>),
+}
+
+#[cfg(any(feature = "cbor", feature = "json"))]
+impl<W, A> Sink<'_, W, A>
+where
+    A: Allocator,
+{
+    /// The number of bytes written to the buffer, if any.
+    #[inline]
+    pub(crate) fn buffered(&self) -> usize {
+        match self {
+            Sink::Buffer(buffer) => buffer.len(),
+            _ => 0,
+        }
+    }
+}
+
+impl<'a, W, A> Writer for Sink<'a, W, A>
+where
+    W: Writer,
+    A: Allocator,
+{
+    type Ok = ();
+    type Mut<'this>
+        = Sink<'this, W, A>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn finish<C>(&mut self, _: C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::Mut<'_> {
+        match self {
+            Sink::Owned(writer) => Sink::Borrowed(writer),
+            Sink::Borrowed(writer) => Sink::Borrowed(writer),
+            Sink::Buffer(buffer) => Sink::Buffer(buffer),
+        }
+    }
+
+    #[inline]
+    fn extend<C>(&mut self, cx: C, buffer: Vec<u8, C::Allocator>) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        match self {
+            Sink::Owned(writer) => writer.extend(cx, buffer),
+            Sink::Borrowed(writer) => writer.extend(cx, buffer),
+            Sink::Buffer(this) => this.extend_from_slice(buffer.as_slice()).map_err(cx.map()),
+        }
+    }
+
+    #[inline]
+    fn write_bytes<C>(&mut self, cx: C, bytes: &[u8]) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        match self {
+            Sink::Owned(writer) => writer.write_bytes(cx, bytes),
+            Sink::Borrowed(writer) => writer.write_bytes(cx, bytes),
+            Sink::Buffer(buffer) => buffer.extend_from_slice(bytes).map_err(cx.map()),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "test")]
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(Text, name_all = "kebab-case")]
+struct User {
+    #[musli(Text, alias = "user_name", alias = "login")]
+    user_name: String,
+    #[musli(Binary, alias = 7usize)]
+    age: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct UserV1 {
+    #[musli(Binary, name = 0usize)]
+    login: String,
+    #[musli(Binary, name = 7usize)]
+    age: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum State {
+    #[musli(Text, name = "stopped", alias = "halted", alias = "paused")]
+    #[musli(Binary, alias = 2usize)]
+    Stopped,
+    Running,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Patterned {
+    #[musli(pattern = ("value" | "val"), alias = "v")]
+    value: u32,
+}
+
+const OLD_NAME: &str = "old";
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Constant {
+    #[musli(alias = OLD_NAME)]
+    value: u32,
+}
+
+fn user() -> User {
+    User {
+        user_name: String::from("jane"),
+        age: 42,
+    }
+}
+
+#[test]
+fn field_alias() {
+    musli::macros::assert_roundtrip_eq!(full, user(), json = r#"{"user-name":"jane","age":42}"#);
+
+    for json in [
+        r#"{"user-name":"jane","age":42}"#,
+        r#"{"user_name":"jane","age":42}"#,
+        r#"{"login":"jane","age":42}"#,
+    ] {
+        let decoded: User = musli::json::from_str(json).unwrap();
+        assert_eq!(decoded, user());
+    }
+
+    let v1 = UserV1 {
+        login: String::from("jane"),
+        age: 42,
+    };
+
+    let bytes = musli::wire::to_vec(&v1).unwrap();
+    let decoded: User = musli::wire::from_slice(&bytes).unwrap();
+    assert_eq!(decoded, user());
+}
+
+#[test]
+fn variant_alias() {
+    musli::macros::assert_roundtrip_eq!(full, State::Stopped, json = r#""stopped""#);
+
+    for json in [r#""stopped""#, r#""halted""#, r#""paused""#] {
+        let decoded: State = musli::json::from_str(json).unwrap();
+        assert_eq!(decoded, State::Stopped);
+    }
+
+    let bytes = musli::wire::to_vec(&2usize).unwrap();
+    let decoded: State = musli::wire::from_slice(&bytes).unwrap();
+    assert_eq!(decoded, State::Stopped);
+
+    assert!(musli::json::from_str::<State>(r#""stopping""#).is_err());
+}
+
+#[test]
+fn alias_with_pattern() {
+    for json in [r#"{"value":1}"#, r#"{"val":1}"#, r#"{"v":1}"#] {
+        let decoded: Patterned = musli::json::from_str(json).unwrap();
+        assert_eq!(decoded, Patterned { value: 1 });
+    }
+}
+
+#[test]
+fn alias_constant() {
+    for json in [r#"{"value":1}"#, r#"{"old":1}"#] {
+        let decoded: Constant = musli::json::from_str(json).unwrap();
+        assert_eq!(decoded, Constant { value: 1 });
+    }
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "test")]
+
+use musli::context;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Message {
+    id: u32,
+    body: String,
+    values: Vec<u64>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Other {
+    id: String,
+}
+
+fn message(id: u32) -> Message {
+    Message {
+        id,
+        body: format!("message {id}"),
+        values: (0..u64::from(id)).collect(),
+    }
+}
+
+#[tokio::test]
+async fn roundtrip_frames() {
+    let (mut client, mut server) = tokio::io::duplex(16);
+
+    let writer = tokio::spawn(async move {
+        for id in 0..4 {
+            musli::wire::encode_async(&mut client, &message(id))
+                .await
+                .unwrap();
+        }
+    });
+
+    for id in 0..4 {
+        let actual: Message = musli::wire::decode_async(&mut server).await.unwrap();
+        assert_eq!(actual, message(id));
+    }
+
+    writer.await.unwrap();
+}
+
+#[tokio::test]
+async fn all_formats() {
+    macro_rules! test {
+        ($what:ident) => {{
+            let mut data = Vec::new();
+            musli::$what::encode_async(&mut data, &message(3))
+                .await
+                .unwrap();
+            let actual: Message = musli::$what::decode_async(&data[..]).await.unwrap();
+            assert_eq!(actual, message(3));
+        }};
+    }
+
+    test!(storage);
+    test!(wire);
+    test!(packed);
+    test!(descriptive);
+    test!(json);
+}
+
+#[tokio::test]
+async fn truncated_frame() {
+    let mut data = Vec::new();
+    musli::storage::encode_async(&mut data, &message(2))
+        .await
+        .unwrap();
+    data.pop();
+
+    let result: Result<Message, _> = musli::storage::decode_async(&data[..]).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn traced_errors() {
+    let encoding = musli::wire::Encoding::new();
+
+    let mut data = Vec::new();
+
+    let cx = context::new().with_trace();
+    encoding
+        .encode_async_with(&cx, &mut data, &message(2))
+        .await
+        .unwrap();
+
+    let cx = context::new().with_trace();
+
+    let Err(..) = encoding
+        .decode_async_with::<_, _, Other>(&cx, &data[..])
+        .await
+    else {
+        panic!("expected decoding to fail");
+    };
+
+    let error = cx.errors().next().expect("expected an error");
+    assert!(error.to_string().starts_with(".id"), "{error}");
+}
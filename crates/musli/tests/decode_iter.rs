@@ -0,0 +1,115 @@
+#![cfg(feature = "test")]
+
+use musli::context;
+use musli::reader::{Reader, SliceReader};
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Record<'a> {
+    id: u32,
+    name: &'a str,
+}
+
+const RECORDS: [Record<'static>; 3] = [
+    Record { id: 1, name: "one" },
+    Record { id: 2, name: "two" },
+    Record {
+        id: 3,
+        name: "three",
+    },
+];
+
+macro_rules! test_decode_iter {
+    ($what:ident) => {
+        #[test]
+        fn $what() {
+            let encoding = musli::$what::Encoding::new();
+
+            let mut data = Vec::new();
+
+            for record in &RECORDS {
+                encoding.to_writer(&mut data, record).unwrap();
+            }
+
+            let records = encoding
+                .decode_iter::<_, Record<'_>>(&data[..])
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(records, RECORDS);
+
+            let mut reader = SliceReader::new(&data);
+            let records = encoding
+                .decode_iter::<_, Record<'_>>(&mut reader)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(records, RECORDS);
+            assert!(reader.is_eof());
+
+            let last = encoding.to_vec(&RECORDS[2]).unwrap();
+            let limit = data.len() - last.len();
+
+            let mut reader = SliceReader::new(&data).limit(limit);
+            let records = encoding
+                .decode_iter::<_, Record<'_>>(&mut reader)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(records, RECORDS[..2]);
+
+            let truncated = &data[..data.len() - 1];
+            let mut it = encoding.decode_iter::<_, Record<'_>>(truncated);
+            assert_eq!(it.next().unwrap().unwrap(), RECORDS[0]);
+            assert_eq!(it.next().unwrap().unwrap(), RECORDS[1]);
+            let error = it.next().unwrap().unwrap_err().to_string();
+            assert!(error.starts_with("Record 2: "), "{error}");
+            assert_eq!(it.index(), 2);
+            assert!(it.next().is_none());
+
+            let cx = context::new().with_trace();
+            let result = encoding
+                .decode_iter_with::<_, _, Record<'_>>(&cx, truncated)
+                .collect::<Result<Vec<_>, _>>();
+            assert!(result.is_err());
+            let report = cx.report().to_string();
+            assert!(report.starts_with("[2]"), "{report}");
+
+            let mut it = encoding.decode_iter::<_, Record<'_>>(&[][..]);
+            assert!(it.next().is_none());
+        }
+    };
+}
+
+test_decode_iter!(storage);
+test_decode_iter!(wire);
+test_decode_iter!(packed);
+test_decode_iter!(descriptive);
+
+#[test]
+fn json_lines() {
+    let encoding = musli::json::Encoding::new();
+
+    let input = "{\"id\":1,\"name\":\"one\"}\n{\"id\":2,\"name\":\"two\"}\n  {\"id\":3,\"name\":\"three\"}\n\n";
+
+    let records = encoding
+        .decode_iter::<_, Record<'_>>(input)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(records, RECORDS);
+
+    let mut it = encoding.decode_iter::<_, Record<'_>>("{\"id\":1,\"name\":\"one\"}\n{\"id\":2,");
+    assert_eq!(it.next().unwrap().unwrap(), RECORDS[0]);
+    let error = it.next().unwrap().unwrap_err().to_string();
+    assert!(error.starts_with("Record 1: "), "{error}");
+    assert_eq!(it.index(), 1);
+    assert!(it.next().is_none());
+
+    let cx = context::new().with_trace();
+    let result = encoding
+        .decode_iter_with::<_, _, Record<'_>>(&cx, "{\"id\":1,\"name\":\"one\"}\n{\"id\":2,")
+        .collect::<Result<Vec<_>, _>>();
+    assert!(result.is_err());
+    let report = cx.report().to_string();
+    assert!(report.starts_with("[1]"), "{report}");
+
+    let mut it = encoding.decode_iter::<_, Record<'_>>(" \n ");
+    assert!(it.next().is_none());
+}
@@ -0,0 +1,193 @@
+#![cfg(feature = "test")]
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use bstr::BStr;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(deny_unknown_fields)]
+struct Strict {
+    name: String,
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Extended {
+    name: String,
+    port: u16,
+    debug: bool,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(Text, deny_unknown_fields)]
+struct TextOnly {
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name", deny_unknown_fields, tag = "type")]
+enum Internal {
+    #[musli(name_all = "name")]
+    Start {
+        delay: u32,
+    },
+    Stop,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(deny_unknown_fields)]
+enum External {
+    Start { delay: u32 },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name", deny_unknown_fields)]
+struct CatchAll {
+    name: String,
+    #[musli(flatten)]
+    rest: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name(type = [u8], method = "unsized_bytes", format_with = BStr::new), deny_unknown_fields)]
+struct Bytes {
+    #[musli(name = b"value")]
+    value: u32,
+}
+
+#[derive(Encode)]
+#[musli(transparent)]
+struct Key<'a>(#[musli(bytes)] &'a [u8]);
+
+impl fmt::Display for Key<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        BStr::new(self.0).fmt(f)
+    }
+}
+
+#[derive(Encode)]
+#[musli(name(type = Key))]
+struct BytesKeys {
+    #[musli(name = Key(b"value"))]
+    value: u32,
+    #[musli(name = Key(b"other"), skip_encoding_if = Option::is_none)]
+    other: Option<u32>,
+}
+
+#[test]
+fn deny_unknown_fields() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Strict {
+            name: String::from("server"),
+            port: 80,
+        },
+        json = r#"{"name":"server","port":80}"#
+    );
+
+    let error =
+        musli::json::from_str::<Strict>(r#"{"name":"server","port":80,"debug":true}"#).unwrap_err();
+    assert!(
+        error.to_string().contains(r#"unknown field "debug""#),
+        "{error}"
+    );
+
+    let extended = Extended {
+        name: String::from("server"),
+        port: 80,
+        debug: true,
+    };
+
+    let bytes = musli::wire::to_vec(&extended).unwrap();
+    let error = musli::wire::from_slice::<Strict>(&bytes).unwrap_err();
+    assert!(error.to_string().contains("unknown field 2"), "{error}");
+
+    let bytes = musli::descriptive::to_vec(&extended).unwrap();
+    let error = musli::descriptive::from_slice::<Strict>(&bytes).unwrap_err();
+    assert!(error.to_string().contains("unknown field 2"), "{error}");
+}
+
+#[test]
+fn deny_unknown_fields_per_mode() {
+    let error = musli::json::from_str::<TextOnly>(r#"{"name":"a","extra":1}"#).unwrap_err();
+    assert!(
+        error.to_string().contains(r#"unknown field "extra""#),
+        "{error}"
+    );
+
+    let extended = Extended {
+        name: String::from("a"),
+        port: 80,
+        debug: true,
+    };
+
+    let bytes = musli::wire::to_vec(&extended).unwrap();
+    let decoded: TextOnly = musli::wire::from_slice(&bytes).unwrap();
+    assert_eq!(decoded.name, "a");
+}
+
+#[test]
+fn deny_unknown_variant_fields() {
+    musli::macros::assert_roundtrip_eq!(
+        descriptive,
+        Internal::Start { delay: 10 },
+        json = r#"{"type":"Start","delay":10}"#
+    );
+
+    musli::macros::assert_roundtrip_eq!(descriptive, Internal::Stop, json = r#"{"type":"Stop"}"#);
+
+    let error =
+        musli::json::from_str::<Internal>(r#"{"type":"Start","delay":10,"now":true}"#).unwrap_err();
+    assert!(
+        error.to_string().contains(r#"unknown field "now""#),
+        "{error}"
+    );
+
+    let error =
+        musli::json::from_str::<External>(r#"{"Start":{"delay":10,"now":true}}"#).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains(r#"unknown field "now" for variant"#),
+        "{error}"
+    );
+}
+
+#[test]
+fn deny_unknown_fields_flatten() {
+    let decoded: CatchAll = musli::json::from_str(r#"{"name":"a","x":1,"y":2}"#).unwrap();
+
+    assert_eq!(
+        decoded,
+        CatchAll {
+            name: String::from("a"),
+            rest: BTreeMap::from([(String::from("x"), 1), (String::from("y"), 2)]),
+        }
+    );
+}
+
+#[test]
+fn deny_unknown_fields_bytes() {
+    let bytes = musli::descriptive::to_vec(&BytesKeys {
+        value: 1,
+        other: None,
+    })
+    .unwrap();
+
+    let decoded: Bytes = musli::descriptive::from_slice(&bytes).unwrap();
+    assert_eq!(decoded, Bytes { value: 1 });
+
+    let bytes = musli::descriptive::to_vec(&BytesKeys {
+        value: 1,
+        other: Some(2),
+    })
+    .unwrap();
+
+    let error = musli::descriptive::from_slice::<Bytes>(&bytes).unwrap_err();
+    assert!(
+        error.to_string().contains(r#"unknown field "other""#),
+        "{error}"
+    );
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "test")]
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+#[repr(u16)]
+#[musli(name_all = "discriminant")]
+enum Opcode {
+    Ping = 0x10,
+    Pong = 0x20,
+    Next,
+    #[musli(name = 0x40)]
+    Renamed = 0x30,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+#[repr(i8)]
+#[musli(name_all = "discriminant")]
+enum Signed {
+    Negative = -2,
+    Implicit,
+    Zero = 0,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+#[musli(name_all = "discriminant")]
+enum Implicit {
+    First,
+    Second,
+    Third = 10,
+    Fourth,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[repr(u8)]
+#[musli(name_all = "discriminant")]
+enum Message {
+    Ping = 1,
+    Data { value: u32 } = 5,
+}
+
+#[test]
+fn discriminant() {
+    musli::macros::assert_roundtrip_eq!(full, Opcode::Ping);
+    musli::macros::assert_roundtrip_eq!(full, Opcode::Pong);
+    musli::macros::assert_roundtrip_eq!(full, Opcode::Next);
+    musli::macros::assert_roundtrip_eq!(full, Opcode::Renamed);
+
+    for (opcode, tag) in [
+        (Opcode::Ping, 0x10u16),
+        (Opcode::Pong, 0x20),
+        (Opcode::Next, 0x21),
+        (Opcode::Renamed, 0x40),
+    ] {
+        assert_eq!(
+            musli::storage::to_vec(&opcode).unwrap(),
+            musli::storage::to_vec(&tag).unwrap()
+        );
+
+        let bytes = musli::storage::to_vec(&tag).unwrap();
+        assert_eq!(
+            musli::storage::from_slice::<Opcode>(&bytes).unwrap(),
+            opcode
+        );
+    }
+
+    let bytes = musli::storage::to_vec(&0x30u16).unwrap();
+    assert!(musli::storage::from_slice::<Opcode>(&bytes).is_err());
+}
+
+#[test]
+fn negative_discriminant() {
+    for (value, tag) in [
+        (Signed::Negative, -2i8),
+        (Signed::Implicit, -1),
+        (Signed::Zero, 0),
+    ] {
+        musli::macros::assert_roundtrip_eq!(full, value);
+
+        assert_eq!(
+            musli::storage::to_vec(&value).unwrap(),
+            musli::storage::to_vec(&tag).unwrap()
+        );
+    }
+}
+
+#[test]
+fn implicit_discriminant() {
+    for (value, tag) in [
+        (Implicit::First, 0isize),
+        (Implicit::Second, 1),
+        (Implicit::Third, 10),
+        (Implicit::Fourth, 11),
+    ] {
+        musli::macros::assert_roundtrip_eq!(full, value);
+
+        assert_eq!(
+            musli::storage::to_vec(&value).unwrap(),
+            musli::storage::to_vec(&tag).unwrap()
+        );
+    }
+}
+
+#[test]
+fn discriminant_with_fields() {
+    musli::macros::assert_roundtrip_eq!(full, Message::Ping);
+    musli::macros::assert_roundtrip_eq!(full, Message::Data { value: 42 });
+}
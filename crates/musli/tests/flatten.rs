@@ -0,0 +1,149 @@
+#![cfg(feature = "test")]
+
+use std::collections::{BTreeMap, HashMap};
+
+use musli::alloc::{Allocator, Global};
+use musli::value::Value;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name", flattenable)]
+struct Metadata {
+    id: u32,
+    #[musli(default, skip_encoding_if = Option::is_none)]
+    trace: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name", flattenable)]
+struct Message {
+    body: String,
+    #[musli(flatten)]
+    metadata: Metadata,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Envelope {
+    #[musli(flatten)]
+    message: Message,
+    sent: bool,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Counters {
+    body: String,
+    #[musli(flatten)]
+    metadata: Metadata,
+    #[musli(flatten)]
+    counters: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name", bound = {A: Allocator}, decode_bound = {A: Allocator})]
+struct Open<A>
+where
+    A: Allocator,
+{
+    body: String,
+    #[musli(flatten)]
+    metadata: Metadata,
+    #[musli(flatten)]
+    unknown: HashMap<String, Value<A>>,
+}
+
+fn message(trace: Option<&str>) -> Message {
+    Message {
+        body: String::from("hello"),
+        metadata: Metadata {
+            id: 42,
+            trace: trace.map(String::from),
+        },
+    }
+}
+
+#[test]
+fn flatten() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        message(Some("abc")),
+        json = r#"{"body":"hello","id":42,"trace":"abc"}"#
+    );
+
+    musli::macros::assert_roundtrip_eq!(full, message(None), json = r#"{"body":"hello","id":42}"#);
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Envelope {
+            message: message(Some("abc")),
+            sent: true,
+        },
+        json = r#"{"body":"hello","id":42,"trace":"abc","sent":true}"#
+    );
+}
+
+#[test]
+fn flatten_decode_order() {
+    let decoded: Envelope =
+        musli::json::from_str(r#"{"sent":false,"id":7,"ignored":[1,2],"body":"out of order"}"#)
+            .unwrap();
+
+    assert_eq!(
+        decoded,
+        Envelope {
+            message: Message {
+                body: String::from("out of order"),
+                metadata: Metadata { id: 7, trace: None },
+            },
+            sent: false,
+        }
+    );
+
+    let error = musli::json::from_str::<Message>(r#"{"body":"no id"}"#).unwrap_err();
+    assert!(error.to_string().contains("Metadata"), "{error}");
+}
+
+#[test]
+fn flatten_catch_all() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Counters {
+            body: String::from("counted"),
+            metadata: Metadata { id: 1, trace: None },
+            counters: BTreeMap::from([(String::from("a"), 1), (String::from("b"), 2)]),
+        },
+        json = r#"{"body":"counted","id":1,"a":1,"b":2}"#
+    );
+
+    let json = r#"{"id":1,"extra":[1,"two"],"body":"open","flag":true}"#;
+
+    let open: Open<Global> = musli::json::from_str(json).unwrap();
+    assert_eq!(open.body, "open");
+    assert_eq!(open.metadata, Metadata { id: 1, trace: None });
+
+    let mut keys = open.unknown.keys().map(String::as_str).collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, ["extra", "flag"]);
+
+    let flag: bool = musli::value::decode(&open.unknown["flag"]).unwrap();
+    assert!(flag);
+
+    let extra: (u32, String) = musli::value::decode(&open.unknown["extra"]).unwrap();
+    assert_eq!(extra, (1, String::from("two")));
+
+    // Unknown fields are preserved when encoded again.
+    let json = musli::json::to_string(&open).unwrap();
+    let decoded: Open<Global> = musli::json::from_str(&json).unwrap();
+    assert_eq!(decoded, open);
+
+    let bytes = musli::descriptive::to_vec(&open).unwrap();
+    let decoded: Open<Global> = musli::descriptive::from_slice(&bytes).unwrap();
+    assert_eq!(decoded, open);
+
+    // Types without a catch-all skip over unknown fields.
+    let bytes = musli::wire::to_vec(&open).unwrap();
+    let decoded: Message = musli::wire::from_slice(&bytes).unwrap();
+    assert_eq!(decoded.body, "open");
+    assert_eq!(decoded.metadata.id, 1);
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "test")]
+
+use std::fmt;
+
+use musli::context;
+use musli::{Decode, Encode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+#[musli(into = u8, from = u8)]
+enum Level {
+    Low,
+    High,
+}
+
+impl From<Level> for u8 {
+    fn from(level: Level) -> u8 {
+        match level {
+            Level::Low => 0,
+            Level::High => 1,
+        }
+    }
+}
+
+impl From<u8> for Level {
+    fn from(value: u8) -> Level {
+        match value {
+            0 => Level::Low,
+            _ => Level::High,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+#[musli(into = String, try_from = String)]
+struct Email(String);
+
+#[derive(Debug)]
+struct InvalidEmail;
+
+impl fmt::Display for InvalidEmail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid e-mail address")
+    }
+}
+
+impl std::error::Error for InvalidEmail {}
+
+impl TryFrom<String> for Email {
+    type Error = InvalidEmail;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if !value.contains('@') {
+            return Err(InvalidEmail);
+        }
+
+        Ok(Email(value))
+    }
+}
+
+impl From<Email> for String {
+    fn from(email: Email) -> String {
+        email.0
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct User {
+    email: Email,
+    level: Level,
+}
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+#[musli(Text, into = String, from = String)]
+struct Tagged<T> {
+    value: T,
+}
+
+impl From<Tagged<u32>> for String {
+    fn from(tagged: Tagged<u32>) -> String {
+        tagged.value.to_string()
+    }
+}
+
+impl From<String> for Tagged<u32> {
+    fn from(value: String) -> Self {
+        Tagged {
+            value: value.parse().unwrap_or_default(),
+        }
+    }
+}
+
+#[test]
+fn from_into() {
+    musli::macros::assert_roundtrip_eq!(full, Level::Low, json = "0");
+    musli::macros::assert_roundtrip_eq!(full, Level::High, json = "1");
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        User {
+            email: Email(String::from("user@example.com")),
+            level: Level::High,
+        },
+        json = r#"{"email":"user@example.com","level":1}"#
+    );
+}
+
+#[test]
+fn try_from() {
+    let error = musli::json::from_str::<Email>(r#""example.com""#).unwrap_err();
+    assert!(
+        error.to_string().contains("Invalid e-mail address"),
+        "{error}"
+    );
+
+    let cx = context::new().with_trace();
+
+    let encoding = musli::json::Encoding::new();
+    let result = encoding.from_str_with::<_, User>(&cx, r#"{"email":"example.com","level":0}"#);
+    assert!(result.is_err());
+
+    let error = cx.errors().next().expect("expected an error").to_string();
+    assert!(error.starts_with(".email"), "{error}");
+    assert!(error.contains("Invalid e-mail address"), "{error}");
+}
+
+#[test]
+fn from_into_per_mode() {
+    let value = Tagged { value: 42u32 };
+
+    let json = musli::json::to_string(&value).unwrap();
+    assert_eq!(json, r#""42""#);
+    assert_eq!(musli::json::from_str::<Tagged<u32>>(&json).unwrap(), value);
+
+    let bytes = musli::storage::to_vec(&value).unwrap();
+    assert_eq!(
+        musli::storage::from_slice::<Tagged<u32>>(&bytes).unwrap(),
+        value
+    );
+    assert_ne!(bytes, musli::storage::to_vec("42").unwrap());
+}
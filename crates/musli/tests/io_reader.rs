@@ -0,0 +1,80 @@
+#![cfg(feature = "test")]
+
+use std::collections::HashMap;
+use std::io::BufReader;
+
+use musli::wrap;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Record {
+    id: u64,
+    name: String,
+    tags: Vec<String>,
+    values: HashMap<String, i32>,
+    payload: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Borrowed<'a> {
+    name: &'a str,
+}
+
+fn record() -> Record {
+    Record {
+        id: 42,
+        name: "a fairly long name which straddles buffer boundaries".to_string(),
+        tags: vec!["first".to_string(), "second".to_string()],
+        values: [("a".to_string(), 1), ("b".to_string(), -2)]
+            .into_iter()
+            .collect(),
+        payload: (0..=255).collect(),
+    }
+}
+
+macro_rules! test_io_reader {
+    ($what:ident) => {
+        #[test]
+        fn $what() {
+            let expected = record();
+            let data = musli::$what::to_vec(&expected).unwrap();
+
+            // Tiny capacities force reads to span multiple buffer refills.
+            for capacity in [1, 3, 16, 4096] {
+                let reader = wrap::wrap(BufReader::with_capacity(capacity, &data[..]));
+                let actual: Record = musli::$what::decode(reader).unwrap();
+                assert_eq!(actual, expected, "capacity {capacity}");
+            }
+
+            let reader = wrap::wrap(BufReader::new(&data[..data.len() - 1]));
+            let result: Result<Record, _> = musli::$what::decode(reader);
+            assert!(result.is_err());
+        }
+    };
+}
+
+test_io_reader!(storage);
+test_io_reader!(wire);
+test_io_reader!(packed);
+test_io_reader!(descriptive);
+
+#[test]
+fn borrowed_string_errors() {
+    let data = musli::storage::to_vec(&Borrowed { name: "Jane Doe" }).unwrap();
+
+    let reader = wrap::wrap(BufReader::new(&data[..]));
+    let result: Result<Borrowed<'_>, _> = musli::storage::decode(reader);
+    assert!(result.is_err());
+}
+
+#[test]
+fn consecutive_values() {
+    let mut data = musli::wire::to_vec(&42u32).unwrap();
+    data.extend(musli::wire::to_vec(&"hello").unwrap());
+
+    let mut reader = wrap::wrap(BufReader::new(&data[..]));
+    let first: u32 = musli::wire::decode(&mut reader).unwrap();
+    let second: String = musli::wire::decode(&mut reader).unwrap();
+    assert_eq!(first, 42);
+    assert_eq!(second, "hello");
+}
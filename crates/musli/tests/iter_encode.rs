@@ -0,0 +1,172 @@
+#![cfg(feature = "test")]
+
+use std::collections::BTreeMap;
+
+use musli::en::IterEncode;
+use musli::mode::{Binary, Text};
+use musli::{Decode, Encode, Encoder};
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(name_all = "name")]
+struct Decoded {
+    name: String,
+    values: Vec<u32>,
+    map: BTreeMap<String, u32>,
+}
+
+/// Encodes its fields from iterators, with and without an exact length.
+struct Filtered {
+    values: Vec<u32>,
+    exact: bool,
+}
+
+impl<M> Encode<M> for Filtered {
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<(), E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        use musli::en::MapEncoder;
+
+        let exact = self.exact;
+
+        encoder.encode_map_fn(3, |map| {
+            map.insert_entry("name", "filtered")?;
+
+            map.encode_entry_fn(|entry| {
+                use musli::en::EntryEncoder;
+
+                entry.encode_key()?.encode("values")?;
+
+                if exact {
+                    entry.encode_value()?.encode_sequence_iter(&self.values)
+                } else {
+                    entry
+                        .encode_value()?
+                        .encode_sequence_iter(self.values.iter().filter(|n| *n % 2 == 0))
+                }
+            })?;
+
+            map.encode_entry_fn(|entry| {
+                use musli::en::EntryEncoder;
+
+                entry.encode_key()?.encode("map")?;
+
+                let pairs = self.values.iter().map(|n| (format!("k{n}"), *n));
+
+                if exact {
+                    entry.encode_value()?.encode_map_iter(pairs)
+                } else {
+                    entry
+                        .encode_value()?
+                        .encode_map_iter(pairs.filter(|(_, n)| *n % 2 == 0))
+                }
+            })
+        })
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+fn expected(exact: bool) -> Decoded {
+    let values = (0..10u32)
+        .filter(|n| exact || n % 2 == 0)
+        .collect::<Vec<_>>();
+
+    Decoded {
+        name: String::from("filtered"),
+        map: values.iter().map(|n| (format!("k{n}"), *n)).collect(),
+        values,
+    }
+}
+
+macro_rules! test_format {
+    ($what:ident) => {
+        #[test]
+        fn $what() {
+            for exact in [true, false] {
+                let value = Filtered {
+                    values: (0..10).collect(),
+                    exact,
+                };
+
+                let bytes = musli::$what::to_vec(&value).unwrap();
+                let decoded: Decoded = musli::$what::from_slice(&bytes).unwrap();
+                assert_eq!(decoded, expected(exact));
+            }
+        }
+    };
+}
+
+test_format!(storage);
+test_format!(wire);
+test_format!(descriptive);
+test_format!(json);
+
+#[derive(Encode)]
+#[musli(Binary, bound = {I: Clone + IntoIterator<Item: Encode<Binary>>})]
+#[musli(Text, bound = {I: Clone + IntoIterator<Item: Encode<Text>>})]
+struct Wrapped<I> {
+    values: IterEncode<I>,
+}
+
+#[test]
+fn iter_encode_wrapper() {
+    let values = [1u32, 2, 3, 4, 5];
+
+    let wrapped = Wrapped {
+        values: IterEncode::new(values.iter().skip_while(|n| **n < 3)),
+    };
+
+    let json = musli::json::to_string(&wrapped).unwrap();
+    assert_eq!(json, r#"{"values":[3,4,5]}"#);
+
+    // Encoding twice clones the iterator, so the output is the same.
+    let first = musli::wire::to_vec(&wrapped).unwrap();
+    let second = musli::wire::to_vec(&wrapped).unwrap();
+    assert_eq!(first, second);
+
+    #[derive(Debug, PartialEq, Decode)]
+    struct Values {
+        values: Vec<u32>,
+    }
+
+    let decoded: Values = musli::wire::from_slice(&first).unwrap();
+    assert_eq!(decoded.values, [3, 4, 5]);
+}
+
+/// An iterator which misreports its exact length.
+#[derive(Clone)]
+struct Lying(u32);
+
+impl Iterator for Lying {
+    type Item = u32;
+
+    #[inline]
+    fn next(&mut self) -> Option<u32> {
+        self.0 = self.0.checked_sub(1)?;
+        Some(self.0)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (2, Some(2))
+    }
+}
+
+#[test]
+fn length_mismatch() {
+    let error = musli::storage::to_vec(&IterEncode::new(Lying(3))).unwrap_err();
+    assert!(error.to_string().contains("reported"), "{error}");
+
+    let error = musli::storage::to_vec(&IterEncode::new(Lying(1))).unwrap_err();
+    assert!(error.to_string().contains("reported"), "{error}");
+
+    let json = musli::json::to_string(&IterEncode::new(Lying(3))).unwrap();
+    assert_eq!(json, "[2,1,0]");
+}
@@ -0,0 +1,162 @@
+#![cfg(feature = "std")]
+
+use std::collections::VecDeque;
+
+use musli::json::{BytesFormat, Encoding};
+use musli::{Decode, Encode};
+
+const ARRAY: Encoding = Encoding::new();
+const BASE64: Encoding = Encoding::new().with_bytes(BytesFormat::Base64);
+const BASE64_URL: Encoding = Encoding::new().with_bytes(BytesFormat::Base64Url);
+const HEX: Encoding = Encoding::new().with_bytes(BytesFormat::Hex);
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(transparent)]
+struct Blob(#[musli(bytes)] Vec<u8>);
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(transparent)]
+struct Ring(#[musli(bytes)] VecDeque<u8>);
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(transparent)]
+struct Digest(#[musli(bytes)] [u8; 3]);
+
+#[track_caller]
+fn roundtrip(encoding: Encoding, input: &[u8], expected: &str) {
+    let blob = Blob(input.to_vec());
+    let out = encoding.to_string(&blob).unwrap();
+    assert_eq!(out, expected, "{input:?}");
+    let actual: Blob = encoding.from_str(&out).unwrap();
+    assert_eq!(actual, blob);
+}
+
+#[test]
+fn rfc4648_vectors() {
+    let vectors: [(&[u8], &str, &str, &str); 7] = [
+        (b"", r#""""#, r#""""#, r#""""#),
+        (b"f", r#""Zg==""#, r#""Zg""#, r#""66""#),
+        (b"fo", r#""Zm8=""#, r#""Zm8""#, r#""666f""#),
+        (b"foo", r#""Zm9v""#, r#""Zm9v""#, r#""666f6f""#),
+        (b"foob", r#""Zm9vYg==""#, r#""Zm9vYg""#, r#""666f6f62""#),
+        (b"fooba", r#""Zm9vYmE=""#, r#""Zm9vYmE""#, r#""666f6f6261""#),
+        (
+            b"foobar",
+            r#""Zm9vYmFy""#,
+            r#""Zm9vYmFy""#,
+            r#""666f6f626172""#,
+        ),
+    ];
+
+    for (input, base64, base64_url, hex) in vectors {
+        roundtrip(BASE64, input, base64);
+        roundtrip(BASE64_URL, input, base64_url);
+        roundtrip(HEX, input, hex);
+    }
+}
+
+#[test]
+fn alphabets() {
+    let input = [0xfb, 0xff, 0xbf];
+    roundtrip(BASE64, &input, r#""+/+/""#);
+    roundtrip(BASE64_URL, &input, r#""-_-_""#);
+    roundtrip(HEX, &input, r#""fbffbf""#);
+    roundtrip(ARRAY, &input, "[251,255,191]");
+}
+
+#[test]
+fn vectored() {
+    // A ring buffer which wraps around is encoded as two slices, which must
+    // produce the same output as a contiguous buffer.
+    let mut ring = VecDeque::from(b"xxxf".to_vec());
+    ring.drain(..3);
+    ring.extend(*b"oob");
+    assert!(!ring.as_slices().1.is_empty());
+
+    let ring = Ring(ring);
+    assert_eq!(BASE64.to_string(&ring).unwrap(), r#""Zm9vYg==""#);
+    assert_eq!(HEX.to_string(&ring).unwrap(), r#""666f6f62""#);
+
+    let actual: Ring = BASE64.from_str(r#""Zm9vYg==""#).unwrap();
+    assert_eq!(actual, ring);
+}
+
+#[test]
+fn fixed_arrays() {
+    let digest = Digest(*b"foo");
+    assert_eq!(BASE64.to_string(&digest).unwrap(), r#""Zm9v""#);
+
+    let actual: Digest = BASE64.from_str(r#""Zm9v""#).unwrap();
+    assert_eq!(actual, digest);
+
+    assert!(BASE64.from_str::<Digest>(r#""Zm9vYg==""#).is_err());
+    assert!(HEX.from_str::<Digest>(r#""666f""#).is_err());
+}
+
+#[test]
+fn lenient_decoding() {
+    // Padding is optional, and hex digits may be uppercase.
+    let actual: Blob = BASE64.from_str(r#""Zm9vYg""#).unwrap();
+    assert_eq!(actual.0, b"foob");
+
+    let actual: Blob = BASE64_URL.from_str(r#""Zm9vYg==""#).unwrap();
+    assert_eq!(actual.0, b"foob");
+
+    let actual: Blob = HEX.from_str(r#""FBFFbf""#).unwrap();
+    assert_eq!(actual.0, [0xfb, 0xff, 0xbf]);
+}
+
+#[test]
+fn invalid_input() {
+    // Only the configured representation is accepted.
+    assert!(BASE64.from_str::<Blob>("[1,2,3]").is_err());
+    assert!(ARRAY.from_str::<Blob>(r#""Zm9v""#).is_err());
+
+    // Characters from the other alphabet.
+    assert!(BASE64.from_str::<Blob>(r#""-_-_""#).is_err());
+    assert!(BASE64_URL.from_str::<Blob>(r#""+/+/""#).is_err());
+    assert!(BASE64.from_str::<Blob>(r#""Zm9\u00ff""#).is_err());
+    assert!(HEX.from_str::<Blob>(r#""66\u00ff""#).is_err());
+
+    // Lengths which don't correspond to whole bytes.
+    assert!(BASE64.from_str::<Blob>(r#""Zm9vY""#).is_err());
+    assert!(BASE64.from_str::<Blob>(r#""Zm9vY===""#).is_err());
+    assert!(HEX.from_str::<Blob>(r#""666""#).is_err());
+
+    // Non-zero bits after the last byte.
+    assert!(BASE64.from_str::<Blob>(r#""Zh==""#).is_err());
+
+    assert!(HEX.from_str::<Blob>(r#""6g""#).is_err());
+}
+
+#[test]
+fn canonical_and_pretty() {
+    const CANONICAL: Encoding = Encoding::new()
+        .with_bytes(BytesFormat::Hex)
+        .with_canonical();
+    const PRETTY: Encoding = Encoding::new()
+        .with_bytes(BytesFormat::Base64)
+        .with_pretty("  ");
+
+    #[derive(Encode)]
+    struct Entry {
+        #[musli(bytes)]
+        value: Vec<u8>,
+        key: u32,
+    }
+
+    let entry = Entry {
+        value: b"foo".to_vec(),
+        key: 1,
+    };
+
+    assert_eq!(
+        CANONICAL.to_string(&entry).unwrap(),
+        r#"{"key":1,"value":"666f6f"}"#
+    );
+
+    assert_eq!(
+        PRETTY.to_string(&entry).unwrap(),
+        "{\n  \"value\": \"Zm9v\",\n  \"key\": 1\n}"
+    );
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "std")]
+
+use musli::alloc::Global;
+use musli::json::{Encoding, Number};
+use musli::mode::Text;
+use musli::value::{OPTIONS, Value};
+use musli::{Decode, Encode};
+
+const ARBITRARY: Encoding = Encoding::new().with_arbitrary_precision();
+const VALUE: musli::value::Encoding<OPTIONS, Text> = musli::value::Encoding::new().with_mode();
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Ledger {
+    total: Number,
+    entries: Vec<Number>,
+}
+
+#[test]
+fn number_roundtrip() {
+    let json = r#"{"total":-123456789012345678901234567890123456789012,"entries":[0,1.50,2e-400,-0.0,1E+2]}"#;
+
+    let ledger: Ledger = musli::json::from_str(json).unwrap();
+    assert_eq!(
+        ledger.total.as_str(),
+        "-123456789012345678901234567890123456789012"
+    );
+
+    let entries = ledger
+        .entries
+        .iter()
+        .map(Number::as_str)
+        .collect::<Vec<_>>();
+    assert_eq!(entries, ["0", "1.50", "2e-400", "-0.0", "1E+2"]);
+
+    let out = musli::json::to_string(&ledger).unwrap();
+    assert_eq!(out, json);
+}
+
+#[test]
+fn number_conversions() {
+    let number: Number = "340282366920938463463374607431768211455".parse().unwrap();
+    assert_eq!(number.as_u128(), Some(u128::MAX));
+    assert_eq!(number.as_i128(), None);
+    assert_eq!(number.as_u64(), None);
+
+    let number: Number = "-9223372036854775808".parse().unwrap();
+    assert_eq!(number.as_i64(), Some(i64::MIN));
+    assert_eq!(number.as_i128(), Some(i64::MIN as i128));
+    assert_eq!(number.as_u128(), None);
+
+    let number: Number = "0.1".parse().unwrap();
+    assert_eq!(number.as_f64(), Some(0.1));
+    assert_eq!(number.as_u64(), None);
+
+    let number: Number = "-1e309".parse().unwrap();
+    assert_eq!(number.as_f64(), None);
+
+    assert!("".parse::<Number>().is_err());
+    assert!("-".parse::<Number>().is_err());
+    assert!("1e".parse::<Number>().is_err());
+    assert!(".5".parse::<Number>().is_err());
+    assert!("NaN".parse::<Number>().is_err());
+    assert!(" 1".parse::<Number>().is_err());
+}
+
+#[test]
+fn number_from_primitives() {
+    assert_eq!(Number::from(u128::MAX).as_str(), u128::MAX.to_string());
+    assert_eq!(Number::from(i8::MIN).as_str(), "-128");
+    assert_eq!(Number::from_f64(0.1).unwrap().as_str(), "0.1");
+    assert!(Number::from_f64(f64::INFINITY).is_none());
+}
+
+#[test]
+fn number_invalid_input() {
+    assert!(musli::json::from_str::<Number>("\"1\"").is_err());
+    assert!(musli::json::from_str::<Number>("true").is_err());
+    assert!(musli::json::from_str::<Number>("-").is_err());
+}
+
+#[test]
+fn number_from_other_formats() {
+    let bytes = musli::cbor::to_vec(&42u64).unwrap();
+    let number: Number = musli::cbor::from_slice(&bytes).unwrap();
+    assert_eq!(number.as_str(), "42");
+
+    let bytes = musli::cbor::to_vec(&1.5f32).unwrap();
+    let number: Number = musli::cbor::from_slice(&bytes).unwrap();
+    assert_eq!(number.as_str(), "1.5");
+
+    // Formats without arbitrary precision encode the closest primitive.
+    let number: Number = "-12".parse().unwrap();
+    let bytes = musli::cbor::to_vec(&number).unwrap();
+    let value: i64 = musli::cbor::from_slice(&bytes).unwrap();
+    assert_eq!(value, -12);
+}
+
+#[test]
+fn value_arbitrary_precision() {
+    let json = r#"{"big":123456789012345678901234567890,"precise":1.234567890123456789012345678901,"small":1}"#;
+
+    let value: Value<Global> = ARBITRARY.from_str(json).unwrap();
+    assert_eq!(ARBITRARY.to_string(&value).unwrap(), json);
+
+    // The textual number can be decoded into primitives when in range.
+    #[derive(Debug, PartialEq, Decode)]
+    struct Small {
+        small: u8,
+        precise: f64,
+    }
+
+    let small: Small = VALUE.decode(&value).unwrap();
+    assert_eq!(
+        small,
+        Small {
+            small: 1,
+            precise: 1.2345678901234568
+        }
+    );
+
+    #[derive(Debug, Decode)]
+    #[allow(unused)]
+    struct Big {
+        big: u64,
+    }
+
+    assert!(VALUE.decode::<Big>(&value).is_err());
+
+    #[derive(Debug, Decode)]
+    struct Precise {
+        precise: Number,
+    }
+
+    let precise: Precise = VALUE.decode(&value).unwrap();
+    assert_eq!(precise.precise.as_str(), "1.234567890123456789012345678901");
+}
+
+#[test]
+fn value_default_precision() {
+    let json = r#"[1,2.50,-3]"#;
+
+    let value: Value<Global> = musli::json::from_str(json).unwrap();
+    assert_eq!(musli::json::to_string(&value).unwrap(), "[1,2.5,-3]");
+
+    let value: Value<Global> = ARBITRARY.from_str(json).unwrap();
+    assert_eq!(ARBITRARY.to_string(&value).unwrap(), json);
+}
+
+#[test]
+fn arbitrary_precision_decode_iter() {
+    let json = "100000000000000000000000 1.000000000000000000001";
+
+    let values = ARBITRARY
+        .decode_iter::<_, Value<Global>>(json.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let out = values
+        .iter()
+        .map(|value| ARBITRARY.to_string(value).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(out, ["100000000000000000000000", "1.000000000000000000001"]);
+}
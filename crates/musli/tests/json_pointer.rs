@@ -0,0 +1,120 @@
+#![cfg(feature = "std")]
+
+use musli::context;
+use musli::json::Encoding;
+use musli::{Decode, Encode};
+
+const ENCODING: Encoding = Encoding::new();
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Item<'a> {
+    name: &'a str,
+    tags: Vec<u32>,
+}
+
+const DOCUMENT: &str = r#"{
+    "version": 1,
+    "skipped": {"deep": [[[{"a": "å"}]]], "s": "x\"y"},
+    "items": [
+        {"name": "a", "tags": []},
+        {"name": "b", "tags": [1, 2]},
+        {"name": "c\n", "tags": [3]},
+        {"name": "d", "tags": [4]}
+    ],
+    "a/b": {"m~n": true, "": null},
+    "\u0065sc\"ap\u00e5d\ud83d\ude00": 7,
+    "escaped": "yes"
+}"#;
+
+#[test]
+fn decode_at() {
+    let name: &str = ENCODING
+        .decode_at(DOCUMENT.as_bytes(), "/items/3/name")
+        .unwrap();
+    assert_eq!(name, "d");
+
+    // Escaped strings can't be borrowed.
+    let name: String = ENCODING
+        .decode_at(DOCUMENT.as_bytes(), "/items/2/name")
+        .unwrap();
+    assert_eq!(name, "c\n");
+
+    let item: Item<'_> = ENCODING.decode_at(DOCUMENT.as_bytes(), "/items/1").unwrap();
+    assert_eq!(
+        item,
+        Item {
+            name: "b",
+            tags: vec![1, 2]
+        }
+    );
+
+    let tag: u32 = ENCODING
+        .decode_at(DOCUMENT.as_bytes(), "/items/1/tags/1")
+        .unwrap();
+    assert_eq!(tag, 2);
+
+    let version: u32 = ENCODING.decode_at(DOCUMENT.as_bytes(), "/version").unwrap();
+    assert_eq!(version, 1);
+
+    // Escapes in both the pointer and the document.
+    let flag: bool = ENCODING
+        .decode_at(DOCUMENT.as_bytes(), "/a~1b/m~0n")
+        .unwrap();
+    assert!(flag);
+
+    let empty: Option<u32> = ENCODING.decode_at(DOCUMENT.as_bytes(), "/a~1b/").unwrap();
+    assert_eq!(empty, None);
+
+    let escaped: &str = ENCODING.decode_at(DOCUMENT.as_bytes(), "/escaped").unwrap();
+    assert_eq!(escaped, "yes");
+
+    // Escaped keys are compared as they're being unescaped.
+    let number: u32 = ENCODING
+        .decode_at(DOCUMENT.as_bytes(), "/esc\"apåd😀")
+        .unwrap();
+    assert_eq!(number, 7);
+
+    let relaxed = ENCODING.with_relaxed();
+    let number: u32 = relaxed
+        .decode_at(&b"{a: 1, 'b\\'c': 2, d: 3}"[..], "/b'c")
+        .unwrap();
+    assert_eq!(number, 2);
+    let number: u32 = relaxed
+        .decode_at(&b"{a: 1, 'b\\'c': 2, d: 3}"[..], "/d")
+        .unwrap();
+    assert_eq!(number, 3);
+
+    // The empty pointer refers to the whole document.
+    let whole: Vec<u32> = ENCODING.decode_at(&b"[1, 2]"[..], "").unwrap();
+    assert_eq!(whole, [1, 2]);
+}
+
+#[test]
+fn decode_at_errors() {
+    macro_rules! error {
+        ($pointer:expr) => {{
+            let cx = context::new().with_trace();
+            let result = ENCODING.decode_at_with::<_, _, u32>(&cx, DOCUMENT.as_bytes(), $pointer);
+            assert!(result.is_err(), "{}", $pointer);
+            cx.report().to_string()
+        }};
+    }
+
+    assert!(
+        error!("/items/4").starts_with("[items]: Index 4 is out of bounds for array of length 4")
+    );
+    assert!(error!("/items/01").contains("Expected array index in JSON pointer, found `01`"));
+    assert!(error!("/items/-").contains("Expected array index in JSON pointer, found `-`"));
+    assert!(error!("/a~1b/missing").starts_with("[a/b]: Missing key `missing`"));
+    assert!(error!("/items/1/name").starts_with("[items][1][name]: "));
+    assert!(error!("/version/0").starts_with("[version]: Cannot look up `0`"));
+    assert!(error!("/a~2b").contains("Invalid escape sequence"));
+    assert!(error!("items").contains("must be empty or start with `/`"));
+
+    let result = ENCODING.decode_at::<_, u32>(&br#"{"a": 1 "b": 2}"#[..], "/b");
+    let error = result.unwrap_err().to_string();
+    assert!(
+        error.contains("Expected comma `,` or closing brace `}`"),
+        "{error}"
+    );
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "std")]
+
+use std::collections::BTreeMap;
+
+use musli::json::Encoding;
+use musli::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+const PRETTY: Encoding = Encoding::new().with_pretty("  ");
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Config {
+    name: String,
+    ports: Vec<u16>,
+    empty: Vec<u32>,
+    labels: BTreeMap<String, String>,
+    nested: Nested,
+    #[musli(bytes)]
+    bytes: [u8; 2],
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Nested {
+    enabled: bool,
+    shape: Shape,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Shape {
+    Circle(u32),
+    Rectangle { width: u32, height: u32 },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum SerdeShape {
+    Point(u32, u32),
+    Rectangle { width: u32, height: u32 },
+}
+
+#[derive(Encode, Decode)]
+#[musli(transparent)]
+struct Serde(#[musli(with = musli::serde)] SerdeShape);
+
+fn config() -> Config {
+    Config {
+        name: String::from("server"),
+        ports: vec![80, 443],
+        empty: Vec::new(),
+        labels: [(String::from("env"), String::from("prod"))]
+            .into_iter()
+            .collect(),
+        nested: Nested {
+            enabled: true,
+            shape: Shape::Rectangle {
+                width: 1,
+                height: 2,
+            },
+        },
+        bytes: [1, 2],
+    }
+}
+
+#[test]
+fn pretty_structs() {
+    let value = config();
+    let out = PRETTY.to_string(&value).unwrap();
+
+    let expected = r#"{
+  "name": "server",
+  "ports": [
+    80,
+    443
+  ],
+  "empty": [],
+  "labels": {
+    "env": "prod"
+  },
+  "nested": {
+    "enabled": true,
+    "shape": {
+      "Rectangle": {
+        "width": 1,
+        "height": 2
+      }
+    }
+  },
+  "bytes": [
+    1,
+    2
+  ]
+}"#;
+
+    assert_eq!(out, expected);
+    assert_eq!(musli::json::to_string_pretty(&value).unwrap(), expected);
+    assert_eq!(PRETTY.from_str::<Config>(&out).unwrap(), value);
+
+    // Compact output is unaffected.
+    let compact = musli::json::to_string(&value).unwrap();
+    assert!(!compact.contains('\n'));
+    assert_eq!(musli::json::from_str::<Config>(&compact).unwrap(), value);
+}
+
+#[test]
+fn pretty_variants() {
+    let out = PRETTY.to_string(&Shape::Circle(2)).unwrap();
+    assert_eq!(out, "{\n  \"Circle\": {\n    \"0\": 2\n  }\n}");
+
+    let out = PRETTY.to_string(&Serde(SerdeShape::Point(1, 2))).unwrap();
+    assert_eq!(out, "{\n  \"Point\": [\n    1,\n    2\n  ]\n}");
+
+    let out = PRETTY
+        .to_string(&Serde(SerdeShape::Rectangle {
+            width: 1,
+            height: 2,
+        }))
+        .unwrap();
+
+    assert_eq!(
+        out,
+        "{\n  \"Rectangle\": {\n    \"width\": 1,\n    \"height\": 2\n  }\n}"
+    );
+
+    let Serde(shape) = PRETTY.from_str(&out).unwrap();
+    assert_eq!(
+        shape,
+        SerdeShape::Rectangle {
+            width: 1,
+            height: 2
+        }
+    );
+}
+
+#[test]
+fn pretty_writers() {
+    const CRLF: Encoding = Encoding::new().with_pretty("\t").with_newline("\r\n");
+
+    let value = vec![vec![1u32], vec![]];
+
+    let out = CRLF.to_vec(&value).unwrap();
+    assert_eq!(out, b"[\r\n\t[\r\n\t\t1\r\n\t],\r\n\t[]\r\n]");
+
+    let mut buf = [0u8; 64];
+    let n = CRLF.to_slice(&mut buf, &value).unwrap();
+    assert_eq!(&buf[..n], out);
+
+    // The configuration is retained when changing modes.
+    enum Custom {}
+    let custom = CRLF.with_mode::<Custom>();
+    assert_eq!(custom.to_vec(&value).unwrap(), out);
+}
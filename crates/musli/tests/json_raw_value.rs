@@ -0,0 +1,74 @@
+#![cfg(feature = "std")]
+
+use musli::json::{OwnedRawValue, RawValue};
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Borrowed<'a> {
+    id: u32,
+    payload: RawValue<'a>,
+    rest: Vec<RawValue<'a>>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Owned {
+    id: u32,
+    payload: OwnedRawValue,
+}
+
+#[test]
+fn borrowed() {
+    let json = r#"{ "id": 1, "payload" :  { "a" : [1, 2.5, "å"], "b": null } , "rest": [true, "x\"y", -1e10, {}] }"#;
+
+    let value: Borrowed<'_> = musli::json::from_str(json).unwrap();
+    assert_eq!(value.id, 1);
+    assert_eq!(
+        value.payload.as_str(),
+        r#"{ "a" : [1, 2.5, "å"], "b": null }"#
+    );
+
+    let rest = value.rest.iter().map(RawValue::as_str).collect::<Vec<_>>();
+    assert_eq!(rest, ["true", r#""x\"y""#, "-1e10", "{}"]);
+
+    // Raw values are written back verbatim.
+    let out = musli::json::to_string(&value).unwrap();
+    assert_eq!(
+        out,
+        r#"{"id":1,"payload":{ "a" : [1, 2.5, "å"], "b": null },"rest":[true,"x\"y",-1e10,{}]}"#
+    );
+
+    let again: Borrowed<'_> = musli::json::from_str(&out).unwrap();
+    assert_eq!(again, value);
+}
+
+#[test]
+fn owned() {
+    let json = br#"{"id": 2, "payload": ["a", {"b": 1}]}"#;
+
+    let value: Owned = musli::json::from_slice(json).unwrap();
+    assert_eq!(value.payload.as_str(), r#"["a", {"b": 1}]"#);
+    assert_eq!(
+        value.payload.as_raw_value().as_str(),
+        value.payload.as_str()
+    );
+
+    let out = musli::json::to_string(&value).unwrap();
+    assert_eq!(out, r#"{"id":2,"payload":["a", {"b": 1}]}"#);
+
+    let raw = OwnedRawValue::from_string(String::from("\n 42 \n")).unwrap();
+    assert_eq!(raw.as_str(), "42");
+    assert_eq!(raw.into_string(), "42");
+}
+
+#[test]
+fn invalid() {
+    assert!(musli::json::from_str::<RawValue<'_>>("[1, 2").is_err());
+    assert!(musli::json::from_str::<RawValue<'_>>("{\"a\" 1}").is_err());
+    assert!(RawValue::from_str("1 2").is_err());
+    assert!(RawValue::from_str("").is_err());
+
+    // Raw values are only supported by JSON.
+    let raw = RawValue::from_str("1").unwrap();
+    assert!(musli::storage::to_vec(&raw).is_err());
+    assert!(musli::json::from_slice::<RawValue<'_>>(b"\"a\"").is_ok());
+}
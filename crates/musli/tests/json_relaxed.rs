@@ -0,0 +1,172 @@
+#![cfg(feature = "std")]
+
+use std::collections::BTreeMap;
+
+use musli::alloc::Global;
+use musli::json::Encoding;
+use musli::value::Value;
+use musli::{Decode, Encode};
+
+const RELAXED: Encoding = Encoding::new().with_relaxed();
+const STRICT: Encoding = Encoding::new();
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Server {
+    name: String,
+    #[musli(Text, name = "type")]
+    kind: String,
+    flags: Vec<bool>,
+    limits: Option<Limits>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Limits {
+    max_connections: u32,
+    timeout: f64,
+}
+
+const CONFIG: &str = r#"
+// A hand-written configuration.
+{
+    name: 'primary', // Identifier keys and single quoted strings.
+    "type": "http",
+    /* Block comments
+       spanning lines. */
+    flags: [true, false, /* inline */ true,],
+    limits: {
+        max_connections: 1024,
+        timeout: 2.5,
+    },
+}
+// Trailing comment.
+"#;
+
+fn expected() -> Server {
+    Server {
+        name: "primary".to_string(),
+        kind: "http".to_string(),
+        flags: vec![true, false, true],
+        limits: Some(Limits {
+            max_connections: 1024,
+            timeout: 2.5,
+        }),
+    }
+}
+
+#[test]
+fn relaxed_config() {
+    let server: Server = RELAXED.from_str(CONFIG).unwrap();
+    assert_eq!(server, expected());
+
+    // Decoding through a mutable slice uses a different parser.
+    let slice = &mut CONFIG.as_bytes();
+    let server: Server = RELAXED.decode(slice).unwrap();
+    assert_eq!(server, expected());
+
+    assert!(STRICT.from_str::<Server>(CONFIG).is_err());
+}
+
+#[test]
+fn relaxed_strings() {
+    let value: String = RELAXED.from_str(r#"'It\'s "quoted"\nA'"#).unwrap();
+    assert_eq!(value, "It's \"quoted\"\nA");
+
+    let value: String = RELAXED.from_str(r#""double \"quoted\"""#).unwrap();
+    assert_eq!(value, "double \"quoted\"");
+
+    assert!(RELAXED.from_str::<String>("'unterminated").is_err());
+    assert!(RELAXED.from_str::<String>("'\u{1}'").is_err());
+    assert!(STRICT.from_str::<String>("'single'").is_err());
+}
+
+#[test]
+fn relaxed_keys() {
+    let map: BTreeMap<String, u32> = RELAXED
+        .from_str("{ $id: 1, _private: 2, name2: 3, nullable: 4, 'quoted key': 5 }")
+        .unwrap();
+
+    let keys = map.keys().map(String::as_str).collect::<Vec<_>>();
+    assert_eq!(keys, ["$id", "_private", "name2", "nullable", "quoted key"]);
+
+    // Numeric keys are still parsed from strings.
+    let map: BTreeMap<u32, bool> = RELAXED.from_str("{ '1': true, \"2\": false, }").unwrap();
+    assert_eq!(map, BTreeMap::from([(1, true), (2, false)]));
+}
+
+#[test]
+fn relaxed_skip_unknown() {
+    #[derive(Debug, PartialEq, Decode)]
+    struct Partial {
+        name: String,
+    }
+
+    let partial: Partial = RELAXED
+        .from_str("{ extra: 'skipped \\' value', other: [1, 'a', { k: 'v' }], name: 'kept' }")
+        .unwrap();
+
+    assert_eq!(partial.name, "kept");
+}
+
+#[test]
+fn relaxed_value() {
+    let value: Value<Global> = RELAXED
+        .from_str("/* leading */ { list: [1, 'two', null,], } // trailing")
+        .unwrap();
+
+    assert_eq!(
+        STRICT.to_string(&value).unwrap(),
+        r#"{"list":[1,"two",null]}"#
+    );
+}
+
+#[test]
+fn relaxed_decode_iter_and_pointer() {
+    let input = "{ id: 1 } // first\n/* second */ { id: 2 }\n// done\n";
+
+    #[derive(Debug, PartialEq, Decode)]
+    struct Record {
+        id: u32,
+    }
+
+    let records = RELAXED
+        .decode_iter::<_, Record>(input)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(records, [Record { id: 1 }, Record { id: 2 }]);
+
+    let id: u32 = RELAXED
+        .decode_at("{ items: [ /* none */ { id: 7, }, ], }", "/items/0/id")
+        .unwrap();
+
+    assert_eq!(id, 7);
+}
+
+#[test]
+fn relaxed_errors() {
+    assert!(RELAXED.from_str::<u32>("/* unterminated 1").is_err());
+    assert!(RELAXED.from_str::<u32>("/ 1").is_err());
+    assert!(
+        RELAXED
+            .from_str::<Vec<u32>>("[1 // unterminated ]")
+            .is_err()
+    );
+    assert!(RELAXED.from_str::<Vec<u32>>("[, 1]").is_err());
+
+    // Identifiers are only accepted as object keys.
+    assert!(RELAXED.from_str::<Vec<String>>("[ident]").is_err());
+    assert!(
+        RELAXED
+            .from_str::<BTreeMap<String, String>>("{a: b}")
+            .is_err()
+    );
+    assert!(
+        RELAXED
+            .from_str::<Value<Global>>("{ enabled: ture }")
+            .is_err()
+    );
+    assert!(RELAXED.from_str::<Value<Global>>("{ mode: nul }").is_err());
+
+    assert!(STRICT.from_str::<u32>("// comment\n1").is_err());
+    assert!(STRICT.from_str::<BTreeMap<String, u32>>("{a: 1}").is_err());
+}
@@ -0,0 +1,205 @@
+#![cfg(feature = "test")]
+
+use std::collections::BTreeMap;
+use std::io::BufReader;
+
+use musli::de::{LazyMap, LazyMapEncoding, LazySeq};
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Point {
+    x: u32,
+    y: String,
+}
+
+#[derive(Encode)]
+struct Owned {
+    name: String,
+    points: Vec<Point>,
+    trailer: u64,
+}
+
+fn points(n: u32) -> Vec<Point> {
+    (0..n)
+        .map(|x| Point {
+            x,
+            y: format!("point {x}"),
+        })
+        .collect()
+}
+
+macro_rules! test_lazy_seq {
+    ($what:ident) => {
+        #[test]
+        fn $what() {
+            #[derive(Decode)]
+            struct Lazy<'de> {
+                name: &'de str,
+                points: LazySeq<'de, Point, musli::$what::Encoding>,
+                trailer: u64,
+            }
+
+            let encoding = musli::$what::Encoding::new();
+
+            for n in [0, 1, 16] {
+                let data = encoding
+                    .to_vec(&Owned {
+                        name: String::from("points"),
+                        points: points(n),
+                        trailer: 42,
+                    })
+                    .unwrap();
+
+                let lazy: Lazy<'_> = encoding.from_slice(&data).unwrap();
+                assert_eq!(lazy.name, "points");
+                assert_eq!(lazy.points.len(), n as usize);
+                assert_eq!(lazy.points.is_empty(), n == 0);
+                assert_eq!(lazy.trailer, 42);
+
+                let decoded = lazy.points.iter().collect::<Result<Vec<_>, _>>().unwrap();
+                assert_eq!(decoded, points(n));
+
+                let decoded = encoding
+                    .decode_iter::<_, Point>(lazy.points.as_bytes())
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+                assert_eq!(decoded, points(n));
+            }
+        }
+    };
+}
+
+test_lazy_seq!(wire);
+test_lazy_seq!(descriptive);
+
+#[derive(Encode)]
+struct OwnedMap {
+    name: String,
+    points: BTreeMap<String, Point>,
+    trailer: u64,
+}
+
+fn point_map(n: u32) -> BTreeMap<String, Point> {
+    points(n)
+        .into_iter()
+        .map(|point| (point.y.clone(), point))
+        .collect()
+}
+
+macro_rules! test_lazy_map {
+    ($name:ident, $what:ident) => {
+        #[test]
+        fn $name() {
+            #[derive(Decode)]
+            struct Lazy<'de> {
+                name: &'de str,
+                points: LazyMap<'de, &'de str, Point, musli::$what::Encoding>,
+                trailer: u64,
+            }
+
+            let encoding = musli::$what::Encoding::new();
+
+            for n in [0, 1, 16] {
+                let data = encoding
+                    .to_vec(&OwnedMap {
+                        name: String::from("points"),
+                        points: point_map(n),
+                        trailer: 42,
+                    })
+                    .unwrap();
+
+                let lazy: Lazy<'_> = encoding.from_slice(&data).unwrap();
+                assert_eq!(lazy.name, "points");
+                assert_eq!(lazy.points.len(), n as usize);
+                assert_eq!(lazy.points.is_empty(), n == 0);
+                assert_eq!(lazy.trailer, 42);
+
+                let decoded = lazy
+                    .points
+                    .iter()
+                    .map(|entry| entry.map(|(key, value)| (key.to_owned(), value)))
+                    .collect::<Result<BTreeMap<_, _>, _>>()
+                    .unwrap();
+
+                assert_eq!(decoded, point_map(n));
+            }
+        }
+    };
+}
+
+test_lazy_map!(wire_map, wire);
+test_lazy_map!(descriptive_map, descriptive);
+
+#[test]
+fn lazy_map_truncated() {
+    let encoding = musli::wire::Encoding::new();
+
+    let data = encoding.to_vec(&point_map(4)).unwrap();
+    let map: LazyMap<'_, String, Point, musli::wire::Encoding> =
+        encoding.from_slice(&data).unwrap();
+
+    // Cut off the value of the last entry, so that only its key remains.
+    let last = encoding.to_vec(&point_map(4)["point 3"]).unwrap();
+    let bytes = &map.as_bytes()[..map.as_bytes().len() - last.len()];
+
+    let entries =
+        <musli::wire::Encoding as LazyMapEncoding<'_, String, Point>>::decode_lazy_map(bytes)
+            .collect::<Vec<_>>();
+
+    assert_eq!(entries.len(), 4);
+    assert!(entries[..3].iter().all(Result::is_ok));
+
+    let Err(error) = &entries[3] else {
+        panic!("expected decoding to fail");
+    };
+
+    assert!(error.to_string().starts_with("Entry 3: "), "{error}");
+}
+
+macro_rules! test_unsupported_format {
+    ($name:ident, $what:ident) => {
+        #[test]
+        fn $name() {
+            let encoding = musli::$what::Encoding::new();
+
+            let data = encoding.to_vec(&points(4)).unwrap();
+            let result = encoding.from_slice::<LazySeq<'_, Point, musli::$what::Encoding>>(&data);
+
+            let Err(error) = result else {
+                panic!("expected decoding to fail");
+            };
+
+            assert!(error.to_string().contains("skip values"), "{error}");
+
+            let data = encoding.to_vec(&point_map(4)).unwrap();
+            let result =
+                encoding.from_slice::<LazyMap<'_, String, Point, musli::$what::Encoding>>(&data);
+
+            let Err(error) = result else {
+                panic!("expected decoding to fail");
+            };
+
+            assert!(error.to_string().contains("skip values"), "{error}");
+        }
+    };
+}
+
+test_unsupported_format!(storage_unsupported, storage);
+test_unsupported_format!(packed_unsupported, packed);
+
+#[test]
+fn unsupported_reader() {
+    let encoding = musli::storage::Encoding::new();
+
+    let data = encoding.to_vec(&points(4)).unwrap();
+
+    let result = encoding.decode::<_, LazySeq<'_, Point, musli::storage::Encoding>>(
+        musli::wrap::wrap(BufReader::new(&data[..])),
+    );
+
+    let Err(error) = result else {
+        panic!("expected decoding to fail");
+    };
+
+    assert!(error.to_string().contains("borrowed slice"), "{error}");
+}
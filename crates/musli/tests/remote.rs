@@ -0,0 +1,161 @@
+#![cfg(feature = "test")]
+
+use musli::{Decode, Encode};
+
+mod other {
+    #[derive(Debug, PartialEq)]
+    pub struct Point {
+        pub x: u32,
+        pub y: u32,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct Timeout {
+        secs: u64,
+        millis: u32,
+    }
+
+    impl Timeout {
+        pub fn new(secs: u64, millis: u32) -> Self {
+            Self { secs, millis }
+        }
+
+        pub fn secs(&self) -> u64 {
+            self.secs
+        }
+
+        pub fn millis(&self) -> u32 {
+            self.millis
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub enum Shape {
+        Circle { radius: u32 },
+        Square(u32),
+        Empty,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct Wrapper<T> {
+        pub value: T,
+    }
+}
+
+#[derive(Encode, Decode)]
+#[musli(remote = other::Point)]
+struct PointDef {
+    x: u32,
+    y: u32,
+}
+
+#[derive(Encode, Decode)]
+#[musli(remote = other::Timeout)]
+struct TimeoutDef {
+    #[musli(getter = other::Timeout::secs)]
+    secs: u64,
+    #[musli(getter = other::Timeout::millis)]
+    millis: u32,
+}
+
+impl From<TimeoutDef> for other::Timeout {
+    fn from(def: TimeoutDef) -> Self {
+        other::Timeout::new(def.secs, def.millis)
+    }
+}
+
+#[derive(Encode, Decode)]
+#[musli(remote = other::Shape)]
+enum ShapeDef {
+    Circle { radius: u32 },
+    Square(u32),
+    Empty,
+}
+
+#[derive(Encode, Decode)]
+#[musli(remote = other::Wrapper<T>)]
+struct WrapperDef<T> {
+    value: T,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Container {
+    #[musli(with = PointDef)]
+    point: other::Point,
+    #[musli(with = TimeoutDef)]
+    timeout: other::Timeout,
+    #[musli(with = ShapeDef)]
+    shape: other::Shape,
+    #[musli(with = WrapperDef)]
+    wrapper: other::Wrapper<String>,
+}
+
+#[test]
+fn remote() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Container {
+            point: other::Point { x: 1, y: 2 },
+            timeout: other::Timeout::new(10, 500),
+            shape: other::Shape::Circle { radius: 4 },
+            wrapper: other::Wrapper {
+                value: String::from("hello"),
+            },
+        },
+        json = r#"{"point":{"x":1,"y":2},"timeout":{"secs":10,"millis":500},"shape":{"Circle":{"radius":4}},"wrapper":{"value":"hello"}}"#
+    );
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Container {
+            point: other::Point { x: 0, y: 0 },
+            timeout: other::Timeout::new(0, 0),
+            shape: other::Shape::Square(3),
+            wrapper: other::Wrapper {
+                value: String::new(),
+            },
+        }
+    );
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Container {
+            point: other::Point { x: 0, y: 0 },
+            timeout: other::Timeout::new(0, 0),
+            shape: other::Shape::Empty,
+            wrapper: other::Wrapper {
+                value: String::new(),
+            },
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Local {
+    x: u32,
+    y: u32,
+}
+
+#[test]
+fn remote_matches_local() {
+    #[derive(Encode)]
+    struct Wrapped {
+        #[musli(with = PointDef)]
+        point: other::Point,
+    }
+
+    #[derive(Debug, PartialEq, Decode)]
+    struct Unwrapped {
+        point: Local,
+    }
+
+    let json = musli::json::to_string(&Wrapped {
+        point: other::Point { x: 1, y: 2 },
+    })
+    .unwrap();
+
+    assert_eq!(json, r#"{"point":{"x":1,"y":2}}"#);
+
+    let decoded: Unwrapped = musli::json::from_str(&json).unwrap();
+    assert_eq!(decoded.point, Local { x: 1, y: 2 });
+}
@@ -0,0 +1,138 @@
+#![cfg(feature = "test")]
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Timings {
+    elapsed: Duration,
+    at: SystemTime,
+}
+
+#[test]
+fn roundtrip() {
+    for (secs, nanos) in [(0, 0), (1, 500_000_000), (1_700_000_000, 123_456_789)] {
+        musli::macros::assert_roundtrip_eq!(
+            full,
+            Timings {
+                elapsed: Duration::new(secs, nanos),
+                at: UNIX_EPOCH + Duration::new(secs, nanos),
+            }
+        );
+    }
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Timings {
+            elapsed: Duration::MAX,
+            at: UNIX_EPOCH - Duration::new(86_400 * 365, 250_000_000),
+        }
+    );
+}
+
+#[test]
+fn text_duration() {
+    let json = musli::json::to_string(&Duration::new(90, 500_000_000)).unwrap();
+    assert_eq!(json, "\"90.5\"");
+
+    let json = musli::json::to_string(&Duration::new(3, 0)).unwrap();
+    assert_eq!(json, "\"3\"");
+
+    let json = musli::json::to_string(&Duration::new(0, 1)).unwrap();
+    assert_eq!(json, "\"0.000000001\"");
+
+    for (input, expected) in [
+        ("\"1.25\"", Duration::new(1, 250_000_000)),
+        ("\"PT1.5S\"", Duration::new(1, 500_000_000)),
+        ("\"PT1H2M3S\"", Duration::new(3_723, 0)),
+        ("\"P1DT1S\"", Duration::new(86_401, 0)),
+        ("\"P2W\"", Duration::new(1_209_600, 0)),
+    ] {
+        let actual: Duration = musli::json::from_str(input).unwrap();
+        assert_eq!(actual, expected, "{input}");
+    }
+
+    for input in [
+        "\"\"",
+        "\"-1\"",
+        "\"1.\"",
+        "\"P\"",
+        "\"PT\"",
+        "\"P1M\"",
+        "\"PT1S2M\"",
+    ] {
+        let error = musli::json::from_str::<Duration>(input).unwrap_err();
+        assert!(
+            error.to_string().contains("Invalid duration"),
+            "{input}: {error}"
+        );
+    }
+
+    let error = musli::json::from_str::<Duration>("\"1.0000000001\"").unwrap_err();
+    assert!(error.to_string().contains("precision"), "{error}");
+}
+
+#[test]
+fn text_system_time() {
+    let json = musli::json::to_string(&UNIX_EPOCH).unwrap();
+    assert_eq!(json, "\"1970-01-01T00:00:00Z\"");
+
+    let time = UNIX_EPOCH + Duration::new(951_782_400, 120_000_000);
+    let json = musli::json::to_string(&time).unwrap();
+    assert_eq!(json, "\"2000-02-29T00:00:00.12Z\"");
+
+    let time = UNIX_EPOCH - Duration::new(1, 500_000_000);
+    let json = musli::json::to_string(&time).unwrap();
+    assert_eq!(json, "\"1969-12-31T23:59:58.5Z\"");
+
+    for (input, expected) in [
+        ("\"1970-01-01T00:00:00Z\"", UNIX_EPOCH),
+        (
+            "\"2000-02-29t00:00:00.12z\"",
+            UNIX_EPOCH + Duration::new(951_782_400, 120_000_000),
+        ),
+        ("\"1970-01-01T02:00:00+02:00\"", UNIX_EPOCH),
+        ("\"1969-12-31 22:00:00-02:00\"", UNIX_EPOCH),
+    ] {
+        let actual: SystemTime = musli::json::from_str(input).unwrap();
+        assert_eq!(actual, expected, "{input}");
+    }
+
+    for input in [
+        "\"1970-01-01\"",
+        "\"1970-13-01T00:00:00Z\"",
+        "\"2001-02-29T00:00:00Z\"",
+        "\"1970-01-01T24:00:00Z\"",
+        "\"1970-01-01T00:00:00\"",
+        "\"1970-01-01T00:00:00+2:00\"",
+    ] {
+        let error = musli::json::from_str::<SystemTime>(input).unwrap_err();
+        assert!(
+            error.to_string().contains("Invalid timestamp"),
+            "{input}: {error}"
+        );
+    }
+}
+
+#[test]
+fn nanos_out_of_range() {
+    #[derive(Encode)]
+    #[musli(packed)]
+    struct Raw {
+        secs: u64,
+        nanos: u32,
+    }
+
+    let bytes = musli::storage::to_vec(&Raw {
+        secs: 1,
+        nanos: 1_000_000_000,
+    })
+    .unwrap();
+
+    let error = musli::storage::from_slice::<Duration>(&bytes).unwrap_err();
+    assert!(error.to_string().contains("Nanoseconds"), "{error}");
+
+    let error = musli::storage::from_slice::<SystemTime>(&bytes).unwrap_err();
+    assert!(error.to_string().contains("Nanoseconds"), "{error}");
+}
@@ -0,0 +1,25 @@
+use musli::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+#[musli(flattenable)]
+enum Enum {
+    Variant { field: u32 },
+}
+
+#[derive(Encode, Decode)]
+#[musli(packed, flattenable)]
+struct Packed {
+    field: u32,
+}
+
+#[derive(Encode, Decode)]
+#[musli(version = 1, flattenable)]
+struct Versioned {
+    field: u32,
+}
+
+#[derive(Encode, Decode)]
+#[musli(flattenable)]
+struct Empty;
+
+fn main() {}
@@ -0,0 +1,26 @@
+use musli::{Decode, Encode};
+
+const VALUE: isize = 10;
+
+#[derive(Encode, Decode)]
+#[musli(name_all = "discriminant")]
+enum NonLiteral {
+    First = VALUE,
+    Second,
+}
+
+#[derive(Encode, Decode)]
+#[repr(C)]
+#[musli(name_all = "discriminant")]
+enum UnsupportedRepr {
+    First = 1,
+    Second = 2,
+}
+
+#[derive(Encode, Decode)]
+#[musli(name_all = "discriminant")]
+struct Struct {
+    field: u32,
+}
+
+fn main() {}
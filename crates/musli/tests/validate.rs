@@ -0,0 +1,173 @@
+#![cfg(feature = "test")]
+
+use musli::context;
+use musli::{Decode, Encode};
+
+fn not_empty(value: &str) -> Result<(), &'static str> {
+    if value.is_empty() {
+        return Err("must not be empty");
+    }
+
+    Ok(())
+}
+
+fn non_zero(value: &u32) -> Result<(), String> {
+    if *value == 0 {
+        return Err(format!("{value} is not allowed"));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name", flattenable)]
+struct Server {
+    #[musli(validate = not_empty)]
+    name: String,
+    #[musli(validate = non_zero)]
+    port: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(validate = Range::validate)]
+struct Range {
+    start: u32,
+    end: u32,
+}
+
+impl Range {
+    fn validate(&self) -> Result<(), &'static str> {
+        if self.start > self.end {
+            return Err("start must not be greater than end");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+struct Packed(#[musli(validate = non_zero)] u32, u32);
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(transparent)]
+struct Port(#[musli(validate = non_zero)] u32);
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Config {
+    #[musli(flatten)]
+    server: Server,
+    debug: bool,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Command {
+    Connect {
+        #[musli(validate = not_empty)]
+        host: String,
+    },
+}
+
+#[test]
+fn validate_field() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Server {
+            name: String::from("server"),
+            port: 80,
+        },
+        json = r#"{"name":"server","port":80}"#
+    );
+
+    let error = musli::json::from_str::<Server>(r#"{"name":"","port":80}"#).unwrap_err();
+    assert!(error.to_string().contains("must not be empty"), "{error}");
+
+    let error = musli::json::from_str::<Server>(r#"{"name":"a","port":0}"#).unwrap_err();
+    assert!(error.to_string().contains("0 is not allowed"), "{error}");
+
+    let bytes = musli::storage::to_vec(&Server {
+        name: String::new(),
+        port: 80,
+    })
+    .unwrap();
+
+    let error = musli::storage::from_slice::<Server>(&bytes).unwrap_err();
+    assert!(error.to_string().contains("must not be empty"), "{error}");
+}
+
+#[test]
+fn validate_field_trace() {
+    let cx = context::new().with_trace();
+
+    let encoding = musli::json::Encoding::new();
+    let result = encoding.from_str_with::<_, Server>(&cx, r#"{"name":"a","port":0}"#);
+    assert!(result.is_err());
+
+    let error = cx.errors().next().expect("expected an error").to_string();
+    assert!(error.starts_with(".port"), "{error}");
+    assert!(error.contains("0 is not allowed"), "{error}");
+}
+
+#[test]
+fn validate_container() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Range { start: 1, end: 2 },
+        json = r#"{"start":1,"end":2}"#
+    );
+
+    let error = musli::json::from_str::<Range>(r#"{"start":10,"end":5}"#).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("start must not be greater than end"),
+        "{error}"
+    );
+}
+
+#[test]
+fn validate_packed_transparent() {
+    musli::macros::assert_roundtrip_eq!(full, Packed(1, 0));
+    musli::macros::assert_roundtrip_eq!(full, Port(80), json = "80");
+
+    let bytes = musli::storage::to_vec(&Packed(0, 1)).unwrap();
+    let error = musli::storage::from_slice::<Packed>(&bytes).unwrap_err();
+    assert!(error.to_string().contains("0 is not allowed"), "{error}");
+
+    let error = musli::json::from_str::<Port>("0").unwrap_err();
+    assert!(error.to_string().contains("0 is not allowed"), "{error}");
+}
+
+#[test]
+fn validate_flatten() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Config {
+            server: Server {
+                name: String::from("server"),
+                port: 80,
+            },
+            debug: true,
+        },
+        json = r#"{"name":"server","port":80,"debug":true}"#
+    );
+
+    let error =
+        musli::json::from_str::<Config>(r#"{"name":"","port":80,"debug":true}"#).unwrap_err();
+    assert!(error.to_string().contains("must not be empty"), "{error}");
+}
+
+#[test]
+fn validate_variant_field() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Command::Connect {
+            host: String::from("localhost"),
+        },
+        json = r#"{"Connect":{"host":"localhost"}}"#
+    );
+
+    let error = musli::json::from_str::<Command>(r#"{"Connect":{"host":""}}"#).unwrap_err();
+    assert!(error.to_string().contains("must not be empty"), "{error}");
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "test")]
+
+use musli::{Decode, Encode};
+
+/// Types as they were defined by earlier versions of the program, which
+/// produced the data we want to be able to read.
+mod old {
+    use musli::{Decode, Encode};
+
+    pub mod v1 {
+        use super::*;
+
+        #[derive(Debug, PartialEq, Encode, Decode)]
+        #[musli(version = 1)]
+        pub struct Config {
+            pub host: String,
+            pub port: u16,
+        }
+    }
+
+    pub mod v2 {
+        use super::*;
+
+        #[derive(Debug, PartialEq, Encode, Decode)]
+        #[musli(version = 2, migrate_from(1 = Config::from_v1))]
+        pub struct Config {
+            pub address: String,
+            pub port: u32,
+        }
+
+        impl Config {
+            fn from_v1(old: super::super::ConfigV1) -> Self {
+                Self {
+                    address: old.host,
+                    port: u32::from(old.port),
+                }
+            }
+        }
+    }
+}
+
+/// Shadow type for the layout of version 1.
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct ConfigV1 {
+    host: String,
+    port: u16,
+}
+
+/// Shadow type for the layout of version 2.
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct ConfigV2 {
+    address: String,
+    port: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(version = 3, migrate_from(1 = Config::from_v1, 2 = Config::from_v2))]
+struct Config {
+    address: String,
+    port: u32,
+    verbose: bool,
+}
+
+impl Config {
+    fn from_v1(old: ConfigV1) -> Self {
+        Self::from_v2(ConfigV2 {
+            address: old.host,
+            port: u32::from(old.port),
+        })
+    }
+
+    fn from_v2(old: ConfigV2) -> Self {
+        Self {
+            address: old.address,
+            port: old.port,
+            verbose: false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(version = 1)]
+enum Command {
+    Start { delay: u32 },
+    Stop,
+}
+
+#[test]
+fn versioning() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Config {
+            address: String::from("localhost"),
+            port: 8080,
+            verbose: true,
+        },
+        json = r#"[3,{"address":"localhost","port":8080,"verbose":true}]"#
+    );
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Command::Start { delay: 10 },
+        json = r#"[1,{"Start":{"delay":10}}]"#
+    );
+
+    musli::macros::assert_roundtrip_eq!(full, Command::Stop, json = r#"[1,{"Stop":{}}]"#);
+}
+
+#[test]
+fn migrate() {
+    let v1 = old::v1::Config {
+        host: String::from("localhost"),
+        port: 80,
+    };
+
+    let bytes = musli::storage::to_vec(&v1).unwrap();
+
+    assert_eq!(
+        musli::storage::from_slice::<old::v2::Config>(&bytes).unwrap(),
+        old::v2::Config {
+            address: String::from("localhost"),
+            port: 80,
+        }
+    );
+
+    assert_eq!(
+        musli::storage::from_slice::<Config>(&bytes).unwrap(),
+        Config {
+            address: String::from("localhost"),
+            port: 80,
+            verbose: false,
+        }
+    );
+
+    let v2 = old::v2::Config {
+        address: String::from("example.com"),
+        port: 443,
+    };
+
+    let json = musli::json::to_string(&v2).unwrap();
+    assert_eq!(json, r#"[2,{"address":"example.com","port":443}]"#);
+
+    assert_eq!(
+        musli::json::from_str::<Config>(&json).unwrap(),
+        Config {
+            address: String::from("example.com"),
+            port: 443,
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn unsupported_version() {
+    let new = Config {
+        address: String::from("localhost"),
+        port: 80,
+        verbose: true,
+    };
+
+    let bytes = musli::storage::to_vec(&new).unwrap();
+    let error = musli::storage::from_slice::<old::v2::Config>(&bytes).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Type Config does not support version 3"),
+        "{error}"
+    );
+
+    let error =
+        musli::json::from_str::<old::v1::Config>(r#"[2,{"address":"a","port":1}]"#).unwrap_err();
+    assert!(error.to_string().contains("version 2"), "{error}");
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "test")]
+
+use std::cell::{Cell, RefCell};
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::ops::Bound;
+use std::sync::{Arc, Mutex, RwLock};
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Bounds {
+    start: Bound<u32>,
+    end: Bound<String>,
+    unbounded: Bound<u64>,
+}
+
+#[test]
+fn bounds() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Bounds {
+            start: Bound::Included(10),
+            end: Bound::Excluded(String::from("end")),
+            unbounded: Bound::Unbounded,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Orderings {
+    heap: Vec<Reverse<u32>>,
+    orderings: Vec<Ordering>,
+}
+
+#[test]
+fn orderings() {
+    let heap = BinaryHeap::from([Reverse(3u32), Reverse(1), Reverse(2)]).into_sorted_vec();
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Orderings {
+            heap: heap.clone(),
+            orderings: vec![Ordering::Less, Ordering::Equal, Ordering::Greater],
+        }
+    );
+
+    let json = musli::json::to_string(&Ordering::Less).unwrap();
+    assert_eq!(json, "-1");
+
+    let error = musli::json::from_str::<Ordering>("2").unwrap_err();
+    assert!(error.to_string().contains("ordering"), "{error}");
+}
+
+#[derive(Encode, Decode)]
+struct Shared {
+    counter: Cell<u32>,
+    names: RefCell<Vec<String>>,
+    state: Mutex<String>,
+    config: RwLock<u64>,
+}
+
+#[test]
+fn interior_mutability() {
+    let shared = Shared {
+        counter: Cell::new(7),
+        names: RefCell::new(vec![String::from("a"), String::from("b")]),
+        state: Mutex::new(String::from("ready")),
+        config: RwLock::new(42),
+    };
+
+    let bytes = musli::wire::to_vec(&shared).unwrap();
+    let decoded: Shared = musli::wire::from_slice(&bytes).unwrap();
+
+    assert_eq!(decoded.counter.get(), 7);
+    assert_eq!(*decoded.names.borrow(), ["a", "b"]);
+    assert_eq!(*decoded.state.lock().unwrap(), "ready");
+    assert_eq!(*decoded.config.read().unwrap(), 42);
+}
+
+#[test]
+fn ref_cell_borrowed() {
+    let cell = RefCell::new(1u32);
+    let _guard = cell.borrow_mut();
+
+    let error = musli::storage::to_vec(&cell).unwrap_err();
+    assert!(error.to_string().contains("mutably borrowed"), "{error}");
+}
+
+#[test]
+fn poisoned_locks() {
+    let mutex = Arc::new(Mutex::new(1u32));
+    let rw_lock = Arc::new(RwLock::new(1u32));
+
+    let _ = std::thread::spawn({
+        let mutex = mutex.clone();
+        let rw_lock = rw_lock.clone();
+
+        move || {
+            let _a = mutex.lock().unwrap();
+            let _b = rw_lock.write().unwrap();
+            panic!("poison");
+        }
+    })
+    .join();
+
+    let error = musli::storage::to_vec(&*mutex).unwrap_err();
+    assert!(error.to_string().contains("Mutex is poisoned"), "{error}");
+
+    let error = musli::storage::to_vec(&*rw_lock).unwrap_err();
+    assert!(error.to_string().contains("RwLock is poisoned"), "{error}");
+}
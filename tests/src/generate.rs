@@ -665,6 +665,87 @@ where
     }
 }
 
+impl<T> Generate for core::cmp::Reverse<T>
+where
+    T: Generate,
+{
+    #[inline]
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: rand::Rng,
+    {
+        core::cmp::Reverse(T::generate(rng))
+    }
+}
+
+impl Generate for core::cmp::Ordering {
+    #[inline]
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: rand::Rng,
+    {
+        match rng.random_range(0..3) {
+            0 => core::cmp::Ordering::Less,
+            1 => core::cmp::Ordering::Equal,
+            _ => core::cmp::Ordering::Greater,
+        }
+    }
+}
+
+impl<T> Generate for core::cell::Cell<T>
+where
+    T: Generate,
+{
+    #[inline]
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: rand::Rng,
+    {
+        core::cell::Cell::new(T::generate(rng))
+    }
+}
+
+impl<T> Generate for core::cell::RefCell<T>
+where
+    T: Generate,
+{
+    #[inline]
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: rand::Rng,
+    {
+        core::cell::RefCell::new(T::generate(rng))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Generate for std::sync::Mutex<T>
+where
+    T: Generate,
+{
+    #[inline]
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: rand::Rng,
+    {
+        std::sync::Mutex::new(T::generate(rng))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Generate for std::sync::RwLock<T>
+where
+    T: Generate,
+{
+    #[inline]
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: rand::Rng,
+    {
+        std::sync::RwLock::new(T::generate(rng))
+    }
+}
+
 macro_rules! non_zero {
     ($($non_zero:ident, $signed_non_zero:ident, $ty:ty),* $(,)?) => {
         $(
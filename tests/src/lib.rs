@@ -154,6 +154,10 @@ macro_rules! feature_matrix {
         $call!(mock $(, $($tt)*)*);
         #[cfg(feature = "musli-json")]
         $call!(musli_json $(, $($tt)*)*);
+        #[cfg(feature = "musli-msgpack")]
+        $call!(musli_msgpack $(, $($tt)*)*);
+        #[cfg(feature = "musli-cbor")]
+        $call!(musli_cbor $(, $($tt)*)*);
         #[cfg(feature = "musli-wire")]
         $call!(musli_wire $(, $($tt)*)*);
         #[cfg(feature = "musli-descriptive")]
@@ -353,6 +357,59 @@ where
     a.atomic_eq(&b)
 }
 
+#[doc(hidden)]
+pub trait LockEq<B: ?Sized = Self> {
+    fn lock_eq(&self, other: &B) -> bool;
+}
+
+impl<A, B> LockEq<&B> for &A
+where
+    A: ?Sized + LockEq<B>,
+    B: ?Sized,
+{
+    #[inline]
+    fn lock_eq(&self, other: &&B) -> bool {
+        LockEq::lock_eq(*self, *other)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> LockEq for std::sync::Mutex<T>
+where
+    T: PartialEq,
+{
+    #[inline]
+    fn lock_eq(&self, other: &Self) -> bool {
+        match (self.lock(), other.lock()) {
+            (Ok(a), Ok(b)) => *a == *b,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> LockEq for std::sync::RwLock<T>
+where
+    T: PartialEq,
+{
+    #[inline]
+    fn lock_eq(&self, other: &Self) -> bool {
+        match (self.read(), other.read()) {
+            (Ok(a), Ok(b)) => *a == *b,
+            _ => false,
+        }
+    }
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn lock_eq<A, B>(a: A, b: B) -> bool
+where
+    A: LockEq<B>,
+{
+    a.lock_eq(&b)
+}
+
 #[macro_export]
 macro_rules! basic_types {
     ($call:path $(, $($tt:tt)*)?) => {
@@ -425,6 +482,19 @@ macro_rules! basic_types {
         #[cfg(not(feature = "no-wrapping-isize"))]
         $call!(wrapping_isize, core::num::Wrapping<isize>, $crate::partial_eq $(, $($tt)*)*);
 
+        #[cfg(not(feature = "no-reverse"))]
+        $call!(reverse_u32, core::cmp::Reverse<u32>, $crate::partial_eq $(, $($tt)*)*);
+        #[cfg(not(feature = "no-ordering"))]
+        $call!(ordering, core::cmp::Ordering, $crate::partial_eq $(, $($tt)*)*);
+        #[cfg(not(feature = "no-cell"))]
+        $call!(cell_u32, core::cell::Cell<u32>, $crate::partial_eq $(, $($tt)*)*);
+        #[cfg(not(feature = "no-cell"))]
+        $call!(ref_cell_u32, core::cell::RefCell<u32>, $crate::partial_eq $(, $($tt)*)*);
+        #[cfg(all(feature = "std", not(feature = "no-lock")))]
+        $call!(mutex_u32, ::std::sync::Mutex<u32>, $crate::lock_eq $(, $($tt)*)*);
+        #[cfg(all(feature = "std", not(feature = "no-lock")))]
+        $call!(rw_lock_u32, ::std::sync::RwLock<u32>, $crate::lock_eq $(, $($tt)*)*);
+
         #[cfg(not(feature = "no-nonzero-u8"))]
         $call!(nonzero_u8, core::num::NonZeroU8, $crate::partial_eq $(, $($tt)*)*);
         #[cfg(not(feature = "no-nonzero-u16"))]
@@ -81,6 +81,10 @@ local_deref_sized! {
     f64,
     {T} core::num::Wrapping<T>,
     {T} core::num::Saturating<T>,
+    {T} core::cmp::Reverse<T>,
+    core::cmp::Ordering,
+    {T} core::cell::Cell<T>,
+    {T} core::cell::RefCell<T>,
     core::num::NonZeroU8,
     core::num::NonZeroU16,
     core::num::NonZeroU32,
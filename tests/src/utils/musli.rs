@@ -35,6 +35,43 @@ pub mod musli_json {
     }
 }
 
+#[cfg(feature = "musli-msgpack")]
+#[crate::benchmarker]
+pub mod musli_msgpack {
+    use alloc::vec::Vec;
+
+    use musli::alloc::Global;
+    use musli::mode::Text;
+    use musli::msgpack::Encoding;
+    use musli::msgpack::Error;
+    use musli::{Decode, Encode};
+
+    const ENCODING: Encoding = Encoding::new();
+
+    pub fn buffer() -> Vec<u8> {
+        Vec::with_capacity(4096)
+    }
+
+    pub fn reset(buffer: &mut Vec<u8>) {
+        buffer.clear();
+    }
+
+    pub fn encode<'buf, T>(buffer: &'buf mut Vec<u8>, value: &T) -> Result<&'buf [u8], Error>
+    where
+        T: Encode<Text>,
+    {
+        ENCODING.encode(&mut *buffer, value)?;
+        Ok(buffer)
+    }
+
+    pub fn decode<'buf, T>(buffer: &'buf [u8]) -> Result<T, Error>
+    where
+        T: Decode<'buf, Text, Global>,
+    {
+        ENCODING.from_slice(buffer)
+    }
+}
+
 #[cfg(feature = "musli-packed")]
 #[crate::benchmarker]
 pub mod musli_packed {
@@ -188,6 +225,43 @@ pub mod musli_descriptive {
     }
 }
 
+#[cfg(feature = "musli-cbor")]
+#[crate::benchmarker]
+pub mod musli_cbor {
+    use alloc::vec::Vec;
+
+    use musli::alloc::Global;
+    use musli::cbor::Encoding;
+    use musli::cbor::Error;
+    use musli::mode::Binary;
+    use musli::{Decode, Encode};
+
+    const ENCODING: Encoding = Encoding::new();
+
+    pub fn buffer() -> Vec<u8> {
+        Vec::with_capacity(4096)
+    }
+
+    pub fn reset(buf: &mut Vec<u8>) {
+        buf.clear();
+    }
+
+    pub fn encode<'buf, T>(buf: &'buf mut Vec<u8>, value: &T) -> Result<&'buf [u8], Error>
+    where
+        T: Encode<Binary>,
+    {
+        ENCODING.encode(&mut *buf, value)?;
+        Ok(buf)
+    }
+
+    pub fn decode<'buf, T>(buf: &'buf [u8]) -> Result<T, Error>
+    where
+        T: Decode<'buf, Binary, Global>,
+    {
+        ENCODING.from_slice(buf)
+    }
+}
+
 #[cfg(feature = "musli-value")]
 #[crate::benchmarker(as_bytes_disabled)]
 pub mod musli_value {